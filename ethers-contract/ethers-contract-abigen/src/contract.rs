@@ -121,7 +121,7 @@ impl Context {
         let (contract_methods, call_structs) = self.methods_and_call_structs()?;
 
         // 5. generate deploy function if
-        let deployment_methods = self.deployment_methods();
+        let deployment_methods = self.deployment_methods()?;
 
         // 6. Declare the structs parsed from the human readable abi
         let abi_structs_decl = self.abi_structs()?;
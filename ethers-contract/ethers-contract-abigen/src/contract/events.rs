@@ -18,6 +18,11 @@ impl Context {
             .flatten()
             .map(|event| self.expand_event(event))
             .collect::<Result<Vec<_>>>()?;
+        let topic_setters = sorted_events
+            .values()
+            .flatten()
+            .map(|event| self.expand_topic_setters(event))
+            .collect::<Result<Vec<_>>>()?;
 
         // only expand enums when multiple events are present
         let events_enum_decl = if sorted_events.values().flatten().count() > 1 {
@@ -29,6 +34,8 @@ impl Context {
         Ok(quote! {
             #( #data_types )*
 
+            #( #topic_setters )*
+
             #events_enum_decl
         })
     }
@@ -234,6 +241,74 @@ impl Context {
         }
     }
 
+    /// Expands a trait with a `with_<param>` setter for each indexed parameter of the event, which
+    /// sets the parameter's topic of the event filter. The prefix avoids that the setters are
+    /// shadowed by the inherent methods of the event filter, like `query` or `stream`.
+    ///
+    /// Indexed parameters of struct types are skipped, since their topic is the hash of their
+    /// encoding. Returns nothing if no parameter has a setter.
+    fn expand_topic_setters(&self, event: &Event) -> Result<TokenStream> {
+        let ethers_contract = ethers_contract_crate();
+        let alias = self.event_aliases.get(&event.abi_signature()).cloned();
+        let event_name = event_struct_name(&event.name, alias);
+        let trait_name = util::ident(&format!("{}Topics", event_name));
+
+        // the first topic of non-anonymous events is the event signature
+        let first_topic = if event.anonymous { 0 } else { 1 };
+        let mut declarations = Vec::new();
+        let mut definitions = Vec::new();
+        for (topic, (i, input)) in
+            event.inputs.iter().enumerate().filter(|(_, input)| input.indexed).enumerate()
+        {
+            if self.is_struct_input(input) {
+                continue
+            }
+            let name = util::expand_input_name(i, &input.name);
+            let setter = util::ident(&format!("with_{}", name));
+            let ty = self.expand_input_type(input)?;
+            let topic = Literal::usize_unsuffixed(first_topic + topic);
+            let doc = util::expand_doc(&format!("Filters by the indexed `{}` parameter", name));
+            declarations.push(quote! {
+                #doc
+                fn #setter(self, #name: #ty) -> Self;
+            });
+            definitions.push(quote! {
+                fn #setter(self, #name: #ty) -> Self {
+                    self.indexed(#topic, #name)
+                }
+            });
+        }
+        if declarations.is_empty() {
+            return Ok(quote! {})
+        }
+
+        let doc = util::expand_doc(&format!(
+            "Sets the topics of the indexed parameters of a `{}` event filter",
+            event.name
+        ));
+        Ok(quote! {
+            #doc
+            pub trait #trait_name: Sized {
+                #( #declarations )*
+            }
+
+            impl<M> #trait_name for #ethers_contract::builders::Event<'_, M, #event_name> {
+                #( #definitions )*
+            }
+        })
+    }
+
+    /// Returns true if the event parameter is expanded to a struct or an array of structs
+    fn is_struct_input(&self, input: &EventParam) -> bool {
+        let is_tuple = match &input.kind {
+            ParamType::Array(ty) | ParamType::FixedArray(ty, _) => {
+                matches!(**ty, ParamType::Tuple(..))
+            }
+            kind => matches!(kind, ParamType::Tuple(..)),
+        };
+        is_tuple && self.abi_parser.structs.contains_key(&input.name)
+    }
+
     /// Expands an ABI event into a single event data type. This can expand either
     /// into a structure or a tuple in the case where all event parameters (topics
     /// and data) are anonymous.
@@ -403,6 +478,45 @@ mod tests {
         });
     }
 
+    #[test]
+    fn expand_transfer_topic_setters() {
+        let event = Event {
+            name: "Transfer".into(),
+            inputs: vec![
+                EventParam { name: "from".into(), kind: ParamType::Address, indexed: true },
+                EventParam { name: "amount".into(), kind: ParamType::Uint(256), indexed: false },
+                EventParam { name: "to".into(), kind: ParamType::Address, indexed: true },
+            ],
+            anonymous: false,
+        };
+        let cx = test_context();
+        assert_quote!(cx.expand_topic_setters(&event).unwrap(), {
+            #[doc = "Sets the topics of the indexed parameters of a `Transfer` event filter"]
+            pub trait TransferFilterTopics: Sized {
+                #[doc = "Filters by the indexed `from` parameter"]
+                fn with_from(self, from: ethers_core::types::Address) -> Self;
+                #[doc = "Filters by the indexed `to` parameter"]
+                fn with_to(self, to: ethers_core::types::Address) -> Self;
+            }
+
+            impl<M> TransferFilterTopics for ethers_contract::builders::Event<'_, M, TransferFilter> {
+                fn with_from(self, from: ethers_core::types::Address) -> Self {
+                    self.indexed(1, from)
+                }
+                fn with_to(self, to: ethers_core::types::Address) -> Self {
+                    self.indexed(2, to)
+                }
+            }
+        });
+
+        let event = Event {
+            name: "Foo".into(),
+            inputs: vec![EventParam { name: "a".into(), kind: ParamType::Bool, indexed: false }],
+            anonymous: false,
+        };
+        assert!(cx.expand_topic_setters(&event).unwrap().is_empty());
+    }
+
     #[test]
     fn expand_data_struct_value() {
         let event = Event {
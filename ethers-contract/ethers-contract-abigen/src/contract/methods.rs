@@ -43,10 +43,10 @@ impl Context {
     }
 
     /// Returns all deploy (constructor) implementations
-    pub(crate) fn deployment_methods(&self) -> TokenStream {
+    pub(crate) fn deployment_methods(&self) -> Result<TokenStream> {
         if self.contract_bytecode.is_none() {
             // don't generate deploy if no bytecode
-            return quote! {}
+            return Ok(quote! {})
         }
         let ethers_core = ethers_core_crate();
         let ethers_contract = ethers_contract_crate();
@@ -61,10 +61,15 @@ impl Context {
             #bytecode_name.clone().into()
         };
 
+        let (args_ty, tokens) = self.expand_constructor_args()?;
+
         let deploy = quote! {
             /// Constructs the general purpose `Deployer` instance based on the provided constructor arguments and sends it.
             /// Returns a new instance of a deployer that returns an instance of this contract after sending the transaction
             ///
+            /// The constructor arguments are passed as a tuple in the order of the constructor
+            /// params, a single argument is passed as is.
+            ///
             /// Notes:
             /// 1. If there are no constructor arguments, you should pass `()` as the argument.
             /// 1. The default poll duration is 7 seconds.
@@ -85,16 +90,47 @@ impl Context {
             ///    let msg = greeter_contract.greet().call().await.unwrap();
             /// # }
             /// ```
-            pub fn deploy<T: #ethers_core::abi::Tokenize >(client: ::std::sync::Arc<M>, constructor_args: T) -> Result<#ethers_contract::builders::ContractDeployer<M, Self>, #ethers_contract::ContractError<M>> {
+            pub fn deploy(client: ::std::sync::Arc<M>, constructor_args: #args_ty) -> Result<#ethers_contract::builders::ContractDeployer<M, Self>, #ethers_contract::ContractError<M>> {
+               let tokens: ::std::vec::Vec<#ethers_core::abi::Token> = ::std::vec![#( #tokens ),*];
                let factory = #ethers_contract::ContractFactory::new(#get_abi, #get_bytecode, client);
-               let deployer = factory.deploy(constructor_args)?;
+               let deployer = factory.deploy_tokens(tokens)?;
                let deployer = #ethers_contract::ContractDeployer::new(deployer);
                Ok(deployer)
             }
 
         };
 
-        deploy
+        Ok(deploy)
+    }
+
+    /// Expands to the type of the constructor arguments of `deploy` and the expressions that turn
+    /// `constructor_args` into the tokens of each argument.
+    ///
+    /// The arguments are tokenized one by one instead of as a whole because `Tokenize` flattens
+    /// a single struct argument into its fields.
+    fn expand_constructor_args(&self) -> Result<(TokenStream, Vec<TokenStream>)> {
+        let ethers_core = ethers_core_crate();
+        let inputs = self.abi.constructor.as_ref().map(|c| c.inputs.as_slice()).unwrap_or_default();
+        let types = inputs
+            .iter()
+            .map(|param| self.expand_input_param_type("constructor", &param.name, &param.kind))
+            .collect::<Result<Vec<_>>>()?;
+
+        let into_token = quote! { #ethers_core::abi::Tokenizable::into_token };
+        let expanded = match types.len() {
+            0 => (quote! { () }, Vec::new()),
+            1 => (quote! { #( #types )* }, vec![quote! { #into_token(constructor_args) }]),
+            _ => {
+                let tokens = (0..types.len())
+                    .map(|idx| {
+                        let idx = syn::Index::from(idx);
+                        quote! { #into_token(constructor_args.#idx) }
+                    })
+                    .collect();
+                (quote! { ( #( #types ),* ) }, tokens)
+            }
+        };
+        Ok(expanded)
     }
 
     /// Expands to the corresponding struct type based on the inputs of the given function
@@ -224,7 +260,7 @@ impl Context {
         let mut args = Vec::with_capacity(fun.inputs.len());
         for (idx, param) in fun.inputs.iter().enumerate() {
             let name = util::expand_input_name(idx, &param.name);
-            let ty = self.expand_input_param_type(fun, &param.name, &param.kind)?;
+            let ty = self.expand_input_param_type(&fun.name, &param.name, &param.kind)?;
             args.push((name, ty));
         }
         Ok(args)
@@ -280,25 +316,25 @@ impl Context {
     /// returns the Tokenstream for the corresponding rust type of the param
     fn expand_input_param_type(
         &self,
-        fun: &Function,
+        function: &str,
         param: &str,
         kind: &ParamType,
     ) -> Result<TokenStream> {
         match kind {
             ParamType::Array(ty) => {
-                let ty = self.expand_input_param_type(fun, param, ty)?;
+                let ty = self.expand_input_param_type(function, param, ty)?;
                 Ok(quote! {
                     ::std::vec::Vec<#ty>
                 })
             }
             ParamType::FixedArray(ty, size) => {
-                let ty = self.expand_input_param_type(fun, param, ty)?;
+                let ty = self.expand_input_param_type(function, param, ty)?;
                 let size = *size;
                 Ok(quote! {[#ty; #size]})
             }
             ParamType::Tuple(_) => {
                 let ty = if let Some(rust_struct_name) =
-                    self.internal_structs.get_function_input_struct_type(&fun.name, param)
+                    self.internal_structs.get_function_input_struct_type(function, param)
                 {
                     let ident = util::ident(rust_struct_name);
                     quote! {#ident}
@@ -254,7 +254,13 @@ impl InternalStructs {
             .into_iter()
             .filter(|item| item.type_field == "constructor" || item.type_field == "function")
         {
-            if let Some(name) = item.name {
+            // constructors are unnamed, their params are tracked as `constructor` like in
+            // human readable abis
+            let name = match item.name {
+                None if item.type_field == "constructor" => Some("constructor".to_string()),
+                name => name,
+            };
+            if let Some(name) = name {
                 for input in item.inputs {
                     if let Some(ty) = input
                         .internal_type
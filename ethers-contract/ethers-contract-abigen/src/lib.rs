@@ -23,7 +23,7 @@ mod util;
 pub mod multi;
 pub use multi::MultiAbigen;
 
-pub use ethers_core::types::Address;
+pub use ethers_core::types::{Address, Chain};
 pub use source::Source;
 pub use util::parse_address;
 
@@ -89,6 +89,41 @@ impl Abigen {
         })
     }
 
+    /// Creates a new builder for the ABI of a contract that has been verified on the Etherscan
+    /// compatible explorer of the chain.
+    ///
+    /// The ABI is retrieved once when the bindings are generated and cached on disk afterwards.
+    /// If no `api_key` is set, the key is read from the env var of the explorer, e.g.
+    /// `ETHERSCAN_API_KEY` or `POLYGONSCAN_API_KEY`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ethers_contract_abigen::{Abigen, Address, Chain};
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let dai: Address = "0x6b175474e89094c44da98b954eedeac495271d0f".parse()?;
+    /// Abigen::from_etherscan("Dai", dai, Chain::Mainnet, None)
+    ///     .generate()?
+    ///     .write_to_file("dai.rs")?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn from_etherscan(
+        contract_name: &str,
+        address: Address,
+        chain: Chain,
+        api_key: Option<String>,
+    ) -> Self {
+        Self {
+            abi_source: Source::Explorer { chain, address, api_key },
+            contract_name: contract_name.to_owned(),
+            method_aliases: HashMap::new(),
+            event_derives: Vec::new(),
+            event_aliases: HashMap::new(),
+            rustfmt: true,
+        }
+    }
+
     /// Attempts to load a new builder from an ABI JSON file at the specific
     /// path.
     pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
@@ -138,12 +173,14 @@ impl Abigen {
         self
     }
 
-    /// Add a custom derive to the derives for event structs and enums.
+    /// Add a custom derive to the derives of all generated structs and enums, i.e. the event,
+    /// call and ABI struct types.
     ///
-    /// This makes it possible to for example derive serde::Serialize and
-    /// serde::Deserialize for events.
+    /// This makes it possible to for example derive `serde::Serialize` and
+    /// `serde::Deserialize`, with which `U256`, `Address` and `Bytes` fields are serialized as
+    /// hex strings, and structs of unnamed parameters as arrays.
     #[must_use]
-    pub fn add_event_derive<S>(mut self, derive: S) -> Self
+    pub fn add_derive<S>(mut self, derive: S) -> Self
     where
         S: Into<String>,
     {
@@ -151,6 +188,16 @@ impl Abigen {
         self
     }
 
+    /// Add a custom derive to the derives of all generated structs and enums, same as
+    /// [`Abigen::add_derive`].
+    #[must_use]
+    pub fn add_event_derive<S>(self, derive: S) -> Self
+    where
+        S: Into<String>,
+    {
+        self.add_derive(derive)
+    }
+
     /// Generates the contract bindings.
     pub fn generate(self) -> Result<ContractBindings> {
         let rustfmt = self.rustfmt;
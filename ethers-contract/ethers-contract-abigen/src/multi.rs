@@ -11,7 +11,7 @@ use std::{
     path::Path,
 };
 
-use crate::{util, Abigen, Context, ContractBindings, ExpandedContract};
+use crate::{rawabi::JsonAbi, util, Abigen, Context, ContractBindings, ExpandedContract};
 
 /// Represents a collection of [`Abigen::expand()`]
 pub struct MultiExpansion {
@@ -262,6 +262,45 @@ impl MultiAbigen {
         util::json_files(root.as_ref()).into_iter().map(Abigen::from_file).collect()
     }
 
+    /// Recursively discovers all contract artifacts in the given `dir`, like the output directory
+    /// of a build, and uses the file name of each artifact for the name of the `ContractBindings`.
+    ///
+    /// Unlike [`MultiAbigen::from_json_files`] this skips all json files that are neither an
+    /// artifact nor an ABI, like build info or cache files. Artifacts without bytecode, like those
+    /// of interfaces and abstract contracts, get their call bindings but no `deploy` function.
+    ///
+    /// # Example
+    ///
+    /// ```text
+    /// out
+    /// ├── ERC20.sol
+    /// │   ├── ERC20.json
+    /// │   └── IERC20.json
+    /// ├── Contract1.sol
+    /// │   └── Contract1.json
+    /// ...
+    /// ```
+    ///
+    /// ```no_run
+    /// # use ethers_contract_abigen::MultiAbigen;
+    /// let gen = MultiAbigen::from_json_dir("./out").unwrap();
+    /// gen.build().unwrap().write_to_module("./src/contracts", false).unwrap();
+    /// ```
+    pub fn from_json_dir(root: impl AsRef<Path>) -> Result<Self> {
+        let mut files = util::json_files(root.as_ref());
+        files.sort();
+
+        let mut abigens = Vec::with_capacity(files.len());
+        for file in files {
+            let content = fs::read_to_string(&file)?;
+            if serde_json::from_str::<JsonAbi>(&content).is_err() {
+                continue
+            }
+            abigens.push(Abigen::from_file(&file)?);
+        }
+        Ok(Self::from_abigens(abigens))
+    }
+
     /// Add another Abigen to the module or lib
     pub fn push(&mut self, abigen: Abigen) {
         self.abigens.push(abigen)
@@ -1083,4 +1122,61 @@ contract Greeter2 {
         assert!(content.contains("pub struct Inner"));
         assert!(content.contains("pub struct Stuff"));
     }
+
+    #[test]
+    fn can_generate_from_json_dir() {
+        let tmp = TempProject::dapptools().unwrap();
+
+        tmp.add_source(
+            "Token",
+            r#"
+// SPDX-License-Identifier: MIT
+pragma solidity >=0.8.0;
+
+struct Balance {
+    address owner;
+    uint256 amount;
+}
+
+interface IToken {
+    function balance(address owner) external view returns (Balance memory);
+}
+
+contract Token is IToken {
+    function balance(address owner) external pure override returns (Balance memory) {
+        return Balance(owner, 0);
+    }
+}
+"#,
+        )
+        .unwrap();
+
+        let _ = tmp.compile().unwrap();
+        // json files that aren't artifacts are skipped
+        fs::write(tmp.artifacts_path().join("build-info.json"), r#"{"id":"0"}"#).unwrap();
+
+        let gen = MultiAbigen::from_json_dir(tmp.artifacts_path()).unwrap();
+        let mut names: Vec<_> = gen.iter().map(|gen| gen.contract_name.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(names, ["IToken", "Token"]);
+
+        let bindings_dir = tmp.root().join("bindings");
+        gen.build().unwrap().write_to_module(&bindings_dir, false).unwrap();
+
+        let content = fs::read_to_string(bindings_dir.join("mod.rs")).unwrap();
+        assert!(content.contains("pub mod i_token"));
+        assert!(content.contains("pub mod token"));
+        assert!(content.contains("pub mod shared_types"));
+
+        let content = fs::read_to_string(bindings_dir.join("shared_types.rs")).unwrap();
+        assert!(content.contains("pub struct Balance {"));
+
+        let content = fs::read_to_string(bindings_dir.join("i_token.rs")).unwrap();
+        assert!(content.contains("pub fn balance"));
+        assert!(!content.contains("pub fn deploy"));
+        assert!(!content.contains("pub struct Balance {"));
+
+        let content = fs::read_to_string(bindings_dir.join("token.rs")).unwrap();
+        assert!(content.contains("pub fn deploy"));
+    }
 }
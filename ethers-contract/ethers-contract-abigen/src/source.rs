@@ -1,6 +1,6 @@
 //! Module implements reading of contract artifacts from various sources.
 use super::util;
-use ethers_core::types::Address;
+use ethers_core::types::{Address, Chain};
 
 use crate::util::resolve_path;
 use cfg_if::cfg_if;
@@ -29,6 +29,18 @@ pub enum Source {
     /// An address of a mainnet contract that has been verified on snowtrace.io.
     Snowtrace(Address),
 
+    /// An address of a contract that has been verified on the Etherscan compatible explorer of
+    /// the chain, e.g. bscscan.com for the Binance Smart Chain.
+    Explorer {
+        /// The chain the contract is deployed on
+        chain: Chain,
+        /// The address of the contract
+        address: Address,
+        /// The API key of the explorer, if not set the key is read from the env var of the
+        /// explorer, e.g. `ETHERSCAN_API_KEY`
+        api_key: Option<String>,
+    },
+
     /// The package identifier of an npm package with a path to a Truffle
     /// artifact or ABI to be retrieved from `unpkg.io`.
     Npm(String),
@@ -55,6 +67,10 @@ impl Source {
     /// - `etherscan:0xXX..XX` or `https://etherscan.io/address/0xXX..XX`: a address or URL of a
     ///   verified contract on Etherscan.
     ///
+    /// - `etherscan:<chain>:0xXX..XX` or the URL of the address on an Etherscan compatible
+    ///   explorer, e.g. `etherscan:bsc:0xXX..XX` or `https://bscscan.com/address/0xXX..XX`: a
+    ///   verified contract on the explorer of the chain, see [`Chain`] for the chain names.
+    ///
     /// - `npm:@org/package@1.0.0/path/to/contract.json` an npmjs package with an optional version
     ///   and path (defaulting to the latest version and `index.js`). The contract ABI will be
     ///   retrieved through `unpkg.io`.
@@ -117,9 +133,25 @@ impl Source {
                         .next()
                         .ok_or_else(|| eyre!("HTTP URL does not have a path"))?,
                 ),
+                Some(host) => match explorer_chain(host) {
+                    Some(chain) => Source::explorer(
+                        chain,
+                        url.path()
+                            .rsplit('/')
+                            .next()
+                            .ok_or_else(|| eyre!("HTTP URL does not have a path"))?,
+                    ),
+                    None => Ok(Source::Http(url)),
+                },
                 _ => Ok(Source::Http(url)),
             },
-            "etherscan" => Source::etherscan(url.path()),
+            "etherscan" => match url.path().split_once(':') {
+                Some((chain, address)) => Source::explorer(
+                    chain.parse().map_err(|err| eyre!("invalid Etherscan source: {}", err))?,
+                    address,
+                ),
+                None => Source::etherscan(url.path()),
+            },
             "polygonscan" => Source::polygonscan(url.path()),
             "snowtrace" => Source::snowtrace(url.path()),
             "npm" => Ok(Source::npm(url.path())),
@@ -170,6 +202,17 @@ impl Source {
         Ok(Source::Snowtrace(address))
     }
 
+    /// Creates a source of a contract verified on the explorer of the chain from an address
+    /// string.
+    pub fn explorer<S>(chain: Chain, address: S) -> Result<Self>
+    where
+        S: AsRef<str>,
+    {
+        let address = util::parse_address(address)
+            .with_context(|| format!("failed to parse address for {} explorer source", chain))?;
+        Ok(Source::Explorer { chain, address, api_key: None })
+    }
+
     /// Creates an Etherscan source from an address string.
     pub fn npm<S>(package_path: S) -> Self
     where
@@ -190,6 +233,7 @@ impl Source {
                     Source::Etherscan(_) => panic!("Etherscan abi location are not supported for wasm"),
                     Source::Polygonscan(_) => panic!("Polygonscan abi location are not supported for wasm"),
                     Source::Snowtrace(_) => panic!("Snowtrace abi location are not supported for wasm"),
+                    Source::Explorer { .. } => panic!("Explorer abi location are not supported for wasm"),
                     Source::Npm(_) => panic!("npm abi location are not supported for wasm"),
                     Source::String(abi) => Ok(abi.clone()),
                 }
@@ -197,9 +241,12 @@ impl Source {
                 match self {
                     Source::Local(path) => get_local_contract(path),
                     Source::Http(url) => get_http_contract(url),
-                    Source::Etherscan(address) => get_etherscan_contract(*address, "etherscan.io"),
-                    Source::Polygonscan(address) => get_etherscan_contract(*address, "polygonscan.com"),
-                    Source::Snowtrace(address) => get_etherscan_contract(*address, "snowtrace.io"),
+                    Source::Etherscan(address) => get_etherscan_contract(&cache_dir(), Chain::Mainnet, *address, None),
+                    Source::Polygonscan(address) => get_etherscan_contract(&cache_dir(), Chain::Polygon, *address, None),
+                    Source::Snowtrace(address) => get_etherscan_contract(&cache_dir(), Chain::Avalanche, *address, None),
+                    Source::Explorer { chain, address, api_key } => {
+                        get_etherscan_contract(&cache_dir(), *chain, *address, api_key.as_deref())
+                    }
                     Source::Npm(package) => get_npm_contract(package),
                     Source::String(abi) => Ok(abi.clone()),
                 }
@@ -252,43 +299,124 @@ fn get_http_contract(url: &Url) -> Result<String> {
     Ok(json)
 }
 
-/// Retrieves a contract ABI from the Etherscan HTTP API and wraps it in an
-/// artifact JSON for compatibility with the code generation facilities.
+/// Retrieves a contract ABI from the Etherscan compatible HTTP API of the chain and wraps it in
+/// an artifact JSON for compatibility with the code generation facilities.
+///
+/// The ABIs of verified contracts are cached in `cache_dir`, so that the API is only queried once
+/// per contract. Sources use the directory returned by [`cache_dir`].
 #[cfg(not(target_arch = "wasm32"))]
-fn get_etherscan_contract(address: Address, domain: &str) -> Result<String> {
+fn get_etherscan_contract(
+    cache_dir: &Path,
+    chain: Chain,
+    address: Address,
+    api_key: Option<&str>,
+) -> Result<String> {
+    let cache_path = cache_dir.join(format!("{}-{:?}.json", chain as u64, address));
+    if let Ok(abi) = fs::read_to_string(&cache_path) {
+        return Ok(abi)
+    }
+
     // NOTE: We do not retrieve the bytecode since deploying contracts with the
     //   same bytecode is unreliable as the libraries have already linked and
     //   probably don't reference anything when deploying on other networks.
-    let api_key = {
-        let key_res = match domain {
-            "etherscan.io" => env::var("ETHERSCAN_API_KEY").ok(),
-            "polygonscan.com" => env::var("POLYGONSCAN_API_KEY").ok(),
-            "snowtrace.io" => env::var("SNOWTRACE_API_KEY").ok(),
-            _ => None,
-        };
-        key_res.map(|key| format!("&apikey={}", key)).unwrap_or_default()
-    };
+    let (api_url, api_key_var) = explorer_api(chain)?;
+    let api_key = api_key
+        .map(str::to_owned)
+        .or_else(|| env::var(api_key_var).ok())
+        .map(|key| format!("&apikey={}", key))
+        .unwrap_or_default();
 
     let abi_url = format!(
-        "http://api.{}/api?module=contract&action=getabi&address={:?}&format=raw{}",
-        domain, address, api_key,
+        "{}?module=contract&action=getabi&address={:?}&format=raw{}",
+        api_url, address, api_key,
     );
-    let abi =
-        util::http_get(&abi_url).context(format!("failed to retrieve ABI from {}", domain))?;
+    let abi = util::http_get(&abi_url)
+        .with_context(|| format!("failed to retrieve ABI from {}", api_url))?;
 
     if abi.starts_with("Contract source code not verified") {
-        eyre::bail!("Contract source code not verified: {:?}", address);
+        eyre::bail!(
+            "Contract source code not verified: {:?} on {}, only the ABIs of verified contracts \
+             can be retrieved",
+            address,
+            chain
+        );
     }
     if abi.starts_with('{') && abi.contains("Max rate limit reached") {
         eyre::bail!(
-            "Max rate limit reached, please use etherscan API Key for higher rate limit: {:?}",
+            "Max rate limit reached, please set the {} env var for a higher rate limit: {:?}",
+            api_key_var,
             address
         );
     }
+    if abi.starts_with('{') {
+        // an error response, e.g. `{"status":"0","message":"NOTOK","result":"Invalid API Key"}`
+        let result = serde_json::from_str::<serde_json::Value>(&abi)
+            .ok()
+            .and_then(|res| res.get("result").and_then(|res| res.as_str()).map(str::to_owned))
+            .unwrap_or(abi);
+        eyre::bail!("failed to retrieve ABI of {:?} on {}: {}", address, chain, result);
+    }
+
+    // failing to cache the ABI is not an error, it is retrieved again on the next build
+    let _ = fs::create_dir_all(cache_dir).and_then(|_| fs::write(&cache_path, &abi));
 
     Ok(abi)
 }
 
+/// Returns the directory the ABIs retrieved from explorers are cached in, this is the
+/// `ETHERS_ABIGEN_CACHE` env var if set and `ethers-abigen` in the temporary directory otherwise
+#[cfg(not(target_arch = "wasm32"))]
+fn cache_dir() -> std::path::PathBuf {
+    env::var_os("ETHERS_ABIGEN_CACHE")
+        .map(Into::into)
+        .unwrap_or_else(|| env::temp_dir().join("ethers-abigen"))
+}
+
+/// Returns the API URL of the Etherscan compatible explorer of the chain and the env var of its
+/// API key
+#[cfg(not(target_arch = "wasm32"))]
+fn explorer_api(chain: Chain) -> Result<(&'static str, &'static str)> {
+    let (api_url, _) = chain
+        .etherscan_urls()
+        .ok_or_else(|| eyre::format_err!("there is no supported explorer for {}", chain))?;
+    let api_key_var = match chain {
+        Chain::Polygon | Chain::PolygonMumbai => "POLYGONSCAN_API_KEY",
+        Chain::Avalanche | Chain::AvalancheFuji => "SNOWTRACE_API_KEY",
+        Chain::Fantom | Chain::FantomTestnet => "FTMSCAN_API_KEY",
+        Chain::BinanceSmartChain | Chain::BinanceSmartChainTestnet => "BSCSCAN_API_KEY",
+        Chain::Arbitrum | Chain::ArbitrumTestnet => "ARBISCAN_API_KEY",
+        Chain::Cronos | Chain::CronosTestnet => "CRONOSCAN_API_KEY",
+        Chain::Moonbeam | Chain::MoonbeamDev | Chain::Moonriver => "MOONSCAN_API_KEY",
+        _ => "ETHERSCAN_API_KEY",
+    };
+    Ok((api_url, api_key_var))
+}
+
+/// Returns the chain of the Etherscan compatible explorer with the host name
+fn explorer_chain(host: &str) -> Option<Chain> {
+    let chain = match host {
+        "ropsten.etherscan.io" => Chain::Ropsten,
+        "kovan.etherscan.io" => Chain::Kovan,
+        "rinkeby.etherscan.io" => Chain::Rinkeby,
+        "goerli.etherscan.io" => Chain::Goerli,
+        "optimistic.etherscan.io" => Chain::Optimism,
+        "kovan-optimistic.etherscan.io" => Chain::OptimismKovan,
+        "mumbai.polygonscan.com" => Chain::PolygonMumbai,
+        "testnet.snowtrace.io" => Chain::AvalancheFuji,
+        "ftmscan.com" => Chain::Fantom,
+        "testnet.ftmscan.com" => Chain::FantomTestnet,
+        "bscscan.com" => Chain::BinanceSmartChain,
+        "testnet.bscscan.com" => Chain::BinanceSmartChainTestnet,
+        "arbiscan.io" => Chain::Arbitrum,
+        "testnet.arbiscan.io" => Chain::ArbitrumTestnet,
+        "cronoscan.com" => Chain::Cronos,
+        "moonbeam.moonscan.io" => Chain::Moonbeam,
+        "moonriver.moonscan.io" => Chain::Moonriver,
+        _ => return None,
+    };
+    Some(chain)
+}
+
 /// Retrieves a Truffle artifact or ABI from an npm package through `unpkg.io`.
 #[cfg(not(target_arch = "wasm32"))]
 fn get_npm_contract(package: &str) -> Result<String> {
@@ -337,6 +465,19 @@ mod tests {
                 "https://snowtrace.io/address/0x0001020304050607080910111213141516171819",
                 Source::snowtrace("0x0001020304050607080910111213141516171819").unwrap(),
             ),
+            (
+                "etherscan:goerli:0x0001020304050607080910111213141516171819",
+                Source::explorer(Chain::Goerli, "0x0001020304050607080910111213141516171819")
+                    .unwrap(),
+            ),
+            (
+                "https://bscscan.com/address/0x0001020304050607080910111213141516171819",
+                Source::explorer(
+                    Chain::BinanceSmartChain,
+                    "0x0001020304050607080910111213141516171819",
+                )
+                .unwrap(),
+            ),
             (
                 "npm:@openzeppelin/contracts@2.5.0/build/contracts/IERC20.json",
                 Source::npm("@openzeppelin/contracts@2.5.0/build/contracts/IERC20.json"),
@@ -345,6 +486,11 @@ mod tests {
             let source = Source::with_root(root, url).unwrap();
             assert_eq!(source, *expected);
         }
+        assert!(Source::with_root(
+            root,
+            "etherscan:unknown:0x0001020304050607080910111213141516171819"
+        )
+        .is_err());
 
         let src = r#"[{"constant":true,"inputs":[],"name":"name","outputs":[{"name":"name","type":"string"}],"payable":false,"type":"function"},{"constant":true,"inputs":[],"name":"symbol","outputs":[{"name":"symbol","type":"string"}],"payable":false,"type":"function"},{"constant":true,"inputs":[],"name":"decimals","outputs":[{"name":"decimals","type":"uint8"}],"payable":false,"type":"function"},{"constant":false,"inputs":[{"name":"spender","type":"address"},{"name":"value","type":"uint256"}],"name":"approve","outputs":[{"name":"success","type":"bool"}],"payable":false,"type":"function"},{"constant":true,"inputs":[],"name":"totalSupply","outputs":[{"name":"totalSupply","type":"uint256"}],"payable":false,"type":"function"},{"constant":false,"inputs":[{"name":"from","type":"address"},{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"name":"transferFrom","outputs":[{"name":"success","type":"bool"}],"payable":false,"type":"function"},{"constant":true,"inputs":[{"name":"who","type":"address"}],"name":"balanceOf","outputs":[{"name":"balance","type":"uint256"}],"payable":false,"type":"function"},{"constant":false,"inputs":[{"name":"to","type":"address"},{"name":"value","type":"uint256"}],"name":"transfer","outputs":[{"name":"success","type":"bool"}],"payable":false,"type":"function"},{"constant":true,"inputs":[{"name":"owner","type":"address"},{"name":"spender","type":"address"}],"name":"allowance","outputs":[{"name":"remaining","type":"uint256"}],"payable":false,"type":"function"},{"anonymous":false,"inputs":[{"indexed":true,"name":"owner","type":"address"},{"indexed":true,"name":"spender","type":"address"},{"indexed":false,"name":"value","type":"uint256"}],"name":"Approval","type":"event"},{"anonymous":false,"inputs":[{"indexed":true,"name":"from","type":"address"},{"indexed":true,"name":"to","type":"address"},{"indexed":false,"name":"value","type":"uint256"}],"name":"Transfer","type":"event"}]"#;
         let parsed = Source::parse(src).unwrap();
@@ -358,6 +504,19 @@ mod tests {
         assert_eq!(hardhat_parsed, Source::String(hardhat_src));
     }
 
+    #[test]
+    fn reads_cached_explorer_abi() {
+        let cache = tempfile::tempdir().unwrap();
+        let address: Address = "0x0001020304050607080910111213141516171819".parse().unwrap();
+        let abi = r#"[{"inputs":[],"name":"foo","outputs":[],"type":"function"}]"#;
+        fs::write(cache.path().join("137-0x0001020304050607080910111213141516171819.json"), abi)
+            .unwrap();
+        assert_eq!(
+            get_etherscan_contract(cache.path(), Chain::Polygon, address, None).unwrap(),
+            abi
+        );
+    }
+
     #[test]
     #[ignore]
     fn get_etherscan_contract() {
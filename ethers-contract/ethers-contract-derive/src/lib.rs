@@ -37,21 +37,29 @@ pub(crate) mod utils;
 /// abigen!(MyContract, "etherscan:0x0001020304050607080910111213141516171819");
 /// abigen!(MyContract, "https://etherscan.io/address/0x0001020304050607080910111213141516171819");
 ///
+/// // Etherscan compatible explorers of other chains
+/// abigen!(MyContract, "etherscan:polygon:0x0001020304050607080910111213141516171819");
+/// abigen!(MyContract, "https://bscscan.com/address/0x0001020304050607080910111213141516171819");
+///
 /// // npmjs
 /// abigen!(MyContract, "npm:@org/package@1.0.0/path/to/contract.json");
 /// ```
 ///
 /// Note that Etherscan rate-limits requests to their API, to avoid this an
 /// `ETHERSCAN_API_KEY` environment variable can be set. If it is, it will use
-/// that API key when retrieving the contract ABI.
+/// that API key when retrieving the contract ABI. The other explorers use their own env var,
+/// e.g. `POLYGONSCAN_API_KEY` or `BSCSCAN_API_KEY`. The ABIs of verified contracts are cached in
+/// `$ETHERS_ABIGEN_CACHE`, or `ethers-abigen` in the temporary directory, so the explorer is only
+/// queried on the first build.
 ///
 /// Currently, the proc macro accepts additional parameters to configure some
 /// aspects of the code generation. Specifically it accepts:
 /// - `methods`: A list of mappings from method signatures to method names allowing methods names to
 ///   be explicitely set for contract methods. This also provides a workaround for generating code
 ///   for contracts with multiple methods with the same name.
-/// - `event_derives`: A list of additional derives that should be added to contract event structs
-///   and enums.
+/// - `derives`: A list of additional derives that should be added to all generated structs and
+///   enums, i.e. the contract events, calls and ABI structs, e.g. `serde::Serialize` and
+///   `serde::Deserialize`. This was formerly named `event_derives`, which is still accepted.
 ///
 /// # Example
 ///
@@ -62,7 +70,7 @@ pub(crate) mod utils;
 ///     methods {
 ///         myMethod(uint256,bool) as my_renamed_method;
 ///     },
-///     event_derives (serde::Deserialize, serde::Serialize),
+///     derives (serde::Deserialize, serde::Serialize),
 /// );
 /// ```
 ///
@@ -82,11 +90,11 @@ pub(crate) mod utils;
 ///     methods {
 ///         myMethod(uint256,bool) as my_renamed_method;
 ///     },
-///     event_derives (serde::Deserialize, serde::Serialize);
+///     derives (serde::Deserialize, serde::Serialize);
 ///
 ///     MyOtherContract,
 ///     "path/to/MyOtherContract.json",
-///     event_derives (serde::Deserialize, serde::Serialize);
+///     derives (serde::Deserialize, serde::Serialize);
 /// );
 /// ```
 #[proc_macro]
@@ -1,9 +1,13 @@
 #![allow(clippy::return_self_not_must_use)]
 
-use crate::{log::LogMeta, stream::EventStream, ContractError, EthLogDecode};
+use crate::{
+    log::LogMeta,
+    stream::{EventStream, EventStreamMeta},
+    ContractError, EthLogDecode,
+};
 
 use ethers_core::{
-    abi::{Detokenize, RawLog},
+    abi::{encode, Detokenize, RawLog, Tokenizable},
     types::{BlockNumber, Filter, Log, ValueOrArray, H256},
 };
 use ethers_providers::{FilterWatcher, Middleware, PubsubClient, SubscriptionStream};
@@ -109,6 +113,20 @@ impl<M, D: EthLogDecode> Event<'_, M, D> {
         self.filter.topics[3] = Some(topic.into());
         self
     }
+
+    /// Sets the filter's topic at `index` to the value of an indexed event parameter. Indexed
+    /// parameters of dynamic types are emitted as their hash, which must be passed instead.
+    ///
+    /// This is used by the topic setters which `abigen` generates for indexed event parameters.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is larger than 3 or the value is not encoded as a single word
+    pub fn indexed<T: Tokenizable>(mut self, index: usize, value: T) -> Self {
+        let topic = H256::from_slice(&encode(&[value.into_token()]));
+        self.filter.topics[index] = Some(topic.into());
+        self
+    }
 }
 
 impl<'a, M, D> Event<'a, M, D>
@@ -128,6 +146,17 @@ where
             self.provider.watch(&self.filter).await.map_err(ContractError::MiddlewareError)?;
         Ok(EventStream::new(filter.id, filter, Box::new(move |log| self.parse_log(log))))
     }
+
+    /// Returns a stream for the event that also yields the [`LogMeta`] of every event, i.e. the
+    /// block, transaction and log index it was emitted at
+    pub async fn stream_with_meta(
+        &'a self,
+    ) -> Result<
+        EventStreamMeta<'a, FilterWatcher<'a, M::Provider, Log>, D, ContractError<M>>,
+        ContractError<M>,
+    > {
+        Ok(self.stream().await?.with_meta())
+    }
 }
 
 impl<'a, M, D> Event<'a, M, D>
@@ -151,6 +180,17 @@ where
             .map_err(ContractError::MiddlewareError)?;
         Ok(EventStream::new(filter.id, filter, Box::new(move |log| self.parse_log(log))))
     }
+
+    /// Returns a subscription for the event that also yields the [`LogMeta`] of every event, see
+    /// [`Event::stream_with_meta`]
+    pub async fn subscribe_with_meta(
+        &'a self,
+    ) -> Result<
+        EventStreamMeta<'a, SubscriptionStream<'a, M::Provider, Log>, D, ContractError<M>>,
+        ContractError<M>,
+    > {
+        Ok(self.subscribe().await?.with_meta())
+    }
 }
 
 impl<M, D> Event<'_, M, D>
@@ -23,7 +23,7 @@ pub use log::{decode_logs, EthLogDecode, LogMeta};
 pub mod stream;
 
 mod multicall;
-pub use multicall::Multicall;
+pub use multicall::{Multicall, MULTICALL3_ADDRESS};
 
 /// This module exposes low lever builder structures which are only consumed by the
 /// type-safe ABI bindings generators.
@@ -1,6 +1,6 @@
 use ethers_core::{
     abi::{Detokenize, Function, Token},
-    types::{Address, BlockNumber, Bytes, Chain, NameOrAddress, TxHash, U256},
+    types::{Address, BlockNumber, Bytes, Chain, NameOrAddress, TxHash, H160, U256},
 };
 use ethers_providers::Middleware;
 
@@ -35,6 +35,16 @@ pub static ADDRESS_BOOK: Lazy<HashMap<U256, Address>> = Lazy::new(|| {
     .into()
 });
 
+/// The address of the [`Multicall3` contract](https://github.com/mds1/multicall), which is
+/// deployed at the same address on most networks.
+///
+/// Unlike the Multicall contracts of the [`ADDRESS_BOOK`], it supports
+/// [`Multicall::aggregate3`], which tolerates failing calls.
+pub const MULTICALL3_ADDRESS: Address = H160([
+    0xca, 0x11, 0xbd, 0xe0, 0x59, 0x77, 0xb3, 0x63, 0x11, 0x67, 0x02, 0x88, 0x62, 0xbe, 0x2a, 0x17,
+    0x39, 0x76, 0xca, 0x11,
+]);
+
 /// A Multicall is an abstraction for sending batched calls/transactions to the Ethereum blockchain.
 /// It stores an instance of the [`Multicall` smart contract](https://etherscan.io/address/0xeefba1e63905ef1d7acba5a8513c70307c1ce441#code)
 /// and the user provided list of transactions to be made.
@@ -145,6 +155,7 @@ pub struct Call {
     target: Address,
     data: Bytes,
     function: Function,
+    allow_failure: bool,
 }
 
 impl<M: Middleware> Multicall<M> {
@@ -200,9 +211,30 @@ impl<M: Middleware> Multicall<M> {
 
     /// Appends a `call` to the list of calls for the Multicall instance
     pub fn add_call<D: Detokenize>(&mut self, call: ContractCall<M, D>) -> &mut Self {
+        self.push_call(call, false)
+    }
+
+    /// Appends a `call` that is allowed to fail to the list of calls for the Multicall instance.
+    ///
+    /// Failing calls are only tolerated by [`aggregate3`](Multicall::aggregate3), all other
+    /// methods treat this like [`add_call`](Multicall::add_call).
+    pub fn add_call_allow_failure<D: Detokenize>(&mut self, call: ContractCall<M, D>) -> &mut Self {
+        self.push_call(call, true)
+    }
+
+    fn push_call<D: Detokenize>(
+        &mut self,
+        call: ContractCall<M, D>,
+        allow_failure: bool,
+    ) -> &mut Self {
         match (call.tx.to(), call.tx.data()) {
             (Some(NameOrAddress::Address(target)), Some(data)) => {
-                let call = Call { target: *target, data: data.clone(), function: call.function };
+                let call = Call {
+                    target: *target,
+                    data: data.clone(),
+                    function: call.function,
+                    allow_failure,
+                };
                 self.calls.push(call);
                 self
             }
@@ -339,18 +371,96 @@ impl<M: Middleware> Multicall<M> {
             .calls
             .iter()
             .zip(&return_data)
-            .map(|(call, bytes)| {
-                let mut tokens: Vec<Token> = call.function.decode_output(bytes.as_ref())?;
-                Ok(match tokens.len() {
-                    0 => Token::Tuple(vec![]),
-                    1 => tokens.remove(0),
-                    _ => Token::Tuple(tokens),
-                })
-            })
+            .map(|(call, bytes)| Ok(output_token(call.function.decode_output(bytes.as_ref())?)))
             .collect::<Result<Vec<Token>, ContractError<M>>>()?;
         Ok(tokens)
     }
 
+    /// Queries the Ethereum blockchain via an `eth_call` of the `aggregate3` function of the
+    /// [Multicall3 contract](MULTICALL3_ADDRESS), which returns the revert data of the calls
+    /// added with [`add_call_allow_failure`](Multicall::add_call_allow_failure) instead of
+    /// failing.
+    ///
+    /// All calls must return the same type, use
+    /// [`aggregate3_raw`](Multicall::aggregate3_raw) for calls with different return types.
+    ///
+    /// ```no_run
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// # use ethers_core::{abi::Abi, types::{Address, U256}};
+    /// # use ethers_providers::{Provider, Http};
+    /// # use ethers_contract::{Contract, Multicall, MULTICALL3_ADDRESS};
+    /// # use std::{convert::TryFrom, sync::Arc};
+    /// #
+    /// # let client = Arc::new(Provider::<Http>::try_from("http://localhost:8545")?);
+    /// # let abi: Abi = serde_json::from_str("")?;
+    /// # let token = Contract::<Provider<Http>>::new(Address::zero(), abi.clone(), client.clone());
+    /// # let other_token = Contract::<Provider<Http>>::new(Address::zero(), abi, client.clone());
+    /// # let owner = Address::zero();
+    /// let mut multicall = Multicall::new(client, Some(MULTICALL3_ADDRESS)).await?;
+    /// multicall
+    ///     .add_call_allow_failure(token.method::<_, U256>("balanceOf", owner)?)
+    ///     .add_call_allow_failure(other_token.method::<_, U256>("balanceOf", owner)?);
+    ///
+    /// for result in multicall.aggregate3::<U256>().await? {
+    ///     match result {
+    ///         Ok(balance) => println!("balance: {}", balance),
+    ///         Err(revert_data) => println!("reverted: {}", revert_data),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Note: this method _does not_ send a transaction from your account
+    pub async fn aggregate3<D: Detokenize>(
+        &self,
+    ) -> Result<Vec<Result<D, Bytes>>, ContractError<M>> {
+        self.aggregate3_raw()
+            .await?
+            .into_iter()
+            .map(|result| match result {
+                Ok(token) => Ok(Ok(D::from_tokens(vec![token])?)),
+                Err(revert_data) => Ok(Err(revert_data)),
+            })
+            .collect()
+    }
+
+    /// Queries the Ethereum blockchain via an `eth_call` of the `aggregate3` function of the
+    /// [Multicall3 contract](MULTICALL3_ADDRESS), without detokenization.
+    ///
+    /// Returns the decoded output of each successful call, or the revert data of each call that
+    /// was allowed to fail and reverted or returned data that could not be decoded, e.g. because
+    /// the target is not a contract. The whole aggregate fails if any other call fails.
+    ///
+    /// Note: this method _does not_ send a transaction from your account
+    pub async fn aggregate3_raw(&self) -> Result<Vec<Result<Token, Bytes>>, ContractError<M>> {
+        let calls = self
+            .calls
+            .iter()
+            .map(|call| (call.target, call.allow_failure, call.data.clone()))
+            .collect();
+        let mut contract_call = self.contract.aggregate_3(calls);
+        if let Some(block) = self.block {
+            contract_call = contract_call.block(block)
+        };
+
+        let return_data = contract_call.call().await?;
+        self.calls
+            .iter()
+            .zip(return_data)
+            .map(|(call, (success, data))| {
+                if !success {
+                    return Ok(Err(data))
+                }
+                match call.function.decode_output(data.as_ref()) {
+                    Ok(tokens) => Ok(Ok(output_token(tokens))),
+                    Err(_) if call.allow_failure => Ok(Err(data)),
+                    Err(err) => Err(err.into()),
+                }
+            })
+            .collect()
+    }
+
     /// Signs and broadcasts a batch of transactions by using the Multicall contract as proxy.
     ///
     /// ```no_run
@@ -396,3 +506,12 @@ impl<M: Middleware> Multicall<M> {
         contract_call
     }
 }
+
+/// Returns the decoded output of a call as a single token
+fn output_token(mut tokens: Vec<Token>) -> Token {
+    match tokens.len() {
+        0 => Token::Tuple(vec![]),
+        1 => tokens.remove(0),
+        _ => Token::Tuple(tokens),
+    }
+}
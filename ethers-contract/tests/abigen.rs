@@ -210,6 +210,48 @@ fn can_gen_human_readable_with_structs() {
     assert_eq!(encoded_call, contract_call.encode().into());
 }
 
+#[test]
+fn can_derive_serde() {
+    abigen!(
+        SimpleContract,
+        r#"[
+        struct Point { uint256 x; address owner; }
+        event Moved(address indexed owner, bytes data)
+        event Burned(address indexed, uint256)
+        function setPoint(Point memory point)
+        function burn(uint256, bytes32) external
+    ]"#,
+        derives(serde::Deserialize, serde::Serialize)
+    );
+
+    fn round_trip<T>(value: T, json: serde_json::Value)
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + PartialEq + std::fmt::Debug,
+    {
+        assert_eq!(serde_json::to_value(&value).unwrap(), json);
+        assert_eq!(serde_json::from_value::<T>(json).unwrap(), value);
+    }
+
+    let owner = Address::repeat_byte(0xee);
+    let point = Point { x: 1337u64.into(), owner };
+    round_trip(point.clone(), serde_json::json!({ "x": "0x539", "owner": format!("{:?}", owner) }));
+    round_trip(
+        SimpleContractCalls::SetPoint(SetPointCall { point }),
+        serde_json::json!({ "SetPoint": { "point": { "x": "0x539", "owner": format!("{:?}", owner) } } }),
+    );
+    // structs of unnamed parameters are serialized as arrays
+    let hash = [1u8; 32];
+    round_trip(BurnCall(1u64.into(), hash), serde_json::json!(["0x1", hash]));
+    round_trip(
+        MovedFilter { owner, data: vec![0xab, 0xcd].into() },
+        serde_json::json!({ "owner": format!("{:?}", owner), "data": "0xabcd" }),
+    );
+    round_trip(
+        SimpleContractEvents::BurnedFilter(BurnedFilter(owner, 1u64.into())),
+        serde_json::json!({ "BurnedFilter": [format!("{:?}", owner), "0x1"] }),
+    );
+}
+
 #[test]
 fn can_handle_overloaded_functions() {
     abigen!(
@@ -481,6 +523,21 @@ fn can_handle_case_sensitive_calls() {
     let _ = contract.INDEX();
 }
 
+#[test]
+fn can_deploy_with_struct_and_array_args() {
+    abigen!(DeployArgs, "./tests/solidity-contracts/DeployArgs.json");
+
+    let (client, _mock) = Provider::mocked();
+    let config = Config { owner: Address::repeat_byte(1), limit: 100u64.into() };
+    let values = vec![U256::from(1u64), U256::from(2u64)];
+    let deployer =
+        DeployArgs::deploy(Arc::new(client), (config.clone(), values.clone())).unwrap().deployer;
+
+    let mut expected = DEPLOYARGS_BYTECODE.to_vec();
+    expected.extend(ethers_core::abi::encode(&[config.into_token(), values.into_token()]));
+    assert_eq!(deployer.tx.data().unwrap().to_vec(), expected);
+}
+
 #[tokio::test]
 async fn can_deploy_greeter() {
     abigen!(Greeter, "ethers-contract/tests/solidity-contracts/greeter.json",);
@@ -570,6 +627,31 @@ fn can_handle_overloaded_events() {
     let _ev2 = ActionPaused2Filter { action: "action".to_string(), pause_state: false };
 }
 
+#[test]
+fn can_filter_indexed_event_params() {
+    abigen!(
+        SimpleContract,
+        r#"[
+            event Transfer(address indexed from, address indexed to, uint256 value)
+    ]"#
+    );
+
+    let (provider, _mock) = Provider::mocked();
+    let contract = SimpleContract::new(Address::zero(), Arc::new(provider));
+    let from = Address::repeat_byte(1);
+    let to = Address::repeat_byte(2);
+
+    let filter = contract.transfer_filter().with_to(to).filter;
+    assert_eq!(filter.topics[0], Some(TransferFilter::signature().into()));
+    assert_eq!(filter.topics[1], None);
+    assert_eq!(filter.topics[2], Some(to.into()));
+
+    let filter = contract.transfer_filter().with_from(from).with_to(to).from_block(1u64).filter;
+    assert_eq!(filter.topics[1], Some(from.into()));
+    assert_eq!(filter.topics[2], Some(to.into()));
+    assert_eq!(filter.topics[3], None);
+}
+
 #[tokio::test]
 #[cfg(not(feature = "celo"))]
 async fn can_send_struct_param() {
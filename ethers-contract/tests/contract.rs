@@ -276,6 +276,11 @@ mod eth_tests {
         let mut subscription_meta = event2.subscribe().await.unwrap().with_meta();
         assert_eq!(subscription_meta.0.id, 3.into());
 
+        let mut subscription_with_meta = event2.subscribe_with_meta().await.unwrap();
+        assert_eq!(subscription_with_meta.0.id, 4.into());
+
+        let mut stream_meta = event.stream_with_meta().await.unwrap();
+
         let num_calls = 3u64;
 
         // and we make a few calls
@@ -291,7 +296,13 @@ mod eth_tests {
             let log = stream.next().await.unwrap().unwrap();
             let log2 = subscription.next().await.unwrap().unwrap();
             let (log3, meta) = subscription_meta.next().await.unwrap().unwrap();
+            let (log4, meta4) = stream_meta.next().await.unwrap().unwrap();
+            let (log5, meta5) = subscription_with_meta.next().await.unwrap().unwrap();
             assert_eq!(log.new_value, log3.new_value);
+            assert_eq!(log.new_value, log4.new_value);
+            assert_eq!(log.new_value, log5.new_value);
+            assert_eq!(meta, meta4);
+            assert_eq!(meta, meta5);
             assert_eq!(log.new_value, log2.new_value);
             assert_eq!(log.new_value, i.to_string());
             assert_eq!(meta.block_number, num + i + 1);
@@ -536,6 +547,47 @@ mod eth_tests {
             })
             .collect();
         assert_eq!(results, ["many"; 17]);
+
+        // `aggregate3` returns the revert data of the calls that are allowed to fail, the
+        // SimpleStorage contract has no `getValues` function and reverts
+        let missing = not_so_simple_contract
+            .at(simple_contract.address())
+            .method::<_, (String, Address)>("getValues", ())
+            .unwrap();
+        multicall
+            .clear_calls()
+            .add_call(simple_contract.method::<_, String>("getValue", ()).unwrap())
+            .add_call_allow_failure(missing.clone())
+            .add_call_allow_failure(
+                not_so_simple_contract.method::<_, (String, Address)>("getValues", ()).unwrap(),
+            );
+        let results = multicall.aggregate3_raw().await.unwrap();
+        assert_eq!(results[0], Ok(Token::String("many".to_string())));
+        assert_eq!(results[1], Err(Bytes::default()));
+        assert_eq!(
+            results[2],
+            Ok(Token::Tuple(vec![
+                Token::String("second reset again".to_string()),
+                Token::Address(multicall_contract.address()),
+            ]))
+        );
+
+        // the same calls, decoded as a single type
+        multicall.clear_calls().add_call_allow_failure(missing.clone()).add_call(
+            not_so_simple_contract.method::<_, (String, Address)>("getValues", ()).unwrap(),
+        );
+        let results = multicall.aggregate3::<(String, Address)>().await.unwrap();
+        assert_eq!(
+            results,
+            vec![
+                Err(Bytes::default()),
+                Ok(("second reset again".to_string(), multicall_contract.address())),
+            ]
+        );
+
+        // the aggregate fails if a call that is not allowed to fail reverts
+        multicall.clear_calls().add_call(missing);
+        assert!(multicall.aggregate3_raw().await.is_err());
     }
 
     #[tokio::test]
@@ -615,6 +667,7 @@ mod eth_tests {
         };
 
         let sig = wallet.sign_typed_data(&foo_bar).await.expect("failed to sign typed data");
+        assert_eq!(sig.recover_typed(&foo_bar).unwrap(), wallet.address());
 
         let r = <[u8; 32]>::try_from(sig.r)
             .expect("failed to parse 'r' value from signature into [u8; 32]");
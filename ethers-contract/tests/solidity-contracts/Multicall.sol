@@ -20,6 +20,24 @@ contract Multicall {
             returnData[i] = ret;
         }
     }
+    struct Call3 {
+        address target;
+        bool allowFailure;
+        bytes callData;
+    }
+    struct Result {
+        bool success;
+        bytes returnData;
+    }
+    // aggregate3 of Multicall3, which tolerates failing calls that are allowed to fail
+    function aggregate3(Call3[] memory calls) public payable returns (Result[] memory returnData) {
+        returnData = new Result[](calls.length);
+        for(uint256 i = 0; i < calls.length; i++) {
+            (bool success, bytes memory ret) = calls[i].target.call(calls[i].callData);
+            require(success || calls[i].allowFailure, "Multicall3: call failed");
+            returnData[i] = Result(success, ret);
+        }
+    }
     // Helper functions
     function getEthBalance(address addr) public view returns (uint256 balance) {
         balance = addr.balance;
@@ -82,6 +82,9 @@ impl AbiParser {
             if line.starts_with("event") {
                 let event = self.parse_event(line)?;
                 abi.events.entry(event.name.clone()).or_default().push(event);
+            } else if line.starts_with("error ") {
+                let error = self.parse_error(line)?;
+                abi.errors.entry(error.name.clone()).or_default().push(error);
             } else if line.starts_with("constructor") {
                 let inputs = self
                     .constructor_inputs(line)?
@@ -207,6 +210,37 @@ impl AbiParser {
         }
     }
 
+    /// Parses a solidity error declaration from `error <name> (args*)`
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_core::abi::AbiParser;
+    /// let error = AbiParser::default()
+    ///     .parse_error("error InsufficientBalance(uint256 available, uint256 required)").unwrap();
+    /// assert_eq!(error.inputs.len(), 2);
+    /// ```
+    pub fn parse_error(&self, s: &str) -> Result<ethabi::AbiError> {
+        let mut error = s.trim();
+        if !error.starts_with("error ") {
+            bail!("Not an error `{}`", s)
+        }
+        error = &error[5..];
+
+        let name = parse_identifier(&mut error)?;
+
+        let params = error
+            .trim_start()
+            .strip_prefix('(')
+            .ok_or_else(|| format_err!("Expected leading `(` in `{}`", s))?
+            .trim_end()
+            .strip_suffix(')')
+            .ok_or_else(|| format_err!("Expected closing `)` in `{}`", s))?;
+
+        let inputs = self.parse_params(params)?.into_iter().map(|(input, _)| input).collect();
+        Ok(ethabi::AbiError { name, inputs })
+    }
+
     /// Parse a single event param
     fn parse_event_arg(&self, input: &str) -> Result<EventParam> {
         let mut iter = input.trim().rsplitn(3, is_whitespace);
@@ -732,6 +766,76 @@ mod tests {
         parse(abi).unwrap();
     }
 
+    #[test]
+    fn parses_error() {
+        assert_eq!(
+            AbiParser::default()
+                .parse_error("error InsufficientBalance(uint256 available, uint256 required)")
+                .unwrap(),
+            ethabi::AbiError {
+                name: "InsufficientBalance".to_string(),
+                inputs: vec![
+                    Param {
+                        name: "available".to_string(),
+                        kind: ParamType::Uint(256),
+                        internal_type: None
+                    },
+                    Param {
+                        name: "required".to_string(),
+                        kind: ParamType::Uint(256),
+                        internal_type: None
+                    },
+                ]
+            }
+        );
+        assert!(AbiParser::default()
+            .parse_error("error Unauthorized()")
+            .unwrap()
+            .inputs
+            .is_empty());
+    }
+
+    #[test]
+    fn can_parse_errors_with_structs() {
+        let abi = parse(&[
+            "struct Foo { uint256 a; address b; }",
+            "error InvalidFoo(Foo foo, Foo[] others)",
+            "error InsufficientBalance(uint256 available, uint256 required)",
+            "function foo(Foo memory foo)",
+        ])
+        .unwrap();
+
+        let foo = ParamType::Tuple(vec![ParamType::Uint(256), ParamType::Address]);
+        let error = &abi.errors["InvalidFoo"][0];
+        assert_eq!(error.inputs[0].kind, foo);
+        assert_eq!(error.inputs[1].kind, ParamType::Array(Box::new(foo)));
+        assert_eq!(abi.errors["InsufficientBalance"][0].inputs.len(), 2);
+        assert_eq!(abi.functions.len(), 1);
+    }
+
+    #[test]
+    fn fails_on_malformed_errors_and_structs() {
+        for error in [
+            "error InsufficientBalance(uint256 available",
+            "error InsufficientBalance uint256 available)",
+            "error (uint256 available)",
+            "error InsufficientBalance(Unknown available)",
+            "function error(uint256)",
+        ] {
+            assert!(AbiParser::default().parse_error(error).is_err(), "{}", error);
+        }
+
+        let abis: [&[&str]; 4] = [
+            &["error InsufficientBalance(uint256 available"],
+            &["struct Foo { uint256 a; address b;"],
+            &["struct Foo { Bar a; }"],
+            &["struct Foo { uint256 a; }", "struct Foo { address b; }"],
+        ];
+        for abi in abis {
+            assert!(parse(abi).is_err(), "{:?}", abi);
+        }
+    }
+
     #[test]
     fn can_parse_params() {
         [
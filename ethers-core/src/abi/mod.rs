@@ -19,12 +19,22 @@ pub use codec::{AbiDecode, AbiEncode};
 mod error;
 pub use error::{AbiError, ParseError};
 
+mod revert;
+pub use revert::{decode_revert, panic_description, RevertReason, ERROR_SELECTOR, PANIC_SELECTOR};
+
+mod stream;
+pub use stream::{decode_array_stream, decode_stream, DecodeStream};
+
+mod packed;
+pub use packed::{encode_packed, encode_packed_typed, EncodePackedError};
+
 mod human_readable;
 pub use human_readable::{
     lexer::HumanReadableParser, parse as parse_abi, parse_str as parse_abi_str, AbiParser,
 };
 
 use crate::types::{H256, H512, I256, U128, U256, U64};
+use std::collections::BTreeMap;
 
 /// Extension trait for `ethabi::Function`.
 pub trait FunctionExt {
@@ -70,6 +80,51 @@ impl EventExt for Event {
     }
 }
 
+/// The parameters of a log decoded with [`AbiExt::decode_log_by_topic`], by their name.
+///
+/// Unnamed parameters are keyed by their position in the event's inputs.
+pub type DecodedLog = BTreeMap<String, Token>;
+
+/// Extension trait for `ethabi::Contract`.
+pub trait AbiExt {
+    /// Finds the event of the ABI that emitted the log and decodes its indexed and non-indexed
+    /// parameters.
+    ///
+    /// Events are matched by comparing `topics[0]` against their signature hash. Since different
+    /// events can have the same signature, like ERC20's and ERC721's `Transfer`, the first event
+    /// the log can be decoded with is returned. If no event matches, anonymous events, which
+    /// don't emit their signature hash, are tried.
+    fn decode_log_by_topic(&self, log: &RawLog) -> Option<(Event, DecodedLog)>;
+}
+
+impl AbiExt for Abi {
+    fn decode_log_by_topic(&self, log: &RawLog) -> Option<(Event, DecodedLog)> {
+        let topic = log.topics.first();
+        let (anonymous, events): (Vec<_>, Vec<_>) =
+            self.events().partition(|event| event.anonymous);
+        events
+            .into_iter()
+            .filter(|event| Some(&event.signature()) == topic)
+            .chain(anonymous)
+            .find_map(|event| Some((event.clone(), decode_event_log(event, log)?)))
+    }
+}
+
+/// Decodes the parameters of the log with the event, without comparing its signature hash
+pub(crate) fn decode_event_log(event: &Event, log: &RawLog) -> Option<DecodedLog> {
+    let decoded = event.parse_log(log.clone()).ok()?;
+    let params = decoded
+        .params
+        .into_iter()
+        .enumerate()
+        .map(|(idx, param)| {
+            let name = if param.name.is_empty() { idx.to_string() } else { param.name };
+            (name, param.value)
+        })
+        .collect();
+    Some(params)
+}
+
 /// A trait for types that can be represented in the ethereum ABI.
 pub trait AbiType {
     /// The native ABI type this type represents.
@@ -238,6 +293,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn decode_log_by_topic() {
+        let abi = parse_abi(&[
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+            "event Transfer(address indexed from, address indexed to, uint256 indexed tokenId)",
+            "event Ping(uint256 indexed, bytes32) anonymous",
+        ])
+        .unwrap();
+        let transfer = H256::from(crate::utils::keccak256("Transfer(address,address,uint256)"));
+        let from = H256::from_low_u64_be(1);
+        let to = H256::from_low_u64_be(2);
+
+        let erc20 = RawLog { topics: vec![transfer, from, to], data: U256::from(100u64).encode() };
+        let (event, params) = abi.decode_log_by_topic(&erc20).unwrap();
+        assert!(!event.inputs[2].indexed);
+        assert_eq!(params["value"], Token::Uint(100u64.into()));
+        assert_eq!(params["to"], Token::Address(Address::from_low_u64_be(2)));
+
+        let erc721 =
+            RawLog { topics: vec![transfer, from, to, H256::from_low_u64_be(3)], data: vec![] };
+        let (event, params) = abi.decode_log_by_topic(&erc721).unwrap();
+        assert!(event.inputs[2].indexed);
+        assert_eq!(params["tokenId"], Token::Uint(3u64.into()));
+
+        let ping = RawLog { topics: vec![H256::from_low_u64_be(5)], data: vec![0xff; 32] };
+        let (event, params) = abi.decode_log_by_topic(&ping).unwrap();
+        assert_eq!(event.name, "Ping");
+        assert_eq!(params["0"], Token::Uint(5u64.into()));
+        assert_eq!(params["1"], Token::FixedBytes(vec![0xff; 32]));
+
+        let unknown = RawLog { topics: vec![H256::from_low_u64_be(5), from], data: vec![] };
+        assert!(abi.decode_log_by_topic(&unknown).is_none());
+    }
+
     #[test]
     fn abi_type_works() {
         assert_eq!(ParamType::Bytes, Bytes::param_type());
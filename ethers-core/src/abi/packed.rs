@@ -0,0 +1,322 @@
+//! Non-standard packed ABI encoding, see [`encode_packed`]
+
+use crate::{
+    abi::{ParamType, Token},
+    types::Bytes,
+};
+use thiserror::Error;
+
+/// Error returned by [`encode_packed`] and [`encode_packed_typed`]
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum EncodePackedError {
+    /// Tuples (structs) can't be packed
+    #[error("packed encoding of tuples is not supported")]
+    Tuple,
+    /// Arrays can only contain values of static elementary types
+    #[error("packed encoding of arrays of arrays, bytes or strings is not supported")]
+    NestedArray,
+    /// The number of types and tokens differ
+    #[error("expected {expected} tokens, got {actual}")]
+    LengthMismatch { expected: usize, actual: usize },
+    /// The token is not a value of the type, or does not fit into its width
+    #[error("token {token:?} is not a valid {param}")]
+    TypeMismatch { param: ParamType, token: Token },
+}
+
+/// Encodes the tokens in the non-standard packed mode of Solidity's `abi.encodePacked`:
+///
+/// - `bytes` and `string` are encoded as is, without padding or length
+/// - `address` is encoded as 20 bytes, `bool` as 1 byte and `bytesN` as `N` bytes
+/// - the elements of arrays are padded to 32 bytes, the array has no length
+///
+/// [`Token`] doesn't know the width of integers, so integers are always encoded as 32 bytes like
+/// `uint256` and `int256`. Use [`encode_packed_typed`] to pack smaller integers like `uint8`.
+///
+/// The packed encoding is lossy and can't be decoded: the values are concatenated, so two
+/// dynamic values are ambiguous, e.g. `("a", "bc")` and `("ab", "c")` are encoded the same. Don't
+/// sign or hash the packed encoding of several dynamic values if the preimage must be unique.
+///
+/// ```
+/// use ethers_core::{abi::{encode_packed, Token}, types::Address, utils::keccak256};
+///
+/// let owner = Address::repeat_byte(0x11);
+/// let packed =
+///     encode_packed(&[Token::Address(owner), Token::Uint(1u64.into()), Token::String("hi".into())])
+///         .unwrap();
+/// assert_eq!(packed.len(), 20 + 32 + 2);
+/// let hash = keccak256(&packed);
+/// ```
+pub fn encode_packed(tokens: &[Token]) -> Result<Bytes, EncodePackedError> {
+    let mut out = Vec::new();
+    for token in tokens {
+        encode_token(token, None, &mut out)?;
+    }
+    Ok(out.into())
+}
+
+/// Encodes the tokens as values of the `types` in the non-standard packed mode of Solidity's
+/// `abi.encodePacked`, integers are encoded with the width of their type, e.g. 1 byte for
+/// `uint8`.
+///
+/// See [`encode_packed`] for the rules of the packed encoding and why it can't be decoded.
+///
+/// ```
+/// use ethers_core::abi::{encode_packed_typed, ParamType, Token};
+///
+/// // abi.encodePacked(int16(-1), bytes1(0x42), uint16(0x03), string("Hello, world!"))
+/// let packed = encode_packed_typed(
+///     &[ParamType::Int(16), ParamType::FixedBytes(1), ParamType::Uint(16), ParamType::String],
+///     &[
+///         Token::Int(ethers_core::types::I256::minus_one().into_raw()),
+///         Token::FixedBytes(vec![0x42]),
+///         Token::Uint(3u64.into()),
+///         Token::String("Hello, world!".into()),
+///     ],
+/// )
+/// .unwrap();
+/// assert_eq!(packed.to_string(), "0xffff42000348656c6c6f2c20776f726c6421");
+/// ```
+pub fn encode_packed_typed(
+    types: &[ParamType],
+    tokens: &[Token],
+) -> Result<Bytes, EncodePackedError> {
+    if types.len() != tokens.len() {
+        return Err(EncodePackedError::LengthMismatch {
+            expected: types.len(),
+            actual: tokens.len(),
+        })
+    }
+    let mut out = Vec::new();
+    for (param, token) in types.iter().zip(tokens) {
+        encode_token(token, Some(param), &mut out)?;
+    }
+    Ok(out.into())
+}
+
+/// Appends the packed encoding of the token, checks that the token is a value of `param` if the
+/// type is known
+fn encode_token(
+    token: &Token,
+    param: Option<&ParamType>,
+    out: &mut Vec<u8>,
+) -> Result<(), EncodePackedError> {
+    match (token, param) {
+        (Token::Address(address), None | Some(ParamType::Address)) => {
+            out.extend_from_slice(address.as_bytes())
+        }
+        (Token::Bool(value), None | Some(ParamType::Bool)) => out.push(*value as u8),
+        (Token::Bytes(bytes), None | Some(ParamType::Bytes)) => out.extend_from_slice(bytes),
+        (Token::String(s), None | Some(ParamType::String)) => out.extend_from_slice(s.as_bytes()),
+        (Token::FixedBytes(bytes), None) => out.extend_from_slice(bytes),
+        (Token::FixedBytes(bytes), Some(param @ ParamType::FixedBytes(size))) => {
+            if *size == 0 || *size > 32 || bytes.len() > *size {
+                return Err(type_mismatch(param, token))
+            }
+            out.extend_from_slice(bytes);
+            out.resize(out.len() + size - bytes.len(), 0);
+        }
+        (Token::Uint(value), None) | (Token::Int(value), None) => {
+            out.extend_from_slice(&<[u8; 32]>::from(*value))
+        }
+        (Token::Uint(value), Some(param @ ParamType::Uint(bits))) => {
+            if !is_valid_int_width(*bits) || value.bits() > *bits {
+                return Err(type_mismatch(param, token))
+            }
+            out.extend_from_slice(&<[u8; 32]>::from(*value)[32 - bits / 8..]);
+        }
+        (Token::Int(value), Some(param @ ParamType::Int(bits))) => {
+            // two's complement, the value fits if all bits above the sign bit match it
+            let magnitude = if value.bit(255) { !*value } else { *value };
+            if !is_valid_int_width(*bits) || magnitude.bits() >= *bits {
+                return Err(type_mismatch(param, token))
+            }
+            out.extend_from_slice(&<[u8; 32]>::from(*value)[32 - bits / 8..]);
+        }
+        (Token::Array(tokens), None | Some(ParamType::Array(_))) |
+        (Token::FixedArray(tokens), None | Some(ParamType::FixedArray(..))) => {
+            let element = match param {
+                Some(ParamType::Array(element)) => Some(element.as_ref()),
+                Some(param @ ParamType::FixedArray(element, len)) => {
+                    if tokens.len() != *len {
+                        return Err(type_mismatch(param, token))
+                    }
+                    Some(element.as_ref())
+                }
+                _ => None,
+            };
+            for token in tokens {
+                encode_array_element(token, element, out)?;
+            }
+        }
+        (Token::Tuple(_), _) | (_, Some(ParamType::Tuple(_))) => {
+            return Err(EncodePackedError::Tuple)
+        }
+        (_, Some(param)) => return Err(type_mismatch(param, token)),
+    }
+    Ok(())
+}
+
+/// Whether `bits` is the width of a Solidity integer type, i.e. `uint8` up to `uint256`
+fn is_valid_int_width(bits: usize) -> bool {
+    bits > 0 && bits <= 256 && bits % 8 == 0
+}
+
+fn type_mismatch(param: &ParamType, token: &Token) -> EncodePackedError {
+    EncodePackedError::TypeMismatch { param: param.clone(), token: token.clone() }
+}
+
+/// Appends the element of an array, elements are padded to 32 bytes like in the standard encoding
+fn encode_array_element(
+    token: &Token,
+    param: Option<&ParamType>,
+    out: &mut Vec<u8>,
+) -> Result<(), EncodePackedError> {
+    match (token, param) {
+        (Token::Tuple(_), _) | (_, Some(ParamType::Tuple(_))) => Err(EncodePackedError::Tuple),
+        (
+            Token::Address(_) |
+            Token::Bool(_) |
+            Token::Uint(_) |
+            Token::Int(_) |
+            Token::FixedBytes(_),
+            _,
+        ) => {
+            if let Token::FixedBytes(bytes) = token {
+                // `bytesN` elements are padded to 32 bytes and never truncated
+                if bytes.len() > 32 {
+                    return Err(type_mismatch(&ParamType::FixedBytes(bytes.len()), token))
+                }
+            }
+            let start = out.len();
+            encode_token(token, param, out)?;
+            let len = out.len() - start;
+            if let Token::FixedBytes(_) = token {
+                out.resize(start + 32, 0);
+            } else {
+                // sign extension of negative integers
+                let fill =
+                    if matches!(token, Token::Int(value) if value.bit(255)) { 0xff } else { 0 };
+                out.splice(start..start, std::iter::repeat(fill).take(32 - len));
+            }
+            Ok(())
+        }
+        _ => Err(EncodePackedError::NestedArray),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Address, I256, U256};
+
+    #[test]
+    fn can_encode_packed() {
+        let address = Address::repeat_byte(0x11);
+        let packed = encode_packed(&[
+            Token::Address(address),
+            Token::Bool(true),
+            Token::Uint(U256::from(0x42)),
+            Token::Bytes(vec![0xaa, 0xbb]),
+            Token::String("hi".to_string()),
+            Token::FixedBytes(vec![0x01, 0x02]),
+        ])
+        .unwrap();
+        let mut expected = address.as_bytes().to_vec();
+        expected.push(1);
+        expected.extend(<[u8; 32]>::from(U256::from(0x42)));
+        expected.extend([0xaa, 0xbb, b'h', b'i', 0x01, 0x02]);
+        assert_eq!(packed.to_vec(), expected);
+
+        // dynamic values are ambiguous
+        assert_eq!(
+            encode_packed(&[Token::String("a".into()), Token::String("bc".into())]).unwrap(),
+            encode_packed(&[Token::String("ab".into()), Token::String("c".into())]).unwrap(),
+        );
+
+        assert_eq!(encode_packed(&[Token::Tuple(vec![])]), Err(EncodePackedError::Tuple));
+        assert_eq!(
+            encode_packed(&[Token::Array(vec![Token::String("a".into())])]),
+            Err(EncodePackedError::NestedArray)
+        );
+    }
+
+    #[test]
+    fn can_encode_packed_typed() {
+        // <https://docs.soliditylang.org/en/v0.8.15/abi-spec.html#non-standard-packed-mode>
+        let packed = encode_packed_typed(
+            &[ParamType::Int(16), ParamType::FixedBytes(1), ParamType::Uint(16), ParamType::String],
+            &[
+                Token::Int(I256::minus_one().into_raw()),
+                Token::FixedBytes(vec![0x42]),
+                Token::Uint(3u64.into()),
+                Token::String("Hello, world!".to_string()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(packed.to_string(), "0xffff42000348656c6c6f2c20776f726c6421");
+
+        let packed = encode_packed_typed(
+            &[ParamType::Uint(8), ParamType::Address, ParamType::Uint(32)],
+            &[
+                Token::Uint(0xffu64.into()),
+                Token::Address(Address::repeat_byte(0x11)),
+                Token::Uint(1u64.into()),
+            ],
+        )
+        .unwrap();
+        assert_eq!(packed.len(), 1 + 20 + 4);
+        assert_eq!(packed[0], 0xff);
+        assert_eq!(&packed[21..], &[0, 0, 0, 1]);
+
+        // array elements are padded to 32 bytes
+        let packed = encode_packed_typed(
+            &[ParamType::Array(Box::new(ParamType::Int(8)))],
+            &[Token::Array(vec![
+                Token::Int(I256::minus_one().into_raw()),
+                Token::Int(2u64.into()),
+            ])],
+        )
+        .unwrap();
+        let mut expected = vec![0xff; 32];
+        expected.extend(<[u8; 32]>::from(U256::from(2)));
+        assert_eq!(packed.to_vec(), expected);
+
+        assert!(matches!(
+            encode_packed_typed(&[ParamType::Uint(8)], &[Token::Uint(256u64.into())]),
+            Err(EncodePackedError::TypeMismatch { .. })
+        ));
+        assert!(matches!(
+            encode_packed_typed(&[ParamType::Int(8)], &[Token::Int(I256::from(-129).into_raw())]),
+            Err(EncodePackedError::TypeMismatch { .. })
+        ));
+        encode_packed_typed(&[ParamType::Int(8)], &[Token::Int(I256::from(-128).into_raw())])
+            .unwrap();
+        // invalid integer widths
+        for param in [ParamType::Uint(0), ParamType::Uint(12), ParamType::Uint(264)] {
+            assert!(matches!(
+                encode_packed_typed(&[param], &[Token::Uint(1u64.into())]),
+                Err(EncodePackedError::TypeMismatch { .. })
+            ));
+        }
+        for param in [ParamType::Int(4), ParamType::Int(512)] {
+            assert!(matches!(
+                encode_packed_typed(&[param], &[Token::Int(1u64.into())]),
+                Err(EncodePackedError::TypeMismatch { .. })
+            ));
+        }
+        assert!(matches!(
+            encode_packed_typed(&[ParamType::FixedBytes(33)], &[Token::FixedBytes(vec![1; 33])]),
+            Err(EncodePackedError::TypeMismatch { .. })
+        ));
+        // array elements longer than 32 bytes are not truncated
+        assert!(matches!(
+            encode_packed(&[Token::Array(vec![Token::FixedBytes(vec![1; 33])])]),
+            Err(EncodePackedError::TypeMismatch { .. })
+        ));
+
+        assert_eq!(
+            encode_packed_typed(&[ParamType::Bool], &[]),
+            Err(EncodePackedError::LengthMismatch { expected: 1, actual: 0 })
+        );
+    }
+}
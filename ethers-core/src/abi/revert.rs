@@ -0,0 +1,218 @@
+//! Decoding of the data of reverted calls
+use crate::{
+    abi::{Abi, ParamType, Token},
+    types::{Bytes, Selector, U256},
+    utils::id,
+};
+use std::fmt;
+
+/// Selector of the builtin `Error(string)`, used by `revert("reason")` and
+/// `require(condition, "reason")`
+pub const ERROR_SELECTOR: Selector = [0x08, 0xc3, 0x79, 0xa0];
+
+/// Selector of the builtin `Panic(uint256)`, used by failed assertions, arithmetic overflows etc.
+pub const PANIC_SELECTOR: Selector = [0x4e, 0x48, 0x7b, 0x71];
+
+/// The decoded revert data of a call, see [`decode_revert`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RevertReason {
+    /// The call reverted without any data, for example `revert()` or `require(condition)`
+    Empty,
+    /// The message of a `Error(string)`
+    Error(String),
+    /// The code of a `Panic(uint256)`, see [`panic_description`]
+    Panic(U256),
+    /// A custom error of the ABI with its decoded parameters
+    Custom {
+        /// The definition of the error
+        error: ethabi::AbiError,
+        /// The decoded parameters, in the order of `error.inputs`
+        params: Vec<Token>,
+    },
+    /// Data that doesn't match any of the known errors
+    Unknown(Bytes),
+}
+
+impl fmt::Display for RevertReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RevertReason::Empty => f.write_str("reverted without data"),
+            RevertReason::Error(message) => f.write_str(message),
+            RevertReason::Panic(code) => match panic_description(*code) {
+                Some(description) => write!(f, "panic: {} ({:#04x})", description, code.low_u32()),
+                None => write!(f, "panic: unknown code {:#x}", code),
+            },
+            RevertReason::Custom { error, params } => {
+                write!(f, "{}(", error.name)?;
+                for (idx, (input, param)) in error.inputs.iter().zip(params).enumerate() {
+                    if idx > 0 {
+                        f.write_str(", ")?;
+                    }
+                    if !input.name.is_empty() {
+                        write!(f, "{}: ", input.name)?;
+                    }
+                    write!(f, "{}", param)?;
+                }
+                f.write_str(")")
+            }
+            RevertReason::Unknown(data) => write!(f, "unknown revert data {}", data),
+        }
+    }
+}
+
+/// Returns the description of a `Panic(uint256)` code.
+///
+/// See <https://docs.soliditylang.org/en/latest/control-structures.html#panic-via-assert-and-error-via-require>
+pub fn panic_description(code: U256) -> Option<&'static str> {
+    if code > U256::from(u8::MAX) {
+        return None
+    }
+    let description = match code.low_u32() {
+        0x00 => "generic compiler inserted panic",
+        0x01 => "assertion failed",
+        0x11 => "arithmetic underflow or overflow",
+        0x12 => "division or modulo by zero",
+        0x21 => "conversion into non-existent enum type",
+        0x22 => "access to incorrectly encoded storage byte array",
+        0x31 => "pop on empty array",
+        0x32 => "array index out of bounds",
+        0x41 => "out of memory",
+        0x51 => "call to zero-initialized variable of internal function type",
+        _ => return None,
+    };
+    Some(description)
+}
+
+/// Decodes the data of a reverted call.
+///
+/// The 4-byte selector of the data is matched against the builtin `Error(string)` and
+/// `Panic(uint256)` errors and all custom errors of the ABI. If the selector is unknown or the
+/// parameters can't be decoded, [`RevertReason::Unknown`] is returned.
+///
+/// # Example
+///
+/// ```
+/// use ethers_core::abi::{decode_revert, parse_abi, RevertReason};
+///
+/// let abi = parse_abi(&["error Unauthorized()"]).unwrap();
+/// let data = hex::decode("4e487b710000000000000000000000000000000000000000000000000000000000000011").unwrap();
+/// let reason = decode_revert(&data, &abi);
+/// assert_eq!(reason, RevertReason::Panic(0x11u64.into()));
+/// assert_eq!(reason.to_string(), "panic: arithmetic underflow or overflow (0x11)");
+/// ```
+pub fn decode_revert(data: &[u8], abi: &Abi) -> RevertReason {
+    if data.is_empty() {
+        return RevertReason::Empty
+    }
+    if data.len() < 4 {
+        return RevertReason::Unknown(data.to_vec().into())
+    }
+    let (selector, params) = data.split_at(4);
+
+    let decoded = if selector == ERROR_SELECTOR {
+        decode_single(ParamType::String, params)
+            .and_then(Token::into_string)
+            .map(RevertReason::Error)
+    } else if selector == PANIC_SELECTOR {
+        decode_single(ParamType::Uint(256), params)
+            .and_then(Token::into_uint)
+            .map(RevertReason::Panic)
+    } else {
+        abi.errors.values().flatten().find_map(|error| {
+            let kinds = error.inputs.iter().map(|input| input.kind.clone()).collect::<Vec<_>>();
+            let signature = format!(
+                "{}({})",
+                error.name,
+                kinds.iter().map(ToString::to_string).collect::<Vec<_>>().join(",")
+            );
+            if id(signature) != selector {
+                return None
+            }
+            let params = ethabi::decode(&kinds, params).ok()?;
+            Some(RevertReason::Custom { error: error.clone(), params })
+        })
+    };
+
+    decoded.unwrap_or_else(|| RevertReason::Unknown(data.to_vec().into()))
+}
+
+fn decode_single(kind: ParamType, data: &[u8]) -> Option<Token> {
+    ethabi::decode(&[kind], data).ok()?.pop()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::parse_abi;
+
+    fn revert_data(selector: Selector, params: &[Token]) -> Vec<u8> {
+        [&selector[..], &ethabi::encode(params)].concat()
+    }
+
+    #[test]
+    fn decodes_builtin_errors() {
+        let abi = parse_abi(&[]).unwrap();
+        assert_eq!(id("Panic(uint256)"), PANIC_SELECTOR);
+        assert_eq!(id("Error(string)"), ERROR_SELECTOR);
+
+        let data = revert_data(ERROR_SELECTOR, &[Token::String("balance too low".to_string())]);
+        let reason = decode_revert(&data, &abi);
+        assert_eq!(reason, RevertReason::Error("balance too low".to_string()));
+        assert_eq!(reason.to_string(), "balance too low");
+
+        let data = revert_data(PANIC_SELECTOR, &[Token::Uint(0x12u64.into())]);
+        assert_eq!(
+            decode_revert(&data, &abi).to_string(),
+            "panic: division or modulo by zero (0x12)"
+        );
+
+        let data = revert_data(PANIC_SELECTOR, &[Token::Uint(0x99u64.into())]);
+        assert_eq!(decode_revert(&data, &abi).to_string(), "panic: unknown code 0x99");
+
+        assert_eq!(decode_revert(&[], &abi), RevertReason::Empty);
+    }
+
+    #[test]
+    fn decodes_custom_errors() {
+        let abi = parse_abi(&[
+            "error InsufficientBalance(uint256 available, uint256 required)",
+            "error Unauthorized(address)",
+        ])
+        .unwrap();
+
+        let selector = id("InsufficientBalance(uint256,uint256)");
+        let data = revert_data(selector, &[Token::Uint(1u64.into()), Token::Uint(2u64.into())]);
+        let reason = decode_revert(&data, &abi);
+        match &reason {
+            RevertReason::Custom { error, params } => {
+                assert_eq!(error.name, "InsufficientBalance");
+                assert_eq!(params, &vec![Token::Uint(1u64.into()), Token::Uint(2u64.into())]);
+            }
+            reason => panic!("unexpected reason {:?}", reason),
+        }
+        assert_eq!(reason.to_string(), "InsufficientBalance(available: 1, required: 2)");
+
+        let data = revert_data(id("Unauthorized(address)"), &[Token::Address(Default::default())]);
+        assert!(matches!(
+            decode_revert(&data, &abi),
+            RevertReason::Custom { error, .. } if error.name == "Unauthorized"
+        ));
+    }
+
+    #[test]
+    fn returns_unknown_data() {
+        let abi =
+            parse_abi(&["error InsufficientBalance(uint256 available, uint256 required)"]).unwrap();
+
+        // unknown selector
+        let data = revert_data(id("Unknown()"), &[]);
+        assert_eq!(decode_revert(&data, &abi), RevertReason::Unknown(data.clone().into()));
+
+        // params that can't be decoded
+        let data = id("InsufficientBalance(uint256,uint256)").to_vec();
+        assert_eq!(decode_revert(&data, &abi), RevertReason::Unknown(data.clone().into()));
+
+        let data = vec![0x08, 0xc3];
+        assert_eq!(decode_revert(&data, &abi), RevertReason::Unknown(data.clone().into()));
+    }
+}
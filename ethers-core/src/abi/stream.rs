@@ -0,0 +1,364 @@
+//! Lazy decoding of ABI encoded data, see [`decode_stream`]
+use crate::{
+    abi::{Error, ParamType, Token},
+    types::{Address, U256},
+};
+use std::iter;
+
+const WORD: usize = 32;
+
+/// An iterator over lazily decoded tokens, see [`decode_stream`] and [`decode_array_stream`].
+///
+/// The iterator ends after the first error.
+#[derive(Debug, Clone)]
+pub struct DecodeStream<'a> {
+    params: Params<'a>,
+    data: &'a [u8],
+    /// The offset of the encoding that the offsets of dynamic tokens are relative to
+    base: usize,
+    /// The offset of the head of the next token
+    head: usize,
+    index: usize,
+    done: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Params<'a> {
+    List(&'a [ParamType]),
+    Repeat(&'a ParamType, usize),
+}
+
+impl<'a> Params<'a> {
+    fn get(&self, index: usize) -> Option<&'a ParamType> {
+        match self {
+            Params::List(params) => params.get(index),
+            Params::Repeat(param, len) => (index < *len).then(|| *param),
+        }
+    }
+
+    fn len(&self) -> usize {
+        match self {
+            Params::List(params) => params.len(),
+            Params::Repeat(_, len) => *len,
+        }
+    }
+}
+
+/// Lazily decodes the ABI encoded `data` of the `params`, e.g. the return data of a function,
+/// one top-level token at a time.
+///
+/// Unlike [`decode`](crate::abi::decode) this does not decode all tokens upfront, so that the
+/// tokens can be processed and dropped one after another.
+///
+/// # Example
+///
+/// ```
+/// use ethers_core::abi::{decode_stream, encode, ParamType, Token};
+///
+/// let data = encode(&[Token::Uint(1.into()), Token::String("ethers".to_string())]);
+/// let mut tokens = decode_stream(&[ParamType::Uint(256), ParamType::String], &data);
+/// assert_eq!(tokens.next().unwrap().unwrap(), Token::Uint(1.into()));
+/// assert_eq!(tokens.next().unwrap().unwrap(), Token::String("ethers".to_string()));
+/// assert!(tokens.next().is_none());
+/// ```
+pub fn decode_stream<'a>(params: &'a [ParamType], data: &'a [u8]) -> DecodeStream<'a> {
+    DecodeStream { params: Params::List(params), data, base: 0, head: 0, index: 0, done: false }
+}
+
+/// Lazily decodes the elements of the ABI encoded `data` of a single dynamic array `element[]`,
+/// e.g. the return data of a function that returns a large array.
+///
+/// Fails if the offset or the length of the array is invalid.
+///
+/// # Example
+///
+/// ```
+/// use ethers_core::abi::{decode_array_stream, encode, ParamType, Token};
+///
+/// let data = encode(&[Token::Array((0..1000u64).map(|i| Token::Uint(i.into())).collect())]);
+/// let sum = decode_array_stream(&ParamType::Uint(256), &data)?
+///     .map(|token| token.map(|token| token.into_uint().unwrap().as_u64()))
+///     .sum::<Result<u64, _>>()?;
+/// assert_eq!(sum, 499500);
+/// # Ok::<_, ethers_core::abi::Error>(())
+/// ```
+pub fn decode_array_stream<'a>(
+    element: &'a ParamType,
+    data: &'a [u8],
+) -> Result<DecodeStream<'a>, Error> {
+    let offset = read_usize(data, 0)?;
+    let len = read_usize(data, offset)?;
+    let base = offset + WORD;
+    check_array_len(element, len, data, base)?;
+    Ok(DecodeStream {
+        params: Params::Repeat(element, len),
+        data,
+        base,
+        head: base,
+        index: 0,
+        done: false,
+    })
+}
+
+impl<'a> Iterator for DecodeStream<'a> {
+    type Item = Result<Token, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None
+        }
+        let param = match self.params.get(self.index) {
+            Some(param) => param,
+            None => {
+                self.done = true;
+                return None
+            }
+        };
+        let token = decode_param(param, self.data, self.base, self.head);
+        self.index += 1;
+        self.head += head_len(param);
+        self.done = token.is_err();
+        Some(token)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.done {
+            (0, Some(0))
+        } else {
+            (0, Some(self.params.len() - self.index))
+        }
+    }
+}
+
+fn is_dynamic(param: &ParamType) -> bool {
+    match param {
+        ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+        ParamType::FixedArray(param, _) => is_dynamic(param),
+        ParamType::Tuple(params) => params.iter().any(is_dynamic),
+        _ => false,
+    }
+}
+
+/// Returns the size of the head of the param, which is the offset for dynamic types and the
+/// whole encoding for static types
+fn head_len(param: &ParamType) -> usize {
+    if is_dynamic(param) {
+        return WORD
+    }
+    match param {
+        ParamType::FixedArray(param, len) => head_len(param) * len,
+        ParamType::Tuple(params) => params.iter().map(head_len).sum(),
+        _ => WORD,
+    }
+}
+
+/// Decodes the param whose head is at `head`, the offsets of dynamic params are relative to
+/// `base`
+fn decode_param(param: &ParamType, data: &[u8], base: usize, head: usize) -> Result<Token, Error> {
+    if is_dynamic(param) {
+        let offset = base.checked_add(read_usize(data, head)?).ok_or(Error::InvalidData)?;
+        decode_value(param, data, offset)
+    } else {
+        decode_value(param, data, head)
+    }
+}
+
+/// Decodes the param whose encoding starts at `offset`
+fn decode_value(param: &ParamType, data: &[u8], offset: usize) -> Result<Token, Error> {
+    let token = match param {
+        ParamType::Address => Token::Address(Address::from_slice(&word(data, offset)?[12..])),
+        ParamType::Int(_) => Token::Int(U256::from_big_endian(word(data, offset)?)),
+        ParamType::Uint(_) => Token::Uint(U256::from_big_endian(word(data, offset)?)),
+        ParamType::Bool => match U256::from_big_endian(word(data, offset)?) {
+            value if value.is_zero() => Token::Bool(false),
+            value if value == U256::one() => Token::Bool(true),
+            _ => return Err(Error::InvalidData),
+        },
+        ParamType::FixedBytes(len) => {
+            Token::FixedBytes(word(data, offset)?.get(..*len).ok_or(Error::InvalidData)?.to_vec())
+        }
+        ParamType::Bytes => Token::Bytes(read_bytes(data, offset)?.to_vec()),
+        ParamType::String => Token::String(
+            String::from_utf8(read_bytes(data, offset)?.to_vec())
+                .map_err(|_| Error::InvalidData)?,
+        ),
+        ParamType::Array(param) => {
+            let len = read_usize(data, offset)?;
+            let base = offset + WORD;
+            check_array_len(param, len, data, base)?;
+            Token::Array(decode_tuple(iter::repeat(&**param).take(len), data, base)?)
+        }
+        ParamType::FixedArray(param, len) => {
+            Token::FixedArray(decode_tuple(iter::repeat(&**param).take(*len), data, offset)?)
+        }
+        ParamType::Tuple(params) => Token::Tuple(decode_tuple(params.iter(), data, offset)?),
+    };
+    Ok(token)
+}
+
+/// Decodes the params of a tuple whose encoding starts at `base`
+fn decode_tuple<'a>(
+    params: impl Iterator<Item = &'a ParamType>,
+    data: &[u8],
+    base: usize,
+) -> Result<Vec<Token>, Error> {
+    let mut head = base;
+    params
+        .map(|param| {
+            let token = decode_param(param, data, base, head)?;
+            head += head_len(param);
+            Ok(token)
+        })
+        .collect()
+}
+
+/// Fails if the heads of an array with `len` elements don't fit into the data after `base`, so
+/// that an invalid length can't cause a huge allocation
+fn check_array_len(element: &ParamType, len: usize, data: &[u8], base: usize) -> Result<(), Error> {
+    let heads = len.checked_mul(head_len(element)).ok_or(Error::InvalidData)?;
+    match base.checked_add(heads) {
+        Some(end) if end <= data.len() => Ok(()),
+        _ => Err(Error::InvalidData),
+    }
+}
+
+fn word(data: &[u8], offset: usize) -> Result<&[u8], Error> {
+    let end = offset.checked_add(WORD).ok_or(Error::InvalidData)?;
+    data.get(offset..end).ok_or(Error::InvalidData)
+}
+
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, Error> {
+    let value = U256::from_big_endian(word(data, offset)?);
+    if value > U256::from(usize::MAX) {
+        return Err(Error::InvalidData)
+    }
+    Ok(value.as_usize())
+}
+
+fn read_bytes(data: &[u8], offset: usize) -> Result<&[u8], Error> {
+    let len = read_usize(data, offset)?;
+    let start = offset + WORD;
+    let end = start.checked_add(len).ok_or(Error::InvalidData)?;
+    data.get(start..end).ok_or(Error::InvalidData)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abi::{decode, encode};
+
+    fn params() -> Vec<ParamType> {
+        vec![
+            ParamType::Uint(256),
+            ParamType::Array(Box::new(ParamType::Tuple(vec![
+                ParamType::Address,
+                ParamType::String,
+            ]))),
+            ParamType::Bytes,
+            ParamType::FixedArray(Box::new(ParamType::String), 2),
+            ParamType::Tuple(vec![
+                ParamType::Bool,
+                ParamType::Array(Box::new(ParamType::Uint(64))),
+                ParamType::FixedArray(Box::new(ParamType::FixedBytes(4)), 2),
+            ]),
+            ParamType::FixedArray(Box::new(ParamType::Int(256)), 3),
+            ParamType::Bool,
+        ]
+    }
+
+    fn tokens() -> Vec<Token> {
+        vec![
+            Token::Uint(7.into()),
+            Token::Array(vec![
+                Token::Tuple(vec![
+                    Token::Address(Address::repeat_byte(1)),
+                    Token::String("a".into()),
+                ]),
+                Token::Tuple(vec![
+                    Token::Address(Address::repeat_byte(2)),
+                    Token::String("x".repeat(70)),
+                ]),
+            ]),
+            Token::Bytes(vec![1, 2, 3]),
+            Token::FixedArray(vec![Token::String("b".into()), Token::String(String::new())]),
+            Token::Tuple(vec![
+                Token::Bool(true),
+                Token::Array(vec![Token::Uint(1.into()), Token::Uint(2.into())]),
+                Token::FixedArray(vec![
+                    Token::FixedBytes(vec![1, 2, 3, 4]),
+                    Token::FixedBytes(vec![5, 6, 7, 8]),
+                ]),
+            ]),
+            Token::FixedArray(vec![
+                Token::Int(U256::MAX),
+                Token::Int(0.into()),
+                Token::Int(1.into()),
+            ]),
+            Token::Bool(false),
+        ]
+    }
+
+    #[test]
+    fn decodes_nested_dynamic_types() {
+        let params = params();
+        let data = encode(&tokens());
+        let decoded = decode_stream(&params, &data).collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(decoded, tokens());
+        assert_eq!(decoded, decode(&params, &data).unwrap());
+    }
+
+    #[test]
+    fn decodes_array_elements() {
+        let element = ParamType::Tuple(vec![ParamType::Uint(112), ParamType::Bytes]);
+        let elements: Vec<_> = (0..500u64)
+            .map(|i| Token::Tuple(vec![Token::Uint(i.into()), Token::Bytes(vec![i as u8; 33])]))
+            .collect();
+        let data = encode(&[Token::Array(elements.clone())]);
+
+        let stream = decode_array_stream(&element, &data).unwrap();
+        assert_eq!(stream.size_hint(), (0, Some(500)));
+        assert_eq!(stream.collect::<Result<Vec<_>, _>>().unwrap(), elements);
+
+        let data = encode(&[Token::Array(vec![])]);
+        assert_eq!(decode_array_stream(&element, &data).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn rejects_invalid_offsets_and_lengths() {
+        let params = [ParamType::Uint(256), ParamType::Bytes];
+        let data = encode(&[Token::Uint(1.into()), Token::Bytes(vec![1; 40])]);
+
+        // the first token is decoded before the invalid offset of the second
+        let mut invalid = data.clone();
+        invalid[63] = 0xff;
+        let mut tokens = decode_stream(&params, &invalid);
+        assert!(tokens.next().unwrap().is_ok());
+        assert!(matches!(tokens.next(), Some(Err(Error::InvalidData))));
+        assert!(tokens.next().is_none());
+
+        // truncated tail
+        assert!(decode_stream(&params, &data[..data.len() - 32]).nth(1).unwrap().is_err());
+        // truncated head
+        assert!(decode_stream(&params, &data[..16]).next().unwrap().is_err());
+
+        // an offset that doesn't fit into usize
+        let mut invalid = data.clone();
+        invalid[32] = 1;
+        assert!(decode_stream(&params, &invalid).nth(1).unwrap().is_err());
+
+        // an array length larger than the data
+        let element = ParamType::Uint(256);
+        let mut data = encode(&[Token::Array(vec![Token::Uint(1.into())])]);
+        data[63] = 2;
+        assert!(decode_array_stream(&element, &data).is_err());
+        data[32..64].copy_from_slice(&[0xff; 32]);
+        assert!(decode_array_stream(&element, &data).is_err());
+        let params = [ParamType::Array(Box::new(element))];
+        assert!(decode_stream(&params, &data).next().unwrap().is_err());
+
+        // a bool that is neither 0 nor 1
+        let mut data = encode(&[Token::Bool(true)]);
+        data[31] = 2;
+        assert!(decode_stream(&[ParamType::Bool], &data).next().unwrap().is_err());
+    }
+}
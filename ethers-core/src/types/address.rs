@@ -0,0 +1,26 @@
+use crate::utils;
+use ethabi::ethereum_types::Address;
+
+/// Checksum encoding of [`Address`]es.
+///
+/// See [`utils::parse_checksummed`] to parse addresses and validate their checksum.
+pub trait AddressExt {
+    /// Returns the EIP-55 checksum encoding of the address, or the EIP-1191 checksum encoding if
+    /// the chain id is set
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_core::types::{Address, AddressExt};
+    /// let addr: Address = "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed".parse().unwrap();
+    /// assert_eq!(addr.to_checksum(None), "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed");
+    /// assert_eq!(addr.to_checksum(Some(30)), "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD");
+    /// ```
+    fn to_checksum(&self, chain_id: Option<u64>) -> String;
+}
+
+impl AddressExt for Address {
+    fn to_checksum(&self, chain_id: Option<u64>) -> String {
+        utils::checksum_encode(self, chain_id)
+    }
+}
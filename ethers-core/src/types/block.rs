@@ -21,8 +21,8 @@ pub struct Block<TX> {
     #[cfg(not(feature = "celo"))]
     #[serde(default, rename = "sha3Uncles")]
     pub uncles_hash: H256,
-    /// Miner/author's address.
-    #[serde(default, rename = "miner")]
+    /// Miner/author's address. Zero if the block is pending.
+    #[serde(default, rename = "miner", deserialize_with = "deserialize_null_default")]
     pub author: Address,
     /// State root hash
     #[serde(default, rename = "stateRoot")]
@@ -93,6 +93,15 @@ pub struct Block<TX> {
     pub epoch_snark_data: Option<EpochSnarkData>,
 }
 
+/// Deserializes `null` as the default value, e.g. the `miner` of pending blocks
+fn deserialize_null_default<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Default + Deserialize<'de>,
+{
+    Ok(Option::<T>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 /// Error returned by [`Block::time`].
 #[derive(Clone, Copy, Debug, Error)]
 pub enum TimeError {
@@ -537,6 +546,17 @@ mod tests {
         let _block: Block<Transaction> = serde_json::from_str(block).unwrap();
     }
 
+    #[test]
+    fn deserialize_pending_blk() {
+        // pending blocks have no hash, nonce and miner
+        let block = r#"{"number":"0x4","hash":null,"parentHash":"0xda53da08ef6a3cbde84c33e51c04f68c3853b6a3731f10baa2324968eee63972","mixHash":"0x0000000000000000000000000000000000000000000000000000000000000000","nonce":null,"sha3Uncles":"0x1dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347","logsBloom":null,"transactionsRoot":"0x7270c1c4440180f2bd5215809ee3d545df042b67329499e1ab97eb759d31610d","stateRoot":"0x29f32984517a7d25607da485b23cefabfd443751422ca7e603395e1de9bc8a4b","receiptsRoot":"0x056b23fbba480696b65fe5a59b8f2148a1299103c4f57df839233af2cf4ca2d2","miner":null,"difficulty":"0x0","totalDifficulty":null,"extraData":"0x","size":"0x3e8","gasLimit":"0x6691b7","gasUsed":"0x5208","timestamp":"0x5ecedbbb","transactions":[{"hash":"0xc3c5f700243de37ae986082fd2af88d2a7c2752a0c0f7b9d6ac47c729d45e067","nonce":"0x2","blockHash":null,"blockNumber":null,"transactionIndex":null,"from":"0xfdcedc3bfca10ecb0890337fbdd1977aba84807a","to":"0xdca8ce283150ab773bcbeb8d38289bdb5661de1e","value":"0x0","gas":"0x15f90","gasPrice":"0x4a817c800","input":"0x","v":"0x25","r":"0x19f2694eb9113656dbea0b925e2e7ceb43df83e601c4116aee9c0dd99130be88","s":"0x73e5764b324a4f7679d890a198ba658ba1c8cd36983ff9797e10b1b89dbb448e"}],"uncles":[]}"#;
+        let block: Block<Transaction> = serde_json::from_str(block).unwrap();
+        assert_eq!(block.hash, None);
+        assert_eq!(block.nonce, None);
+        assert_eq!(block.author, Address::zero());
+        assert_eq!(block.transactions[0].block_number, None);
+    }
+
     #[test]
     // <https://github.com/tomusdrw/rust-web3/commit/3a32ee962c0f2f8d50a5e25be9f2dfec7ae0750d>
     fn post_london_block() {
@@ -4,6 +4,7 @@ use thiserror::Error;
 use std::{
     clone::Clone,
     fmt::{Debug, Display, Formatter, LowerHex, Result as FmtResult},
+    iter::FromIterator,
     str::FromStr,
 };
 
@@ -34,6 +35,11 @@ impl Bytes {
     pub fn to_vec(&self) -> Vec<u8> {
         self.as_ref().to_vec()
     }
+
+    /// Returns the hex encoding of the bytes without the `0x` prefix
+    pub fn to_string_unprefixed(&self) -> String {
+        bytes_to_hex(self)
+    }
 }
 
 impl AsRef<[u8]> for Bytes {
@@ -54,6 +60,18 @@ impl From<Vec<u8>> for Bytes {
     }
 }
 
+impl<'a> From<&'a [u8]> for Bytes {
+    fn from(src: &'a [u8]) -> Self {
+        src.to_vec().into()
+    }
+}
+
+impl FromIterator<u8> for Bytes {
+    fn from_iter<T: IntoIterator<Item = u8>>(iter: T) -> Self {
+        iter.into_iter().collect::<Vec<_>>().into()
+    }
+}
+
 impl<const N: usize> From<[u8; N]> for Bytes {
     fn from(src: [u8; N]) -> Self {
         src.to_vec().into()
@@ -74,14 +92,17 @@ impl FromStr for Bytes {
     type Err = ParseBytesError;
 
     fn from_str(value: &str) -> Result<Self, Self::Err> {
-        if let Some(value) = value.strip_prefix("0x") {
-            hex::decode(value)
-        } else {
-            hex::decode(&value)
-        }
-        .map(Into::into)
-        .map_err(|e| ParseBytesError(format!("Invalid hex: {}", e)))
+        decode_hex(value).map(Into::into).map_err(ParseBytesError)
+    }
+}
+
+/// Decodes hex with or without `0x` prefix
+fn decode_hex(value: &str) -> Result<Vec<u8>, String> {
+    let value = value.strip_prefix("0x").unwrap_or(value);
+    if value.len() % 2 != 0 {
+        return Err(format!("Invalid hex: odd number of digits ({})", value.len()))
     }
+    hex::decode(value).map_err(|e| format!("Invalid hex: {}", e))
 }
 
 pub fn serialize_bytes<S, T>(x: T, s: S) -> Result<S::Ok, S::Error>
@@ -97,13 +118,7 @@ where
     D: Deserializer<'de>,
 {
     let value = String::deserialize(d)?;
-    if let Some(value) = value.strip_prefix("0x") {
-        hex::decode(value)
-    } else {
-        hex::decode(&value)
-    }
-    .map(Into::into)
-    .map_err(|e| serde::de::Error::custom(e.to_string()))
+    decode_hex(&value).map(Into::into).map_err(serde::de::Error::custom)
 }
 
 #[cfg(test)]
@@ -128,5 +143,28 @@ mod tests {
         let b = Bytes::from_str("1213");
         let b = b.unwrap();
         assert_eq!(b.as_ref(), hex::decode("1213").unwrap());
+
+        let err = Bytes::from_str("0x123").unwrap_err();
+        assert!(err.to_string().contains("odd number of digits"));
+        assert_eq!(Bytes::from_str("").unwrap(), Bytes::default());
+    }
+
+    #[test]
+    fn test_serde_with_and_without_prefix() {
+        let b: Bytes = serde_json::from_str(r#""0x1213""#).unwrap();
+        assert_eq!(b, Bytes::from(vec![0x12, 0x13]));
+        let b: Bytes = serde_json::from_str(r#""1213""#).unwrap();
+        assert_eq!(b, Bytes::from(vec![0x12, 0x13]));
+        assert!(serde_json::from_str::<Bytes>(r#""0x121""#).is_err());
+
+        assert_eq!(serde_json::to_string(&b).unwrap(), r#""0x1213""#);
+        assert_eq!(b.to_string_unprefixed(), "1213");
+    }
+
+    #[test]
+    fn test_from_iter_and_slices() {
+        let b: Bytes = (1u8..=3).collect();
+        assert_eq!(b, Bytes::from(&[1u8, 2, 3][..]));
+        assert_eq!(b.to_vec(), vec![1, 2, 3]);
     }
 }
@@ -2,7 +2,7 @@ use serde::Deserialize;
 use thiserror::Error;
 
 use core::convert::TryFrom;
-use std::{convert::TryInto, default, fmt, str::FromStr};
+use std::{convert::TryInto, default, fmt, str::FromStr, time::Duration};
 
 use crate::types::U256;
 
@@ -174,6 +174,33 @@ impl FromStr for Chain {
 }
 
 impl Chain {
+    /// Returns the average time between blocks of the chain, or `None` if it is unknown or
+    /// depends on the node, e.g. for [`Chain::Dev`].
+    ///
+    /// This is meant as a hint for polling intervals and not exact. The block times of proof of
+    /// stake chains are their slot times, the others are the averages reported by the block
+    /// explorers of the chains.
+    pub fn average_blocktime_hint(&self) -> Option<Duration> {
+        let ms = match self {
+            // the slot time of the beacon chain, `SECONDS_PER_SLOT` of the consensus specs
+            Chain::Mainnet | Chain::Ropsten | Chain::Sepolia | Chain::Goerli => 12_000,
+            // the clique period of rinkeby and the aura step of kovan
+            Chain::Rinkeby => 15_000,
+            Chain::Kovan => 4_000,
+            Chain::XDai => 5_000,
+            Chain::Polygon | Chain::PolygonMumbai => 2_100,
+            Chain::Fantom | Chain::FantomTestnet => 1_200,
+            Chain::Avalanche | Chain::AvalancheFuji => 2_000,
+            Chain::Moonbeam | Chain::Moonriver => 12_500,
+            Chain::BinanceSmartChain | Chain::BinanceSmartChainTestnet => 3_000,
+            Chain::Arbitrum | Chain::ArbitrumTestnet => 1_300,
+            Chain::Cronos | Chain::CronosTestnet => 5_700,
+            // optimism creates a block per transaction
+            Chain::Optimism | Chain::OptimismKovan | Chain::Dev | Chain::MoonbeamDev => return None,
+        };
+        Some(Duration::from_millis(ms))
+    }
+
     /// Helper function for checking if a chainid corresponds to a legacy chainid
     /// without eip1559
     pub fn is_legacy(&self) -> bool {
@@ -190,6 +217,62 @@ impl Chain {
                 Chain::ArbitrumTestnet,
         )
     }
+
+    /// Returns the `(api url, base url)` of the Etherscan compatible explorer of the chain, or
+    /// `None` if the chain has no supported explorer
+    pub fn etherscan_urls(&self) -> Option<(&'static str, &'static str)> {
+        let urls = match self {
+            Chain::Mainnet => ("https://api.etherscan.io/api", "https://etherscan.io"),
+            Chain::Ropsten => {
+                ("https://api-ropsten.etherscan.io/api", "https://ropsten.etherscan.io")
+            }
+            Chain::Kovan => ("https://api-kovan.etherscan.io/api", "https://kovan.etherscan.io"),
+            Chain::Rinkeby => {
+                ("https://api-rinkeby.etherscan.io/api", "https://rinkeby.etherscan.io")
+            }
+            Chain::Goerli => ("https://api-goerli.etherscan.io/api", "https://goerli.etherscan.io"),
+            Chain::Polygon => ("https://api.polygonscan.com/api", "https://polygonscan.com"),
+            Chain::PolygonMumbai => {
+                ("https://api-testnet.polygonscan.com/api", "https://mumbai.polygonscan.com")
+            }
+            Chain::Avalanche => ("https://api.snowtrace.io/api", "https://snowtrace.io"),
+            Chain::AvalancheFuji => {
+                ("https://api-testnet.snowtrace.io/api", "https://testnet.snowtrace.io")
+            }
+            Chain::Optimism => {
+                ("https://api-optimistic.etherscan.io/api", "https://optimistic.etherscan.io")
+            }
+            Chain::OptimismKovan => (
+                "https://api-kovan-optimistic.etherscan.io/api",
+                "https://kovan-optimistic.etherscan.io",
+            ),
+            Chain::Fantom => ("https://api.ftmscan.com/api", "https://ftmscan.com"),
+            Chain::FantomTestnet => {
+                ("https://api-testnet.ftmscan.com/api", "https://testnet.ftmscan.com")
+            }
+            Chain::BinanceSmartChain => ("https://api.bscscan.com/api", "https://bscscan.com"),
+            Chain::BinanceSmartChainTestnet => {
+                ("https://api-testnet.bscscan.com/api", "https://testnet.bscscan.com")
+            }
+            Chain::Arbitrum => ("https://api.arbiscan.io/api", "https://arbiscan.io"),
+            Chain::ArbitrumTestnet => {
+                ("https://api-testnet.arbiscan.io/api", "https://testnet.arbiscan.io")
+            }
+            Chain::Cronos => ("https://api.cronoscan.com/api", "https://cronoscan.com"),
+            Chain::Moonbeam => {
+                ("https://api-moonbeam.moonscan.io/api", "https://moonbeam.moonscan.io")
+            }
+            Chain::Moonriver => {
+                ("https://api-moonriver.moonscan.io/api", "https://moonriver.moonscan.io")
+            }
+            Chain::XDai |
+            Chain::Sepolia |
+            Chain::Dev |
+            Chain::MoonbeamDev |
+            Chain::CronosTestnet => return None,
+        };
+        Some(urls)
+    }
 }
 
 impl default::Default for Chain {
@@ -202,3 +285,19 @@ impl default::Default for Chain {
 fn test_default_chain() {
     assert_eq!(Chain::default(), Chain::Mainnet);
 }
+
+#[test]
+fn test_chain_hints() {
+    assert_eq!(Chain::Mainnet.average_blocktime_hint(), Some(Duration::from_secs(12)));
+    assert_eq!(Chain::Polygon.average_blocktime_hint(), Some(Duration::from_millis(2_100)));
+    assert_eq!(Chain::Optimism.average_blocktime_hint(), None);
+    assert_eq!(Chain::Dev.average_blocktime_hint(), None);
+    assert!(Chain::BinanceSmartChain.is_legacy());
+    assert!(!Chain::Mainnet.is_legacy());
+
+    assert_eq!(
+        Chain::Goerli.etherscan_urls(),
+        Some(("https://api-goerli.etherscan.io/api", "https://goerli.etherscan.io"))
+    );
+    assert_eq!(Chain::Dev.etherscan_urls(), None);
+}
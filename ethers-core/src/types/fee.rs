@@ -1,12 +1,20 @@
 use std::str::FromStr;
 
-use crate::types::U256;
+use crate::{types::U256, utils::eip1559_default_estimate};
 use serde::{de::Deserializer, Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize, Debug, Clone)]
+/// Response of `eth_feeHistory`, the base fees, gas used ratios and priority fee percentiles of a
+/// range of blocks
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct FeeHistory {
+    /// The base fee of each block, followed by the base fee of the block after the newest block.
+    ///
+    /// Some nodes omit the base fee of the next block, see [`FeeHistory::next_base_fee`].
+    #[serde(default)]
     pub base_fee_per_gas: Vec<U256>,
+    /// The ratio of gas used to the gas limit of each block
+    #[serde(default)]
     pub gas_used_ratio: Vec<f64>,
     #[serde(deserialize_with = "from_int_or_hex")]
     /// oldestBlock is returned as an unsigned integer up to geth v1.10.6. From
@@ -20,6 +28,39 @@ pub struct FeeHistory {
     pub reward: Vec<Vec<U256>>,
 }
 
+impl FeeHistory {
+    /// Returns the base fee of the block after the newest block of the history.
+    ///
+    /// If the node omitted it, it is derived from the base fee and gas used ratio of the newest
+    /// block like EIP-1559 does: the base fee changes by up to 1/8 depending on how far the gas
+    /// used is from the target of half the gas limit.
+    pub fn next_base_fee(&self) -> Option<U256> {
+        if self.base_fee_per_gas.len() > self.gas_used_ratio.len() {
+            return self.base_fee_per_gas.last().copied()
+        }
+        let base_fee = *self.base_fee_per_gas.last()?;
+        let gas_used_ratio = self.gas_used_ratio.last()?.max(0.0).min(1.0);
+
+        // the deviation from the gas target in per mille
+        let deviation = ((gas_used_ratio * 2.0 - 1.0) * 1000.0).round() as i64;
+        let delta = base_fee * U256::from(deviation.unsigned_abs()) / 8_000;
+        Some(match deviation {
+            d if d > 0 => base_fee + delta.max(U256::one()),
+            d if d < 0 => base_fee - delta,
+            _ => base_fee,
+        })
+    }
+
+    /// Estimates the `(max_fee_per_gas, max_priority_fee_per_gas)` of an EIP-1559 transaction
+    /// from the [next base fee](FeeHistory::next_base_fee) and the first reward percentile of
+    /// each block, see [`eip1559_default_estimate`].
+    ///
+    /// Returns `None` if the history has no base fees.
+    pub fn eip1559_estimate(&self) -> Option<(U256, U256)> {
+        self.next_base_fee().map(|base_fee| eip1559_default_estimate(base_fee, &self.reward))
+    }
+}
+
 fn from_int_or_hex<'de, D>(deserializer: D) -> Result<U256, D::Error>
 where
     D: Deserializer<'de>,
@@ -35,3 +76,35 @@ where
         IntOrHex::Hex(s) => U256::from_str(s.as_str()).map_err(serde::de::Error::custom),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_get_next_base_fee() {
+        let mut history: FeeHistory = serde_json::from_str(
+            r#"{
+            "oldestBlock": "0x1",
+            "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00", "0x4190ab00"],
+            "gasUsedRatio": [0.5, 1.0],
+            "reward": [["0x1"], ["0x2"]]
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(history.next_base_fee(), Some(1_100_000_000u64.into()));
+
+        // the node omitted the next base fee
+        history.base_fee_per_gas.pop();
+        assert_eq!(history.next_base_fee(), Some(1_125_000_000u64.into()));
+        history.gas_used_ratio[1] = 0.0;
+        assert_eq!(history.next_base_fee(), Some(875_000_000u64.into()));
+        history.gas_used_ratio[1] = 0.5;
+        assert_eq!(history.next_base_fee(), Some(1_000_000_000u64.into()));
+
+        let history: FeeHistory =
+            serde_json::from_str(r#"{"oldestBlock": 0, "gasUsedRatio": []}"#).unwrap();
+        assert_eq!(history.next_base_fee(), None);
+        assert_eq!(history.eip1559_estimate(), None);
+    }
+}
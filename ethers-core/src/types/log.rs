@@ -1,5 +1,6 @@
 // Adapted from https://github.com/tomusdrw/rust-web3/blob/master/src/types/log.rs
 use crate::{
+    abi::{HumanReadableParser, ParseError},
     types::{Address, BlockNumber, Bytes, H160, H256, U256, U64},
     utils::keccak256,
 };
@@ -344,6 +345,35 @@ impl Filter {
         self.topic0(hash)
     }
 
+    /// Sets topic0 to the hash of the human readable event signature, either the canonical
+    /// `Transfer(address,address,uint256)` or a declaration like
+    /// `event Transfer(address indexed from, address indexed to, uint256 value)`.
+    ///
+    /// Fails if the signature can't be parsed or is of an anonymous event, which has no topic0.
+    ///
+    /// ```rust
+    /// # use ethers_core::types::{Address, Filter};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let (alice, bob, carol) = (Address::random(), Address::random(), Address::random());
+    /// // Transfers from alice to either bob or carol
+    /// let filter = Filter::new()
+    ///     .event_signature("event Transfer(address indexed from, address indexed to, uint256)")?
+    ///     .topic1(alice)
+    ///     .topic2(vec![bob, carol]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn event_signature(self, signature: &str) -> Result<Self, ParseError> {
+        let event = HumanReadableParser::parse_event(signature)?;
+        if event.anonymous {
+            return Err(ParseError::Message(format!(
+                "anonymous event `{}` has no signature topic",
+                event.name
+            )))
+        }
+        Ok(self.topic0(event.signature()))
+    }
+
     /// Sets topic0 (the event name for non-anonymous events)
     #[must_use]
     pub fn topic0<T: Into<ValueOrArray<H256>>>(mut self, topic: T) -> Self {
@@ -352,6 +382,8 @@ impl Filter {
     }
 
     /// Sets the 1st indexed topic
+    ///
+    /// All set topics must match, a topic set to multiple values matches if any of them matches.
     #[must_use]
     pub fn topic1<T: Into<ValueOrArray<H256>>>(mut self, topic: T) -> Self {
         self.topics[1] = Some(topic.into());
@@ -402,22 +434,50 @@ impl From<H256> for ValueOrArray<H256> {
     }
 }
 
+impl From<Vec<H256>> for ValueOrArray<H256> {
+    fn from(src: Vec<H256>) -> Self {
+        ValueOrArray::Array(src)
+    }
+}
+
 impl From<Address> for ValueOrArray<H256> {
     fn from(src: Address) -> Self {
-        let mut bytes = [0; 32];
-        bytes[12..32].copy_from_slice(src.as_bytes());
-        ValueOrArray::Value(H256::from(bytes))
+        ValueOrArray::Value(address_topic(src))
+    }
+}
+
+impl From<Vec<Address>> for ValueOrArray<H256> {
+    fn from(src: Vec<Address>) -> Self {
+        ValueOrArray::Array(src.into_iter().map(address_topic).collect())
     }
 }
 
 impl From<U256> for ValueOrArray<H256> {
     fn from(src: U256) -> Self {
-        let mut bytes = [0; 32];
-        src.to_big_endian(&mut bytes);
-        ValueOrArray::Value(H256::from(bytes))
+        ValueOrArray::Value(u256_topic(src))
     }
 }
 
+impl From<Vec<U256>> for ValueOrArray<H256> {
+    fn from(src: Vec<U256>) -> Self {
+        ValueOrArray::Array(src.into_iter().map(u256_topic).collect())
+    }
+}
+
+/// Returns the topic of an indexed `address`, left padded to 32 bytes
+fn address_topic(src: Address) -> H256 {
+    let mut bytes = [0; 32];
+    bytes[12..32].copy_from_slice(src.as_bytes());
+    H256::from(bytes)
+}
+
+/// Returns the topic of an indexed `uint256`
+fn u256_topic(src: U256) -> H256 {
+    let mut bytes = [0; 32];
+    src.to_big_endian(&mut bytes);
+    H256::from(bytes)
+}
+
 impl<T> Serialize for ValueOrArray<T>
 where
     T: Serialize,
@@ -547,4 +607,29 @@ mod tests {
         let ser = serialize(&filter.topic1(t1).topic2(t2).topic3(t3));
         assert_eq!(ser, json!({ "address" : addr, "topics": [t0, t1_padded, t2, t3_padded]}));
     }
+
+    #[test]
+    fn filter_event_signature_and_or_topics() {
+        let transfer: H256 =
+            "ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef".parse().unwrap();
+        for signature in &[
+            "Transfer(address,address,uint256)",
+            "event Transfer(address indexed from, address indexed to, uint256 value)",
+        ] {
+            let filter = Filter::new().event_signature(signature).unwrap();
+            assert_eq!(filter.topics[0], Some(transfer.into()));
+        }
+        assert!(Filter::new().event_signature("Transfer(address,").is_err());
+        assert!(Filter::new().event_signature("event Transfer(address) anonymous").is_err());
+
+        let a = Address::repeat_byte(1);
+        let (b, c) = (U256::from(2), U256::from(3));
+        let filter = Filter::new().topic1(a).topic2(vec![b, c]);
+        assert_eq!(
+            serialize(&filter),
+            json!({ "topics": [null, H256::from(a), [u256_topic(b), u256_topic(c)]] })
+        );
+        let filter = Filter::new().topic3(vec![a]);
+        assert_eq!(serialize(&filter), json!({ "topics": [null, null, null, [H256::from(a)]] }));
+    }
 }
@@ -11,10 +11,13 @@ pub mod transaction;
 pub use transaction::{
     eip1559::Eip1559TransactionRequest,
     eip2930::Eip2930TransactionRequest,
-    request::TransactionRequest,
-    response::{Transaction, TransactionReceipt},
+    request::{TransactionRequest, TransactionValidationError},
+    response::{GasReport, ReceiptLog, Transaction, TransactionReceipt},
 };
 
+mod address;
+pub use address::AddressExt;
+
 mod address_or_bytes;
 pub use address_or_bytes::AddressOrBytes;
 
@@ -60,3 +63,6 @@ pub use proof::*;
 
 mod fee;
 pub use fee::*;
+
+mod state_override;
+pub use state_override::{AccountOverride, StateOverride};
@@ -1,32 +1,263 @@
-use crate::types::{Address, Bytes, H256, U256};
+//! Types for the Merkle proofs of `eth_getProof`
+//!
+//! <https://eips.ethereum.org/EIPS/eip-1186>
+use crate::{
+    types::{Address, Bytes, H256, U256},
+    utils::keccak256,
+};
+use rlp::{Rlp, RlpStream};
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
+/// The root hash of an empty Merkle Patricia trie, `keccak256(rlp(""))`
+const EMPTY_ROOT_HASH: H256 = H256([
+    0x56, 0xe8, 0x1f, 0x17, 0x1b, 0xcc, 0x55, 0xa6, 0xff, 0x83, 0x45, 0xe6, 0x92, 0xc0, 0xf8, 0x6e,
+    0x5b, 0x48, 0xe0, 0x1b, 0x99, 0x6c, 0xad, 0xc0, 0x01, 0x62, 0x2f, 0xb5, 0xe3, 0x63, 0xb4, 0x21,
+]);
+
+/// The code hash of accounts without code, `keccak256("")`
+const EMPTY_CODE_HASH: H256 = H256([
+    0xc5, 0xd2, 0x46, 0x01, 0x86, 0xf7, 0x23, 0x3c, 0x92, 0x7e, 0x7d, 0xb2, 0xdc, 0xc7, 0x03, 0xc0,
+    0xe5, 0x00, 0xb6, 0x53, 0xca, 0x82, 0x27, 0x3b, 0x7b, 0xfa, 0xd8, 0x04, 0x5d, 0x85, 0xa4, 0x70,
+]);
+
+/// Error returned by [`EIP1186ProofResponse::verify`]
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ProofError {
+    /// A node of the proof is not valid RLP
+    #[error(transparent)]
+    Rlp(#[from] rlp::DecoderError),
+    /// The node at the index does not match the hash of its parent, or the root
+    #[error("proof node {0} does not match its parent")]
+    NodeMismatch(usize),
+    /// A node of the proof is neither a branch, extension nor leaf node
+    #[error("proof node {0} is not a trie node")]
+    InvalidNode(usize),
+    /// The proof ends before it reaches the value, or continues after it
+    #[error("proof is incomplete")]
+    Incomplete,
+    /// The proven account does not match the account of the response
+    #[error("account does not match the proof")]
+    AccountMismatch,
+    /// The proven value of the storage key does not match the value of the response
+    #[error("storage value of key {0:?} does not match the proof")]
+    StorageMismatch(H256),
+}
+
+/// The value of a storage slot and its Merkle proof
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 pub struct StorageProof {
+    /// The requested storage key
     pub key: H256,
+    /// The RLP encoded trie nodes from the storage root to the value of the key
     pub proof: Vec<Bytes>,
+    /// The value of the storage slot
     pub value: U256,
 }
 
+/// Response of `eth_getProof`, the account and some of its storage slots with their Merkle
+/// proofs
 #[derive(Debug, Default, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct EIP1186ProofResponse {
-    address: Address,
-    balance: U256,
-    code_hash: H256,
-    nonce: U256,
-    storage_hash: H256,
-    account_proof: Vec<Bytes>,
-    storage_proof: Vec<StorageProof>,
+    /// The address of the account
+    pub address: Address,
+    /// The balance of the account
+    pub balance: U256,
+    /// The hash of the code of the account
+    pub code_hash: H256,
+    /// The nonce of the account
+    pub nonce: U256,
+    /// The root hash of the storage trie of the account
+    pub storage_hash: H256,
+    /// The RLP encoded trie nodes from the state root to the account
+    pub account_proof: Vec<Bytes>,
+    /// The requested storage slots
+    pub storage_proof: Vec<StorageProof>,
+}
+
+impl EIP1186ProofResponse {
+    /// Verifies the account and storage proofs against the state root of the block the proof was
+    /// requested for, e.g. the `state_root` of a trusted block header.
+    ///
+    /// Accounts that don't exist must have a zero nonce and balance, no code and no storage.
+    /// Storage slots with a zero value must be absent from the storage trie.
+    pub fn verify(&self, state_root: H256) -> Result<(), ProofError> {
+        let account = verify_trie_proof(
+            state_root,
+            &keccak256(self.address.as_bytes()),
+            &self.account_proof,
+        )?;
+        match account {
+            Some(account) => {
+                let mut stream = RlpStream::new_list(4);
+                stream
+                    .append(&self.nonce)
+                    .append(&self.balance)
+                    .append(&self.storage_hash)
+                    .append(&self.code_hash);
+                if account != stream.out().as_ref() {
+                    return Err(ProofError::AccountMismatch)
+                }
+            }
+            None => {
+                if !self.nonce.is_zero() ||
+                    !self.balance.is_zero() ||
+                    self.storage_hash != EMPTY_ROOT_HASH ||
+                    self.code_hash != EMPTY_CODE_HASH
+                {
+                    return Err(ProofError::AccountMismatch)
+                }
+            }
+        }
+
+        for storage in &self.storage_proof {
+            let value = verify_trie_proof(
+                self.storage_hash,
+                &keccak256(storage.key.as_bytes()),
+                &storage.proof,
+            )?;
+            let matches = match value {
+                Some(value) => {
+                    !storage.value.is_zero() && value == rlp::encode(&storage.value).as_ref()
+                }
+                None => storage.value.is_zero(),
+            };
+            if !matches {
+                return Err(ProofError::StorageMismatch(storage.key))
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Walks the proof from the root to the key, returns the RLP encoded value of the key or `None`
+/// if the proof shows that the key is not part of the trie
+fn verify_trie_proof(
+    root: H256,
+    key: &[u8; 32],
+    proof: &[Bytes],
+) -> Result<Option<Vec<u8>>, ProofError> {
+    if proof.is_empty() {
+        return if root == EMPTY_ROOT_HASH { Ok(None) } else { Err(ProofError::Incomplete) }
+    }
+
+    let nibbles: Vec<u8> = key.iter().flat_map(|b| [b >> 4, b & 0x0f]).collect();
+    let mut nibbles = &nibbles[..];
+    // the parent references its child either by hash, or inline if the node is short
+    let mut expected_hash = Some(root);
+    let mut expected_node: &[u8] = &[];
+
+    for (idx, node) in proof.iter().enumerate() {
+        let is_last = idx == proof.len() - 1;
+        let matches = match expected_hash {
+            Some(hash) => H256(keccak256(node)) == hash,
+            None => node.as_ref() == expected_node,
+        };
+        if !matches {
+            return Err(ProofError::NodeMismatch(idx))
+        }
+
+        let rlp = Rlp::new(node.as_ref());
+        let child = match rlp.item_count()? {
+            17 => {
+                if nibbles.is_empty() {
+                    if !is_last {
+                        return Err(ProofError::Incomplete)
+                    }
+                    let value = rlp.at(16)?.data()?;
+                    return Ok(if value.is_empty() { None } else { Some(value.to_vec()) })
+                }
+                let child = rlp.at(nibbles[0] as usize)?;
+                nibbles = &nibbles[1..];
+                child
+            }
+            2 => {
+                let path = rlp.at(0)?.data()?;
+                if path.is_empty() {
+                    return Err(ProofError::InvalidNode(idx))
+                }
+                // hex prefix encoding, the flag nibble marks leaves and odd length paths
+                let flag = path[0] >> 4;
+                let mut path_nibbles = Vec::with_capacity(path.len() * 2);
+                if flag & 1 == 1 {
+                    path_nibbles.push(path[0] & 0x0f);
+                }
+                path_nibbles.extend(path[1..].iter().flat_map(|b| [b >> 4, b & 0x0f]));
+
+                if flag >= 2 {
+                    if !is_last {
+                        return Err(ProofError::Incomplete)
+                    }
+                    return if nibbles == &path_nibbles[..] {
+                        Ok(Some(rlp.at(1)?.data()?.to_vec()))
+                    } else {
+                        Ok(None)
+                    }
+                }
+                if !nibbles.starts_with(&path_nibbles) {
+                    return if is_last { Ok(None) } else { Err(ProofError::Incomplete) }
+                }
+                nibbles = &nibbles[path_nibbles.len()..];
+                rlp.at(1)?
+            }
+            _ => return Err(ProofError::InvalidNode(idx)),
+        };
+
+        if child.is_list() {
+            expected_hash = None;
+            expected_node = child.as_raw();
+        } else {
+            let hash = child.data()?;
+            if hash.is_empty() {
+                return if is_last { Ok(None) } else { Err(ProofError::Incomplete) }
+            }
+            if hash.len() != 32 {
+                return Err(ProofError::InvalidNode(idx))
+            }
+            expected_hash = Some(H256::from_slice(hash));
+        }
+    }
+    Err(ProofError::Incomplete)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn proof() -> EIP1186ProofResponse {
+        serde_json::from_str(include_str!("../../testdata/proof.json")).unwrap()
+    }
+
     #[test]
     fn can_deserialize_proof() {
-        serde_json::from_str::<EIP1186ProofResponse>(include_str!("../../testdata/proof.json"))
-            .unwrap();
+        let proof = proof();
+        assert_eq!(proof.account_proof.len(), 8);
+        assert_eq!(proof.storage_proof.len(), 1);
+    }
+
+    #[test]
+    fn can_verify_proof() {
+        let proof = proof();
+        let state_root: H256 =
+            "0x57e6e864257daf9d96aaca31edd0cfe4e3892f09061e727c57ab56197dd59287".parse().unwrap();
+        assert_eq!(H256(keccak256(&proof.account_proof[0])), state_root);
+        proof.verify(state_root).unwrap();
+
+        assert_eq!(proof.verify(H256::zero()), Err(ProofError::NodeMismatch(0)));
+
+        let mut tampered = proof.clone();
+        tampered.balance = 1u64.into();
+        assert_eq!(tampered.verify(state_root), Err(ProofError::AccountMismatch));
+
+        // the storage proof shows that the slot is empty
+        let mut tampered = proof.clone();
+        tampered.storage_proof[0].value = 1u64.into();
+        let key = tampered.storage_proof[0].key;
+        assert_eq!(tampered.verify(state_root), Err(ProofError::StorageMismatch(key)));
+
+        let mut tampered = proof;
+        tampered.account_proof.pop();
+        assert_eq!(tampered.verify(state_root), Err(ProofError::Incomplete));
     }
 }
@@ -38,6 +38,16 @@ pub enum SignatureError {
     /// Error in recovering public key from signature
     #[error("Public key recovery error")]
     RecoveryError,
+    /// The `v` value is not a recovery id in any of the known conventions
+    #[error("invalid recovery id, got v = {0}, expected 0, 1, 27, 28 or an EIP-155 value")]
+    InvalidRecoveryId(u64),
+    /// Error when encoding the EIP-712 payload whose signer is recovered
+    #[error("error encoding eip712 struct: {0}")]
+    Eip712Error(String),
+    /// The `s` value has its highest bit set, which the EIP-2098 compact form uses for the
+    /// recovery id
+    #[error("s value exceeds 2^255, the signature has no EIP-2098 compact form")]
+    HighS,
 }
 
 /// Recovery message data.
@@ -89,8 +99,11 @@ impl Signature {
 
     /// Recovers the Ethereum address which was used to sign the given message.
     ///
-    /// Recovery signature data uses 'Electrum' notation, this means the `v`
-    /// value is expected to be either `27` or `28`.
+    /// Binary messages are hashed according to EIP-191 first. The `v` value may be the recovery
+    /// id itself (`0` or `1`), in 'Electrum' notation (`27` or `28`) or an EIP-155 value.
+    ///
+    /// Note that any valid signature recovers to some address, use [`Signature::verify`] to
+    /// check that it is the expected signer.
     pub fn recover<M>(&self, message: M) -> Result<Address, SignatureError>
     where
         M: Into<RecoveryMessage>,
@@ -113,6 +126,17 @@ impl Signature {
         Ok(Address::from_slice(&hash[12..]))
     }
 
+    /// Recovers the Ethereum address which was used to sign the given EIP-712 typed data
+    #[cfg(feature = "eip712")]
+    pub fn recover_typed<T: crate::types::transaction::eip712::Eip712>(
+        &self,
+        payload: &T,
+    ) -> Result<Address, SignatureError> {
+        let encoded =
+            payload.encode_eip712().map_err(|err| SignatureError::Eip712Error(err.to_string()))?;
+        self.recover(H256::from(encoded))
+    }
+
     /// Retrieves the recovery signature.
     fn as_signature(&self) -> Result<(RecoverableSignature, RecoveryId), SignatureError> {
         let recovery_id = self.recovery_id()?;
@@ -132,25 +156,64 @@ impl Signature {
 
     /// Retrieve the recovery ID.
     pub fn recovery_id(&self) -> Result<RecoveryId, SignatureError> {
-        let standard_v = normalize_recovery_id(self.v);
+        let standard_v =
+            normalize_recovery_id(self.v).ok_or(SignatureError::InvalidRecoveryId(self.v))?;
         Ok(RecoveryId::new(standard_v)?)
     }
 
-    /// Copies and serializes `self` into a new `Vec` with the recovery id included
+    /// Copies and serializes `self` into a new `Vec` with the recovery id included, see
+    /// [`Signature::as_bytes`]
     #[allow(clippy::wrong_self_convention)]
     pub fn to_vec(&self) -> Vec<u8> {
         self.into()
     }
+
+    /// Serializes `self` into the 65 bytes `r || s || v`, with `v` normalized to `27` or `28`.
+    ///
+    /// A `v` that is no known recovery id is truncated to a byte.
+    pub fn as_bytes(&self) -> [u8; 65] {
+        self.into()
+    }
+
+    /// Serializes `self` into the 64 bytes EIP-2098 compact form `r || yParityAndS`, where the
+    /// highest bit of `s` is set to the recovery id.
+    ///
+    /// Fails if `v` is no known recovery id, or if `s` has its highest bit set, which ECDSA
+    /// signatures in the canonical low-s form never have.
+    pub fn to_eip2098_compact(&self) -> Result<[u8; 64], SignatureError> {
+        let recovery_id =
+            normalize_recovery_id(self.v).ok_or(SignatureError::InvalidRecoveryId(self.v))?;
+        if self.s.bit(255) {
+            return Err(SignatureError::HighS)
+        }
+
+        let mut compact = [0u8; 64];
+        self.r.to_big_endian(&mut compact[..32]);
+        self.s.to_big_endian(&mut compact[32..]);
+        compact[32] |= recovery_id << 7;
+        Ok(compact)
+    }
+
+    /// Parses a signature in the EIP-2098 compact form `r || yParityAndS`, `v` is set to `27` or
+    /// `28`
+    pub fn from_eip2098_compact(compact: &[u8; 64]) -> Self {
+        let r = U256::from_big_endian(&compact[..32]);
+        let mut s = [0u8; 32];
+        s.copy_from_slice(&compact[32..]);
+        let recovery_id = s[0] >> 7;
+        s[0] &= 0x7f;
+        Signature { r, s: U256::from_big_endian(&s), v: 27 + recovery_id as u64 }
+    }
 }
 
-fn normalize_recovery_id(v: u64) -> u8 {
+fn normalize_recovery_id(v: u64) -> Option<u8> {
     match v {
-        0 => 0,
-        1 => 1,
-        27 => 0,
-        28 => 1,
-        v if v >= 35 => ((v - 1) % 2) as _,
-        _ => 4,
+        0 => Some(0),
+        1 => Some(1),
+        27 => Some(0),
+        28 => Some(1),
+        v if v >= 35 => Some(((v - 1) % 2) as _),
+        _ => None,
     }
 }
 
@@ -192,9 +255,7 @@ impl From<&Signature> for [u8; 65] {
         src.s.to_big_endian(&mut s_bytes);
         sig[..32].copy_from_slice(&r_bytes);
         sig[32..64].copy_from_slice(&s_bytes);
-        // TODO: What if we try to serialize a signature where
-        // the `v` is not normalized?
-        sig[64] = src.v as u8;
+        sig[64] = normalize_recovery_id(src.v).map(|id| id + 27).unwrap_or(src.v as u8);
         sig
     }
 }
@@ -270,6 +331,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn recovers_all_v_conventions() {
+        let signature = Signature::from_str(
+            "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c"
+        ).unwrap();
+        let address = Address::from_str("2c7536E3605D9C16a7a3D7b1898e529396a65c23").unwrap();
+
+        // 28 in 'Electrum' notation, 1 as raw recovery id, 38 with EIP-155 for chain id 1
+        for v in [28, 1, 38] {
+            let signature = Signature { v, ..signature };
+            assert_eq!(signature.recover("Some data").unwrap(), address);
+            signature.verify("Some data", address).unwrap();
+        }
+
+        let signature = Signature { v: 29, ..signature };
+        assert!(matches!(
+            signature.recover("Some data"),
+            Err(SignatureError::InvalidRecoveryId(29))
+        ));
+
+        let other = Address::from_str("0000000000000000000000000000000000000001").unwrap();
+        assert!(matches!(
+            Signature { v: 28, ..signature }.verify("Some data", other),
+            Err(SignatureError::VerificationError(expected, recovered))
+                if expected == other && recovered == address
+        ));
+    }
+
     #[test]
     fn signature_from_str() {
         let s1 = Signature::from_str(
@@ -282,4 +371,42 @@ mod tests {
 
         assert_eq!(s1, s2);
     }
+
+    #[test]
+    fn normalizes_v_of_serialized_signature() {
+        let hex = "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c";
+        let signature = Signature::from_str(hex).unwrap();
+        assert_eq!(signature.to_string(), hex);
+
+        for v in [1, 28, 38] {
+            let bytes = Signature { v, ..signature }.as_bytes();
+            assert_eq!(bytes[64], 28);
+            assert_eq!(Signature::try_from(&bytes[..]).unwrap(), signature);
+        }
+        assert!(matches!(
+            Signature::try_from(&[0u8; 64][..]),
+            Err(SignatureError::InvalidLength(64))
+        ));
+    }
+
+    #[test]
+    fn eip2098_compact_signature() {
+        // test vector from https://eips.ethereum.org/EIPS/eip-2098
+        let signature = Signature {
+            r: U256::from_str("9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76")
+                .unwrap(),
+            s: U256::from_str("139c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793")
+                .unwrap(),
+            v: 28,
+        };
+        let compact = signature.to_eip2098_compact().unwrap();
+        assert_eq!(
+            hex::encode(&compact[..]),
+            "9328da16089fcba9bececa81663203989f2df5fe1faa6291a45381c81bd17f76939c6d6b623b42da56557e5e734a43dc83345ddfadec52cbe24d0cc64f550793"
+        );
+        assert_eq!(Signature::from_eip2098_compact(&compact), signature);
+
+        let high_s = Signature { s: U256::max_value(), ..signature };
+        assert!(matches!(high_s.to_eip2098_compact(), Err(SignatureError::HighS)));
+    }
 }
@@ -0,0 +1,123 @@
+//! Types for the state override set of `eth_call` and `eth_estimateGas`
+//!
+//! <https://geth.ethereum.org/docs/rpc/ns-eth#3-object---state-override-set>
+use crate::types::{Address, Bytes, H256, U256, U64};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The state of an account that is overridden for a call, fields that are not set keep the
+/// state of the block the call is executed at
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountOverride {
+    /// The balance of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// The nonce of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<U64>,
+    /// The code of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// Replaces the whole storage of the account, slots that are not set are empty
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<BTreeMap<H256, H256>>,
+    /// Replaces single storage slots of the account, this can't be combined with `state`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state_diff: Option<BTreeMap<H256, H256>>,
+}
+
+/// The accounts whose state is overridden for a call, keyed by address
+///
+/// # Example
+///
+/// ```rust
+/// # use ethers_core::types::{Address, StateOverride, H256};
+/// let (alice, token) = (Address::random(), Address::random());
+/// let overrides = StateOverride::new()
+///     .balance(alice, 1_000_000_000_000_000_000u64)
+///     .state_diff(token, H256::zero(), H256::from_low_u64_be(1));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct StateOverride(pub BTreeMap<Address, AccountOverride>);
+
+impl StateOverride {
+    /// Creates an empty set of overrides
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the overrides of the account, inserting empty ones if the account has none yet
+    pub fn account(&mut self, address: Address) -> &mut AccountOverride {
+        self.0.entry(address).or_default()
+    }
+
+    /// Overrides the balance of the account
+    #[must_use]
+    pub fn balance(mut self, address: Address, balance: impl Into<U256>) -> Self {
+        self.account(address).balance = Some(balance.into());
+        self
+    }
+
+    /// Overrides the nonce of the account
+    #[must_use]
+    pub fn nonce(mut self, address: Address, nonce: impl Into<U64>) -> Self {
+        self.account(address).nonce = Some(nonce.into());
+        self
+    }
+
+    /// Overrides the code of the account
+    #[must_use]
+    pub fn code(mut self, address: Address, code: impl Into<Bytes>) -> Self {
+        self.account(address).code = Some(code.into());
+        self
+    }
+
+    /// Replaces the whole storage of the account with the given slots
+    #[must_use]
+    pub fn state(mut self, address: Address, state: BTreeMap<H256, H256>) -> Self {
+        self.account(address).state = Some(state);
+        self
+    }
+
+    /// Overrides a single storage slot of the account
+    #[must_use]
+    pub fn state_diff(mut self, address: Address, slot: H256, value: H256) -> Self {
+        self.account(address).state_diff.get_or_insert_with(BTreeMap::new).insert(slot, value);
+        self
+    }
+
+    /// Returns `true` if no account is overridden
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn can_serialize_state_override() {
+        let (alice, token) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let overrides = StateOverride::new()
+            .balance(alice, 100u64)
+            .nonce(alice, 7u64)
+            .code(token, vec![0x60, 0x00])
+            .state_diff(token, H256::zero(), H256::from_low_u64_be(1));
+
+        let expected = json!({
+            format!("{:?}", alice): { "balance": "0x64", "nonce": "0x7" },
+            format!("{:?}", token): {
+                "code": "0x6000",
+                "stateDiff": {
+                    format!("{:?}", H256::zero()): format!("{:?}", H256::from_low_u64_be(1))
+                }
+            }
+        });
+        assert_eq!(serde_json::to_value(&overrides).unwrap(), expected);
+        assert_eq!(serde_json::from_value::<StateOverride>(expected).unwrap(), overrides);
+    }
+}
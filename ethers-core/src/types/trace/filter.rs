@@ -363,4 +363,79 @@ mod tests {
         let _trace: Trace = serde_json::from_str(EXAMPLE_TRACE_SUICIDE).unwrap();
         let _trace: Trace = serde_json::from_str(EXAMPLE_TRACE_REWARD).unwrap();
     }
+
+    #[test]
+    fn test_deserialize_trace_actions() {
+        let trace: Trace = serde_json::from_str(EXAMPLE_TRACE_CALL).unwrap();
+        assert_eq!(trace.action_type, ActionType::Call);
+        match trace.action {
+            Action::Call(call) => assert_eq!(call.call_type, CallType::Call),
+            action => panic!("unexpected action {:?}", action),
+        }
+        assert!(matches!(trace.result, Some(Res::Call(_))));
+        assert_eq!(trace.subtraces, 1);
+        assert!(trace.trace_address.is_empty());
+
+        let trace: Trace = serde_json::from_str(EXAMPLE_TRACE_CREATE).unwrap();
+        assert_eq!(trace.action_type, ActionType::Create);
+        assert!(matches!(trace.action, Action::Create(_)));
+
+        let trace: Trace = serde_json::from_str(EXAMPLE_TRACE_SUICIDE).unwrap();
+        assert_eq!(trace.action_type, ActionType::Suicide);
+        assert!(matches!(trace.action, Action::Suicide(_)));
+
+        let trace: Trace = serde_json::from_str(EXAMPLE_TRACE_REWARD).unwrap();
+        assert_eq!(trace.action_type, ActionType::Reward);
+        match trace.action {
+            Action::Reward(reward) => assert_eq!(reward.reward_type, RewardType::Block),
+            action => panic!("unexpected action {:?}", action),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_reward_trace_without_transaction() {
+        // block rewards are not part of a transaction and have no result
+        let trace: Trace = serde_json::from_str(
+            r#"{
+            "action": {
+                "author": "0x5a0b54d5dc17e0aadc383d2db43b0a0d3e029c4c",
+                "rewardType": "uncle",
+                "value": "0x1a055690d9db80000"
+            },
+            "blockHash": "0x6474a53a9ebf72d306a1406ec12ded12e210b6c3141b4373bfb3a3cea987dfb8",
+            "blockNumber": 988775,
+            "result": null,
+            "subtraces": 0,
+            "traceAddress": [],
+            "type": "reward"
+        }"#,
+        )
+        .unwrap();
+        assert!(trace.result.is_none());
+        assert!(trace.transaction_hash.is_none());
+        assert!(trace.transaction_position.is_none());
+        assert!(matches!(
+            trace.action,
+            Action::Reward(Reward { reward_type: RewardType::Uncle, .. })
+        ));
+    }
+
+    #[test]
+    fn test_serialize_trace_filter() {
+        let from = Address::repeat_byte(1);
+        let filter = TraceFilter::default()
+            .from_block(1u64)
+            .to_block(BlockNumber::Latest)
+            .from_address(vec![from])
+            .count(10);
+        assert_eq!(
+            serde_json::to_value(&filter).unwrap(),
+            serde_json::json!({
+                "fromBlock": "0x1",
+                "toBlock": "latest",
+                "fromAddress": [format!("{:?}", from)],
+                "count": 10
+            })
+        );
+    }
 }
@@ -0,0 +1,291 @@
+//! Types for the Geth debug tracing API
+//!
+//! <https://geth.ethereum.org/docs/rpc/ns-debug#debug_tracetransaction>
+use crate::types::{Address, Bytes, H256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The name of the builtin tracer that returns the call tree of a transaction
+pub const CALL_TRACER: &str = "callTracer";
+
+/// The name of the builtin tracer that returns the accounts touched by a transaction
+pub const PRESTATE_TRACER: &str = "prestateTracer";
+
+/// Options of `debug_traceTransaction`
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GethDebugTracingOptions {
+    /// Disables the storage capture of the struct logger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_storage: Option<bool>,
+    /// Disables the stack capture of the struct logger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_stack: Option<bool>,
+    /// Enables the memory capture of the struct logger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_memory: Option<bool>,
+    /// Enables the return data capture of the struct logger
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_return_data: Option<bool>,
+    /// The name of a builtin tracer, e.g. [`CALL_TRACER`], or a JavaScript tracer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracer: Option<String>,
+    /// The config of the tracer, e.g. `{"onlyTopCall": true}` for the call tracer
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tracer_config: Option<serde_json::Value>,
+    /// Overrides the default timeout of 5 seconds, e.g. `"10s"`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout: Option<String>,
+}
+
+/// The result of `debug_traceTransaction` with a tracer, or the output of the default struct
+/// logger if the node ignored the tracer
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum GethTrace<T> {
+    /// The output of the requested tracer
+    Tracer(T),
+    /// The output of the struct logger
+    StructLogger(DefaultFrame),
+}
+
+impl<T> GethTrace<T> {
+    /// Returns the output of the requested tracer, or `None` if the node returned the output of
+    /// the struct logger instead
+    pub fn into_tracer(self) -> Option<T> {
+        match self {
+            GethTrace::Tracer(trace) => Some(trace),
+            GethTrace::StructLogger(_) => None,
+        }
+    }
+}
+
+/// A call of the [`CALL_TRACER`] output and the calls it made
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+    /// The kind of the call, e.g. `CALL`, `DELEGATECALL` or `CREATE2`
+    #[serde(rename = "type")]
+    pub typ: String,
+    /// The sender of the call
+    pub from: Address,
+    /// The receiver of the call, or the created contract
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub to: Option<Address>,
+    /// The value sent with the call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<U256>,
+    /// The gas available to the call
+    pub gas: U256,
+    /// The gas used by the call
+    pub gas_used: U256,
+    /// The calldata, or the init code of a created contract
+    pub input: Bytes,
+    /// The return data
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output: Option<Bytes>,
+    /// The error of a failed call, e.g. `execution reverted`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The decoded revert reason of a reverted call
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revert_reason: Option<String>,
+    /// The calls made by this call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<CallFrame>,
+}
+
+/// The output of the [`PRESTATE_TRACER`]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum PreStateFrame {
+    /// The state of the touched accounts before the transaction, the default mode
+    Default(BTreeMap<Address, AccountState>),
+    /// The state changed by the transaction, with `{"diffMode": true}` as tracer config
+    Diff(DiffMode),
+}
+
+/// The state changed by a transaction, see [`PreStateFrame::Diff`]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DiffMode {
+    /// The state of the changed accounts before the transaction
+    pub pre: BTreeMap<Address, AccountState>,
+    /// The state of the changed accounts after the transaction
+    pub post: BTreeMap<Address, AccountState>,
+}
+
+/// The state of an account in the [`PRESTATE_TRACER`] output, fields that were not touched or
+/// did not change are omitted
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct AccountState {
+    /// The balance of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub balance: Option<U256>,
+    /// The nonce of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    /// The code of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code: Option<Bytes>,
+    /// The touched storage slots of the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<H256, H256>>,
+}
+
+/// The output of the default struct logger, returned if no tracer is set
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DefaultFrame {
+    /// Whether the transaction failed
+    pub failed: bool,
+    /// The gas used by the transaction
+    pub gas: u64,
+    /// The hex encoded return data, without `0x` prefix
+    pub return_value: String,
+    /// The executed opcodes
+    pub struct_logs: Vec<StructLog>,
+}
+
+/// An executed opcode of the struct logger output
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+    /// The program counter
+    pub pc: u64,
+    /// The name of the opcode
+    pub op: String,
+    /// The gas available before the opcode
+    pub gas: u64,
+    /// The gas cost of the opcode
+    pub gas_cost: u64,
+    /// The call depth
+    pub depth: u64,
+    /// The error of the opcode, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The stack before the opcode, unless disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stack: Option<Vec<U256>>,
+    /// The memory before the opcode as hex encoded 32 byte words, if enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<Vec<String>>,
+    /// The storage slots of the contract accessed so far as hex encoded words, unless disabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub storage: Option<BTreeMap<String, String>>,
+    /// The gas refund counter
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refund: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CALL_TRACE: &str = r#"{
+        "type": "CALL",
+        "from": "0x5c3c9c8b4fc1f4a1d6c4b0d2e9a3f5c3f2e1d0c3",
+        "to": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+        "value": "0x0",
+        "gas": "0x2d7cb",
+        "gasUsed": "0x1f2d4",
+        "input": "0x095ea7b3",
+        "output": "0x",
+        "error": "execution reverted",
+        "revertReason": "insufficient allowance",
+        "calls": [
+            {
+                "type": "STATICCALL",
+                "from": "0x7a250d5630b4cf539739df2c5dacb4c659f2488d",
+                "to": "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2",
+                "gas": "0x2b1a2",
+                "gasUsed": "0x9c4",
+                "input": "0x70a08231",
+                "output": "0x0000000000000000000000000000000000000000000000000000000000000001"
+            }
+        ]
+    }"#;
+
+    const STRUCT_LOGS: &str = r#"{
+        "gas": 21000,
+        "failed": false,
+        "returnValue": "",
+        "structLogs": [
+            {
+                "pc": 0,
+                "op": "PUSH1",
+                "gas": 79000,
+                "gasCost": 3,
+                "depth": 1,
+                "stack": [],
+                "memory": [],
+                "storage": {}
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn can_deserialize_call_trace() {
+        let trace: GethTrace<CallFrame> = serde_json::from_str(CALL_TRACE).unwrap();
+        let frame = trace.into_tracer().unwrap();
+        assert_eq!(frame.typ, "CALL");
+        assert_eq!(frame.gas_used, 0x1f2d4u64.into());
+        assert_eq!(frame.revert_reason.as_deref(), Some("insufficient allowance"));
+        assert_eq!(frame.calls.len(), 1);
+        assert_eq!(frame.calls[0].typ, "STATICCALL");
+        assert!(frame.calls[0].value.is_none());
+        assert!(frame.calls[0].calls.is_empty());
+    }
+
+    #[test]
+    fn can_deserialize_prestate_trace() {
+        let trace = r#"{
+            "0x7a250d5630b4cf539739df2c5dacb4c659f2488d": {
+                "balance": "0x1bc16d674ec80000",
+                "nonce": 1,
+                "code": "0x6080",
+                "storage": {
+                    "0x0000000000000000000000000000000000000000000000000000000000000000": "0x0000000000000000000000000000000000000000000000000000000000000001"
+                }
+            },
+            "0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2": { "balance": "0x0" }
+        }"#;
+        match serde_json::from_str::<GethTrace<PreStateFrame>>(trace).unwrap() {
+            GethTrace::Tracer(PreStateFrame::Default(accounts)) => {
+                assert_eq!(accounts.len(), 2);
+                let account = &accounts
+                    [&"7a250d5630b4cf539739df2c5dacb4c659f2488d".parse::<Address>().unwrap()];
+                assert_eq!(account.nonce, Some(1));
+                assert_eq!(account.storage.as_ref().unwrap().len(), 1);
+            }
+            trace => panic!("unexpected trace {:?}", trace),
+        }
+
+        let trace = r#"{
+            "pre": { "0x7a250d5630b4cf539739df2c5dacb4c659f2488d": { "nonce": 1 } },
+            "post": { "0x7a250d5630b4cf539739df2c5dacb4c659f2488d": { "nonce": 2 } }
+        }"#;
+        match serde_json::from_str::<GethTrace<PreStateFrame>>(trace).unwrap() {
+            GethTrace::Tracer(PreStateFrame::Diff(diff)) => {
+                assert_eq!(diff.pre.values().next().unwrap().nonce, Some(1));
+                assert_eq!(diff.post.values().next().unwrap().nonce, Some(2));
+            }
+            trace => panic!("unexpected trace {:?}", trace),
+        }
+    }
+
+    #[test]
+    fn falls_back_to_struct_logs() {
+        let trace: GethTrace<CallFrame> = serde_json::from_str(STRUCT_LOGS).unwrap();
+        match &trace {
+            GethTrace::StructLogger(frame) => {
+                assert_eq!(frame.gas, 21000);
+                assert_eq!(frame.struct_logs[0].op, "PUSH1");
+            }
+            trace => panic!("unexpected trace {:?}", trace),
+        }
+        assert!(trace.into_tracer().is_none());
+
+        let trace: GethTrace<PreStateFrame> = serde_json::from_str(STRUCT_LOGS).unwrap();
+        assert!(matches!(trace, GethTrace::StructLogger(_)));
+    }
+}
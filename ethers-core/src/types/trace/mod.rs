@@ -1,4 +1,4 @@
-//! Types for the Parity Ad-Hoc Trace API
+//! Types for the Parity Ad-Hoc Trace API and the Geth debug tracing API
 //!
 //! <https://openethereum.github.io/wiki/JSONRPC-trace-module>
 use crate::types::{Bytes, H160, H256, U256};
@@ -8,6 +8,9 @@ use std::collections::BTreeMap;
 mod filter;
 pub use filter::*;
 
+mod geth;
+pub use geth::*;
+
 #[derive(Debug, Clone, Serialize)]
 /// Description of the type of trace to make
 pub enum TraceType {
@@ -1,4 +1,7 @@
-use super::{decode_to, eip2718::TypedTransaction, eip2930::AccessList, normalize_v, rlp_opt};
+use super::{
+    decode_to, eip2718::TypedTransaction, eip2930::AccessList, normalize_v,
+    request::TransactionValidationError, rlp_opt,
+};
 use crate::types::{
     Address, Bytes, NameOrAddress, Signature, SignatureError, Transaction, U256, U64,
 };
@@ -154,6 +157,24 @@ impl Eip1559TransactionRequest {
         self
     }
 
+    /// Checks the fields that are set for values the node would reject
+    pub fn validate(&self) -> Result<(), TransactionValidationError> {
+        if self.gas.map(|gas| gas.is_zero()).unwrap_or_default() {
+            return Err(TransactionValidationError::ZeroGasLimit)
+        }
+        if let (Some(max_priority_fee_per_gas), Some(max_fee_per_gas)) =
+            (self.max_priority_fee_per_gas, self.max_fee_per_gas)
+        {
+            if max_priority_fee_per_gas > max_fee_per_gas {
+                return Err(TransactionValidationError::PriorityFeeExceedsMaxFee {
+                    max_priority_fee_per_gas,
+                    max_fee_per_gas,
+                })
+            }
+        }
+        Ok(())
+    }
+
     /// Gets the unsigned transaction's RLP encoding
     pub fn rlp(&self) -> Bytes {
         let mut rlp = RlpStream::new();
@@ -1,7 +1,7 @@
 use super::{
     eip1559::{Eip1559RequestError, Eip1559TransactionRequest},
     eip2930::{AccessList, Eip2930RequestError, Eip2930TransactionRequest},
-    request::RequestError,
+    request::{RequestError, TransactionValidationError},
 };
 use crate::{
     types::{
@@ -282,6 +282,31 @@ impl TypedTransaction {
         keccak256(encoded).into()
     }
 
+    /// Checks the fields that are set for values the node would reject, e.g. a zero gas limit or
+    /// a `maxPriorityFeePerGas` that exceeds the `maxFeePerGas`
+    pub fn validate(&self) -> Result<(), TransactionValidationError> {
+        match self {
+            Legacy(inner) => inner.validate(),
+            Eip2930(inner) => inner.tx.validate(),
+            Eip1559(inner) => inner.validate(),
+        }
+    }
+
+    /// Same as [`Self::validate`], but also checks that the chain id, if set, is `chain_id`
+    pub fn validate_for_chain(
+        &self,
+        chain_id: impl Into<U64>,
+    ) -> Result<(), TransactionValidationError> {
+        self.validate()?;
+        let expected = chain_id.into();
+        match self.chain_id() {
+            Some(actual) if actual != expected => {
+                Err(TransactionValidationError::ChainIdMismatch { expected, actual })
+            }
+            _ => Ok(()),
+        }
+    }
+
     /// Max cost of the transaction
     pub fn max_cost(&self) -> Option<U256> {
         let gas_limit = self.gas();
@@ -298,7 +323,17 @@ impl TypedTransaction {
     }
 
     /// Decodes a signed TypedTransaction from a rlp encoded byte stream
+    ///
+    /// This accepts typed [EIP-2718](https://eips.ethereum.org/EIPS/eip-2718) transactions, which
+    /// are prefixed with their transaction type, as well as untyped legacy transactions. The
+    /// `from` address of the returned transaction is recovered from the signature.
     pub fn decode_signed(rlp: &rlp::Rlp) -> Result<(Self, Signature), TypedTransactionError> {
+        if rlp.is_list() {
+            // Legacy transactions are not prefixed with a transaction type
+            let decoded_request = TransactionRequest::decode_signed_rlp(rlp)?;
+            return Ok((Self::Legacy(decoded_request.0), decoded_request.1))
+        }
+
         let tx_type: Option<U64> = match rlp.is_data() {
             true => Ok(Some(rlp.data()?.into())),
             false => Err(TypedTransactionError::MissingTransactionType),
@@ -321,7 +356,6 @@ impl TypedTransaction {
             }
             _ => {
                 // Legacy (0x00)
-                // use the original rlp
                 let decoded_request = TransactionRequest::decode_signed_rlp(&rest)?;
                 Ok((Self::Legacy(decoded_request.0), decoded_request.1))
             }
@@ -550,6 +584,47 @@ mod tests {
         assert_eq!(tx.sighash(), decoded_transaction.sighash());
     }
 
+    #[cfg(not(feature = "celo"))]
+    #[test]
+    fn test_legacy_signed_tx_decode() {
+        let from = Address::from_str("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F").unwrap();
+        let cases = [
+            // EIP-155 replay protected, from the EIP-155 example
+            (
+                "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83",
+                "0x33469b22e9f636356c4160a87eb19df52b7412e8eac32a4a55ffe88ea8350788",
+                Some(U64::from(1u64)),
+            ),
+            // the same transaction signed with the same key, but without a chain id
+            (
+                "f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000801ca0f973a0b87062c389d125d8199e803b832b6ac6bf7867a4f6cd87506060fc4c58a053271217f374b54b0fce070a1589ffcaf2bf49da14eca2cae8dcf11c301bf420",
+                "0xd30c4db35137b5eebe00007da5b56a103166fd88fc17b313e315d4fd2e32efd3",
+                None,
+            ),
+        ];
+
+        for (raw, hash, chain_id) in cases {
+            let raw = hex::decode(raw).unwrap();
+            let (tx, sig) = TypedTransaction::decode_signed(&rlp::Rlp::new(&raw)).unwrap();
+
+            assert_eq!(tx.from(), Some(&from));
+            assert_eq!(tx.chain_id(), chain_id);
+            assert_eq!(tx.rlp_signed(&sig).as_ref(), raw.as_slice());
+            assert_eq!(tx.hash(&sig), H256::from_str(hash).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_typed_signed_tx_roundtrip() {
+        let raw = hex::decode("02f899018085602b94278b85b2f7a17de88302cf5c940aa7420c43b8c1a7b165d216948870c8ecfe1ee18802c68af0bb140000a46ecd23060000000000000000000000000000000000000000000000000000000000000002c080a0c5f35bf1cc6ab13053e33b1af7400c267be17218aeadcdb4ae3eefd4795967e8a04f6871044dd6368aea8deecd1c29f55b5531020f5506502e3f79ad457051bc4a").unwrap();
+        let (tx, sig) = TypedTransaction::decode_signed(&rlp::Rlp::new(&raw)).unwrap();
+        assert_eq!(
+            tx.from(),
+            Some(&Address::from_str("0x1acadd971da208d25122b645b2ef879868a83e21").unwrap())
+        );
+        assert_eq!(tx.rlp_signed(&sig).as_ref(), raw.as_slice());
+    }
+
     #[test]
     fn test_eip1559_deploy_tx_decode() {
         let typed_tx_hex =
@@ -614,4 +689,36 @@ mod tests {
         let addr = Address::from_str("0x216b32eCEbAe6aF164921D3943cd7A9634FcB199").unwrap();
         assert_eq!(addr, tx.from.unwrap());
     }
+
+    #[test]
+    fn validates_transactions() {
+        let tx: TypedTransaction = TransactionRequest::new().gas(21_000u64).chain_id(1u64).into();
+        assert_eq!(tx.validate(), Ok(()));
+        assert_eq!(tx.validate_for_chain(1u64), Ok(()));
+        assert_eq!(
+            tx.validate_for_chain(5u64),
+            Err(TransactionValidationError::ChainIdMismatch {
+                expected: 5u64.into(),
+                actual: 1u64.into()
+            })
+        );
+
+        let tx: TypedTransaction = TransactionRequest::new().gas(0u64).into();
+        assert_eq!(tx.validate(), Err(TransactionValidationError::ZeroGasLimit));
+
+        let tx: TypedTransaction = Eip1559TransactionRequest::new()
+            .max_fee_per_gas(10u64)
+            .max_priority_fee_per_gas(11u64)
+            .into();
+        assert_eq!(
+            tx.validate(),
+            Err(TransactionValidationError::PriorityFeeExceedsMaxFee {
+                max_priority_fee_per_gas: 11u64.into(),
+                max_fee_per_gas: 10u64.into()
+            })
+        );
+        // the chain id is filled in later
+        let tx: TypedTransaction = Eip1559TransactionRequest::new().max_fee_per_gas(10u64).into();
+        assert_eq!(tx.validate_for_chain(5u64), Ok(()));
+    }
 }
@@ -1,6 +1,8 @@
 use convert_case::{Case, Casing};
 use core::convert::TryFrom;
 use proc_macro2::TokenStream;
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
 use syn::{
     parse::Error, spanned::Spanned as _, AttrStyle, Data, DeriveInput, Expr, Fields,
     GenericArgument, Lit, NestedMeta, PathArguments, Type,
@@ -9,7 +11,7 @@ use syn::{
 use crate::{
     abi,
     abi::{ParamType, Token},
-    types::{Address, H160, U256},
+    types::{Address, H160, I256, U256},
     utils::keccak256,
 };
 
@@ -46,6 +48,8 @@ pub enum Eip712Error {
     NestedEip712StructNotImplemented,
     #[error("Error from Eip712 struct: {0:?}")]
     Inner(String),
+    #[error("Invalid typed data: {0}")]
+    TypedData(String),
 }
 
 /// The Eip712 trait provides helper methods for computing
@@ -580,3 +584,395 @@ pub fn encode_eip712_type(token: Token) -> Token {
         }
     }
 }
+
+/// A field of a struct type of [`TypedData`]
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct TypedDataField {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub r#type: String,
+}
+
+/// The struct types of [`TypedData`] by their name
+pub type Types = BTreeMap<String, Vec<TypedDataField>>;
+
+/// The typed data of an `eth_signTypedData_v4` request.
+///
+/// Unlike the [`Eip712`] trait, which is implemented for Rust structs at compile time, this
+/// computes the EIP-712 hashes of structs that are only known at runtime.
+///
+/// # Example
+///
+/// ```
+/// use ethers_core::types::transaction::eip712::TypedData;
+///
+/// let json = serde_json::json!({
+///     "types": {
+///         "EIP712Domain": [
+///             { "name": "name", "type": "string" },
+///             { "name": "chainId", "type": "uint256" }
+///         ],
+///         "Greeting": [{ "name": "text", "type": "string" }]
+///     },
+///     "primaryType": "Greeting",
+///     "domain": { "name": "Greeter", "chainId": 1 },
+///     "message": { "text": "hello" }
+/// });
+/// let data: TypedData = serde_json::from_value(json).unwrap();
+/// assert_eq!(data.encode_type("Greeting").unwrap(), "Greeting(string text)");
+/// let digest = data.encode_eip712().unwrap();
+/// ```
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    /// All struct types, including `EIP712Domain`
+    pub types: Types,
+    /// The name of the struct type of the `message`
+    pub primary_type: String,
+    /// The domain, a struct of type `EIP712Domain`
+    pub domain: Map<String, Value>,
+    /// The struct to sign
+    pub message: Map<String, Value>,
+}
+
+impl TypedData {
+    /// Returns the encoded type of the struct type with the given name, according to the EIP-712
+    /// definition of `encodeType`: `Mail(Person from,Person to,string contents)Person(string
+    /// name,address wallet)`.
+    ///
+    /// All struct types the type depends on, directly or nested, are appended in alphabetical
+    /// order.
+    pub fn encode_type(&self, name: &str) -> Result<String, Eip712Error> {
+        let fields = self.struct_type(name)?;
+        let mut dependencies = BTreeSet::new();
+        for field in fields {
+            self.collect_dependencies(&field.r#type, &mut dependencies);
+        }
+        dependencies.remove(name);
+
+        let mut encoded = encode_struct_type(name, fields);
+        for dependency in dependencies {
+            encoded.push_str(&encode_struct_type(dependency, &self.types[dependency]));
+        }
+        Ok(encoded)
+    }
+
+    /// Returns the hash of [`TypedData::encode_type`]
+    pub fn type_hash(&self, name: &str) -> Result<[u8; 32], Eip712Error> {
+        Ok(keccak256(self.encode_type(name)?))
+    }
+
+    /// Hash of the given struct, according to the EIP-712 definition of `hashStruct`
+    pub fn hash_struct(
+        &self,
+        name: &str,
+        data: &Map<String, Value>,
+    ) -> Result<[u8; 32], Eip712Error> {
+        let fields = self.struct_type(name)?;
+        let mut encoded = Vec::with_capacity(32 * (fields.len() + 1));
+        encoded.extend_from_slice(&self.type_hash(name)?);
+        for field in fields {
+            let value = data.get(&field.name).ok_or_else(|| {
+                Eip712Error::TypedData(format!("missing field `{}` of `{}`", field.name, name))
+            })?;
+            encoded.extend_from_slice(&self.encode_field(&field.r#type, value)?);
+        }
+        Ok(keccak256(encoded))
+    }
+
+    /// The hash of the `domain`
+    pub fn domain_separator(&self) -> Result<[u8; 32], Eip712Error> {
+        self.hash_struct("EIP712Domain", &self.domain)
+    }
+
+    /// Hash of the `message` of type `primary_type`
+    pub fn struct_hash(&self) -> Result<[u8; 32], Eip712Error> {
+        self.hash_struct(&self.primary_type, &self.message)
+    }
+
+    /// The final EIP-712 digest that is signed, see [`Eip712::encode_eip712`]
+    pub fn encode_eip712(&self) -> Result<[u8; 32], Eip712Error> {
+        let domain_separator = self.domain_separator()?;
+        let struct_hash = self.struct_hash()?;
+        let digest_input = [&[0x19, 0x01], &domain_separator[..], &struct_hash[..]].concat();
+        Ok(keccak256(digest_input))
+    }
+
+    fn struct_type(&self, name: &str) -> Result<&[TypedDataField], Eip712Error> {
+        self.types
+            .get(name)
+            .map(Vec::as_slice)
+            .ok_or_else(|| Eip712Error::TypedData(format!("unknown struct type `{}`", name)))
+    }
+
+    fn collect_dependencies<'a>(&'a self, ty: &'a str, dependencies: &mut BTreeSet<&'a str>) {
+        let ty = ty.split('[').next().unwrap_or(ty);
+        if let Some((name, fields)) = self.types.get_key_value(ty) {
+            if dependencies.insert(name.as_str()) {
+                for field in fields {
+                    self.collect_dependencies(&field.r#type, dependencies);
+                }
+            }
+        }
+    }
+
+    /// Encodes a value according to the EIP-712 definition of `encodeData`
+    fn encode_field(&self, ty: &str, value: &Value) -> Result<[u8; 32], Eip712Error> {
+        let invalid =
+            || Eip712Error::TypedData(format!("invalid value for type `{}`: {}", ty, value));
+
+        if let Some(idx) = ty.rfind('[') {
+            let inner = &ty[..idx];
+            let len = ty[idx + 1..]
+                .strip_suffix(']')
+                .ok_or_else(|| Eip712Error::TypedData(format!("invalid array type `{}`", ty)))?;
+            let values = value.as_array().ok_or_else(invalid)?;
+            if !len.is_empty() && len.parse::<usize>().ok() != Some(values.len()) {
+                return Err(invalid())
+            }
+            let mut encoded = Vec::with_capacity(32 * values.len());
+            for value in values {
+                encoded.extend_from_slice(&self.encode_field(inner, value)?);
+            }
+            return Ok(keccak256(encoded))
+        }
+
+        if self.types.contains_key(ty) {
+            return self.hash_struct(ty, value.as_object().ok_or_else(invalid)?)
+        }
+
+        let token = match ty {
+            "string" => return Ok(keccak256(value.as_str().ok_or_else(invalid)?)),
+            "bytes" => return Ok(keccak256(decode_hex(value).ok_or_else(invalid)?)),
+            "bool" => Token::Bool(value.as_bool().ok_or_else(invalid)?),
+            "address" => Token::Address(
+                value.as_str().and_then(|s| s.parse::<Address>().ok()).ok_or_else(invalid)?,
+            ),
+            ty if ty.starts_with("bytes") => {
+                let len = ty["bytes".len()..]
+                    .parse::<usize>()
+                    .ok()
+                    .filter(|len| (1..=32).contains(len))
+                    .ok_or_else(|| Eip712Error::TypedData(format!("unknown type `{}`", ty)))?;
+                let bytes = decode_hex(value).filter(|b| b.len() <= len).ok_or_else(invalid)?;
+                Token::FixedBytes(bytes)
+            }
+            ty if ty.starts_with("uint") => Token::Uint(parse_uint(value).ok_or_else(invalid)?),
+            ty if ty.starts_with("int") => {
+                Token::Int(parse_int(value).ok_or_else(invalid)?.into_raw())
+            }
+            _ => return Err(Eip712Error::TypedData(format!("unknown type `{}`", ty))),
+        };
+        let mut encoded = [0u8; 32];
+        encoded.copy_from_slice(&abi::encode(&[token]));
+        Ok(encoded)
+    }
+}
+
+/// Encodes a single struct type: `Person(string name,address wallet)`
+fn encode_struct_type(name: &str, fields: &[TypedDataField]) -> String {
+    let fields = fields
+        .iter()
+        .map(|field| format!("{} {}", field.r#type, field.name))
+        .collect::<Vec<_>>()
+        .join(",");
+    format!("{}({})", name, fields)
+}
+
+fn decode_hex(value: &Value) -> Option<Vec<u8>> {
+    let s = value.as_str()?;
+    hex::decode(s.strip_prefix("0x").unwrap_or(s)).ok()
+}
+
+/// Parses a json number or a decimal or `0x` prefixed hex string
+fn parse_uint(value: &Value) -> Option<U256> {
+    match value {
+        Value::Number(n) => n.as_u64().map(U256::from),
+        Value::String(s) => match s.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).ok(),
+            None => U256::from_dec_str(s).ok(),
+        },
+        _ => None,
+    }
+}
+
+/// Parses a json number or a decimal or `0x` prefixed hex string, hex strings are the two's
+/// complement of negative numbers
+fn parse_int(value: &Value) -> Option<I256> {
+    match value {
+        Value::Number(n) => n.as_i64().map(I256::from),
+        Value::String(s) => match s.strip_prefix("0x") {
+            Some(_) => parse_uint(value).map(I256::from_raw),
+            None => I256::from_dec_str(s).ok(),
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The example of the EIP: <https://eips.ethereum.org/assets/eip-712/Example.js>
+    fn mail() -> TypedData {
+        serde_json::from_value(serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "message": {
+                "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+                "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+                "contents": "Hello, Bob!"
+            }
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn can_hash_mail_example() {
+        let data = mail();
+        assert_eq!(
+            data.encode_type("Mail").unwrap(),
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+        );
+        assert_eq!(
+            hex::encode(data.type_hash("Mail").unwrap()),
+            "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2"
+        );
+        assert_eq!(
+            hex::encode(data.struct_hash().unwrap()),
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+        );
+        assert_eq!(
+            hex::encode(data.domain_separator().unwrap()),
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+        );
+        assert_eq!(
+            hex::encode(data.encode_eip712().unwrap()),
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+        );
+
+        // matches the domain separator of the statically typed domain
+        let domain = EIP712Domain {
+            name: "Ether Mail".to_string(),
+            version: "1".to_string(),
+            chain_id: 1.into(),
+            verifying_contract: "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC".parse().unwrap(),
+            salt: None,
+        };
+        assert_eq!(data.domain_separator().unwrap(), domain.separator());
+    }
+
+    #[test]
+    fn sorts_nested_dependencies() {
+        let data: TypedData = serde_json::from_value(serde_json::json!({
+            "types": {
+                "EIP712Domain": [],
+                "Top": [
+                    { "name": "z", "type": "Zoo" },
+                    { "name": "items", "type": "Item[]" }
+                ],
+                "Item": [{ "name": "tags", "type": "Tag[2]" }],
+                "Tag": [{ "name": "top", "type": "Top" }],
+                "Zoo": [{ "name": "value", "type": "int8" }]
+            },
+            "primaryType": "Top",
+            "domain": {},
+            "message": {}
+        }))
+        .unwrap();
+        assert_eq!(
+            data.encode_type("Top").unwrap(),
+            "Top(Zoo z,Item[] items)Item(Tag[2] tags)Tag(Top top)Zoo(int8 value)"
+        );
+    }
+
+    #[test]
+    fn can_encode_arrays_and_atomic_values() {
+        let mut data = mail();
+        data.types.insert(
+            "Group".to_string(),
+            vec![
+                TypedDataField { name: "members".to_string(), r#type: "Person[]".to_string() },
+                TypedDataField { name: "ids".to_string(), r#type: "int256[2]".to_string() },
+                TypedDataField { name: "flag".to_string(), r#type: "bool".to_string() },
+                TypedDataField { name: "data".to_string(), r#type: "bytes".to_string() },
+                TypedDataField { name: "id".to_string(), r#type: "bytes4".to_string() },
+            ],
+        );
+        let (from, to) = (data.message["from"].clone(), data.message["to"].clone());
+        let group = serde_json::json!({
+            "members": [from.clone(), to.clone()],
+            "ids": [-1, "0x10"],
+            "flag": true,
+            "data": "0x1234",
+            "id": "0xdeadbeef"
+        });
+        let group = group.as_object().unwrap();
+
+        let from = data.hash_struct("Person", from.as_object().unwrap()).unwrap();
+        let to = data.hash_struct("Person", to.as_object().unwrap()).unwrap();
+        let mut ids = [0xff; 64];
+        U256::from(16).to_big_endian(&mut ids[32..]);
+
+        let mut id = [0u8; 32];
+        id[..4].copy_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        let mut flag = [0u8; 32];
+        flag[31] = 1;
+
+        let expected = keccak256(
+            [
+                &data.type_hash("Group").unwrap()[..],
+                &keccak256([from, to].concat())[..],
+                &keccak256(ids)[..],
+                &flag[..],
+                &keccak256([0x12, 0x34])[..],
+                &id[..],
+            ]
+            .concat(),
+        );
+        assert_eq!(data.hash_struct("Group", group).unwrap(), expected);
+
+        // fixed size arrays must have the right length
+        let mut invalid = group.clone();
+        invalid.insert("ids".to_string(), serde_json::json!([1]));
+        assert!(data.hash_struct("Group", &invalid).is_err());
+
+        invalid.remove("ids");
+        assert!(data.hash_struct("Group", &invalid).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_types() {
+        let data = mail();
+        let bytes40 = serde_json::json!(format!("0x{}", "ab".repeat(40)));
+        for ty in ["bytes40", "bytes0", "bytes33"] {
+            assert!(matches!(data.encode_field(ty, &bytes40), Err(Eip712Error::TypedData(_))));
+        }
+        for ty in ["uint256[", "uint256[2", "uint256[]x"] {
+            let err = data.encode_field(ty, &serde_json::json!([1, 2])).unwrap_err();
+            assert!(matches!(err, Eip712Error::TypedData(_)));
+        }
+    }
+}
@@ -22,6 +22,25 @@ pub enum RequestError {
     RecoveryError(#[from] SignatureError),
 }
 
+/// A transaction request that would be rejected by the node, see
+/// [`TransactionRequest::validate`] and [`TypedTransaction::validate`]
+///
+/// [`TypedTransaction::validate`]: crate::types::transaction::eip2718::TypedTransaction::validate
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum TransactionValidationError {
+    /// The gas limit is set to zero
+    #[error("gas limit is zero")]
+    ZeroGasLimit,
+    /// The `maxPriorityFeePerGas` is higher than the `maxFeePerGas`
+    #[error(
+        "max priority fee per gas {max_priority_fee_per_gas} exceeds max fee per gas {max_fee_per_gas}"
+    )]
+    PriorityFeeExceedsMaxFee { max_priority_fee_per_gas: U256, max_fee_per_gas: U256 },
+    /// The chain id of the transaction differs from the chain it is sent to
+    #[error("chain id {actual} of the transaction does not match chain id {expected}")]
+    ChainIdMismatch { expected: U64, actual: U64 },
+}
+
 /// Parameters for sending a transaction
 #[derive(Clone, Default, Serialize, Deserialize, PartialEq, Eq, Debug)]
 pub struct TransactionRequest {
@@ -149,6 +168,14 @@ impl TransactionRequest {
         self
     }
 
+    /// Checks the fields that are set for values the node would reject
+    pub fn validate(&self) -> Result<(), TransactionValidationError> {
+        if self.gas.map(|gas| gas.is_zero()).unwrap_or_default() {
+            return Err(TransactionValidationError::ZeroGasLimit)
+        }
+        Ok(())
+    }
+
     /// Hashes the transaction's data with the provided chain id
     pub fn sighash(&self) -> H256 {
         match self.chain_id {
@@ -4,11 +4,13 @@ use super::{
     rlp_opt_list,
 };
 use crate::{
+    abi::{decode_event_log, Abi, DecodedLog, Event, RawLog},
     types::{Address, Bloom, Bytes, Log, Signature, SignatureError, H256, U256, U64},
-    utils::keccak256,
+    utils::{format_ether_trimmed, format_units, keccak256},
 };
 use rlp::{Decodable, DecoderError, RlpStream};
 use serde::{Deserialize, Serialize};
+use std::fmt;
 
 /// Details of a signed transaction
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
@@ -418,6 +420,180 @@ pub struct TransactionReceipt {
     pub effective_gas_price: Option<U256>,
 }
 
+impl TransactionReceipt {
+    /// Decodes the logs with the events of the ABIs, logs that can't be decoded are returned as
+    /// [`ReceiptLog::Raw`].
+    ///
+    /// Since events of different ABIs can have the same signature, the first ABI that can decode
+    /// a log is used. Anonymous events are only tried if no event matches the signature hash of
+    /// the log. To prefer the ABI of the contract that emitted a log see
+    /// [`TransactionReceipt::decode_contract_logs`].
+    pub fn decode_logs(&self, abis: &[Abi]) -> Vec<ReceiptLog> {
+        self.logs.iter().map(|log| decode_receipt_log(log, abis.iter())).collect()
+    }
+
+    /// Breaks down the gas and the fee of the executed transaction of this receipt.
+    ///
+    /// The effective gas price of the receipt is used, or the gas price of the transaction if the
+    /// node doesn't return it. The gas used is zero if the node runs in light client mode.
+    pub fn gas_report(&self, tx: &Transaction) -> GasReport {
+        let gas_used = self.gas_used.unwrap_or_default();
+        let effective_gas_price = self.effective_gas_price.or(tx.gas_price).unwrap_or_default();
+
+        // the price is `base fee + max priority fee` unless the max fee capped it, the base fee
+        // of the block is unknown then
+        let priority_fee_per_gas = match (tx.max_fee_per_gas, tx.max_priority_fee_per_gas) {
+            (Some(max_fee), Some(max_priority_fee))
+                if effective_gas_price < max_fee || max_priority_fee.is_zero() =>
+            {
+                Some(max_priority_fee.min(effective_gas_price))
+            }
+            _ => None,
+        };
+
+        let intrinsic_gas = intrinsic_gas(tx);
+        GasReport {
+            gas_limit: tx.gas,
+            gas_used,
+            intrinsic_gas,
+            execution_gas: gas_used.saturating_sub(intrinsic_gas),
+            effective_gas_price,
+            fee: gas_used * effective_gas_price,
+            base_fee: priority_fee_per_gas.map(|fee| (effective_gas_price - fee) * gas_used),
+            priority_fee: priority_fee_per_gas.map(|fee| fee * gas_used),
+        }
+    }
+
+    /// Decodes the logs with the ABIs of the contracts at the addresses, same as
+    /// [`TransactionReceipt::decode_logs`] except that the ABI of the contract that emitted a log
+    /// is tried before all others.
+    pub fn decode_contract_logs(&self, contracts: &[(Address, Abi)]) -> Vec<ReceiptLog> {
+        self.logs
+            .iter()
+            .map(|log| {
+                let emitter = contracts.iter().filter(|(address, _)| *address == log.address);
+                let others = contracts.iter().filter(|(address, _)| *address != log.address);
+                decode_receipt_log(log, emitter.chain(others).map(|(_, abi)| abi))
+            })
+            .collect()
+    }
+}
+
+fn decode_receipt_log<'a>(log: &Log, abis: impl Iterator<Item = &'a Abi> + Clone) -> ReceiptLog {
+    let raw = RawLog { topics: log.topics.clone(), data: log.data.0.to_vec() };
+    let topic = log.topics.first();
+    let events = abis
+        .clone()
+        .flat_map(|abi| abi.events())
+        .filter(|event| !event.anonymous && Some(&event.signature()) == topic);
+    let anonymous = abis.flat_map(|abi| abi.events()).filter(|event| event.anonymous);
+    events
+        .chain(anonymous)
+        .find_map(|event| {
+            let params = decode_event_log(event, &raw)?;
+            Some(ReceiptLog::Decoded { event: event.clone(), params, log: log.clone() })
+        })
+        .unwrap_or_else(|| ReceiptLog::Raw(log.clone()))
+}
+
+/// A log of a [`TransactionReceipt`], see [`TransactionReceipt::decode_logs`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReceiptLog {
+    /// The log was decoded with an event of the ABIs
+    Decoded {
+        /// The event that emitted the log
+        event: Event,
+        /// The parameters of the event by their name
+        params: DecodedLog,
+        /// The undecoded log
+        log: Log,
+    },
+    /// None of the ABIs could decode the log
+    Raw(Log),
+}
+
+impl ReceiptLog {
+    /// Returns the undecoded log
+    pub fn log(&self) -> &Log {
+        match self {
+            ReceiptLog::Decoded { log, .. } => log,
+            ReceiptLog::Raw(log) => log,
+        }
+    }
+
+    /// Returns the name of the event that emitted the log, if it was decoded
+    pub fn event_name(&self) -> Option<&str> {
+        match self {
+            ReceiptLog::Decoded { event, .. } => Some(&event.name),
+            ReceiptLog::Raw(_) => None,
+        }
+    }
+}
+
+/// The gas a transaction needs before its execution: the base cost, the cost of the calldata and
+/// the access list, and the cost of the contract creation for deployments
+fn intrinsic_gas(tx: &Transaction) -> U256 {
+    let calldata: u64 = tx.input.as_ref().iter().map(|b| if *b == 0 { 4 } else { 16 }).sum();
+    let access_list: u64 = tx
+        .access_list
+        .iter()
+        .flat_map(|list| list.0.iter())
+        .map(|item| 2_400 + 1_900 * item.storage_keys.len() as u64)
+        .sum();
+    let creation = if tx.to.is_none() { 32_000 } else { 0 };
+    U256::from(21_000 + calldata + access_list + creation)
+}
+
+/// The gas and the fee of an executed transaction, see [`TransactionReceipt::gas_report`]
+///
+/// The [`Display`](fmt::Display) impl renders the report with the price in gwei and the fees in
+/// ether.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GasReport {
+    /// The gas limit of the transaction
+    pub gas_limit: U256,
+    /// The gas used by the transaction
+    pub gas_used: U256,
+    /// The part of the gas used that every transaction pays before its execution
+    pub intrinsic_gas: U256,
+    /// The part of the gas used by the execution
+    pub execution_gas: U256,
+    /// The price the transaction paid per gas
+    pub effective_gas_price: U256,
+    /// The total fee, `gas_used * effective_gas_price`
+    pub fee: U256,
+    /// The part of the fee that was burned by the base fee of the block.
+    ///
+    /// This is only known for EIP-1559 transactions whose max fee did not cap the gas price.
+    pub base_fee: Option<U256>,
+    /// The part of the fee that was paid to the block producer.
+    ///
+    /// This is only known for EIP-1559 transactions whose max fee did not cap the gas price.
+    pub priority_fee: Option<U256>,
+}
+
+impl fmt::Display for GasReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "gas used:            {} of {}", self.gas_used, self.gas_limit)?;
+        writeln!(f, "  intrinsic:         {}", self.intrinsic_gas)?;
+        writeln!(f, "  execution:         {}", self.execution_gas)?;
+        let price = format_units(self.effective_gas_price, "gwei").expect("gwei is a valid unit");
+        writeln!(
+            f,
+            "effective gas price: {} gwei",
+            price.trim_end_matches('0').trim_end_matches('.')
+        )?;
+        write!(f, "fee:                 {} ETH", format_ether_trimmed(self.fee))?;
+        if let Some(base_fee) = self.base_fee {
+            write!(f, "\n  base fee burned:   {} ETH", format_ether_trimmed(base_fee))?;
+        }
+        if let Some(priority_fee) = self.priority_fee {
+            write!(f, "\n  priority fee:      {} ETH", format_ether_trimmed(priority_fee))?;
+        }
+        Ok(())
+    }
+}
+
 impl rlp::Encodable for TransactionReceipt {
     fn rlp_append(&self, s: &mut RlpStream) {
         s.begin_list(4);
@@ -808,4 +984,112 @@ mod tests {
         )
         .unwrap();
     }
+
+    #[test]
+    fn can_report_gas() {
+        let gwei = U256::exp10(9);
+        let tx = Transaction {
+            to: Some(Address::zero()),
+            gas: 60_000u64.into(),
+            input: Bytes::from(vec![0u8, 1, 2]),
+            transaction_type: Some(2u64.into()),
+            max_fee_per_gas: Some(gwei * 20),
+            max_priority_fee_per_gas: Some(gwei * 2),
+            access_list: Some(AccessList(vec![AccessListItem {
+                address: Address::zero(),
+                storage_keys: vec![H256::zero()],
+            }])),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            gas_used: Some(50_000u64.into()),
+            effective_gas_price: Some(gwei * 12),
+            ..Default::default()
+        };
+
+        let report = receipt.gas_report(&tx);
+        assert_eq!(report.intrinsic_gas, U256::from(21_000 + 4 + 16 * 2 + 2_400 + 1_900));
+        assert_eq!(report.execution_gas, report.gas_used - report.intrinsic_gas);
+        assert_eq!(report.fee, gwei * 12 * 50_000);
+        assert_eq!(report.base_fee, Some(gwei * 10 * 50_000));
+        assert_eq!(report.priority_fee, Some(gwei * 2 * 50_000));
+        assert_eq!(
+            report.to_string(),
+            "gas used:            50000 of 60000
+  intrinsic:         25336
+  execution:         24664
+effective gas price: 12 gwei
+fee:                 0.0006 ETH
+  base fee burned:   0.0005 ETH
+  priority fee:      0.0001 ETH"
+        );
+
+        // the max fee capped the price
+        let receipt = TransactionReceipt { effective_gas_price: Some(gwei * 20), ..receipt };
+        let report = receipt.gas_report(&tx);
+        assert_eq!(report.fee, gwei * 20 * 50_000);
+        assert_eq!(report.base_fee, None);
+        assert_eq!(report.priority_fee, None);
+
+        // legacy transactions fall back to the gas price of the transaction
+        let tx = Transaction { to: None, gas_price: Some(gwei), ..Default::default() };
+        let receipt = TransactionReceipt { gas_used: Some(60_000u64.into()), ..Default::default() };
+        let report = receipt.gas_report(&tx);
+        assert_eq!(report.intrinsic_gas, U256::from(53_000));
+        assert_eq!(report.effective_gas_price, gwei);
+        assert_eq!(report.base_fee, None);
+    }
+
+    #[test]
+    fn decode_receipt_logs() {
+        use crate::abi::{parse_abi, AbiEncode, Token};
+
+        let token =
+            parse_abi(&["event Transfer(address indexed from, address indexed to, uint256 value)"])
+                .unwrap();
+        let weth =
+            parse_abi(&["event Transfer(address indexed src, address indexed dst, uint256 wad)"])
+                .unwrap();
+        let (token_address, weth_address) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let transfer = H256::from(keccak256("Transfer(address,address,uint256)"));
+        let log = |address: Address, topics: Vec<H256>| Log {
+            address,
+            topics,
+            data: U256::from(100u64).encode().into(),
+            ..Default::default()
+        };
+        let receipt = TransactionReceipt {
+            logs: vec![
+                log(token_address, vec![transfer, H256::zero(), H256::zero()]),
+                log(weth_address, vec![transfer, H256::zero(), H256::zero()]),
+                log(weth_address, vec![H256::from_low_u64_be(1)]),
+            ],
+            ..Default::default()
+        };
+
+        let logs = receipt.decode_logs(&[token.clone(), weth.clone()]);
+        for log in &logs[..2] {
+            match log {
+                ReceiptLog::Decoded { params, .. } => {
+                    assert_eq!(params["value"], Token::Uint(100u64.into()))
+                }
+                log => panic!("log was not decoded: {:?}", log),
+            }
+        }
+        assert_eq!(logs[2], ReceiptLog::Raw(receipt.logs[2].clone()));
+
+        let logs = receipt.decode_contract_logs(&[(token_address, token), (weth_address, weth)]);
+        let names: Vec<_> = logs
+            .iter()
+            .map(|log| match log {
+                ReceiptLog::Decoded { params, .. } => params.keys().cloned().collect::<Vec<_>>(),
+                ReceiptLog::Raw(_) => Vec::new(),
+            })
+            .collect();
+        assert_eq!(names[0], ["from", "to", "value"]);
+        assert_eq!(names[1], ["dst", "src", "wad"]);
+        assert!(names[2].is_empty());
+        assert_eq!(logs[1].event_name(), Some("Transfer"));
+        assert_eq!(logs[2].log(), &receipt.logs[2]);
+    }
 }
@@ -1,4 +1,38 @@
-use ethabi::ethereum_types::U256;
+use ethabi::ethereum_types::{U256, U512};
+use std::convert::TryFrom;
+
+/// Additional arithmetic on [`U256`].
+///
+/// [`U256`] already provides the overflow checked `checked_add`, `checked_sub`, `checked_mul`,
+/// `checked_div` and the `saturating_add`, `saturating_sub`, `saturating_mul` methods which
+/// return `None` or saturate at [`U256::MAX`] and zero instead of panicking in debug builds and
+/// wrapping in release builds.
+pub trait U256Ext: Sized {
+    /// Computes `self * num / denom` rounded down, using a 512-bit intermediate product so that
+    /// `self * num` can't overflow.
+    ///
+    /// Returns `None` if `denom` is zero or the result doesn't fit into 256 bits.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_core::types::{U256, U256Ext};
+    /// // 0.3% fee of the max amount
+    /// let fee = U256::MAX.mul_div(3.into(), 1000.into()).unwrap();
+    /// assert_eq!(fee, U256::MAX / 1000 * 3 + U256::MAX % 1000 * 3 / 1000);
+    /// ```
+    fn mul_div(self, num: U256, denom: U256) -> Option<U256>;
+}
+
+impl U256Ext for U256 {
+    fn mul_div(self, num: U256, denom: U256) -> Option<U256> {
+        if denom.is_zero() {
+            return None
+        }
+        let result = self.full_mul(num) / U512::from(denom);
+        U256::try_from(result).ok()
+    }
+}
 
 /// Convert a floating point value to its nearest f64 integer.
 ///
@@ -52,6 +86,32 @@ mod tests {
     use super::*;
     use std::f64;
 
+    #[test]
+    fn test_checked_and_saturating_at_boundaries() {
+        let max = U256::MAX;
+        assert_eq!(max.checked_add(1.into()), None);
+        assert_eq!(max.checked_add(0.into()), Some(max));
+        assert_eq!(U256::zero().checked_sub(1.into()), None);
+        assert_eq!(max.checked_mul(2.into()), None);
+        assert_eq!(max.checked_div(U256::zero()), None);
+
+        assert_eq!(max.saturating_add(1.into()), max);
+        assert_eq!(U256::zero().saturating_sub(1.into()), U256::zero());
+        assert_eq!(max.saturating_mul(2.into()), max);
+    }
+
+    #[test]
+    fn test_mul_div() {
+        let max = U256::MAX;
+        assert_eq!(U256::from(10).mul_div(3.into(), 4.into()), Some(7.into()));
+        assert_eq!(max.mul_div(max, max), Some(max));
+        assert_eq!(max.mul_div(2.into(), 2.into()), Some(max));
+        assert_eq!(max.mul_div(max - 1, max), Some(max - 1));
+        assert_eq!(max.mul_div(2.into(), 1.into()), None);
+        assert_eq!(max.mul_div(1.into(), U256::zero()), None);
+        assert_eq!(U256::zero().mul_div(max, 1.into()), Some(U256::zero()));
+    }
+
     #[test]
     fn test_small_integers() {
         for i in 0..=255 {
@@ -1,9 +1,67 @@
 //! Various utilities for manipulating Ethereum related dat
+//!
+//! All Keccak-256 hashes of this crate are computed by [`keccak256`], which dispatches to the
+//! installed [`Keccak256`] backend. The default backend is [`TinyKeccak`], which requires the
+//! default `tiny-keccak` feature. Other implementations, e.g. a smaller or hardware accelerated
+//! one, can be installed with [`set_keccak256_backend`].
 use ethabi::ethereum_types::H256;
-use tiny_keccak::{Hasher, Keccak};
+use once_cell::sync::OnceCell;
 
 const PREFIX: &str = "\x19Ethereum Signed Message:\n";
 
+/// An implementation of the Keccak-256 hash function, see [`set_keccak256_backend`]
+pub trait Keccak256: Send + Sync {
+    /// Returns the Keccak-256 hash of the data
+    fn keccak256(&self, data: &[u8]) -> [u8; 32];
+}
+
+/// The Keccak-256 implementation of the `tiny-keccak` crate, the default backend
+#[cfg(feature = "tiny-keccak")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TinyKeccak;
+
+#[cfg(feature = "tiny-keccak")]
+impl Keccak256 for TinyKeccak {
+    fn keccak256(&self, data: &[u8]) -> [u8; 32] {
+        use tiny_keccak::{Hasher, Keccak};
+
+        let mut output = [0u8; 32];
+        let mut hasher = Keccak::v256();
+        hasher.update(data);
+        hasher.finalize(&mut output);
+        output
+    }
+}
+
+static BACKEND: OnceCell<&'static dyn Keccak256> = OnceCell::new();
+
+/// Installs the backend that computes all Keccak-256 hashes.
+///
+/// The backend can be installed once, before the first hash is computed, so that all hashes are
+/// computed by the same backend. Otherwise the backend is returned as the error.
+///
+/// Without the `tiny-keccak` feature there is no default backend, and hashing panics until a
+/// backend is installed.
+pub fn set_keccak256_backend(
+    backend: &'static dyn Keccak256,
+) -> Result<(), &'static dyn Keccak256> {
+    BACKEND.set(backend)
+}
+
+/// Returns the installed backend, installs the default backend if there is none
+fn keccak256_backend() -> &'static dyn Keccak256 {
+    #[cfg(feature = "tiny-keccak")]
+    {
+        *BACKEND.get_or_init(|| &TinyKeccak)
+    }
+    #[cfg(not(feature = "tiny-keccak"))]
+    {
+        *BACKEND.get().expect(
+            "no Keccak-256 backend installed, enable the `tiny-keccak` feature or call `set_keccak256_backend`",
+        )
+    }
+}
+
 /// Hash a message according to EIP-191.
 ///
 /// The data is a UTF-8 encoded string and will enveloped as follows:
@@ -21,17 +79,13 @@ where
     keccak256(&eth_message).into()
 }
 
-/// Compute the Keccak-256 hash of input bytes.
+/// Compute the Keccak-256 hash of input bytes with the installed [`Keccak256`] backend.
 // TODO: Add Solidity Keccak256 packing support
 pub fn keccak256<S>(bytes: S) -> [u8; 32]
 where
     S: AsRef<[u8]>,
 {
-    let mut output = [0u8; 32];
-    let mut hasher = Keccak::v256();
-    hasher.update(bytes.as_ref());
-    hasher.finalize(&mut output);
-    output
+    keccak256_backend().keccak256(bytes.as_ref())
 }
 
 /// Calculate the function selector as per the contract ABI specification. This
@@ -39,12 +93,31 @@ where
 /// signature.
 pub fn id<S: AsRef<str>>(signature: S) -> [u8; 4] {
     let mut output = [0u8; 4];
+    output.copy_from_slice(&keccak256(signature.as_ref())[..4]);
+    output
+}
 
-    let mut hasher = Keccak::v256();
-    hasher.update(signature.as_ref().as_bytes());
-    hasher.finalize(&mut output);
+/// Returns the ENS namehash as specified in [EIP-137](https://eips.ethereum.org/EIPS/eip-137)
+///
+/// The name is normalized with [UTS-46](https://unicode.org/reports/tr46/) before hashing, like
+/// ENS resolvers expect it to be: labels are case folded and punycode labels are decoded, so
+/// `Vitalik.ETH` has the same namehash as `vitalik.eth`. An empty name returns the zero hash.
+pub fn namehash(name: &str) -> H256 {
+    if name.is_empty() {
+        return H256::zero()
+    }
 
-    output
+    // labels that violate UTS-46 are still mapped, so the errors can be ignored
+    let (name, _) = idna::Config::default()
+        .use_std3_ascii_rules(true)
+        .transitional_processing(false)
+        .check_hyphens(false)
+        .to_unicode(name);
+
+    // iterate in reverse
+    name.rsplit('.')
+        .fold([0u8; 32], |node, label| keccak256(&[node, keccak256(label.as_bytes())].concat()))
+        .into()
 }
 
 /// Serialize a type.
@@ -69,6 +142,15 @@ mod tests {
         );
     }
 
+    #[test]
+    #[cfg(feature = "tiny-keccak")]
+    fn keeps_the_first_keccak256_backend() {
+        let hash = keccak256(b"hello");
+        assert_eq!(TinyKeccak.keccak256(b"hello"), hash);
+        // the default backend was installed by the first hash
+        assert!(set_keccak256_backend(&TinyKeccak).is_err());
+    }
+
     // test vector taken from:
     // https://web3js.readthedocs.io/en/v1.2.2/web3-eth-accounts.html#hashmessage
     #[test]
@@ -88,6 +170,23 @@ mod tests {
         assert_eq!(id("myMethod(uint256,string)"), [0x24, 0xee, 0x00, 0x97],);
     }
 
+    #[test]
+    fn test_namehash() {
+        for (name, expected) in &[
+            ("", "0x0000000000000000000000000000000000000000000000000000000000000000"),
+            ("eth", "0x93cdeb708b7545dc668eb9280176169d1c33cfd8ed6f04690a0bcc88a93fc4ae"),
+            ("vitalik.eth", "0xee6c4522aab0003e8d14cd40a6af439055fd2577951148c14b6cea9a53475835"),
+            ("Vitalik.ETH", "0xee6c4522aab0003e8d14cd40a6af439055fd2577951148c14b6cea9a53475835"),
+            ("bücher.eth", "0x8085f6357df16022483fd54e58889133990e30ff76223ecbc4a07c41c2a1477c"),
+            (
+                "XN--BCHER-KVA.eth",
+                "0x8085f6357df16022483fd54e58889133990e30ff76223ecbc4a07c41c2a1477c",
+            ),
+        ] {
+            assert_eq!(namehash(name), expected.parse::<H256>().unwrap(), "{}", name);
+        }
+    }
+
     #[test]
     fn revert_function_signature() {
         assert_eq!(id("Error(string)"), [0x08, 0xc3, 0x79, 0xa0]);
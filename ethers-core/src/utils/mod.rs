@@ -19,8 +19,10 @@ pub use anvil::{Anvil, AnvilInstance};
 /// Moonbeam utils
 pub mod moonbeam;
 
-mod hash;
-pub use hash::{hash_message, id, keccak256, serialize};
+pub mod hash;
+pub use hash::{
+    hash_message, id, keccak256, namehash, serialize, set_keccak256_backend, Keccak256,
+};
 
 mod units;
 pub use units::Units;
@@ -31,7 +33,7 @@ pub use rlp;
 /// Re-export hex
 pub use hex;
 
-use crate::types::{Address, Bytes, I256, U256};
+use crate::types::{Address, Bytes, U256Ext, I256, U256};
 use elliptic_curve::sec1::ToEncodedPoint;
 use ethabi::ethereum_types::FromDecStrErr;
 use k256::{ecdsa::SigningKey, PublicKey as K256PublicKey};
@@ -50,6 +52,10 @@ pub enum ConversionError {
     InvalidFloat(#[from] std::num::ParseFloatError),
     #[error(transparent)]
     FromDecStrError(#[from] FromDecStrErr),
+    #[error("Invalid address: {0}")]
+    InvalidAddress(String),
+    #[error("Invalid address checksum, expected {0}")]
+    InvalidAddressChecksum(String),
 }
 
 /// 1 Ether = 1e18 Wei == 0x0de0b6b3a7640000 Wei
@@ -67,6 +73,14 @@ pub const EIP1559_FEE_ESTIMATION_PRIORITY_FEE_TRIGGER: u64 = 100_000_000_000;
 /// The threshold max change/difference (in %) at which we will ignore the fee history values
 /// under it.
 pub const EIP1559_FEE_ESTIMATION_THRESHOLD_MAX_CHANGE: i64 = 200;
+/// The default multiplier of the base fee used by [`eip1559_default_estimate`], which leaves
+/// room for the base fee to double before the transaction becomes unexecutable.
+pub const EIP1559_FEE_ESTIMATION_BASE_FEE_MULTIPLIER: f64 = 2.0;
+/// The default percentile of the blocks' rewards [`eip1559_default_estimate`] uses as priority
+/// fee, this is the median.
+pub const EIP1559_FEE_ESTIMATION_PRIORITY_FEE_PERCENTILE: f64 = 50.0;
+/// The minimum priority fee [`eip1559_estimate`] returns.
+pub const EIP1559_FEE_ESTIMATION_MIN_PRIORITY_FEE: u64 = 1_000_000_000;
 
 /// Format the output for the user which prefer to see values
 /// in ether (instead of wei)
@@ -107,6 +121,91 @@ where
     ))
 }
 
+/// Divides the provided amount with 10^{units} provided and rounds the result half-up to the
+/// given number of fractional digits.
+///
+/// Amounts that are not zero but would be rounded to zero are formatted as `<0.0001`, with the
+/// smallest value that can be represented with the given number of digits.
+///
+/// Fails for units larger than 77, since `10^78` doesn't fit into a `U256`.
+///
+/// ```
+/// use ethers_core::utils::format_units_rounded;
+///
+/// let eth = format_units_rounded(1234567890000000000_u128, "ether", 4).unwrap();
+/// assert_eq!(eth, "1.2346");
+///
+/// let eth = format_units_rounded(1234567890000000000_u128, "ether", 0).unwrap();
+/// assert_eq!(eth, "1");
+///
+/// let eth = format_units_rounded(10000000000000_u128, "ether", 4).unwrap();
+/// assert_eq!(eth, "<0.0001");
+/// ```
+pub fn format_units_rounded<T, K>(
+    amount: T,
+    units: K,
+    decimals: usize,
+) -> Result<String, ConversionError>
+where
+    T: Into<U256>,
+    K: TryInto<Units, Error = ConversionError>,
+{
+    let units = units.try_into()?.as_num() as usize;
+    if units > 77 {
+        return Err(ConversionError::UnrecognizedUnits(units.to_string()))
+    }
+    let amount = amount.into();
+
+    // the number of fractional digits the amount has at most
+    let precision = decimals.min(units);
+    let rounded = if precision == units {
+        amount
+    } else {
+        let divisor = U256::exp10(units - precision);
+        let (quotient, remainder) = amount.div_mod(divisor);
+        // round half-up, `remainder * 2` could overflow
+        if remainder >= divisor - remainder {
+            quotient + 1
+        } else {
+            quotient
+        }
+    };
+
+    if rounded.is_zero() && !amount.is_zero() {
+        return Ok(if decimals == 0 {
+            "<1".to_string()
+        } else {
+            format!("<0.{:0>width$}", 1, width = decimals)
+        })
+    }
+
+    let (integer, fraction) = rounded.div_mod(U256::exp10(precision));
+    if decimals == 0 {
+        return Ok(integer.to_string())
+    }
+    let mut fraction = if precision == 0 {
+        String::new()
+    } else {
+        format!("{:0>width$}", fraction.to_string(), width = precision)
+    };
+    fraction.push_str(&"0".repeat(decimals - precision));
+    Ok(format!("{}.{}", integer, fraction))
+}
+
+/// Formats the amount in ether without trailing zeros in the fractional part.
+///
+/// ```
+/// use ethers_core::utils::format_ether_trimmed;
+///
+/// assert_eq!(format_ether_trimmed(1500000000000000000_u128), "1.5");
+/// assert_eq!(format_ether_trimmed(2000000000000000000_u128), "2");
+/// assert_eq!(format_ether_trimmed(1_u128), "0.000000000000000001");
+/// ```
+pub fn format_ether_trimmed<T: Into<U256>>(amount: T) -> String {
+    let formatted = format_units(amount, "ether").expect("ether is a valid unit");
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
 /// Converts the input to a U256 and converts from Ether to Wei.
 ///
 /// ```
@@ -259,7 +358,16 @@ pub fn secret_key_to_address(secret_key: &SigningKey) -> Address {
 
 /// Converts an Ethereum address to the checksum encoding
 /// Ref: <https://github.com/ethereum/EIPs/blob/master/EIPS/eip-55.md>
+/// and to the chain id prefixed encoding of EIP-1191 if the chain id is set
+/// Ref: <https://github.com/ethereum/EIPs/blob/master/EIPS/eip-1191.md>
+///
+/// See [`crate::types::AddressExt::to_checksum`] for chain ids that don't fit into a `u8`.
 pub fn to_checksum(addr: &Address, chain_id: Option<u8>) -> String {
+    checksum_encode(addr, chain_id.map(u64::from))
+}
+
+/// Checksum encodes the address, prefixed with the chain id if it is set
+pub(crate) fn checksum_encode(addr: &Address, chain_id: Option<u64>) -> String {
     let prefixed_addr = match chain_id {
         Some(chain_id) => format!("{}0x{:x}", chain_id, addr),
         None => format!("{:x}", addr),
@@ -280,6 +388,39 @@ pub fn to_checksum(addr: &Address, chain_id: Option<u8>) -> String {
     })
 }
 
+/// Parses a hex encoded address, with or without `0x` prefix, and validates its checksum.
+///
+/// Mixed-case addresses must match the EIP-55 checksum, or the EIP-1191 checksum of the chain if
+/// `chain_id` is set. All lowercase or all uppercase addresses carry no checksum and are only
+/// accepted if `strict` is `false`.
+pub fn parse_checksummed(
+    addr: &str,
+    chain_id: Option<u64>,
+    strict: bool,
+) -> Result<Address, ConversionError> {
+    let addr_hex = addr.strip_prefix("0x").unwrap_or(addr);
+    if addr_hex.len() != 40 {
+        return Err(ConversionError::InvalidAddress(addr.to_owned()))
+    }
+    let address: Address =
+        addr_hex.parse().map_err(|_| ConversionError::InvalidAddress(addr.to_owned()))?;
+
+    let checksummed = checksum_encode(&address, chain_id);
+    let has_checksum = addr_hex.chars().any(|c| c.is_ascii_lowercase()) &&
+        addr_hex.chars().any(|c| c.is_ascii_uppercase());
+    if checksummed[2..] == *addr_hex || (!strict && !has_checksum) {
+        Ok(address)
+    } else {
+        Err(ConversionError::InvalidAddressChecksum(checksummed))
+    }
+}
+
+/// Returns `true` if the string is a hex encoded address whose EIP-55 checksum is valid, or which
+/// is all lowercase or all uppercase
+pub fn validate_checksum(addr: &str) -> bool {
+    parse_checksummed(addr, None, false).is_ok()
+}
+
 /// Returns a bytes32 string representation of text. If the length of text exceeds 32 bytes,
 /// an error is returned.
 pub fn format_bytes32_string(text: &str) -> Result<[u8; 32], ConversionError> {
@@ -382,6 +523,68 @@ fn base_fee_surged(base_fee_per_gas: U256) -> U256 {
     }
 }
 
+/// Estimates the `(max_fee_per_gas, max_priority_fee_per_gas)` of an EIP-1559 transaction with
+/// the default parameters [`EIP1559_FEE_ESTIMATION_BASE_FEE_MULTIPLIER`] and
+/// [`EIP1559_FEE_ESTIMATION_PRIORITY_FEE_PERCENTILE`], see [`eip1559_estimate`].
+///
+/// ```
+/// use ethers_core::{types::U256, utils::eip1559_default_estimate};
+///
+/// let gwei = U256::exp10(9);
+/// let rewards = vec![vec![gwei * 2], vec![gwei * 3], vec![gwei * 4]];
+/// let (max_fee, priority_fee) = eip1559_default_estimate(gwei * 50, &rewards);
+/// assert_eq!(priority_fee, gwei * 3);
+/// assert_eq!(max_fee, gwei * 103);
+/// ```
+pub fn eip1559_default_estimate(base_fee_per_gas: U256, rewards: &[Vec<U256>]) -> (U256, U256) {
+    eip1559_estimate(
+        base_fee_per_gas,
+        rewards,
+        EIP1559_FEE_ESTIMATION_BASE_FEE_MULTIPLIER,
+        EIP1559_FEE_ESTIMATION_PRIORITY_FEE_PERCENTILE,
+    )
+}
+
+/// Estimates the `(max_fee_per_gas, max_priority_fee_per_gas)` of an EIP-1559 transaction from
+/// the latest base fee and the `reward` field of `eth_feeHistory`.
+///
+/// The priority fee is the `percentile` of the first non-zero reward of each block, using the
+/// nearest-rank method, and at least [`EIP1559_FEE_ESTIMATION_MIN_PRIORITY_FEE`]. If there are no
+/// rewards [`EIP1559_FEE_ESTIMATION_DEFAULT_PRIORITY_FEE`] is used.
+///
+/// The max fee is `base_fee_per_gas * base_fee_multiplier + max_priority_fee_per_gas`.
+///
+/// Unlike [`eip1559_default_estimator`] this is a pure function of its inputs without any
+/// special cases for high base fees.
+pub fn eip1559_estimate(
+    base_fee_per_gas: U256,
+    rewards: &[Vec<U256>],
+    base_fee_multiplier: f64,
+    percentile: f64,
+) -> (U256, U256) {
+    let mut rewards: Vec<U256> =
+        rewards.iter().filter_map(|r| r.first().copied()).filter(|r| !r.is_zero()).collect();
+    rewards.sort();
+
+    let max_priority_fee_per_gas = if rewards.is_empty() {
+        U256::from(EIP1559_FEE_ESTIMATION_DEFAULT_PRIORITY_FEE)
+    } else {
+        let percentile = percentile.max(0.0).min(100.0);
+        let rank = (percentile / 100.0 * rewards.len() as f64).ceil() as usize;
+        let reward = rewards[rank.saturating_sub(1).min(rewards.len() - 1)];
+        reward.max(EIP1559_FEE_ESTIMATION_MIN_PRIORITY_FEE.into())
+    };
+
+    // the multiplier with 3 decimals
+    let multiplier = U256::from((base_fee_multiplier.max(0.0) * 1000.0).round() as u64);
+    let max_fee_per_gas = base_fee_per_gas
+        .mul_div(multiplier, 1000.into())
+        .unwrap_or_else(U256::max_value)
+        .saturating_add(max_priority_fee_per_gas);
+
+    (max_fee_per_gas, max_priority_fee_per_gas)
+}
+
 /// A bit of hack to find an unused TCP port.
 ///
 /// Does not guarantee that the given port is unused after the function exists, just that it was
@@ -430,6 +633,42 @@ mod tests {
         assert_eq!(eth, "1.005633240123456789");
     }
 
+    #[test]
+    fn test_format_units_rounded() {
+        let amount = U256::from_dec_str("1395633240123456789").unwrap();
+        assert_eq!(format_units_rounded(amount, "ether", 4).unwrap(), "1.3956");
+        assert_eq!(format_units_rounded(amount, "ether", 3).unwrap(), "1.396");
+        assert_eq!(format_units_rounded(amount, "ether", 0).unwrap(), "1");
+        assert_eq!(format_units_rounded(amount, "ether", 18).unwrap(), "1.395633240123456789");
+        assert_eq!(format_units_rounded(amount, "ether", 20).unwrap(), "1.39563324012345678900");
+
+        // half-up, including the carry into the integer part
+        assert_eq!(format_units_rounded(1500_u64, 3, 0).unwrap(), "2");
+        assert_eq!(format_units_rounded(1499_u64, 3, 0).unwrap(), "1");
+        assert_eq!(format_units_rounded(999950_u64, 6, 4).unwrap(), "1.0000");
+
+        // too small to be represented
+        assert_eq!(format_units_rounded(49_u64, 6, 4).unwrap(), "<0.0001");
+        assert_eq!(format_units_rounded(50_u64, 6, 4).unwrap(), "0.0001");
+        assert_eq!(format_units_rounded(1_u64, "ether", 0).unwrap(), "<1");
+        assert_eq!(format_units_rounded(0_u64, "ether", 4).unwrap(), "0.0000");
+
+        assert_eq!(format_units_rounded(15_u64, "wei", 2).unwrap(), "15.00");
+        assert_eq!(format_units_rounded(U256::MAX, 77, 0).unwrap(), "1");
+        assert!(matches!(
+            format_units_rounded(U256::MAX, 78, 0),
+            Err(ConversionError::UnrecognizedUnits(_))
+        ));
+    }
+
+    #[test]
+    fn test_format_ether_trimmed() {
+        assert_eq!(format_ether_trimmed(WEI_IN_ETHER), "1");
+        assert_eq!(format_ether_trimmed(0_u64), "0");
+        assert_eq!(format_ether_trimmed(1230000000000000000_u64), "1.23");
+        assert_eq!(format_ether_trimmed(10000000000000000000_u64), "10");
+    }
+
     #[test]
     fn test_parse_units() {
         let gwei = parse_units(1.5, 9).unwrap();
@@ -536,6 +775,31 @@ mod tests {
         }
     }
 
+    #[test]
+    fn validates_checksums() {
+        let checksummed = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+        assert!(validate_checksum(checksummed));
+        assert!(validate_checksum(&checksummed[2..]));
+        assert!(validate_checksum(&checksummed.to_lowercase()));
+        assert!(validate_checksum(&checksummed.to_uppercase()[2..]));
+        // mixed-case mismatch
+        assert!(!validate_checksum("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"));
+        assert!(!validate_checksum("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeA"));
+        assert!(!validate_checksum("0xzaAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"));
+
+        let addr = parse_checksummed(checksummed, None, true).unwrap();
+        assert_eq!(addr, checksummed.parse().unwrap());
+        assert!(matches!(
+            parse_checksummed(&checksummed.to_lowercase(), None, true),
+            Err(ConversionError::InvalidAddressChecksum(expected)) if expected == checksummed
+        ));
+        // the EIP-55 checksum is not valid for a chain id
+        assert!(parse_checksummed(checksummed, Some(30), false).is_err());
+        assert!(
+            parse_checksummed("0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD", Some(30), true).is_ok()
+        );
+    }
+
     #[test]
     fn contract_address() {
         // http://ethereum.stackexchange.com/questions/760/how-is-the-address-of-an-ethereum-contract-computed
@@ -609,7 +873,15 @@ mod tests {
             assert_eq!(expected, get_create2_address(from, salt.clone(), init_code.clone()));
 
             // get_create2_address_from_hash()
-            let init_code_hash = keccak256(init_code).to_vec();
+            let init_code_hash = keccak256(&init_code);
+            assert_eq!(
+                expected,
+                get_create2_address_from_hash(from, salt.clone(), init_code_hash.to_vec())
+            );
+
+            // fixed size salt and init code hash
+            let salt: [u8; 32] = salt.try_into().unwrap();
+            assert_eq!(expected, get_create2_address(from, salt, init_code));
             assert_eq!(expected, get_create2_address_from_hash(from, salt, init_code_hash))
         }
     }
@@ -693,4 +965,40 @@ mod tests {
         let rewards_overflow: Vec<Vec<U256>> = vec![vec![overflow], vec![overflow]];
         assert_eq!(estimate_priority_fee(rewards_overflow), overflow);
     }
+
+    #[test]
+    fn test_eip1559_estimate() {
+        let gwei = U256::exp10(9);
+        let rewards: Vec<Vec<U256>> = vec![
+            vec![gwei * 5],
+            vec![],
+            vec![gwei * 2, gwei * 10],
+            vec![U256::zero()],
+            vec![gwei * 3],
+            vec![gwei * 4],
+        ];
+
+        // median of 2, 3, 4, 5
+        let (max_fee, priority_fee) = eip1559_default_estimate(gwei * 100, &rewards);
+        assert_eq!(priority_fee, gwei * 3);
+        assert_eq!(max_fee, gwei * 203);
+
+        let (max_fee, priority_fee) = eip1559_estimate(gwei * 100, &rewards, 1.125, 100.0);
+        assert_eq!(priority_fee, gwei * 5);
+        assert_eq!(max_fee, gwei * 117 + gwei / 2);
+
+        let (_, priority_fee) = eip1559_estimate(gwei * 100, &rewards, 2.0, 0.0);
+        assert_eq!(priority_fee, gwei * 2);
+
+        // floor and default priority fee
+        let (_, priority_fee) = eip1559_default_estimate(gwei, &[vec![1.into()]]);
+        assert_eq!(priority_fee, EIP1559_FEE_ESTIMATION_MIN_PRIORITY_FEE.into());
+        let (max_fee, priority_fee) = eip1559_default_estimate(gwei, &[]);
+        assert_eq!(priority_fee, EIP1559_FEE_ESTIMATION_DEFAULT_PRIORITY_FEE.into());
+        assert_eq!(max_fee, gwei * 2 + priority_fee);
+
+        // saturates instead of overflowing
+        let (max_fee, _) = eip1559_default_estimate(U256::MAX, &rewards);
+        assert_eq!(max_fee, U256::MAX);
+    }
 }
@@ -1,17 +1,26 @@
 use std::{
     borrow::Cow,
-    collections::HashMap,
+    collections::{HashMap, VecDeque},
     fmt::{Display, Error, Formatter},
+    time::Duration,
 };
 
 use ethers_core::{
     abi::Address,
     types::{BlockNumber, Bytes, H256, U256, U64},
 };
+use futures_util::stream::{self, Stream};
 use serde::{Deserialize, Serialize};
+use tracing::{trace, warn};
 
 use crate::{Client, EtherscanError, Query, Response, Result};
 
+/// The maximum number of results of a single transaction list request
+const TX_LIST_PAGE_SIZE: u64 = 10000;
+
+/// How often a rate limited request of [`Client::transactions_stream`] is retried
+const MAX_RATE_LIMIT_RETRIES: u32 = 5;
+
 /// The raw response from the balance-related API endpoints
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AccountBalance {
@@ -65,6 +74,22 @@ mod jsonstring {
     }
 }
 
+/// Deserializes the block numbers of Etherscan responses, which are decimal strings unlike the
+/// hex strings of JSON-RPC responses
+fn deserialize_block_number<'de, D>(deserializer: D) -> std::result::Result<BlockNumber, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    use serde::de::Error as _;
+
+    let s = Cow::<'de, str>::deserialize(deserializer)?;
+    match s.parse::<u64>() {
+        Ok(num) => Ok(BlockNumber::Number(num.into())),
+        // hex block numbers and tags, e.g. of serialized responses
+        Err(_) => serde_json::from_str(&format!("\"{}\"", s)).map_err(D::Error::custom),
+    }
+}
+
 /// Possible values for some field responses
 #[derive(Debug)]
 pub enum GenesisOption<T> {
@@ -100,6 +125,7 @@ impl<T> GenesisOption<T> {
 #[serde(rename_all = "camelCase")]
 pub struct NormalTransaction {
     pub is_error: String,
+    #[serde(deserialize_with = "deserialize_block_number")]
     pub block_number: BlockNumber,
     pub time_stamp: String,
     #[serde(with = "jsonstring")]
@@ -130,6 +156,7 @@ pub struct NormalTransaction {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct InternalTransaction {
+    #[serde(deserialize_with = "deserialize_block_number")]
     pub block_number: BlockNumber,
     pub time_stamp: String,
     pub hash: H256,
@@ -154,6 +181,7 @@ pub struct InternalTransaction {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ERC20TokenTransferEvent {
+    #[serde(deserialize_with = "deserialize_block_number")]
     pub block_number: BlockNumber,
     pub time_stamp: String,
     pub hash: H256,
@@ -180,6 +208,7 @@ pub struct ERC20TokenTransferEvent {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ERC721TokenTransferEvent {
+    #[serde(deserialize_with = "deserialize_block_number")]
     pub block_number: BlockNumber,
     pub time_stamp: String,
     pub hash: H256,
@@ -207,6 +236,7 @@ pub struct ERC721TokenTransferEvent {
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MinedBlock {
+    #[serde(deserialize_with = "deserialize_block_number")]
     pub block_number: BlockNumber,
     pub time_stamp: String,
     pub block_reward: String,
@@ -283,6 +313,69 @@ impl From<TxListParams> for HashMap<&'static str, String> {
     }
 }
 
+/// The state of [`Client::transactions_stream`]: the not yet yielded transactions of the last
+/// page and the block the next page starts at
+struct TxPages {
+    page_size: u64,
+    start_block: u64,
+    /// The hashes of the already yielded transactions of `start_block`, which are part of the
+    /// next page again
+    start_block_hashes: Vec<H256>,
+    pending: VecDeque<NormalTransaction>,
+    done: bool,
+}
+
+impl TxPages {
+    fn new(page_size: u64) -> Self {
+        Self {
+            page_size,
+            start_block: 0,
+            start_block_hashes: Vec::new(),
+            pending: VecDeque::new(),
+            done: false,
+        }
+    }
+
+    /// Queues the transactions of the page that were not yielded yet and advances to the last
+    /// block of the page, which may not have been returned completely
+    fn push_page(&mut self, txs: Vec<NormalTransaction>) {
+        self.done = (txs.len() as u64) < self.page_size;
+        let mut last_block = self.start_block;
+        let mut last_block_hashes = Vec::new();
+        for tx in txs {
+            let block = tx.block_number.as_number().map(|num| num.as_u64()).unwrap_or_default();
+            let hash = tx.hash.value().copied();
+            if block == self.start_block &&
+                hash.map_or(false, |hash| self.start_block_hashes.contains(&hash))
+            {
+                continue
+            }
+            if block != last_block {
+                last_block = block;
+                last_block_hashes.clear();
+            }
+            last_block_hashes.extend(hash);
+            self.pending.push_back(tx);
+        }
+
+        if last_block == self.start_block && !self.done {
+            // the whole page is a single block, continuing at this block would return the same
+            // page again
+            warn!(
+                target: "etherscan",
+                "block {} has more than {} transactions, skipping the rest of them",
+                last_block,
+                self.page_size
+            );
+            self.start_block = last_block + 1;
+            self.start_block_hashes.clear();
+        } else {
+            self.start_block = last_block;
+            self.start_block_hashes = last_block_hashes;
+        }
+    }
+}
+
 /// Options for querying internal transactions
 pub enum InternalTxQueryOption {
     ByAddress(Address),
@@ -437,6 +530,80 @@ impl Client {
         Ok(response.result)
     }
 
+    /// Returns a stream of all transactions performed by an address, in ascending order.
+    ///
+    /// The transactions are requested page by page. Since Etherscan returns at most 10000
+    /// results per request, every page starts at the last block of the previous page, the
+    /// transactions of that block that were already yielded are skipped. Rate limited requests
+    /// are retried with exponential backoff. An address without transactions yields an empty
+    /// stream.
+    ///
+    /// ```no_run
+    /// # use ethers_etherscan::Client;
+    /// # use ethers_core::types::Chain;
+    /// use futures_util::{pin_mut, TryStreamExt};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() {
+    ///     let client = Client::new(Chain::Mainnet, "API_KEY").unwrap();
+    ///     let txs = client
+    ///         .transactions_stream("0x58eB28A67731c570Ef827C365c89B5751F9E6b0a".parse().unwrap());
+    ///     pin_mut!(txs);
+    ///     while let Some(tx) = txs.try_next().await.unwrap() {
+    ///         println!("{:?}", tx.hash);
+    ///     }
+    /// # }
+    /// ```
+    pub fn transactions_stream(
+        &self,
+        address: Address,
+    ) -> impl Stream<Item = Result<NormalTransaction>> + '_ {
+        stream::try_unfold(TxPages::new(TX_LIST_PAGE_SIZE), move |mut pages| async move {
+            loop {
+                if let Some(tx) = pages.pending.pop_front() {
+                    return Ok(Some((tx, pages)))
+                }
+                if pages.done {
+                    return Ok(None)
+                }
+                // "No transactions found" is a successful response with an empty result
+                let txs = self
+                    .get_transactions_with_backoff(&address, pages.start_block, pages.page_size)
+                    .await?;
+                pages.push_page(txs);
+            }
+        })
+    }
+
+    /// Requests the first page of the transactions since the start block in ascending order, rate
+    /// limited requests are retried after an exponentially increasing delay
+    async fn get_transactions_with_backoff(
+        &self,
+        address: &Address,
+        start_block: u64,
+        page_size: u64,
+    ) -> Result<Vec<NormalTransaction>> {
+        let mut attempt = 0;
+        loop {
+            let params = TxListParams {
+                start_block,
+                page: 1,
+                offset: page_size,
+                sort: Sort::Asc,
+                ..Default::default()
+            };
+            match self.get_transactions(address, Some(params)).await {
+                Err(EtherscanError::RateLimitExceeded) if attempt < MAX_RATE_LIMIT_RETRIES => {
+                    let delay = Duration::from_secs(1 << attempt);
+                    trace!(target: "etherscan", ?delay, "rate limited, retrying txlist request");
+                    futures_timer::Delay::new(delay).await;
+                    attempt += 1;
+                }
+                res => return res,
+            }
+        }
+    }
+
     /// Returns the list of internal transactions performed by an address or within a transaction,
     /// with optional pagination.
     ///
@@ -570,6 +737,7 @@ impl Client {
 mod tests {
     use std::time::Duration;
 
+    use futures_util::{StreamExt, TryStreamExt};
     use serial_test::serial;
 
     use crate::{tests::run_at_least_duration, Chain};
@@ -630,6 +798,96 @@ mod tests {
         .await
     }
 
+    fn tx(block: u64, hash: u64) -> NormalTransaction {
+        serde_json::from_value(serde_json::json!({
+            "isError": "0",
+            "blockNumber": block.to_string(),
+            "timeStamp": "1438932179",
+            "hash": format!("{:?}", H256::from_low_u64_be(hash)),
+            "nonce": "0",
+            "blockHash": format!("{:?}", H256::zero()),
+            "transactionIndex": "0",
+            "from": format!("{:?}", Address::zero()),
+            "to": format!("{:?}", Address::zero()),
+            "value": "0",
+            "gas": "5208",
+            "gasPrice": "0",
+            "txreceipt_status": "1",
+            "input": "0x",
+            "contractAddress": "",
+            "gasUsed": "5208",
+            "cumulativeGasUsed": "5208",
+            "confirmations": "1"
+        }))
+        .unwrap()
+    }
+
+    fn hashes(pages: &mut TxPages) -> Vec<u64> {
+        pages.pending.drain(..).map(|tx| tx.hash.value().unwrap().to_low_u64_be()).collect()
+    }
+
+    #[test]
+    fn deserializes_decimal_block_numbers() {
+        assert_eq!(tx(14000000, 1).block_number, BlockNumber::Number(14000000u64.into()));
+        for (block_number, expected) in
+            [("0x10", BlockNumber::Number(16u64.into())), ("latest", BlockNumber::Latest)]
+        {
+            let block: MinedBlock = serde_json::from_value(serde_json::json!({
+                "blockNumber": block_number,
+                "timeStamp": "1438932179",
+                "blockReward": "0"
+            }))
+            .unwrap();
+            assert_eq!(block.block_number, expected);
+        }
+    }
+
+    #[test]
+    fn pages_start_at_the_last_block() {
+        let mut pages = TxPages::new(3);
+        pages.push_page(vec![tx(1, 1), tx(2, 2), tx(2, 3)]);
+        assert_eq!(hashes(&mut pages), vec![1, 2, 3]);
+        assert_eq!(pages.start_block, 2);
+        assert!(!pages.done);
+
+        // the transactions of block 2 are returned again
+        pages.push_page(vec![tx(2, 2), tx(2, 3), tx(2, 4)]);
+        assert_eq!(hashes(&mut pages), vec![4]);
+        // the page only contains block 2, so it has more transactions than fit on a page
+        assert_eq!(pages.start_block, 3);
+        assert!(!pages.done);
+
+        pages.push_page(vec![tx(3, 5), tx(5, 6), tx(7, 7)]);
+        assert_eq!(hashes(&mut pages), vec![5, 6, 7]);
+        assert_eq!(pages.start_block, 7);
+
+        pages.push_page(vec![tx(7, 7), tx(8, 8)]);
+        assert_eq!(hashes(&mut pages), vec![8]);
+        assert!(pages.done);
+
+        let mut pages = TxPages::new(3);
+        pages.push_page(Vec::new());
+        assert!(pages.pending.is_empty());
+        assert!(pages.done);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn get_transactions_stream_success() {
+        run_at_least_duration(Duration::from_millis(250), async {
+            let client = Client::new_from_env(Chain::Mainnet).unwrap();
+
+            let txs: Vec<_> = client
+                .transactions_stream("0x58eB28A67731c570Ef827C365c89B5751F9E6b0a".parse().unwrap())
+                .take(5)
+                .try_collect()
+                .await
+                .unwrap();
+            assert_eq!(txs.len(), 5);
+        })
+        .await
+    }
+
     #[tokio::test]
     #[serial]
     async fn get_internal_transactions_success() {
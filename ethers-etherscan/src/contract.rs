@@ -1,8 +1,15 @@
-use std::{collections::HashMap, path::Path};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::{Path, PathBuf},
+};
 
 use serde::{Deserialize, Serialize};
 
 use ethers_core::abi::{Abi, Address};
+use ethers_solc::{
+    artifacts::{Optimizer, Settings, Source},
+    CompilerInput,
+};
 
 use crate::{
     source_tree::{SourceTree, SourceTreeEntry},
@@ -162,6 +169,36 @@ struct EtherscanSourceEntry {
 #[derive(Deserialize)]
 struct EtherscanSourceJsonMetadata {
     sources: HashMap<String, EtherscanSourceEntry>,
+    #[serde(default)]
+    settings: Option<serde_json::Value>,
+}
+
+/// The source files and compiler settings of a verified contract, see
+/// [`Metadata::contract_source`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContractSource {
+    /// The contents of the source files by path
+    pub files: BTreeMap<PathBuf, String>,
+    /// The compiler settings the contract was verified with
+    pub settings: Settings,
+}
+
+impl ContractSource {
+    /// Returns the standard JSON input the contract can be recompiled with, e.g. to check that
+    /// the sources match the deployed code with [`Solc::verify_deployed_bytecode`].
+    ///
+    /// [`Solc::verify_deployed_bytecode`]: ethers_solc::Solc::verify_deployed_bytecode
+    pub fn compiler_input(&self) -> CompilerInput {
+        CompilerInput {
+            language: "Solidity".to_string(),
+            sources: self
+                .files
+                .iter()
+                .map(|(path, content)| (path.clone(), Source { content: content.clone() }))
+                .collect(),
+            settings: self.settings.clone(),
+        }
+    }
 }
 
 impl ContractMetadata {
@@ -247,6 +284,60 @@ pub struct Metadata {
     pub swarm_source: String,
 }
 
+impl Metadata {
+    /// Returns the source files and the compiler settings of the contract.
+    ///
+    /// Etherscan returns the sources of contracts verified as a single file as is, the sources of
+    /// contracts verified with multiple files as a JSON object of the files and those of
+    /// contracts verified with the standard JSON input as the input surrounded in an extra set of
+    /// `{}`. The settings of the standard JSON input are used as is, otherwise they are derived
+    /// from the optimizer and EVM version settings of the metadata.
+    pub fn contract_source(&self) -> Result<ContractSource> {
+        let source = self.source_code.trim();
+        let parsed: Option<EtherscanSourceJsonMetadata> =
+            if source.starts_with("{{") && source.ends_with("}}") {
+                Some(serde_json::from_str(&source[1..source.len() - 1])?)
+            } else if source.starts_with('{') {
+                Some(EtherscanSourceJsonMetadata {
+                    sources: serde_json::from_str(source)?,
+                    settings: None,
+                })
+            } else {
+                None
+            };
+
+        let (files, settings) = match parsed {
+            Some(parsed) => {
+                let files = parsed
+                    .sources
+                    .into_iter()
+                    .map(|(path, source)| (PathBuf::from(path), source.content))
+                    .collect();
+                (files, parsed.settings.map(serde_json::from_value).transpose()?)
+            }
+            None => {
+                let path = PathBuf::from(format!("{}.sol", self.contract_name));
+                (BTreeMap::from([(path, self.source_code.clone())]), None)
+            }
+        };
+        let settings = settings.unwrap_or_else(|| self.settings());
+
+        Ok(ContractSource { files, settings })
+    }
+
+    /// Returns the compiler settings as set in the metadata
+    fn settings(&self) -> Settings {
+        let optimizer = Optimizer {
+            enabled: Some(self.optimization_used == "1"),
+            runs: self.runs.parse().ok(),
+            details: None,
+        };
+        // `Default` if the contract was compiled for the default EVM version of the compiler
+        let evm_version = self.evm_version.parse().ok();
+        Settings { optimizer, evm_version, ..Default::default() }
+    }
+}
+
 impl Client {
     /// Submit Source Code for Verification
     pub async fn submit_contract_verification(
@@ -366,11 +457,19 @@ impl Client {
 
 #[cfg(test)]
 mod tests {
-    use crate::{contract::VerifyContract, tests::run_at_least_duration, Client, EtherscanError};
+    use crate::{
+        contract::{Metadata, VerifyContract},
+        tests::run_at_least_duration,
+        Client, EtherscanError,
+    };
     use ethers_core::types::Chain;
-    use ethers_solc::{Project, ProjectPathsConfig};
+    use ethers_solc::{artifacts::EvmVersion, Project, ProjectPathsConfig};
     use serial_test::serial;
-    use std::{path::PathBuf, time::Duration};
+    use std::{
+        collections::BTreeMap,
+        path::{Path, PathBuf},
+        time::Duration,
+    };
 
     #[allow(unused)]
     fn init_tracing() {
@@ -485,6 +584,69 @@ mod tests {
         .await
     }
 
+    /// Query a contract that was verified with the standard JSON input and ensure all of its
+    /// sources and settings are parsed.
+    #[tokio::test]
+    #[serial]
+    #[ignore]
+    async fn can_fetch_contract_source_for_standard_json_contract() {
+        run_at_least_duration(Duration::from_millis(250), async {
+            let client = Client::new_from_env(Chain::Mainnet).unwrap();
+
+            let meta = client
+                .contract_source_code("0x8d04a8c79cEB0889Bdd12acdF3Fa9D207eD3Ff63".parse().unwrap())
+                .await
+                .unwrap();
+
+            let source = meta.items[0].contract_source().unwrap();
+            assert_eq!(source.files.len(), 15);
+        })
+        .await
+    }
+
+    #[test]
+    fn parses_contract_sources() {
+        let meta = |source_code: &str| Metadata {
+            source_code: source_code.to_string(),
+            abi: "[]".to_string(),
+            contract_name: "Greeter".to_string(),
+            compiler_version: "v0.8.10+commit.fc410830".to_string(),
+            optimization_used: "1".to_string(),
+            runs: "200".to_string(),
+            constructor_arguments: String::new(),
+            evm_version: "Default".to_string(),
+            library: String::new(),
+            license_type: "MIT".to_string(),
+            proxy: "0".to_string(),
+            implementation: String::new(),
+            swarm_source: String::new(),
+        };
+
+        let source = meta("contract Greeter {}").contract_source().unwrap();
+        assert_eq!(
+            source.files,
+            BTreeMap::from([(PathBuf::from("Greeter.sol"), "contract Greeter {}".to_string())])
+        );
+        assert_eq!(source.settings.optimizer.enabled, Some(true));
+        assert_eq!(source.settings.optimizer.runs, Some(200));
+        assert_eq!(source.settings.evm_version, None);
+
+        let source = meta(r#"{"Greeter.sol":{"content":"import \"./Lib.sol\";"},"Lib.sol":{"content":"library Lib {}"}}"#)
+            .contract_source()
+            .unwrap();
+        assert_eq!(source.files.len(), 2);
+        assert_eq!(source.files[Path::new("Lib.sol")], "library Lib {}");
+        assert_eq!(source.settings.optimizer.runs, Some(200));
+
+        let source = meta(r#"{{"language":"Solidity","sources":{"contracts/Greeter.sol":{"content":"contract Greeter {}"}},"settings":{"optimizer":{"enabled":false,"runs":1000},"evmVersion":"london","outputSelection":{"*":{"*":["abi"]}}}}}"#)
+            .contract_source()
+            .unwrap();
+        assert_eq!(source.files[Path::new("contracts/Greeter.sol")], "contract Greeter {}");
+        assert_eq!(source.settings.optimizer.enabled, Some(false));
+        assert_eq!(source.settings.optimizer.runs, Some(1000));
+        assert_eq!(source.settings.evm_version, Some(EvmVersion::London));
+    }
+
     #[tokio::test]
     #[serial]
     async fn can_flatten_and_verify_contract() {
@@ -214,60 +214,12 @@ impl ClientBuilder {
     ///
     /// Fails if the chain is not supported by etherscan
     pub fn chain(self, chain: Chain) -> Result<Self> {
-        fn urls(
-            api: impl IntoUrl,
-            url: impl IntoUrl,
-        ) -> (reqwest::Result<Url>, reqwest::Result<Url>) {
-            (api.into_url(), url.into_url())
-        }
-
-        let (etherscan_api_url, etherscan_url) = match chain {
-            Chain::Mainnet => urls("https://api.etherscan.io/api", "https://etherscan.io"),
-            Chain::Ropsten | Chain::Kovan | Chain::Rinkeby | Chain::Goerli => {
-                let chain_name = chain.to_string().to_lowercase();
-                urls(
-                    format!("https://api-{}.etherscan.io/api", chain_name),
-                    format!("https://{}.etherscan.io", chain_name),
-                )
-            }
-            Chain::Polygon => urls("https://api.polygonscan.com/api", "https://polygonscan.com"),
-            Chain::PolygonMumbai => {
-                urls("https://api-testnet.polygonscan.com/api", "https://mumbai.polygonscan.com")
-            }
-            Chain::Avalanche => urls("https://api.snowtrace.io/api", "https://snowtrace.io"),
-            Chain::AvalancheFuji => {
-                urls("https://api-testnet.snowtrace.io/api", "https://testnet.snowtrace.io")
-            }
-            Chain::Optimism => {
-                urls("https://api-optimistic.etherscan.io/api", "https://optimistic.etherscan.io")
-            }
-            Chain::OptimismKovan => urls(
-                "https://api-kovan-optimistic.etherscan.io/api",
-                "https://kovan-optimistic.etherscan.io",
-            ),
-            Chain::Fantom => urls("https://api.ftmscan.com/api", "https://ftmscan.com"),
-            Chain::FantomTestnet => {
-                urls("https://api-testnet.ftmscan.com/api", "https://testnet.ftmscan.com")
-            }
-            Chain::BinanceSmartChain => urls("https://api.bscscan.com/api", "https://bscscan.com"),
-            Chain::BinanceSmartChainTestnet => {
-                urls("https://api-testnet.bscscan.com/api", "https://testnet.bscscan.com")
-            }
-            Chain::Arbitrum => urls("https://api.arbiscan.io/api", "https://arbiscan.io"),
-            Chain::ArbitrumTestnet => {
-                urls("https://api-testnet.arbiscan.io/api", "https://testnet.arbiscan.io")
-            }
-            Chain::Cronos => urls("https://api.cronoscan.com/api", "https://cronoscan.com"),
-            Chain::Moonbeam => {
-                urls("https://api-moonbeam.moonscan.io/api", "https://moonbeam.moonscan.io/")
-            }
-            Chain::Moonriver => {
-                urls("https://api-moonriver.moonscan.io/api", "https://moonriver.moonscan.io")
-            }
-            Chain::Dev => return Err(EtherscanError::LocalNetworksNotSupported),
-            chain => return Err(EtherscanError::ChainNotSupported(chain)),
+        let (etherscan_api_url, etherscan_url) = match chain.etherscan_urls() {
+            Some(urls) => urls,
+            None if chain == Chain::Dev => return Err(EtherscanError::LocalNetworksNotSupported),
+            None => return Err(EtherscanError::ChainNotSupported(chain)),
         };
-        self.with_api_url(etherscan_api_url?)?.with_url(etherscan_url?)
+        self.with_api_url(etherscan_api_url)?.with_url(etherscan_url)
     }
 
     /// Configures the etherscan url
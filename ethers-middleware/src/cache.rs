@@ -0,0 +1,459 @@
+use async_trait::async_trait;
+use ethers_core::types::{
+    Block, BlockId, BlockNumber, Transaction, TransactionReceipt, TxHash, U256, U64,
+};
+use ethers_providers::{FromErr, Middleware};
+use instant::Instant;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{json, Value};
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Debug,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+use thiserror::Error;
+
+/// The number of confirmations after which blocks, transactions and receipts are cached by
+/// default
+pub const DEFAULT_CONFIRMATIONS: u64 = 12;
+
+/// The number of responses the default [`LruStore`] holds
+pub const DEFAULT_CAPACITY: usize = 10_000;
+
+/// A store for the responses cached by the [`CacheMiddleware`].
+///
+/// Responses are stored as JSON values, keyed by the RPC method and the parameters of the call.
+pub trait CacheStore: Debug + Send + Sync {
+    /// Returns the stored response, if it has not expired yet
+    fn get(&self, key: &str) -> Option<Value>;
+
+    /// Stores the response, if `ttl` is set the response expires after it
+    fn insert(&self, key: String, value: Value, ttl: Option<Duration>);
+
+    /// Removes all responses
+    fn clear(&self);
+
+    /// Returns the number of stored responses
+    fn len(&self) -> usize;
+
+    /// Returns `true` if no response is stored
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// An in-memory [`CacheStore`] that evicts the least recently used response once it holds
+/// `capacity` responses
+#[derive(Debug)]
+pub struct LruStore {
+    capacity: usize,
+    inner: Mutex<LruInner>,
+}
+
+#[derive(Debug, Default)]
+struct LruInner {
+    entries: HashMap<String, LruEntry>,
+    // the keys of the entries by the last time they were used, oldest first
+    order: BTreeMap<u64, String>,
+    tick: u64,
+}
+
+#[derive(Debug)]
+struct LruEntry {
+    value: Value,
+    expires: Option<Instant>,
+    tick: u64,
+}
+
+impl Default for LruStore {
+    fn default() -> Self {
+        Self::new(DEFAULT_CAPACITY)
+    }
+}
+
+impl LruStore {
+    /// Creates a store that holds at most `capacity` responses
+    pub fn new(capacity: usize) -> Self {
+        Self { capacity, inner: Mutex::new(LruInner::default()) }
+    }
+}
+
+impl LruInner {
+    fn next_tick(&mut self) -> u64 {
+        self.tick += 1;
+        self.tick
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.tick);
+        }
+    }
+}
+
+impl CacheStore for LruStore {
+    fn get(&self, key: &str) -> Option<Value> {
+        let mut inner = self.inner.lock().unwrap();
+        let tick = inner.next_tick();
+        let entry = inner.entries.get_mut(key)?;
+        if matches!(entry.expires, Some(expires) if expires <= Instant::now()) {
+            inner.remove(key);
+            return None
+        }
+        let (last_tick, value) = (std::mem::replace(&mut entry.tick, tick), entry.value.clone());
+        inner.order.remove(&last_tick);
+        inner.order.insert(tick, key.to_string());
+        Some(value)
+    }
+
+    fn insert(&self, key: String, value: Value, ttl: Option<Duration>) {
+        if self.capacity == 0 {
+            return
+        }
+        let mut inner = self.inner.lock().unwrap();
+        inner.remove(&key);
+        let tick = inner.next_tick();
+        let expires = ttl.map(|ttl| Instant::now() + ttl);
+        inner.order.insert(tick, key.clone());
+        inner.entries.insert(key, LruEntry { value, expires, tick });
+
+        while inner.entries.len() > self.capacity {
+            let oldest = match inner.order.keys().next() {
+                Some(tick) => *tick,
+                None => break,
+            };
+            if let Some(key) = inner.order.remove(&oldest) {
+                inner.entries.remove(&key);
+            }
+        }
+    }
+
+    fn clear(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.entries.clear();
+        inner.order.clear();
+    }
+
+    fn len(&self) -> usize {
+        self.inner.lock().unwrap().entries.len()
+    }
+}
+
+#[derive(Error, Debug)]
+/// Thrown when an error happens at the Cache Middleware
+pub enum CacheError<M: Middleware> {
+    /// Thrown when the internal middleware errors
+    #[error("{0}")]
+    MiddlewareError(M::Error),
+}
+
+impl<M: Middleware> FromErr<M::Error> for CacheError<M> {
+    fn from(src: M::Error) -> Self {
+        CacheError::MiddlewareError(src)
+    }
+}
+
+/// Middleware that caches the responses of calls that never change, to reduce the load on the
+/// node.
+///
+/// The following calls are cached, all other calls are passed through:
+///
+/// - `eth_chainId`
+/// - `eth_getBlockByHash` and `eth_getBlockByNumber` for block hashes and numbers, but not tags
+///   like `latest`
+/// - `eth_getTransactionByHash`
+/// - `eth_getTransactionReceipt`
+///
+/// Blocks, transactions and receipts are only cached once they have the configured number of
+/// [confirmations](CacheMiddleware::confirmations), so that they are not affected by reorgs.
+/// Responses don't expire unless a [ttl](CacheMiddleware::ttl) is set for their method.
+///
+/// # Example
+///
+/// ```
+/// use ethers_middleware::cache::{CacheMiddleware, LruStore};
+/// use ethers_providers::Provider;
+/// use std::time::Duration;
+///
+/// let (provider, _mock) = Provider::mocked();
+/// let provider = CacheMiddleware::new(provider)
+///     .confirmations(64)
+///     .ttl("eth_chainId", Duration::from_secs(3600))
+///     .store(LruStore::new(1_000));
+/// ```
+#[derive(Debug)]
+pub struct CacheMiddleware<M> {
+    inner: M,
+    store: Box<dyn CacheStore>,
+    confirmations: u64,
+    ttls: HashMap<String, Duration>,
+    // the highest block number seen so far, used to avoid fetching the head on every miss
+    head: AtomicU64,
+}
+
+impl<M> CacheMiddleware<M>
+where
+    M: Middleware,
+{
+    /// Caches the responses of the inner middleware in an [`LruStore`] with
+    /// [`DEFAULT_CAPACITY`], after [`DEFAULT_CONFIRMATIONS`]
+    pub fn new(inner: M) -> Self {
+        Self {
+            inner,
+            store: Box::new(LruStore::default()),
+            confirmations: DEFAULT_CONFIRMATIONS,
+            ttls: HashMap::new(),
+            head: AtomicU64::new(0),
+        }
+    }
+
+    /// Sets the store the responses are cached in
+    #[must_use]
+    pub fn store(mut self, store: impl CacheStore + 'static) -> Self {
+        self.store = Box::new(store);
+        self
+    }
+
+    /// Sets the number of blocks that must be mined on top of a block before it, or its
+    /// transactions and receipts, are cached
+    #[must_use]
+    pub fn confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    /// Sets the time after which the cached responses of the RPC method, e.g.
+    /// `eth_getTransactionReceipt`, expire
+    #[must_use]
+    pub fn ttl(mut self, method: impl Into<String>, ttl: Duration) -> Self {
+        self.ttls.insert(method.into(), ttl);
+        self
+    }
+
+    /// Removes all cached responses
+    pub fn clear(&self) {
+        self.store.clear()
+    }
+
+    /// Returns the number of cached responses
+    pub fn len(&self) -> usize {
+        self.store.len()
+    }
+
+    /// Returns `true` if no response is cached
+    pub fn is_empty(&self) -> bool {
+        self.store.is_empty()
+    }
+
+    fn get_cached<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        // values that can't be deserialized, e.g. of a shared store, are treated as misses
+        self.store.get(key).and_then(|value| serde_json::from_value(value).ok())
+    }
+
+    fn insert<T: Serialize>(&self, method: &str, key: String, value: &T) {
+        if let Ok(value) = serde_json::to_value(value) {
+            self.store.insert(key, value, self.ttls.get(method).copied());
+        }
+    }
+
+    /// Caches the response if the block it belongs to is confirmed
+    async fn insert_confirmed<T: Serialize>(
+        &self,
+        method: &str,
+        key: String,
+        value: &T,
+        block: Option<U64>,
+    ) -> Result<(), CacheError<M>> {
+        if let Some(block) = block {
+            if self.is_confirmed(block.as_u64()).await? {
+                self.insert(method, key, value);
+            }
+        }
+        Ok(())
+    }
+
+    async fn is_confirmed(&self, block: u64) -> Result<bool, CacheError<M>> {
+        let confirmed = |head: u64| block.saturating_add(self.confirmations) <= head;
+        if confirmed(self.head.load(Ordering::SeqCst)) {
+            return Ok(true)
+        }
+        let head = self.inner.get_block_number().await.map_err(FromErr::from)?.as_u64();
+        self.head.fetch_max(head, Ordering::SeqCst);
+        Ok(confirmed(head))
+    }
+}
+
+/// Returns the method and cache key of the block, or `None` for tags which refer to different
+/// blocks over time
+fn block_key(id: BlockId, full: bool) -> Option<(&'static str, String)> {
+    let method = match id {
+        BlockId::Hash(_) => "eth_getBlockByHash",
+        BlockId::Number(BlockNumber::Number(_)) => "eth_getBlockByNumber",
+        _ => return None,
+    };
+    let params = match id {
+        BlockId::Hash(hash) => json!([hash, full]),
+        BlockId::Number(number) => json!([number, full]),
+    };
+    Some((method, cache_key(method, params)))
+}
+
+fn cache_key(method: &str, params: Value) -> String {
+    format!("{}:{}", method, params)
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<M> Middleware for CacheMiddleware<M>
+where
+    M: Middleware,
+{
+    type Error = CacheError<M>;
+    type Provider = M::Provider;
+    type Inner = M;
+
+    fn inner(&self) -> &M {
+        &self.inner
+    }
+
+    async fn get_chainid(&self) -> Result<U256, Self::Error> {
+        let key = cache_key("eth_chainId", json!([]));
+        if let Some(chain_id) = self.get_cached(&key) {
+            return Ok(chain_id)
+        }
+        let chain_id = self.inner.get_chainid().await.map_err(FromErr::from)?;
+        self.insert("eth_chainId", key, &chain_id);
+        Ok(chain_id)
+    }
+
+    async fn get_block<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<TxHash>>, Self::Error> {
+        let id = block_hash_or_number.into();
+        let key = block_key(id, false);
+        if let Some(block) = key.as_ref().and_then(|(_, key)| self.get_cached(key)) {
+            return Ok(Some(block))
+        }
+        let block = self.inner.get_block(id).await.map_err(FromErr::from)?;
+        if let (Some((method, key)), Some(block)) = (key, &block) {
+            self.insert_confirmed(method, key, block, block.number).await?;
+        }
+        Ok(block)
+    }
+
+    async fn get_block_with_txs<T: Into<BlockId> + Send + Sync>(
+        &self,
+        block_hash_or_number: T,
+    ) -> Result<Option<Block<Transaction>>, Self::Error> {
+        let id = block_hash_or_number.into();
+        let key = block_key(id, true);
+        if let Some(block) = key.as_ref().and_then(|(_, key)| self.get_cached(key)) {
+            return Ok(Some(block))
+        }
+        let block = self.inner.get_block_with_txs(id).await.map_err(FromErr::from)?;
+        if let (Some((method, key)), Some(block)) = (key, &block) {
+            self.insert_confirmed(method, key, block, block.number).await?;
+        }
+        Ok(block)
+    }
+
+    async fn get_transaction<T: Send + Sync + Into<TxHash>>(
+        &self,
+        transaction_hash: T,
+    ) -> Result<Option<Transaction>, Self::Error> {
+        const METHOD: &str = "eth_getTransactionByHash";
+        let hash = transaction_hash.into();
+        let key = cache_key(METHOD, json!([hash]));
+        if let Some(tx) = self.get_cached(&key) {
+            return Ok(Some(tx))
+        }
+        let tx = self.inner.get_transaction(hash).await.map_err(FromErr::from)?;
+        if let Some(tx) = &tx {
+            // pending transactions have no block number and are never cached
+            self.insert_confirmed(METHOD, key, tx, tx.block_number).await?;
+        }
+        Ok(tx)
+    }
+
+    async fn get_transaction_receipt<T: Send + Sync + Into<TxHash>>(
+        &self,
+        transaction_hash: T,
+    ) -> Result<Option<TransactionReceipt>, Self::Error> {
+        const METHOD: &str = "eth_getTransactionReceipt";
+        let hash = transaction_hash.into();
+        let key = cache_key(METHOD, json!([hash]));
+        if let Some(receipt) = self.get_cached(&key) {
+            return Ok(Some(receipt))
+        }
+        let receipt = self.inner.get_transaction_receipt(hash).await.map_err(FromErr::from)?;
+        if let Some(receipt) = &receipt {
+            self.insert_confirmed(METHOD, key, receipt, receipt.block_number).await?;
+        }
+        Ok(receipt)
+    }
+}
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_providers::Provider;
+
+    #[test]
+    fn lru_store_evicts_least_recently_used() {
+        let store = LruStore::new(2);
+        store.insert("a".to_string(), json!(1), None);
+        store.insert("b".to_string(), json!(2), None);
+        assert_eq!(store.get("a"), Some(json!(1)));
+        store.insert("c".to_string(), json!(3), None);
+        assert_eq!(store.len(), 2);
+        assert_eq!(store.get("b"), None);
+        assert_eq!(store.get("a"), Some(json!(1)));
+        assert_eq!(store.get("c"), Some(json!(3)));
+
+        store.insert("d".to_string(), json!(4), Some(Duration::ZERO));
+        assert_eq!(store.get("d"), None);
+
+        store.clear();
+        assert!(store.is_empty());
+    }
+
+    #[tokio::test]
+    async fn caches_immutable_responses() {
+        let (provider, mock) = Provider::mocked();
+        let provider = CacheMiddleware::new(provider).confirmations(2);
+
+        mock.push(U256::from(1)).unwrap();
+        assert_eq!(provider.get_chainid().await.unwrap(), 1.into());
+        assert_eq!(provider.get_chainid().await.unwrap(), 1.into());
+        mock.assert_request("eth_chainId", ()).unwrap();
+
+        let block =
+            |number: u64| Block::<TxHash> { number: Some(number.into()), ..Default::default() };
+        // responses are returned in reverse order
+        mock.push(U64::from(10)).unwrap();
+        mock.push(block(8)).unwrap();
+        assert_eq!(provider.get_block(8u64).await.unwrap(), Some(block(8)));
+        assert_eq!(provider.get_block(8u64).await.unwrap(), Some(block(8)));
+
+        // blocks without enough confirmations and tags are not cached
+        for _ in 0..2 {
+            mock.push(U64::from(10)).unwrap();
+            mock.push(block(9)).unwrap();
+        }
+        assert_eq!(provider.get_block(9u64).await.unwrap(), Some(block(9)));
+        assert_eq!(provider.get_block(9u64).await.unwrap(), Some(block(9)));
+        mock.push(block(10)).unwrap();
+        mock.push(block(10)).unwrap();
+        assert_eq!(provider.get_block(BlockNumber::Latest).await.unwrap(), Some(block(10)));
+        assert_eq!(provider.get_block(BlockNumber::Latest).await.unwrap(), Some(block(10)));
+        assert_eq!(provider.len(), 2);
+
+        provider.clear();
+        assert!(provider.is_empty());
+    }
+}
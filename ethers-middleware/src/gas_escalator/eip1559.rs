@@ -0,0 +1,107 @@
+use ethers_core::types::U256;
+
+/// The minimum percentage by which nodes require both fees of a replacement transaction to
+/// increase
+pub const MIN_REPLACEMENT_BUMP_PERCENT: u64 = 10;
+
+/// Escalation of the fees of EIP-1559 transactions.
+///
+/// The [`GasEscalator`](super::GasEscalator) escalates both `maxFeePerGas` and
+/// `maxPriorityFeePerGas` of a pending transaction from the fees of its first broadcast, based on
+/// the time elapsed since then, like the gas price of legacy transactions. The escalated fees
+/// never exceed the upper limits of the policy, which are required because the fees an EIP-1559
+/// transaction pays are not bounded by the gas price of a single block.
+///
+/// The transaction is only rebroadcast if both escalated fees are at least
+/// [`MIN_REPLACEMENT_BUMP_PERCENT`] higher than the fees of its last broadcast, since nodes reject
+/// smaller bumps as underpriced.
+#[derive(Clone, Debug)]
+pub struct Eip1559EscalationPolicy {
+    max_fee_per_gas: U256,
+    max_priority_fee_per_gas: U256,
+}
+
+impl Eip1559EscalationPolicy {
+    /// Escalates the fees up to the given upper limits of the `maxFeePerGas` and the
+    /// `maxPriorityFeePerGas`
+    pub fn new(
+        max_fee_per_gas: impl Into<U256>,
+        max_priority_fee_per_gas: impl Into<U256>,
+    ) -> Self {
+        Self {
+            max_fee_per_gas: max_fee_per_gas.into(),
+            max_priority_fee_per_gas: max_priority_fee_per_gas.into(),
+        }
+    }
+
+    /// Returns the `(max_fee_per_gas, max_priority_fee_per_gas)` to rebroadcast a transaction
+    /// that was last broadcast with the `current` fees, given the `escalated` fees.
+    ///
+    /// The escalated fees are limited to the upper limits, and the priority fee to the max fee.
+    /// Returns `None` if the limited fees are not high enough to replace the transaction.
+    pub fn replacement_fees(
+        &self,
+        current: (U256, U256),
+        escalated: (U256, U256),
+    ) -> Option<(U256, U256)> {
+        let max_fee_per_gas = escalated.0.min(self.max_fee_per_gas);
+        let max_priority_fee_per_gas =
+            escalated.1.min(self.max_priority_fee_per_gas).min(max_fee_per_gas);
+        if max_fee_per_gas < min_replacement_fee(current.0)? ||
+            max_priority_fee_per_gas < min_replacement_fee(current.1)?
+        {
+            return None
+        }
+        Some((max_fee_per_gas, max_priority_fee_per_gas))
+    }
+}
+
+/// The lowest fee that replaces a transaction with the `fee`, nodes require the fees to strictly
+/// increase, even if they are zero
+fn min_replacement_fee(fee: U256) -> Option<U256> {
+    let increase =
+        fee.checked_mul(MIN_REPLACEMENT_BUMP_PERCENT.into())?.checked_add(99.into())? / 100;
+    fee.checked_add(increase.max(1.into()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn requires_bump_of_at_least_ten_percent() {
+        let policy = Eip1559EscalationPolicy::new(1000, 100);
+        let current = (100.into(), 10.into());
+        assert_eq!(
+            policy.replacement_fees(current, (110.into(), 11.into())),
+            Some((110.into(), 11.into()))
+        );
+        assert_eq!(policy.replacement_fees(current, (109.into(), 11.into())), None);
+        assert_eq!(policy.replacement_fees(current, (200.into(), 10.into())), None);
+        // rounded up
+        assert_eq!(policy.replacement_fees((101.into(), 1.into()), (111.into(), 2.into())), None);
+        // zero priority fees must increase too
+        assert_eq!(policy.replacement_fees((100.into(), 0.into()), (110.into(), 0.into())), None);
+        assert_eq!(
+            policy.replacement_fees((100.into(), 0.into()), (110.into(), 1.into())),
+            Some((110.into(), 1.into()))
+        );
+    }
+
+    #[test]
+    fn stops_at_upper_limits() {
+        let policy = Eip1559EscalationPolicy::new(120, 12);
+        assert_eq!(
+            policy.replacement_fees((100.into(), 10.into()), (200.into(), 20.into())),
+            Some((120.into(), 12.into()))
+        );
+        assert_eq!(policy.replacement_fees((120.into(), 12.into()), (300.into(), 30.into())), None);
+
+        // the priority fee never exceeds the max fee
+        let policy = Eip1559EscalationPolicy::new(20, 100);
+        assert_eq!(
+            policy.replacement_fees((10.into(), 10.into()), (30.into(), 30.into())),
+            Some((20.into(), 20.into()))
+        );
+    }
+}
@@ -5,8 +5,11 @@ pub use geometric::GeometricGasPrice;
 mod linear;
 pub use linear::LinearGasPrice;
 
+mod eip1559;
+pub use eip1559::{Eip1559EscalationPolicy, MIN_REPLACEMENT_BUMP_PERCENT};
+
 use async_trait::async_trait;
-use ethers_core::types::{BlockId, TransactionRequest, TxHash, U256};
+use ethers_core::types::{BlockId, Eip1559TransactionRequest, TransactionRequest, TxHash, U256};
 use ethers_providers::{interval, FromErr, Middleware, PendingTransaction, StreamExt};
 use futures_util::lock::Mutex;
 use instant::Instant;
@@ -42,6 +45,10 @@ pub enum Frequency {
 /// A Gas escalator allows bumping transactions' gas price to avoid getting them
 /// stuck in the memory pool.
 ///
+/// The fees of EIP-1559 transactions are only escalated if the middleware is created with an
+/// [`Eip1559EscalationPolicy`], see [`GasEscalatorMiddleware::with_eip1559_policy`], otherwise
+/// sending them fails with [`GasEscalatorError::UnsupportedTxType`].
+///
 /// ```no_run
 /// use ethers_providers::{Provider, Http};
 /// use ethers_middleware::{
@@ -69,15 +76,33 @@ pub struct GasEscalatorMiddleware<M, E> {
     /// The transactions which are currently being monitored for escalation
     #[allow(clippy::type_complexity)]
     pub txs: Arc<Mutex<Vec<(TxHash, TransactionRequest, Instant, Option<BlockId>)>>>,
+    eip1559_policy: Option<Eip1559EscalationPolicy>,
+    /// The EIP-1559 transactions which are currently being monitored for escalation
+    eip1559_txs: Arc<Mutex<Vec<PendingEip1559Tx>>>,
     frequency: Frequency,
 }
 
+/// An EIP-1559 transaction whose fees are escalated
+#[derive(Debug, Clone)]
+struct PendingEip1559Tx {
+    /// The hashes of the transaction and all its replacements
+    tx_hashes: Vec<TxHash>,
+    /// The transaction with the fees of its last broadcast
+    tx: Eip1559TransactionRequest,
+    /// The `(max_fee_per_gas, max_priority_fee_per_gas)` of the first broadcast
+    initial_fees: (U256, U256),
+    time: Instant,
+    block: Option<BlockId>,
+}
+
 impl<M, E: Clone> Clone for GasEscalatorMiddleware<M, E> {
     fn clone(&self) -> Self {
         GasEscalatorMiddleware {
             inner: self.inner.clone(),
             escalator: self.escalator.clone(),
             txs: self.txs.clone(),
+            eip1559_policy: self.eip1559_policy.clone(),
+            eip1559_txs: self.eip1559_txs.clone(),
             frequency: self.frequency.clone(),
         }
     }
@@ -103,7 +128,15 @@ where
         tx: T,
         block: Option<BlockId>,
     ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
-        let tx = tx.into();
+        let mut tx = tx.into();
+
+        if matches!(tx, TypedTransaction::Eip1559(_)) && self.eip1559_policy.is_some() {
+            // fill the tx so that the fees it is broadcast with are known when escalating them
+            self.inner()
+                .fill_transaction(&mut tx, block)
+                .await
+                .map_err(GasEscalatorError::MiddlewareError)?;
+        }
 
         let pending_tx = self
             .inner()
@@ -114,6 +147,21 @@ where
         let tx = match tx {
             TypedTransaction::Legacy(inner) => inner,
             TypedTransaction::Eip2930(inner) => inner.tx,
+            TypedTransaction::Eip1559(inner) if self.eip1559_policy.is_some() => {
+                let initial_fees = match (inner.max_fee_per_gas, inner.max_priority_fee_per_gas) {
+                    (Some(max_fee), Some(max_priority_fee)) => (max_fee, max_priority_fee),
+                    _ => return Err(GasEscalatorError::UnsupportedTxType),
+                };
+                let mut lock = self.eip1559_txs.lock().await;
+                lock.push(PendingEip1559Tx {
+                    tx_hashes: vec![*pending_tx],
+                    tx: inner,
+                    initial_fees,
+                    time: Instant::now(),
+                    block,
+                });
+                return Ok(pending_tx)
+            }
             _ => return Err(GasEscalatorError::UnsupportedTxType),
         };
 
@@ -132,9 +180,39 @@ where
 {
     /// Initializes the middleware with the provided gas escalator and the chosen
     /// escalation frequency (per block or per second)
-    #[allow(clippy::let_and_return)]
     #[cfg(not(target_arch = "wasm32"))]
     pub fn new(inner: M, escalator: E, frequency: Frequency) -> Self
+    where
+        E: Clone + 'static,
+        M: Clone + 'static,
+    {
+        Self::spawn(inner, escalator, None, frequency)
+    }
+
+    /// Initializes the middleware like [`GasEscalatorMiddleware::new`], and also escalates the
+    /// fees of EIP-1559 transactions with the escalator, up to the limits of the policy
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn with_eip1559_policy(
+        inner: M,
+        escalator: E,
+        eip1559_policy: Eip1559EscalationPolicy,
+        frequency: Frequency,
+    ) -> Self
+    where
+        E: Clone + 'static,
+        M: Clone + 'static,
+    {
+        Self::spawn(inner, escalator, Some(eip1559_policy), frequency)
+    }
+
+    #[allow(clippy::let_and_return)]
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spawn(
+        inner: M,
+        escalator: E,
+        eip1559_policy: Option<Eip1559EscalationPolicy>,
+        frequency: Frequency,
+    ) -> Self
     where
         E: Clone + 'static,
         M: Clone + 'static,
@@ -146,6 +224,8 @@ where
             escalator,
             frequency,
             txs: Arc::new(Mutex::new(Vec::new())),
+            eip1559_policy,
+            eip1559_txs: Arc::new(Mutex::new(Vec::new())),
         };
 
         {
@@ -231,6 +311,85 @@ where
                     txs.push((new_txhash, replacement_tx, time, priority));
                 }
             }
+
+            self.escalate_eip1559_txs(now).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Re-broadcasts the pending EIP-1559 transactions with the fees escalated from the fees of
+    /// their first broadcast, see [`Eip1559EscalationPolicy`]
+    async fn escalate_eip1559_txs(&self, now: Instant) -> Result<(), GasEscalatorError<M>> {
+        let policy = match self.eip1559_policy {
+            Some(ref policy) => policy,
+            None => return Ok(()),
+        };
+        let mut txs = self.eip1559_txs.lock().await;
+        let len = txs.len();
+
+        // Pop all transactions and re-insert those that have not been included yet
+        for _ in 0..len {
+            // this must never panic as we're explicitly within bounds
+            let mut pending = txs.pop().expect("should have element in vector");
+
+            // stop escalating once the original tx or any of its replacements was mined
+            let mut mined = false;
+            for tx_hash in &pending.tx_hashes {
+                tracing::trace!(tx_hash = ?tx_hash, "checking if exists");
+                if self.get_transaction_receipt(*tx_hash).await?.is_some() {
+                    mined = true;
+                    break
+                }
+            }
+            if mined {
+                continue
+            }
+
+            let time_elapsed = now.duration_since(pending.time).as_secs();
+            let (initial_max_fee, initial_max_priority_fee) = pending.initial_fees;
+            let escalated = (
+                self.escalator.get_gas_price(initial_max_fee, time_elapsed),
+                self.escalator.get_gas_price(initial_max_priority_fee, time_elapsed),
+            );
+            let current = (
+                pending.tx.max_fee_per_gas.unwrap_or_default(),
+                pending.tx.max_priority_fee_per_gas.unwrap_or_default(),
+            );
+
+            if let Some((max_fee_per_gas, max_priority_fee_per_gas)) =
+                policy.replacement_fees(current, escalated)
+            {
+                pending.tx.max_fee_per_gas = Some(max_fee_per_gas);
+                pending.tx.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
+
+                // the tx hash will be different so we need to update it
+                match self.inner().send_transaction(pending.tx.clone(), pending.block).await {
+                    Ok(new_tx_hash) => {
+                        let new_tx_hash = *new_tx_hash;
+                        tracing::trace!(
+                            old_tx_hash = ?pending.tx_hashes.last(),
+                            new_tx_hash = ?new_tx_hash,
+                            old_max_fee_per_gas = ?current.0,
+                            new_max_fee_per_gas = ?max_fee_per_gas,
+                            old_max_priority_fee_per_gas = ?current.1,
+                            new_max_priority_fee_per_gas = ?max_priority_fee_per_gas,
+                            "escalated"
+                        );
+                        pending.tx_hashes.push(new_tx_hash);
+                    }
+                    Err(err) => {
+                        if err.to_string().contains("nonce too low") {
+                            // one of the previous transactions was already mined
+                            continue
+                        } else {
+                            return Err(GasEscalatorError::MiddlewareError(err))
+                        }
+                    }
+                }
+            }
+
+            txs.push(pending);
         }
 
         Ok(())
@@ -254,3 +413,103 @@ pub enum GasEscalatorError<M: Middleware> {
     #[error("Gas escalation is only supported for EIP2930 or Legacy transactions")]
     UnsupportedTxType,
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_core::types::{Address, TransactionReceipt};
+    use ethers_providers::{MockProvider, Provider};
+    use serde_json::Value;
+    use std::time::Duration;
+
+    fn escalator_middleware(
+    ) -> (GasEscalatorMiddleware<Provider<MockProvider>, GeometricGasPrice>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let middleware = GasEscalatorMiddleware {
+            inner: Arc::new(provider),
+            escalator: GeometricGasPrice::new(1.5, 10u64, None::<u64>),
+            txs: Arc::new(Mutex::new(Vec::new())),
+            eip1559_policy: Some(Eip1559EscalationPolicy::new(1000, 100)),
+            eip1559_txs: Arc::new(Mutex::new(Vec::new())),
+            frequency: Frequency::PerBlock,
+        };
+        (middleware, mock)
+    }
+
+    #[tokio::test]
+    async fn escalates_eip1559_fees_with_elapsed_time() {
+        let (middleware, mock) = escalator_middleware();
+        let tx = Eip1559TransactionRequest::new()
+            .from(Address::repeat_byte(1))
+            .to(Address::zero())
+            .gas(21000)
+            .max_fee_per_gas(200)
+            .max_priority_fee_per_gas(20);
+        let hashes = [TxHash::repeat_byte(1), TxHash::repeat_byte(2), TxHash::repeat_byte(3)];
+
+        mock.push(hashes[0]).unwrap();
+        middleware.send_transaction(tx.clone(), None).await.unwrap();
+        mock.assert_request("eth_sendTransaction", [TypedTransaction::Eip1559(tx.clone())])
+            .unwrap();
+        let start = middleware.eip1559_txs.lock().await[0].time;
+
+        // the fees are not escalated before the escalator increases them
+        mock.push(Value::Null).unwrap();
+        middleware.escalate_eip1559_txs(start + Duration::from_secs(5)).await.unwrap();
+        mock.assert_request("eth_getTransactionReceipt", [hashes[0]]).unwrap();
+
+        mock.push(hashes[1]).unwrap();
+        mock.push(Value::Null).unwrap();
+        middleware.escalate_eip1559_txs(start + Duration::from_secs(10)).await.unwrap();
+        mock.assert_request("eth_getTransactionReceipt", [hashes[0]]).unwrap();
+        let replacement = tx.clone().max_fee_per_gas(300).max_priority_fee_per_gas(30);
+        mock.assert_request("eth_sendTransaction", [TypedTransaction::Eip1559(replacement)])
+            .unwrap();
+
+        // the escalated fees are limited by the policy
+        mock.push(hashes[2]).unwrap();
+        mock.push(Value::Null).unwrap();
+        mock.push(Value::Null).unwrap();
+        middleware.escalate_eip1559_txs(start + Duration::from_secs(60)).await.unwrap();
+        mock.assert_request("eth_getTransactionReceipt", [hashes[0]]).unwrap();
+        mock.assert_request("eth_getTransactionReceipt", [hashes[1]]).unwrap();
+        let replacement = tx.clone().max_fee_per_gas(1000).max_priority_fee_per_gas(100);
+        mock.assert_request("eth_sendTransaction", [TypedTransaction::Eip1559(replacement)])
+            .unwrap();
+
+        // and are not escalated any further
+        for _ in 0..3 {
+            mock.push(Value::Null).unwrap();
+        }
+        middleware.escalate_eip1559_txs(start + Duration::from_secs(100)).await.unwrap();
+        for hash in hashes {
+            mock.assert_request("eth_getTransactionReceipt", [hash]).unwrap();
+        }
+        assert!(mock.assert_request("eth_sendTransaction", ()).is_err());
+
+        // the escalation stops once one of the transactions was mined
+        mock.push(TransactionReceipt::default()).unwrap();
+        mock.push(Value::Null).unwrap();
+        middleware.escalate_eip1559_txs(start + Duration::from_secs(110)).await.unwrap();
+        mock.assert_request("eth_getTransactionReceipt", [hashes[0]]).unwrap();
+        mock.assert_request("eth_getTransactionReceipt", [hashes[1]]).unwrap();
+        assert!(middleware.eip1559_txs.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn eip1559_transactions_require_a_policy() {
+        let (mut middleware, mock) = escalator_middleware();
+        middleware.eip1559_policy = None;
+        let tx = Eip1559TransactionRequest::new()
+            .from(Address::repeat_byte(1))
+            .to(Address::zero())
+            .gas(21000)
+            .max_fee_per_gas(200)
+            .max_priority_fee_per_gas(20);
+
+        mock.push(TxHash::repeat_byte(1)).unwrap();
+        let err = middleware.send_transaction(tx, None).await.unwrap_err();
+        assert!(matches!(err, GasEscalatorError::UnsupportedTxType));
+        assert!(middleware.eip1559_txs.lock().await.is_empty());
+    }
+}
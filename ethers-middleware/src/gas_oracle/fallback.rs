@@ -0,0 +1,60 @@
+use crate::gas_oracle::{GasOracle, GasOracleError};
+use async_trait::async_trait;
+use ethers_core::types::U256;
+use std::{fmt::Debug, future::Future};
+use tracing::warn;
+
+#[derive(Default, Debug)]
+pub struct Fallback {
+    oracles: Vec<Box<dyn GasOracle>>,
+}
+
+/// Queries the oracles one after another and returns the first gas price that is fetched
+/// successfully, so that fee estimation keeps working if an oracle is down.
+///
+/// The errors of the oracles that fail are logged, if all oracles fail the errors of all oracles
+/// are returned. Don't forget to set a timeout on the source oracles, otherwise an oracle that
+/// never answers blocks all oracles after it.
+impl Fallback {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an oracle that is queried if all oracles that were added before fail
+    pub fn add<T: 'static + GasOracle>(&mut self, oracle: T) {
+        self.oracles.push(Box::new(oracle));
+    }
+
+    async fn query_first<'a, Fn, Fut, O>(&'a self, mut f: Fn) -> Result<O, GasOracleError>
+    where
+        Fn: FnMut(&'a dyn GasOracle) -> Fut,
+        Fut: Future<Output = Result<O, GasOracleError>>,
+    {
+        let mut errors = Vec::with_capacity(self.oracles.len());
+        for oracle in &self.oracles {
+            match f(oracle.as_ref()).await {
+                Ok(value) => return Ok(value),
+                Err(err) => {
+                    warn!("Failed to fetch gas price from {:?}: {}", oracle, err);
+                    errors.push(err);
+                }
+            }
+        }
+        if errors.is_empty() {
+            return Err(GasOracleError::NoValues)
+        }
+        Err(GasOracleError::AllOraclesFailed(errors))
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GasOracle for Fallback {
+    async fn fetch(&self) -> Result<U256, GasOracleError> {
+        self.query_first(|oracle| oracle.fetch()).await
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        self.query_first(|oracle| oracle.estimate_eip1559_fees()).await
+    }
+}
@@ -41,19 +41,22 @@ impl Median {
         let results = join_all(futures).await;
 
         // Filter out any errors
-        let values =
-            self.oracles.iter().zip(results).filter_map(
-                |((weight, oracle), result)| match result {
-                    Ok(value) => Some((*weight, value)),
-                    Err(err) => {
-                        warn!("Failed to fetch gas price from {:?}: {}", oracle, err);
-                        None
-                    }
-                },
-            );
-        let values = values.collect::<Vec<_>>();
+        let mut values = Vec::with_capacity(results.len());
+        let mut errors = Vec::new();
+        for ((weight, oracle), result) in self.oracles.iter().zip(results) {
+            match result {
+                Ok(value) => values.push((*weight, value)),
+                Err(err) => {
+                    warn!("Failed to fetch gas price from {:?}: {}", oracle, err);
+                    errors.push(err);
+                }
+            }
+        }
         if values.is_empty() {
-            return Err(GasOracleError::NoValues)
+            if errors.is_empty() {
+                return Err(GasOracleError::NoValues)
+            }
+            return Err(GasOracleError::AllOraclesFailed(errors))
         }
         Ok(values)
     }
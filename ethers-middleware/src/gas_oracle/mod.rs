@@ -16,6 +16,9 @@ pub use middleware::{GasOracleMiddleware, MiddlewareError};
 mod median;
 pub use median::Median;
 
+mod fallback;
+pub use fallback::Fallback;
+
 mod cache;
 pub use cache::Cache;
 
@@ -71,6 +74,10 @@ pub enum GasOracleError {
     #[error("None of the oracles returned a value")]
     NoValues,
 
+    /// Every oracle of a [`Fallback`] or [`Median`] oracle failed, holds the error of each oracle
+    #[error("All oracles failed: {0:?}")]
+    AllOraclesFailed(Vec<GasOracleError>),
+
     #[error("Chain is not supported by the oracle")]
     UnsupportedChain,
 }
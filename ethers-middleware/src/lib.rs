@@ -36,3 +36,8 @@ pub use policy::PolicyMiddleware;
 /// before the chain tip
 pub mod timelag;
 pub use timelag::TimeLag;
+
+/// The [Cache](crate::CacheMiddleware) caches the responses of calls that never change, e.g.
+/// confirmed blocks and receipts, to reduce the load on the node
+pub mod cache;
+pub use cache::CacheMiddleware;
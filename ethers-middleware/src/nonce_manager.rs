@@ -48,6 +48,25 @@ where
         Ok(self.nonce.load(Ordering::SeqCst).into())
     }
 
+    /// Refetches the pending nonce of the address and uses it for the next transaction.
+    ///
+    /// This is useful if transactions of the address were sent without the nonce manager.
+    pub async fn resync(&self) -> Result<U256, NonceManagerError<M>> {
+        let nonce = self.pending_nonce().await?;
+        self.nonce.store(nonce, Ordering::SeqCst);
+        self.initialized.store(true, Ordering::SeqCst);
+        Ok(nonce.into())
+    }
+
+    async fn pending_nonce(&self) -> Result<u64, NonceManagerError<M>> {
+        let nonce = self
+            .inner
+            .get_transaction_count(self.address, Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(FromErr::from)?;
+        Ok(nonce.as_u64())
+    }
+
     async fn get_transaction_count_with_manager(
         &self,
         block: Option<BlockId>,
@@ -67,6 +86,15 @@ where
     }
 }
 
+/// Returns `true` if the node rejected a transaction because its nonce was already used, e.g.
+/// by a transaction that was sent without the nonce manager.
+///
+/// This is not the case for `already known` errors, these mean that the same transaction is
+/// already in the pool and sending it with another nonce would execute it twice.
+fn is_nonce_used<E: std::error::Error>(err: &E) -> bool {
+    err.to_string().to_lowercase().contains("nonce too low")
+}
+
 #[derive(Error, Debug)]
 /// Thrown when an error happens at the Nonce Manager
 pub enum NonceManagerError<M: Middleware> {
@@ -117,25 +145,108 @@ where
     ) -> Result<PendingTransaction<'_, Self::Provider>, Self::Error> {
         let mut tx = tx.into();
 
-        if tx.nonce().is_none() {
-            tx.set_nonce(self.get_transaction_count_with_manager(block).await?);
+        if tx.nonce().is_some() {
+            return self.inner.send_transaction(tx, block).await.map_err(FromErr::from)
         }
 
+        tx.set_nonce(self.get_transaction_count_with_manager(block).await?);
         match self.inner.send_transaction(tx.clone(), block).await {
-            Ok(tx_hash) => Ok(tx_hash),
-            Err(err) => {
-                let nonce = self.get_transaction_count(self.address, block).await?;
-                if nonce != self.nonce.load(Ordering::SeqCst).into() {
-                    // try re-submitting the transaction with the correct nonce if there
-                    // was a nonce mismatch
-                    self.nonce.store(nonce.as_u64(), Ordering::SeqCst);
-                    tx.set_nonce(nonce);
-                    self.inner.send_transaction(tx, block).await.map_err(FromErr::from)
-                } else {
-                    // propagate the error otherwise
-                    Err(FromErr::from(err))
-                }
+            Ok(pending) => Ok(pending),
+            Err(err) if is_nonce_used(&err) => {
+                // the nonce was used by another sender, retry once with the pending nonce. The
+                // local nonce is only moved forward, in case other transactions of the manager
+                // are in flight
+                let nonce = self.pending_nonce().await?;
+                self.nonce.fetch_max(nonce, Ordering::SeqCst);
+                tx.set_nonce(self.next());
+                self.inner.send_transaction(tx, block).await.map_err(FromErr::from)
             }
+            Err(err) => Err(FromErr::from(err)),
         }
     }
 }
+
+#[cfg(all(test, not(target_arch = "wasm32")))]
+mod tests {
+    use super::*;
+    use ethers_providers::{JsonRpcError, Provider};
+
+    #[test]
+    fn detects_used_nonces() {
+        let err = |msg: &str| std::io::Error::new(std::io::ErrorKind::Other, msg);
+        assert!(is_nonce_used(&err("nonce too low")));
+        assert!(is_nonce_used(&err("(code: -32000, message: Nonce too low, data: None)")));
+        assert!(!is_nonce_used(&err("already known")));
+        assert!(!is_nonce_used(&err("insufficient funds for gas * price + value")));
+    }
+
+    #[tokio::test]
+    async fn resync_refetches_pending_nonce() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+        let nonce_manager = NonceManagerMiddleware::new(provider, address);
+
+        mock.push(U256::from(5)).unwrap();
+        assert_eq!(nonce_manager.resync().await.unwrap(), 5.into());
+        assert_eq!(nonce_manager.next(), 5.into());
+        assert_eq!(nonce_manager.next(), 6.into());
+        mock.assert_request("eth_getTransactionCount", (address, "pending")).unwrap();
+
+        // the nonce can also move backwards, e.g. if transactions were dropped
+        mock.push(U256::from(3)).unwrap();
+        assert_eq!(nonce_manager.resync().await.unwrap(), 3.into());
+        assert_eq!(nonce_manager.next(), 3.into());
+    }
+
+    fn rpc_error(message: &str) -> JsonRpcError {
+        JsonRpcError { code: -32000, message: message.to_string(), data: None }
+    }
+
+    #[tokio::test]
+    async fn retries_with_pending_nonce_if_nonce_was_used() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+        let nonce_manager = NonceManagerMiddleware::new(provider, address);
+        let tx =
+            TransactionRequest::new().from(address).to(Address::zero()).gas(21000).gas_price(1);
+        let tx_hash = H256::repeat_byte(2);
+
+        // the responses are popped in reverse order
+        mock.push(tx_hash).unwrap();
+        mock.push(U256::from(7)).unwrap();
+        mock.push_error(rpc_error("nonce too low"));
+        mock.push(U256::from(5)).unwrap();
+
+        let pending = nonce_manager.send_transaction(tx.clone(), None).await.unwrap();
+        assert_eq!(*pending, tx_hash);
+
+        mock.assert_request("eth_getTransactionCount", (address, "latest")).unwrap();
+        let sent: TypedTransaction = tx.clone().nonce(5).into();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+        mock.assert_request("eth_getTransactionCount", (address, "pending")).unwrap();
+        let sent: TypedTransaction = tx.nonce(7).into();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+        assert_eq!(nonce_manager.next(), 8.into());
+    }
+
+    #[tokio::test]
+    async fn does_not_resend_known_transactions() {
+        let (provider, mock) = Provider::mocked();
+        let address = Address::repeat_byte(1);
+        let nonce_manager = NonceManagerMiddleware::new(provider, address);
+        let tx =
+            TransactionRequest::new().from(address).to(Address::zero()).gas(21000).gas_price(1);
+
+        mock.push_error(rpc_error("already known"));
+        mock.push(U256::from(5)).unwrap();
+
+        let err = nonce_manager.send_transaction(tx.clone(), None).await.unwrap_err();
+        assert!(err.to_string().contains("already known"));
+
+        mock.assert_request("eth_getTransactionCount", (address, "latest")).unwrap();
+        let sent: TypedTransaction = tx.nonce(5).into();
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+        // no other request was sent
+        assert!(mock.assert_request("eth_sendTransaction", ()).is_err());
+    }
+}
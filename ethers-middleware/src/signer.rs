@@ -1,8 +1,8 @@
 use ethers_core::types::{
     transaction::{eip2718::TypedTransaction, eip2930::AccessListWithGasUsed},
-    Address, BlockId, Bytes, Signature, U256,
+    Address, BlockId, Bytes, Signature, StateOverride, TransactionValidationError, U256,
 };
-use ethers_providers::{maybe, FromErr, Middleware, PendingTransaction};
+use ethers_providers::{fill_access_list, maybe, FromErr, Middleware, PendingTransaction};
 use ethers_signers::Signer;
 
 use async_trait::async_trait;
@@ -64,6 +64,7 @@ pub struct SignerMiddleware<M, S> {
     pub(crate) inner: M,
     pub(crate) signer: S,
     pub(crate) address: Address,
+    pub(crate) access_list_filling: bool,
 }
 
 impl<M: Middleware, S: Signer> FromErr<M::Error> for SignerMiddlewareError<M, S> {
@@ -98,6 +99,11 @@ pub enum SignerMiddlewareError<M: Middleware, S: Signer> {
     /// Thrown if the signer's chain_id is different than the chain_id of the transaction
     #[error("specified chain_id is different than the signer's chain_id")]
     DifferentChainID,
+    /// Thrown if the transaction would be rejected by the node, see
+    /// [`TypedTransaction::validate`]. A chain id that doesn't match the signer's is reported as
+    /// [`SignerMiddlewareError::DifferentChainID`] instead.
+    #[error(transparent)]
+    InvalidTransaction(TransactionValidationError),
 }
 
 // Helper functions for locally signing transactions
@@ -117,7 +123,7 @@ where
     /// [`Signer`] ethers_signers::Signer
     pub fn new(inner: M, signer: S) -> Self {
         let address = signer.address();
-        SignerMiddleware { inner, signer, address }
+        SignerMiddleware { inner, signer, address, access_list_filling: false }
     }
 
     /// Signs and returns the RLP encoding of the signed transaction.
@@ -187,7 +193,26 @@ where
         let chain_id =
             inner.get_chainid().await.map_err(|e| SignerMiddlewareError::MiddlewareError(e))?;
         let signer = signer.with_chain_id(chain_id.as_u64());
-        Ok(SignerMiddleware { inner, signer, address })
+        Ok(SignerMiddleware { inner, signer, address, access_list_filling: false })
+    }
+
+    /// Sets whether the empty access list of EIP-2930 and EIP-1559 transactions is populated
+    /// before signing (default: false).
+    ///
+    /// If enabled, [`Middleware::fill_transaction`] calls `eth_createAccessList` and uses the
+    /// access list only if it lowers the estimated gas of the transaction, see
+    /// [`fill_access_list`]. Nodes that don't support `eth_createAccessList` are ignored and the
+    /// transaction is sent without an access list.
+    ///
+    /// This is the same as the [`TxFillStep::AccessList`] step of the provider, which is not part
+    /// of its default [`TxFillOrder`] and should not be added while this is enabled.
+    ///
+    /// [`TxFillStep::AccessList`]: ethers_providers::TxFillStep::AccessList
+    /// [`TxFillOrder`]: ethers_providers::TxFillOrder
+    #[must_use]
+    pub fn with_access_list_filling(mut self, access_list_filling: bool) -> Self {
+        self.access_list_filling = access_list_filling;
+        self
     }
 
     fn set_tx_from_if_none(&self, tx: &TypedTransaction) -> TypedTransaction {
@@ -233,6 +258,10 @@ where
     }
 
     /// Helper for filling a transaction's nonce using the wallet
+    ///
+    /// Populates the access list if enabled with
+    /// [`SignerMiddleware::with_access_list_filling`], the remaining fields are filled by the
+    /// inner middleware.
     async fn fill_transaction(
         &self,
         tx: &mut TypedTransaction,
@@ -254,6 +283,14 @@ where
 
         let nonce = maybe(tx.nonce().cloned(), self.get_transaction_count(from, block)).await?;
         tx.set_nonce(nonce);
+        if self.access_list_filling {
+            // the gas is already estimated, so the inner middleware does not estimate it again
+            if let Some(gas) = fill_access_list(self, tx, block).await? {
+                if tx.gas().is_none() {
+                    tx.set_gas(gas);
+                }
+            }
+        }
         self.inner()
             .fill_transaction(tx, block)
             .await
@@ -283,6 +320,13 @@ where
                 .map_err(SignerMiddlewareError::MiddlewareError)
         }
 
+        tx.validate_for_chain(self.signer.chain_id()).map_err(|err| match err {
+            TransactionValidationError::ChainIdMismatch { .. } => {
+                SignerMiddlewareError::DifferentChainID
+            }
+            err => SignerMiddlewareError::InvalidTransaction(err),
+        })?;
+
         // if we have a nonce manager set, we should try handling the result in
         // case there was a nonce mismatch
         let signed_tx = self.sign_transaction(tx).await?;
@@ -329,16 +373,45 @@ where
         let tx = self.set_tx_from_if_none(tx);
         self.inner().call(&tx, block).await.map_err(SignerMiddlewareError::MiddlewareError)
     }
+
+    async fn call_with_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<Bytes, Self::Error> {
+        let tx = self.set_tx_from_if_none(tx);
+        self.inner()
+            .call_with_overrides(&tx, block, overrides)
+            .await
+            .map_err(SignerMiddlewareError::MiddlewareError)
+    }
+
+    async fn estimate_gas_with_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<U256, Self::Error> {
+        let tx = self.set_tx_from_if_none(tx);
+        self.inner()
+            .estimate_gas_with_overrides(&tx, block, overrides)
+            .await
+            .map_err(SignerMiddlewareError::MiddlewareError)
+    }
 }
 
 #[cfg(all(test, not(feature = "celo"), not(target_arch = "wasm32")))]
 mod tests {
     use super::*;
     use ethers_core::{
-        types::TransactionRequest,
+        types::{
+            transaction::eip2930::{AccessList, AccessListItem},
+            Eip1559TransactionRequest, TransactionRequest, H256,
+        },
         utils::{self, keccak256, Ganache},
     };
-    use ethers_providers::Provider;
+    use ethers_providers::{JsonRpcError, MockError, MockProvider, Provider};
     use ethers_signers::LocalWallet;
     use std::convert::TryFrom;
 
@@ -465,4 +538,122 @@ mod tests {
         let tx = client.get_transaction(hash).await.unwrap().unwrap();
         assert_eq!(tx.from, acc);
     }
+
+    fn access_list_client(
+        access_list_filling: bool,
+    ) -> (SignerMiddleware<Provider<MockProvider>, LocalWallet>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let key = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+            .parse::<LocalWallet>()
+            .unwrap()
+            .with_chain_id(1u64);
+        let client =
+            SignerMiddleware::new(provider, key).with_access_list_filling(access_list_filling);
+        (client, mock)
+    }
+
+    fn eip1559_tx() -> TypedTransaction {
+        Eip1559TransactionRequest::new()
+            .to(Address::repeat_byte(2))
+            .nonce(0)
+            .max_fee_per_gas(100)
+            .max_priority_fee_per_gas(10)
+            .into()
+    }
+
+    #[tokio::test]
+    async fn fills_access_list_if_it_lowers_gas() {
+        let access_list = AccessList(vec![AccessListItem {
+            address: Address::repeat_byte(2),
+            storage_keys: vec![H256::repeat_byte(3)],
+        }]);
+
+        let (client, mock) = access_list_client(true);
+        // responses are popped from the back
+        mock.push(AccessListWithGasUsed {
+            access_list: access_list.clone(),
+            gas_used: 25_000.into(),
+        })
+        .unwrap();
+        mock.push(U256::from(30_000)).unwrap();
+        let mut tx = eip1559_tx();
+        client.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.access_list(), Some(&access_list));
+        assert_eq!(tx.gas(), Some(&25_000.into()));
+
+        // the access list is not used if it costs more gas
+        let (client, mock) = access_list_client(true);
+        mock.push(AccessListWithGasUsed { access_list, gas_used: 35_000.into() }).unwrap();
+        mock.push(U256::from(30_000)).unwrap();
+        let mut tx = eip1559_tx();
+        client.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.access_list(), Some(&AccessList::default()));
+        assert_eq!(tx.gas(), Some(&30_000.into()));
+    }
+
+    #[tokio::test]
+    async fn skips_access_list_if_unsupported() {
+        let (client, mock) = access_list_client(true);
+        mock.push_error(JsonRpcError {
+            code: -32601,
+            message: "the method eth_createAccessList does not exist".to_string(),
+            data: None,
+        });
+        mock.push(U256::from(30_000)).unwrap();
+        let mut tx = eip1559_tx();
+        client.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.access_list(), Some(&AccessList::default()));
+        assert_eq!(tx.gas(), Some(&30_000.into()));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_transactions() {
+        let (client, mock) = access_list_client(false);
+        let tx = TransactionRequest::new().to(Address::repeat_byte(2)).nonce(0).gas_price(1);
+        let err = client.send_transaction(tx.clone().gas(0), None).await.unwrap_err();
+        assert!(matches!(
+            err,
+            SignerMiddlewareError::InvalidTransaction(TransactionValidationError::ZeroGasLimit)
+        ));
+
+        // the signer signs for chain 1
+        let err = client.send_transaction(tx.gas(21000).chain_id(5), None).await.unwrap_err();
+        assert!(matches!(err, SignerMiddlewareError::DifferentChainID));
+        // nothing was sent
+        assert!(matches!(
+            mock.assert_request("eth_sendRawTransaction", ()),
+            Err(MockError::EmptyRequests)
+        ));
+    }
+
+    #[tokio::test]
+    async fn sets_sender_of_calls_with_overrides() {
+        let (client, mock) = access_list_client(false);
+        let tx: TypedTransaction = TransactionRequest::new().to(Address::repeat_byte(2)).into();
+        let overrides = StateOverride::new();
+        let expected = client.set_tx_from_if_none(&tx);
+        assert_eq!(expected.from(), Some(&client.address()));
+
+        mock.push(Bytes::default()).unwrap();
+        client.call_with_overrides(&tx, None, &overrides).await.unwrap();
+        mock.assert_request("eth_call", (&expected, "latest", &overrides)).unwrap();
+
+        mock.push(U256::from(21000)).unwrap();
+        client.estimate_gas_with_overrides(&tx, None, &overrides).await.unwrap();
+        mock.assert_request("eth_estimateGas", (&expected, "latest", &overrides)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn access_list_filling_is_opt_in() {
+        let (client, mock) = access_list_client(false);
+        let mut tx = eip1559_tx();
+        tx.set_gas(21_000);
+        client.fill_transaction(&mut tx, None).await.unwrap();
+        assert_eq!(tx.access_list(), Some(&AccessList::default()));
+        // no eth_createAccessList request was made
+        assert!(matches!(
+            mock.assert_request("eth_createAccessList", ()),
+            Err(MockError::EmptyRequests)
+        ));
+    }
 }
@@ -6,8 +6,8 @@ use async_trait::async_trait;
 
 use ethers_core::{types::*, utils::Ganache};
 use ethers_middleware::gas_oracle::{
-    EthGasStation, Etherchain, Etherscan, GasCategory, GasOracle, GasOracleError,
-    GasOracleMiddleware,
+    EthGasStation, Etherchain, Etherscan, Fallback, GasCategory, GasOracle, GasOracleError,
+    GasOracleMiddleware, Median,
 };
 use ethers_providers::{Http, Middleware, Provider};
 use serial_test::serial;
@@ -29,6 +29,58 @@ impl GasOracle for FakeGasOracle {
     }
 }
 
+#[derive(Debug)]
+struct FailingGasOracle;
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl GasOracle for FailingGasOracle {
+    async fn fetch(&self) -> Result<U256, GasOracleError> {
+        Err(GasOracleError::InvalidResponse)
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        Err(GasOracleError::InvalidResponse)
+    }
+}
+
+#[tokio::test]
+async fn fallback_gas_oracle() {
+    let mut oracle = Fallback::new();
+    assert!(matches!(oracle.fetch().await, Err(GasOracleError::NoValues)));
+
+    oracle.add(FailingGasOracle);
+    oracle.add(FakeGasOracle { gas_price: 10.into() });
+    oracle.add(FakeGasOracle { gas_price: 20.into() });
+    // the first oracle that succeeds is used
+    assert_eq!(oracle.fetch().await.unwrap(), 10.into());
+    // the errors of all oracles are returned if all fail
+    match oracle.estimate_eip1559_fees().await {
+        Err(GasOracleError::AllOraclesFailed(errors)) => {
+            assert_eq!(errors.len(), 3);
+            assert!(matches!(errors[0], GasOracleError::InvalidResponse));
+            assert!(matches!(errors[2], GasOracleError::Eip1559EstimationNotSupported));
+        }
+        res => panic!("expected all oracles to fail, got {:?}", res),
+    }
+}
+
+#[tokio::test]
+async fn median_gas_oracle_skips_failing_oracles() {
+    let mut oracle = Median::new();
+    oracle.add(FailingGasOracle);
+    oracle.add(FakeGasOracle { gas_price: 10.into() });
+    oracle.add(FakeGasOracle { gas_price: 30.into() });
+    oracle.add(FakeGasOracle { gas_price: 20.into() });
+    assert_eq!(oracle.fetch().await.unwrap(), 20.into());
+
+    // the errors of all oracles are returned if all fail
+    match oracle.estimate_eip1559_fees().await {
+        Err(GasOracleError::AllOraclesFailed(errors)) => assert_eq!(errors.len(), 4),
+        res => panic!("expected all oracles to fail, got {:?}", res),
+    }
+}
+
 #[tokio::test]
 async fn using_gas_oracle() {
     let ganache = Ganache::new().spawn();
@@ -0,0 +1,207 @@
+//! Batching of JSON-RPC requests
+use crate::{provider::with_timeout, JsonRpcClient, Provider, ProviderError};
+
+use ethers_core::utils;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::marker::PhantomData;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use wasm_timer::Instant;
+
+/// The maximum number of requests of a [`Batch`] that are sent in a single JSON-RPC batch by
+/// default
+pub const DEFAULT_MAX_BATCH_SIZE: usize = 100;
+
+/// A JSON-RPC call and the type of its result, see [`Batch::add`]
+#[derive(Debug, Clone)]
+pub struct RpcCall<R> {
+    method: String,
+    params: Value,
+    _result: PhantomData<fn() -> R>,
+}
+
+impl<R: DeserializeOwned> RpcCall<R> {
+    /// Creates a call of the method with the given params
+    pub fn new<T: Serialize>(method: impl Into<String>, params: T) -> Self {
+        Self { method: method.into(), params: utils::serialize(&params), _result: PhantomData }
+    }
+}
+
+/// The position of a call in a [`Batch`], used to take its result from the [`BatchResponses`]
+#[derive(Debug)]
+pub struct BatchItem<R> {
+    index: usize,
+    _result: PhantomData<fn() -> R>,
+}
+
+/// Multiple JSON-RPC calls that are sent in a single round trip, where the transport supports it.
+///
+/// Batches that are larger than the [max batch size](Batch::max_batch_size) are split into
+/// multiple JSON-RPC batches.
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers_core::types::{Address, U256, U64};
+/// use ethers_providers::{Http, Provider};
+/// use std::convert::TryFrom;
+///
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+///
+/// let mut batch = provider.batch();
+/// let block = batch.add(provider.get_block_number_call());
+/// let balance = batch.add(provider.get_balance_call(Address::zero(), None));
+/// let mut responses = batch.send().await?;
+///
+/// let block: U64 = responses.get(block)?;
+/// let balance: U256 = responses.get(balance)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "batches do nothing unless sent"]
+pub struct Batch<'a, P> {
+    provider: &'a Provider<P>,
+    requests: Vec<(String, Value)>,
+    max_batch_size: usize,
+}
+
+impl<'a, P: JsonRpcClient> Batch<'a, P> {
+    /// Creates an empty batch that is sent with the provider
+    pub fn new(provider: &'a Provider<P>) -> Self {
+        Self { provider, requests: Vec::new(), max_batch_size: DEFAULT_MAX_BATCH_SIZE }
+    }
+
+    /// Sets the maximum number of requests that are sent in a single JSON-RPC batch, defaults to
+    /// [`DEFAULT_MAX_BATCH_SIZE`]
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.max_batch_size = max_batch_size.max(1);
+        self
+    }
+
+    /// Adds the call to the batch and returns the item to get its result with
+    pub fn add<R: DeserializeOwned>(&mut self, call: RpcCall<R>) -> BatchItem<R> {
+        let index = self.requests.len();
+        self.requests.push((call.method, call.params));
+        BatchItem { index, _result: PhantomData }
+    }
+
+    /// Returns the number of calls in the batch
+    pub fn len(&self) -> usize {
+        self.requests.len()
+    }
+
+    /// Returns `true` if the batch has no calls
+    pub fn is_empty(&self) -> bool {
+        self.requests.is_empty()
+    }
+
+    /// Sends all calls of the batch.
+    ///
+    /// This only fails if a request could not be sent at all, errors of individual calls are
+    /// returned by [`BatchResponses::get`].
+    pub async fn send(self) -> Result<BatchResponses, ProviderError> {
+        let mut results = Vec::with_capacity(self.requests.len());
+        for requests in self.requests.chunks(self.max_batch_size) {
+            tracing::trace!(requests = requests.len(), "sending batch");
+            let start = Instant::now();
+            let chunk = async {
+                let chunk = self.provider.as_ref().request_batch(requests).await;
+                let chunk = chunk.map_err(Into::<ProviderError>::into)?;
+                Ok(chunk.into_iter().map(|result| result.map_err(Into::into)).collect::<Vec<_>>())
+            };
+            let chunk: Result<_, ProviderError> =
+                with_timeout(chunk, self.provider.get_request_timeout()).await;
+            self.provider.report_batch(requests, start, chunk.as_deref());
+            results.extend(chunk?.into_iter().map(Some));
+        }
+        Ok(BatchResponses { results })
+    }
+}
+
+/// The results of the calls of a sent [`Batch`]
+#[derive(Debug)]
+pub struct BatchResponses {
+    results: Vec<Option<Result<Value, ProviderError>>>,
+}
+
+impl BatchResponses {
+    /// Takes the result of the call, or the error of the call if it failed.
+    ///
+    /// # Panics
+    ///
+    /// If the item was returned by another batch, which has fewer calls or whose result at the
+    /// item's position was already taken
+    pub fn get<R: DeserializeOwned>(&mut self, item: BatchItem<R>) -> Result<R, ProviderError> {
+        let result = self.results[item.index].take().expect("result of the call was already taken");
+        Ok(serde_json::from_value(result?)?)
+    }
+
+    /// Returns the number of results
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    /// Returns `true` if the batch had no calls
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{JsonRpcError, Provider};
+    use ethers_core::{
+        types::{Address, U256, U64},
+        utils,
+    };
+
+    #[tokio::test]
+    async fn can_send_batches() {
+        let (provider, mock) = Provider::mocked();
+        let mut batch = provider.batch().max_batch_size(1);
+        let block = batch.add(provider.get_block_number_call());
+        let balance = batch.add(provider.get_balance_call(Address::zero(), None));
+        assert_eq!(batch.len(), 2);
+
+        // the mock returns the last pushed response first
+        mock.push(U256::from(100)).unwrap();
+        mock.push(U64::from(7)).unwrap();
+
+        let mut responses = batch.send().await.unwrap();
+        assert_eq!(responses.get(block).unwrap(), U64::from(7));
+        assert_eq!(responses.get(balance).unwrap(), U256::from(100));
+
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request(
+            "eth_getBalance",
+            [utils::serialize(&Address::zero()), "latest".into()],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn returns_errors_of_individual_calls() {
+        let (provider, mock) = Provider::mocked();
+        let mut batch = provider.batch();
+        let block = batch.add(provider.get_block_number_call());
+        let balance = batch.add(provider.get_balance_call(Address::zero(), None));
+
+        mock.push(U256::from(100)).unwrap();
+        mock.push_error(JsonRpcError {
+            code: -32000,
+            message: "header not found".to_string(),
+            data: None,
+        });
+
+        let mut responses = batch.send().await.unwrap();
+        let err = responses.get(block).unwrap_err();
+        assert_eq!(err.as_json_rpc_error().unwrap().message, "header not found");
+        // the calls after the failed call are still sent
+        assert_eq!(responses.get(balance).unwrap(), U256::from(100));
+    }
+}
@@ -1,9 +1,8 @@
 //! [Ethereum Name Service](https://docs.ens.domains/) support
 //! Adapted from <https://github.com/hhatto/rust-ens/blob/master/src/lib.rs>
-use ethers_core::{
-    types::{Address, NameOrAddress, Selector, TransactionRequest, H160, H256},
-    utils::keccak256,
-};
+use ethers_core::types::{Address, NameOrAddress, Selector, TransactionRequest, H160};
+
+pub use ethers_core::utils::namehash;
 
 use std::convert::TryInto;
 
@@ -28,6 +27,9 @@ pub const NAME_SELECTOR: Selector = [105, 31, 52, 49];
 /// text(bytes32, string)
 pub const FIELD_SELECTOR: Selector = [89, 209, 212, 60];
 
+/// supportsInterface(bytes4)
+pub const SUPPORTS_INTERFACE_SELECTOR: Selector = [1, 255, 201, 167];
+
 /// Returns a transaction request for calling the `resolver` method on the ENS server
 pub fn get_resolver<T: Into<Address>>(ens_address: T, name: &str) -> TransactionRequest {
     // keccak256('resolver(bytes32)')
@@ -54,23 +56,26 @@ pub fn resolve<T: Into<Address>>(
     }
 }
 
+/// Returns a transaction request for calling the EIP-165 `supportsInterface` method on a resolver
+pub fn supports_interface<T: Into<Address>>(
+    resolver_address: T,
+    interface_id: Selector,
+) -> TransactionRequest {
+    let mut data = SUPPORTS_INTERFACE_SELECTOR.to_vec();
+    data.extend_from_slice(&interface_id);
+    data.extend_from_slice(&[0; 28]);
+    TransactionRequest {
+        data: Some(data.into()),
+        to: Some(NameOrAddress::Address(resolver_address.into())),
+        ..Default::default()
+    }
+}
+
 /// Returns the reverse-registrar name of an address.
 pub fn reverse_address(addr: Address) -> String {
     format!("{:?}.{}", addr, ENS_REVERSE_REGISTRAR_DOMAIN)[2..].to_string()
 }
 
-/// Returns the ENS namehash as specified in [EIP-137](https://eips.ethereum.org/EIPS/eip-137)
-pub fn namehash(name: &str) -> H256 {
-    if name.is_empty() {
-        return H256::zero()
-    }
-
-    // iterate in reverse
-    name.rsplit('.')
-        .fold([0u8; 32], |node, label| keccak256(&[node, keccak256(label.as_bytes())].concat()))
-        .into()
-}
-
 /// Returns a number in bytes form with padding to fit in 32 bytes.
 pub fn bytes_32ify(n: u64) -> Vec<u8> {
     let b = n.to_be_bytes();
@@ -91,6 +96,7 @@ pub fn parameterhash(name: &str) -> Vec<u8> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ethers_core::types::H256;
 
     fn assert_hex(hash: H256, val: &str) {
         let v = if let Some(stripped) = val.strip_prefix("0x") { stripped } else { val };
@@ -110,6 +116,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_supports_interface() {
+        let tx = supports_interface(Address::zero(), FIELD_SELECTOR);
+        assert_eq!(
+            tx.data.unwrap().to_vec(),
+            hex::decode("01ffc9a759d1d43c00000000000000000000000000000000000000000000000000000000")
+                .unwrap()
+        );
+    }
+
     #[test]
     fn test_parametershash() {
         assert_eq!(
@@ -0,0 +1,191 @@
+//! Filling the missing fields of transactions, see [`Provider::fill_transaction`]
+//!
+//! [`Provider::fill_transaction`]: crate::Provider::fill_transaction
+
+use crate::{maybe, Middleware};
+use ethers_core::types::{transaction::eip2718::TypedTransaction, BlockId, U256};
+
+/// A step of [`Provider::fill_transaction`] that sets some of the missing fields of a
+/// transaction. Fields that are already set are never changed.
+///
+/// [`Provider::fill_transaction`]: crate::Provider::fill_transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TxFillStep {
+    /// Sets `from` to the default sender of the provider, if it has one
+    Sender,
+    /// Resolves an ENS name in `to` to its address
+    Ens,
+    /// Sets `chainId` to the chain id of the node
+    ChainId,
+    /// Sets `nonce` to the transaction count of the sender, if `from` is set
+    Nonce,
+    /// Sets the `gasPrice` of legacy and EIP-2930 transactions, or the `maxFeePerGas` and
+    /// `maxPriorityFeePerGas` of EIP-1559 transactions.
+    ///
+    /// EIP-1559 transactions without any fees are converted to legacy transactions, or EIP-2930
+    /// transactions if they have an access list, if the latest block of the node has no base
+    /// fee. A transaction never has both kinds of fees set.
+    Fees,
+    /// Populates the empty access list of EIP-2930 and EIP-1559 transactions, if the access list
+    /// of `eth_createAccessList` saves gas, see [`fill_access_list`]
+    AccessList,
+    /// Sets `gas` to the estimate of `eth_estimateGas`
+    Gas,
+}
+
+/// The steps of [`Provider::fill_transaction`] in the order they are executed.
+///
+/// The [default](TxFillOrder::default) order fills the sender, ENS name, fees and gas, and leaves
+/// the nonce and chain id to the node or the signer. Populating the access list is opt-in, since
+/// it requires an additional `eth_createAccessList` request. Steps that are not part of the order
+/// are skipped.
+///
+/// # Example
+///
+/// ```
+/// use ethers_providers::{Provider, TxFillOrder, TxFillStep};
+///
+/// let (provider, _mock) = Provider::mocked();
+/// // also fill the nonce and chain id, but never estimate the gas
+/// let order = TxFillOrder::all().without(TxFillStep::Gas);
+/// let provider = provider.fill_order(order);
+/// ```
+///
+/// [`Provider::fill_transaction`]: crate::Provider::fill_transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxFillOrder {
+    steps: Vec<TxFillStep>,
+}
+
+impl Default for TxFillOrder {
+    fn default() -> Self {
+        Self::new([TxFillStep::Sender, TxFillStep::Ens, TxFillStep::Fees, TxFillStep::Gas])
+    }
+}
+
+impl TxFillOrder {
+    /// Executes the steps in the given order, steps that occur more than once are only executed
+    /// the first time
+    pub fn new(steps: impl IntoIterator<Item = TxFillStep>) -> Self {
+        let mut order = Self { steps: Vec::new() };
+        for step in steps {
+            if !order.contains(step) {
+                order.steps.push(step);
+            }
+        }
+        order
+    }
+
+    /// Executes all steps, so that the transaction is ready to be signed
+    pub fn all() -> Self {
+        Self::new([
+            TxFillStep::Sender,
+            TxFillStep::Ens,
+            TxFillStep::ChainId,
+            TxFillStep::Nonce,
+            TxFillStep::Fees,
+            TxFillStep::AccessList,
+            TxFillStep::Gas,
+        ])
+    }
+
+    /// Removes the step from the order
+    #[must_use]
+    pub fn without(mut self, step: TxFillStep) -> Self {
+        self.steps.retain(|s| *s != step);
+        self
+    }
+
+    /// Returns `true` if the step is part of the order
+    pub fn contains(&self, step: TxFillStep) -> bool {
+        self.steps.contains(&step)
+    }
+
+    /// Returns the steps in the order they are executed
+    pub fn steps(&self) -> &[TxFillStep] {
+        &self.steps
+    }
+}
+
+/// Populates the empty access list of an EIP-2930 or EIP-1559 transaction with the access list
+/// of `eth_createAccessList`, if it lowers the gas of the transaction.
+///
+/// Returns the gas of the transaction, the gas used with the access list if it was populated and
+/// the set gas or the estimate of `eth_estimateGas` otherwise. Returns `None` and leaves the
+/// transaction unchanged if it has no empty access list. Nodes that don't support
+/// `eth_createAccessList` are ignored.
+pub async fn fill_access_list<M: Middleware + ?Sized>(
+    client: &M,
+    tx: &mut TypedTransaction,
+    block: Option<BlockId>,
+) -> Result<Option<U256>, M::Error> {
+    // an empty access list marks an EIP-2930 or EIP-1559 transaction whose access list should be
+    // populated, which requires the gas of the transaction without it
+    if !tx.access_list().map_or(false, |access_list| access_list.0.is_empty()) {
+        return Ok(None)
+    }
+
+    let (gas_res, al_res) = futures_util::join!(
+        maybe(tx.gas().cloned(), client.estimate_gas(tx)),
+        client.create_access_list(tx, block)
+    );
+    let mut gas = gas_res?;
+
+    if let Ok(al_with_gas) = al_res {
+        // Set access list if it saves gas over the estimated (or previously set) value
+        if al_with_gas.gas_used < gas {
+            gas = al_with_gas.gas_used;
+            tx.set_access_list(al_with_gas.access_list);
+        }
+    }
+    Ok(Some(gas))
+}
+
+/// The fields of a transaction that were set by [`Provider::fill_transaction`]
+///
+/// [`Provider::fill_transaction`]: crate::Provider::fill_transaction
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FilledFields {
+    /// `from` was set to the default sender
+    pub from: bool,
+    /// The ENS name in `to` was resolved
+    pub to: bool,
+    /// `chainId` was set
+    pub chain_id: bool,
+    /// `nonce` was set
+    pub nonce: bool,
+    /// `gasPrice` was set
+    pub gas_price: bool,
+    /// `maxFeePerGas` was set
+    pub max_fee_per_gas: bool,
+    /// `maxPriorityFeePerGas` was set
+    pub max_priority_fee_per_gas: bool,
+    /// The access list was populated
+    pub access_list: bool,
+    /// `gas` was set
+    pub gas: bool,
+}
+
+impl FilledFields {
+    /// Returns `true` if no field was set
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_fill_order() {
+        let order = TxFillOrder::new([TxFillStep::Gas, TxFillStep::Nonce, TxFillStep::Gas]);
+        assert_eq!(order.steps(), &[TxFillStep::Gas, TxFillStep::Nonce]);
+
+        let order = TxFillOrder::all().without(TxFillStep::Gas);
+        assert!(order.contains(TxFillStep::ChainId));
+        assert!(!order.contains(TxFillStep::Gas));
+        assert!(!TxFillOrder::default().contains(TxFillStep::Nonce));
+        assert!(!TxFillOrder::default().contains(TxFillStep::AccessList));
+    }
+}
@@ -9,21 +9,32 @@ pub use transports::*;
 
 mod provider;
 
+mod batch;
+pub use batch::{Batch, BatchItem, BatchResponses, RpcCall, DEFAULT_MAX_BATCH_SIZE};
+
+mod fill;
+pub use fill::{fill_access_list, FilledFields, TxFillOrder, TxFillStep};
+
 // ENS support
 pub mod ens;
 
 mod pending_transaction;
-pub use pending_transaction::PendingTransaction;
+pub use pending_transaction::{OnReorg, PendingTransaction};
 
 mod pending_escalator;
 pub use pending_escalator::EscalatingPending;
 
 mod stream;
 pub use futures_util::StreamExt;
-pub use stream::{interval, FilterWatcher, TransactionStream, DEFAULT_POLL_INTERVAL};
+pub use stream::{
+    interval, FilterWatcher, GetTransactionError, TransactionStream, DEFAULT_POLL_INTERVAL,
+};
 
 mod pubsub;
-pub use pubsub::{PubsubClient, SubscriptionStream};
+pub use pubsub::{
+    BackfillLogStream, FullPendingTxStream, PubsubClient, SubscriptionStream,
+    DEFAULT_BACKFILL_CHUNK_SIZE,
+};
 
 pub mod erc;
 
@@ -31,10 +42,11 @@ use async_trait::async_trait;
 use auto_impl::auto_impl;
 use ethers_core::types::transaction::{eip2718::TypedTransaction, eip2930::AccessListWithGasUsed};
 use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
 use std::{error::Error, fmt::Debug, future::Future, pin::Pin};
 use url::Url;
 
-pub use provider::{FilterKind, Provider, ProviderError};
+pub use provider::{FilterKind, Provider, ProviderError, RequestEvent};
 
 // feature-enabled support for dev-rpc methods
 #[cfg(feature = "dev-rpc")]
@@ -64,6 +76,23 @@ pub trait JsonRpcClient: Debug + Send + Sync {
     where
         T: Debug + Serialize + Send + Sync,
         R: DeserializeOwned;
+
+    /// Sends the `(method, params)` requests as a single JSON-RPC batch and returns their results
+    /// in the order of the requests.
+    ///
+    /// The errors of individual requests are returned in their place, the outer error is only
+    /// returned if the batch could not be sent at all. The default implementation sends the
+    /// requests one after another, transports that support batches override this.
+    async fn request_batch(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<Result<Value, Self::Error>>, Self::Error> {
+        let mut results = Vec::with_capacity(requests.len());
+        for (method, params) in requests {
+            results.push(self.request(method, params).await);
+        }
+        Ok(results)
+    }
 }
 
 use ethers_core::types::*;
@@ -174,18 +203,15 @@ pub trait Middleware: Sync + Send + Debug {
 
     /// Fill necessary details of a transaction for dispatch
     ///
-    /// This function is defined on providers to behave as follows:
+    /// This function is defined on providers to only set the missing fields, in the
+    /// [`TxFillOrder`] of the provider. By default it behaves as follows:
     /// 1. populate the `from` field with the default sender
     /// 2. resolve any ENS names in the tx `to` field
-    /// 3. Estimate gas usage _without_ access lists
-    /// 4. Estimate gas usage _with_ access lists
-    /// 5. Enable access lists IFF they are cheaper
-    /// 6. Poll and set legacy or 1559 gas prices
-    /// 7. Set the chain_id with the provider's, if not already set
+    /// 3. Poll and set legacy or 1559 gas prices
+    /// 4. Set the gas to the estimate
     ///
-    /// It does NOT set the nonce by default.
-    /// It MAY override the gas amount set by the user, if access lists are
-    /// cheaper.
+    /// It does NOT set the nonce or chain id or populate access lists by default, see
+    /// [`Provider::fill_transaction`] and [`fill_access_list`].
     ///
     /// Middleware are encouraged to override any values _before_ delegating
     /// to the inner implementation AND/OR modify the values provided by the
@@ -328,6 +354,30 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().call(tx, block).await.map_err(FromErr::from)
     }
 
+    /// Executes the call as if the state of the accounts was overridden, e.g. their balance or
+    /// code.
+    ///
+    /// Nodes that don't support state overrides or reject the overridden fields return an error.
+    async fn call_with_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<Bytes, Self::Error> {
+        self.inner().call_with_overrides(tx, block, overrides).await.map_err(FromErr::from)
+    }
+
+    /// Estimates the gas of the transaction as if the state of the accounts was overridden, see
+    /// [`Middleware::call_with_overrides`]
+    async fn estimate_gas_with_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<U256, Self::Error> {
+        self.inner().estimate_gas_with_overrides(tx, block, overrides).await.map_err(FromErr::from)
+    }
+
     async fn syncing(&self) -> Result<SyncingStatus, Self::Error> {
         self.inner().syncing().await.map_err(FromErr::from)
     }
@@ -497,6 +547,36 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().txpool_status().await.map_err(FromErr::from)
     }
 
+    // Geth `debug` tracing support
+
+    /// Replays the transaction with the builtin `callTracer` and returns its call tree.
+    ///
+    /// The `tracer` of the options is overridden. If the node ignores the tracer and returns the
+    /// output of the default struct logger, [`GethTrace::StructLogger`] is returned.
+    async fn debug_trace_transaction_call_tracer(
+        &self,
+        tx_hash: TxHash,
+        opts: GethDebugTracingOptions,
+    ) -> Result<GethTrace<CallFrame>, Self::Error> {
+        self.inner().debug_trace_transaction_call_tracer(tx_hash, opts).await.map_err(FromErr::from)
+    }
+
+    /// Replays the transaction with the builtin `prestateTracer` and returns the state of the
+    /// accounts it touched.
+    ///
+    /// The `tracer` of the options is overridden. If the node ignores the tracer and returns the
+    /// output of the default struct logger, [`GethTrace::StructLogger`] is returned.
+    async fn debug_trace_transaction_prestate_tracer(
+        &self,
+        tx_hash: TxHash,
+        opts: GethDebugTracingOptions,
+    ) -> Result<GethTrace<PreStateFrame>, Self::Error> {
+        self.inner()
+            .debug_trace_transaction_prestate_tracer(tx_hash, opts)
+            .await
+            .map_err(FromErr::from)
+    }
+
     // Parity `trace` support
 
     /// Executes the given call and returns a number of possible traces for it
@@ -626,6 +706,11 @@ pub trait Middleware: Sync + Send + Debug {
         self.inner().subscribe_logs(filter).await.map_err(FromErr::from)
     }
 
+    /// Returns the base fees, gas used ratios and, for each of the `reward_percentiles`, the
+    /// priority fee percentiles of the `block_count` blocks up to `last_block`.
+    ///
+    /// [`FeeHistory::eip1559_estimate`] turns the history into the fees of an EIP-1559
+    /// transaction.
     async fn fee_history<T: Into<U256> + serde::Serialize + Send + Sync>(
         &self,
         block_count: T,
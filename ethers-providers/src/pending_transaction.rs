@@ -26,6 +26,10 @@ use wasm_timer::Delay;
 /// is 1, but may be adjusted with the `confirmations` method. If the transaction does not
 /// have enough confirmations or is not mined, the future will stay in the pending state.
 ///
+/// With [`require_confirmations_with_reorg_check`](Self::require_confirmations_with_reorg_check)
+/// the receipt is fetched again on every new block, so a transaction that was reorged out of its
+/// block doesn't count as confirmed.
+///
 /// # Example
 ///
 ///```
@@ -63,6 +67,21 @@ pub struct PendingTransaction<'a, P> {
     state: PendingTxState<'a>,
     interval: Box<dyn Stream<Item = ()> + Send + Unpin>,
     retries_remaining: usize,
+    /// Whether the receipt is checked for reorgs, and what to do if one happened
+    on_reorg: Option<OnReorg>,
+    /// The block at which the receipt was last checked for reorgs
+    checked_block: Option<U64>,
+}
+
+/// What a [`PendingTransaction`] does when the block of its receipt is no longer part of the
+/// canonical chain, see [`PendingTransaction::require_confirmations_with_reorg_check`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OnReorg {
+    /// Start counting the confirmations again, from the block the transaction is included in
+    /// now
+    Wait,
+    /// Fail with [`ProviderError::Reorged`]
+    Error,
 }
 
 const DEFAULT_RETRIES: usize = 3;
@@ -78,6 +97,8 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
             state: PendingTxState::InitialDelay(delay),
             interval: Box::new(interval(DEFAULT_POLL_INTERVAL)),
             retries_remaining: DEFAULT_RETRIES,
+            on_reorg: None,
+            checked_block: None,
         }
     }
 
@@ -102,6 +123,22 @@ impl<'a, P: JsonRpcClient> PendingTransaction<'a, P> {
         self
     }
 
+    /// Sets the number of confirmations for the pending transaction to resolve to a receipt, and
+    /// checks on every new block that the receipt is still included in the same block.
+    ///
+    /// If the block of the receipt was reorged out, the pending transaction either waits until
+    /// the transaction has enough confirmations in its new block, or fails, see [`OnReorg`].
+    #[must_use]
+    pub fn require_confirmations_with_reorg_check(
+        mut self,
+        confs: usize,
+        on_reorg: OnReorg,
+    ) -> Self {
+        self.confirmations = confs;
+        self.on_reorg = Some(on_reorg);
+        self
+    }
+
     /// Sets the polling interval
     #[must_use]
     pub fn interval<T: Into<Duration>>(mut self, duration: T) -> Self {
@@ -283,22 +320,83 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
                 // `is_none` check
                 let receipt = receipt.take().expect("GettingBlockNumber without receipt");
 
-                // Wait for the interval
-                let inclusion_block = receipt
-                    .block_number
-                    .expect("Receipt did not have a block number. This should never happen");
-                // if the transaction has at least K confirmations, return the receipt
-                // (subtract 1 since the tx already has 1 conf when it's mined)
-                if current_block > inclusion_block + *this.confirmations - 1 {
+                // on every new block, make sure the receipt is still included in the same block
+                if this.on_reorg.is_some() && *this.checked_block != Some(current_block) {
+                    *this.checked_block = Some(current_block);
+                    let fut = Box::pin(this.provider.get_transaction_receipt(*this.tx_hash));
+                    rewake_with_new_state!(
+                        ctx,
+                        this,
+                        PendingTxState::VerifyingReceipt(fut, Some(receipt), current_block)
+                    );
+                }
+
+                if is_confirmed(*this.tx_hash, &receipt, current_block, *this.confirmations) {
                     let receipt = Some(receipt);
                     *this.state = PendingTxState::Completed;
                     return Poll::Ready(Ok(receipt))
                 } else {
-                    tracing::trace!(tx_hash = ?this.tx_hash, "confirmations {}/{}", current_block - inclusion_block + 1, this.confirmations);
                     *this.state = PendingTxState::PausedGettingBlockNumber(Some(receipt));
                     ctx.waker().wake_by_ref();
                 }
             }
+            PendingTxState::VerifyingReceipt(fut, receipt, current_block) => {
+                let current_block = *current_block;
+                let res = futures_util::ready!(fut.as_mut().poll(ctx));
+                // This is safe since we only enter `VerifyingReceipt` from `GettingBlockNumber`
+                let receipt = receipt.take().expect("VerifyingReceipt without receipt");
+
+                let latest = match res {
+                    Ok(latest) => latest,
+                    Err(_) => {
+                        // check again with the next block number
+                        *this.checked_block = None;
+                        rewake_with_new_state!(
+                            ctx,
+                            this,
+                            PendingTxState::PausedGettingBlockNumber(Some(receipt))
+                        );
+                    }
+                };
+
+                let block_hash = receipt.block_hash.unwrap_or_default();
+                match latest {
+                    Some(latest) if latest.block_hash == receipt.block_hash => {
+                        if is_confirmed(*this.tx_hash, &latest, current_block, *this.confirmations)
+                        {
+                            *this.state = PendingTxState::Completed;
+                            return Poll::Ready(Ok(Some(latest)))
+                        }
+                        *this.state = PendingTxState::PausedGettingBlockNumber(Some(latest));
+                        ctx.waker().wake_by_ref();
+                    }
+                    _ if *this.on_reorg == Some(OnReorg::Error) => {
+                        *this.state = PendingTxState::Completed;
+                        return Poll::Ready(Err(ProviderError::Reorged {
+                            tx_hash: *this.tx_hash,
+                            block_hash,
+                        }))
+                    }
+                    latest => {
+                        tracing::debug!(
+                            "Block {:?} of pending tx {:?} was reorged",
+                            block_hash,
+                            *this.tx_hash
+                        );
+                        *this.checked_block = None;
+                        match latest {
+                            // included in another block, count the confirmations from there
+                            Some(latest) if latest.block_number.is_some() => {
+                                *this.state =
+                                    PendingTxState::PausedGettingBlockNumber(Some(latest));
+                            }
+                            // back in the mempool
+                            _ => *this.state = PendingTxState::PausedGettingTx,
+                        }
+                        ctx.waker().wake_by_ref();
+                    }
+                }
+            }
             PendingTxState::Completed => {
                 panic!("polled pending transaction future after completion")
             }
@@ -308,6 +406,31 @@ impl<'a, P: JsonRpcClient> Future for PendingTransaction<'a, P> {
     }
 }
 
+/// Returns whether the receipt has at least the given number of confirmations at the block
+fn is_confirmed(
+    tx_hash: TxHash,
+    receipt: &TransactionReceipt,
+    current_block: U64,
+    confirmations: usize,
+) -> bool {
+    let inclusion_block = receipt
+        .block_number
+        .expect("Receipt did not have a block number. This should never happen");
+    // if the transaction has at least K confirmations, return the receipt
+    // (subtract 1 since the tx already has 1 conf when it's mined)
+    if current_block > inclusion_block + confirmations - 1 {
+        true
+    } else {
+        tracing::trace!(
+            tx_hash = ?tx_hash,
+            "confirmations {}/{}",
+            current_block.saturating_sub(inclusion_block) + 1,
+            confirmations
+        );
+        false
+    }
+}
+
 impl<'a, P> fmt::Debug for PendingTransaction<'a, P> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PendingTransaction")
@@ -368,6 +491,9 @@ enum PendingTxState<'a> {
     /// Polling the blockchain for the current block number
     GettingBlockNumber(PinBoxFut<'a, U64>, Option<TransactionReceipt>),
 
+    /// Fetching the receipt again at the block number to check whether it was reorged
+    VerifyingReceipt(PinBoxFut<'a, Option<TransactionReceipt>>, Option<TransactionReceipt>, U64),
+
     /// Future has completed and should panic if polled again
     Completed,
 }
@@ -382,6 +508,7 @@ impl<'a> fmt::Debug for PendingTxState<'a> {
             PendingTxState::GettingReceipt(_) => "GettingReceipt",
             PendingTxState::GettingBlockNumber(_, _) => "GettingBlockNumber",
             PendingTxState::PausedGettingBlockNumber(_) => "PausedGettingBlockNumber",
+            PendingTxState::VerifyingReceipt(_, _, _) => "VerifyingReceipt",
             PendingTxState::CheckingReceipt(_) => "CheckingReceipt",
             PendingTxState::Completed => "Completed",
         };
@@ -389,3 +516,79 @@ impl<'a> fmt::Debug for PendingTxState<'a> {
         f.debug_struct("PendingTxState").field("state", &state).finish()
     }
 }
+
+#[cfg(test)]
+#[cfg(not(target_arch = "wasm32"))]
+mod tests {
+    use super::*;
+    use ethers_core::types::H256;
+
+    fn receipt(block: u64, hash: u8) -> TransactionReceipt {
+        TransactionReceipt {
+            block_number: Some(block.into()),
+            block_hash: Some(H256::repeat_byte(hash)),
+            ..Default::default()
+        }
+    }
+
+    /// Pushes the responses to the mock in the order they are requested
+    fn mocked(responses: Vec<serde_json::Value>) -> Provider<crate::MockProvider> {
+        let (provider, mock) = Provider::mocked();
+        // the mock returns the last pushed response first
+        for response in responses.into_iter().rev() {
+            mock.push::<serde_json::Value, _>(response).unwrap();
+        }
+        provider
+    }
+
+    fn mined_tx() -> serde_json::Value {
+        serde_json::to_value(Transaction { block_number: Some(10u64.into()), ..Default::default() })
+            .unwrap()
+    }
+
+    fn value<T: serde::Serialize>(value: T) -> serde_json::Value {
+        serde_json::to_value(value).unwrap()
+    }
+
+    #[tokio::test]
+    async fn fails_on_reorg() {
+        let provider = mocked(vec![
+            mined_tx(),
+            value(receipt(10, 1)),
+            value(U64::from(10)),
+            // the transaction is now included in another block
+            value(receipt(10, 2)),
+        ]);
+        let err = PendingTransaction::new(TxHash::zero(), &provider)
+            .interval(Duration::from_millis(1))
+            .require_confirmations_with_reorg_check(2, OnReorg::Error)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ProviderError::Reorged { block_hash, .. } if block_hash == H256::repeat_byte(1)
+        ));
+    }
+
+    #[tokio::test]
+    async fn waits_for_confirmations_after_reorg() {
+        let provider = mocked(vec![
+            mined_tx(),
+            value(receipt(10, 1)),
+            value(U64::from(10)),
+            value(receipt(11, 2)),
+            // 1 confirmation in the new block
+            value(U64::from(12)),
+            value(receipt(11, 2)),
+            value(U64::from(13)),
+            value(receipt(11, 2)),
+        ]);
+        let receipt = PendingTransaction::new(TxHash::zero(), &provider)
+            .interval(Duration::from_millis(1))
+            .require_confirmations_with_reorg_check(2, OnReorg::Wait)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(receipt.block_hash, Some(H256::repeat_byte(2)));
+    }
+}
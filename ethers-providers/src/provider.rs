@@ -1,9 +1,11 @@
 use crate::{
-    ens, erc, maybe,
-    pubsub::{PubsubClient, SubscriptionStream},
-    stream::{FilterWatcher, DEFAULT_POLL_INTERVAL},
-    FromErr, Http as HttpProvider, JsonRpcClient, JsonRpcClientWrapper, MockProvider,
-    PendingTransaction, QuorumProvider, RwClient, SyncingStatus,
+    batch::{Batch, RpcCall},
+    ens, erc, fill_access_list, maybe,
+    pubsub::{BackfillLogStream, FullPendingTxStream, PubsubClient, SubscriptionStream},
+    stream::{interval, FilterWatcher, DEFAULT_POLL_INTERVAL},
+    FilledFields, FromErr, Http as HttpProvider, HttpClientError, JsonRpcClient,
+    JsonRpcClientWrapper, JsonRpcError, MockError, MockProvider, PendingTransaction,
+    QuorumProvider, RwClient, SyncingStatus, TxFillOrder, TxFillStep,
 };
 
 #[cfg(feature = "celo")]
@@ -14,11 +16,16 @@ use async_trait::async_trait;
 use ethers_core::{
     abi::{self, Detokenize, ParamType},
     types::{
-        transaction::{eip2718::TypedTransaction, eip2930::AccessListWithGasUsed},
-        Address, Block, BlockId, BlockNumber, BlockTrace, Bytes, EIP1186ProofResponse, FeeHistory,
-        Filter, Log, NameOrAddress, Selector, Signature, Trace, TraceFilter, TraceType,
-        Transaction, TransactionReceipt, TransactionRequest, TxHash, TxpoolContent, TxpoolInspect,
-        TxpoolStatus, H256, U256, U64,
+        transaction::{
+            eip2718::TypedTransaction,
+            eip2930::{AccessListWithGasUsed, Eip2930TransactionRequest},
+        },
+        Address, Block, BlockId, BlockNumber, BlockTrace, Bytes, CallFrame, Chain,
+        EIP1186ProofResponse, FeeHistory, Filter, GethDebugTracingOptions, GethTrace, Log,
+        NameOrAddress, PreStateFrame, Selector, Signature, StateOverride, Trace, TraceFilter,
+        TraceType, Transaction, TransactionReceipt, TransactionRequest, TransactionValidationError,
+        TxHash, TxpoolContent, TxpoolInspect, TxpoolStatus, CALL_TRACER, H256, PRESTATE_TRACER,
+        U256, U64,
     },
     utils,
 };
@@ -27,10 +34,31 @@ use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use thiserror::Error;
 use url::{ParseError, Url};
 
-use futures_util::{lock::Mutex, try_join};
-use std::{convert::TryFrom, fmt::Debug, str::FromStr, sync::Arc, time::Duration};
+use futures_util::{
+    future::{self, Either},
+    lock::Mutex,
+    pin_mut, Future, StreamExt,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+use std::{
+    convert::TryFrom,
+    fmt::{self, Debug},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 use tracing::trace;
 use tracing_futures::Instrument;
+#[cfg(target_arch = "wasm32")]
+use wasm_timer::Instant;
+
+/// The number of blocks whose timestamps are sampled by [`Provider::set_interval_from_chain`] if
+/// the block time of the chain is not known
+const BLOCK_TIME_SAMPLE_SIZE: u64 = 10;
 
 #[derive(Copy, Clone)]
 pub enum NodeClient {
@@ -87,6 +115,40 @@ pub struct Provider<P> {
     /// Unsupported node client = `Some(None)`
     /// Supported node client = `Some(Some(NodeClient))`
     _node_client: Arc<Mutex<Option<NodeClient>>>,
+    /// Called after every request, see [`Provider::on_request`]
+    request_hook: Option<RequestHook>,
+    /// The steps of [`Provider::fill_transaction`]
+    fill_order: TxFillOrder,
+    /// The deadline of every request, see [`Provider::with_request_timeout`]
+    request_timeout: Option<Duration>,
+}
+
+/// A JSON-RPC call made by a [`Provider`], see [`Provider::on_request`]
+#[derive(Debug)]
+pub struct RequestEvent<'a> {
+    /// The JSON-RPC method
+    pub method: &'a str,
+    /// The sequence number of the call, assigned by the provider. This is not necessarily the
+    /// id of the JSON-RPC request that the transport sends.
+    pub id: u64,
+    /// The params of the call, serialized as JSON
+    pub params: &'a serde_json::Value,
+    /// The time until the response was received and deserialized
+    pub duration: Duration,
+    /// The result, serialized as JSON again, or the error of the call
+    pub result: Result<&'a serde_json::Value, &'a ProviderError>,
+}
+
+#[derive(Clone)]
+struct RequestHook {
+    hook: Arc<dyn Fn(&RequestEvent<'_>) + Send + Sync>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl fmt::Debug for RequestHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestHook").field("next_id", &self.next_id).finish()
+    }
 }
 
 impl<P> AsRef<P> for Provider<P> {
@@ -128,6 +190,10 @@ pub enum ProviderError {
     #[error("custom error: {0}")]
     CustomError(String),
 
+    /// The transaction would be rejected by the node, see [`TypedTransaction::validate`]
+    #[error(transparent)]
+    InvalidTransaction(#[from] TransactionValidationError),
+
     #[error("unsupported RPC")]
     UnsupportedRPC,
 
@@ -136,6 +202,44 @@ pub enum ProviderError {
 
     #[error("Attempted to sign a transaction with no available signer. Hint: did you mean to use a SignerMiddleware?")]
     SignerUnavailable,
+
+    /// The block of a pending transaction was reorged out
+    #[error("transaction {tx_hash:?} was reorged out of block {block_hash:?}")]
+    Reorged { tx_hash: TxHash, block_hash: H256 },
+
+    /// The operation did not complete within the timeout
+    #[error("timed out after {0:?}")]
+    Timeout(Duration),
+}
+
+impl ProviderError {
+    /// Returns the JSON-RPC error the node responded with, if the request failed because of one.
+    ///
+    /// Only the errors of the transports of this crate are inspected.
+    pub fn as_json_rpc_error(&self) -> Option<&JsonRpcError> {
+        let err = match self {
+            ProviderError::JsonRpcClientError(err) => err,
+            _ => return None,
+        };
+        if let Some(err) = err.downcast_ref::<JsonRpcError>() {
+            return Some(err)
+        }
+        if let Some(HttpClientError::JsonRpcError(err)) = err.downcast_ref() {
+            return Some(err)
+        }
+        if let Some(MockError::JsonRpcError(err)) = err.downcast_ref() {
+            return Some(err)
+        }
+        #[cfg(feature = "ws")]
+        if let Some(crate::WsClientError::JsonRpcError(err)) = err.downcast_ref() {
+            return Some(err)
+        }
+        #[cfg(all(feature = "ipc", any(unix, windows)))]
+        if let Some(crate::IpcError::JsonRpcError(err)) = err.downcast_ref() {
+            return Some(err)
+        }
+        None
+    }
 }
 
 /// Types of filters supported by the JSON-RPC.
@@ -161,6 +265,9 @@ impl<P: JsonRpcClient> Provider<P> {
             interval: None,
             from: None,
             _node_client: Arc::new(Mutex::new(None)),
+            request_hook: None,
+            fill_order: TxFillOrder::default(),
+            request_timeout: None,
         }
     }
 
@@ -188,11 +295,69 @@ impl<P: JsonRpcClient> Provider<P> {
         self
     }
 
+    /// Calls the hook after every JSON-RPC call of this provider, with the method, params,
+    /// duration and result of the call.
+    ///
+    /// The params and result are only serialized for the hook if one is installed.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_providers::Provider;
+    ///
+    /// let (provider, _mock) = Provider::mocked();
+    /// let provider = provider.on_request(|event| {
+    ///     println!("{} #{} took {:?}: {:?}", event.method, event.id, event.duration, event.result)
+    /// });
+    /// ```
+    #[must_use]
+    pub fn on_request<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&RequestEvent<'_>) + Send + Sync + 'static,
+    {
+        self.request_hook =
+            Some(RequestHook { hook: Arc::new(hook), next_id: Arc::new(AtomicU64::new(0)) });
+        self
+    }
+
+    /// Fails every request that takes longer than the `timeout` with [`ProviderError::Timeout`]
+    /// (default: no timeout).
+    ///
+    /// The request is cancelled by dropping it, the HTTP transport aborts the connection and the
+    /// WS and IPC transports discard the response once it arrives. Batches of requests share one
+    /// deadline per batch that is sent. Streams of subscriptions are exempt, only the
+    /// `eth_subscribe` request itself is bounded by the timeout.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_providers::Provider;
+    /// use std::time::Duration;
+    ///
+    /// let (provider, _mock) = Provider::mocked();
+    /// let provider = provider.with_request_timeout(Duration::from_secs(10));
+    /// ```
+    #[must_use]
+    pub fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the deadline of every request, if one is set
+    pub fn get_request_timeout(&self) -> Option<Duration> {
+        self.request_timeout
+    }
+
     pub async fn request<T, R>(&self, method: &str, params: T) -> Result<R, ProviderError>
     where
         T: Debug + Serialize + Send + Sync,
         R: Serialize + DeserializeOwned + Debug,
     {
+        let hook = self.request_hook.as_ref().map(|hook| {
+            let id = hook.next_id.fetch_add(1, Ordering::Relaxed);
+            (hook, id, serde_json::to_value(&params).unwrap_or_default(), Instant::now())
+        });
+
         let span =
             tracing::trace_span!("rpc", method = method, params = ?serde_json::to_string(&params)?);
         // https://docs.rs/tracing/0.1.22/tracing/span/struct.Span.html#in-asynchronous-code
@@ -202,9 +367,97 @@ impl<P: JsonRpcClient> Provider<P> {
             trace!(rx = ?serde_json::to_string(&res)?);
             Ok::<_, ProviderError>(res)
         }
-        .instrument(span)
-        .await?;
-        Ok(res)
+        .instrument(span);
+        let res = with_timeout(res, self.request_timeout).await;
+
+        if let Some((hook, id, params, start)) = hook {
+            let result = res.as_ref().map(|res| serde_json::to_value(res).unwrap_or_default());
+            (hook.hook)(&RequestEvent {
+                method,
+                id,
+                params: &params,
+                duration: start.elapsed(),
+                result: result.as_ref().map_err(|err| *err),
+            });
+        }
+        res
+    }
+
+    /// Reports the calls of a sent batch to the request hook, if one is installed
+    pub(crate) fn report_batch(
+        &self,
+        requests: &[(String, serde_json::Value)],
+        start: Instant,
+        results: Result<&[Result<serde_json::Value, ProviderError>], &ProviderError>,
+    ) {
+        let hook = match &self.request_hook {
+            Some(hook) => hook,
+            None => return,
+        };
+        let duration = start.elapsed();
+        for (idx, (method, params)) in requests.iter().enumerate() {
+            let result = match results.map(|results| results.get(idx)) {
+                Ok(Some(result)) => result.as_ref(),
+                Ok(None) => continue,
+                Err(err) => Err(err),
+            };
+            (hook.hook)(&RequestEvent {
+                method,
+                id: hook.next_id.fetch_add(1, Ordering::Relaxed),
+                params,
+                duration,
+                result,
+            });
+        }
+    }
+
+    /// Calls `debug_traceTransaction` with the given builtin tracer
+    async fn debug_trace_transaction<R>(
+        &self,
+        tx_hash: TxHash,
+        mut opts: GethDebugTracingOptions,
+        tracer: &str,
+    ) -> Result<GethTrace<R>, ProviderError>
+    where
+        R: Serialize + DeserializeOwned + Debug,
+    {
+        opts.tracer = Some(tracer.to_string());
+        let tx_hash = utils::serialize(&tx_hash);
+        let opts = utils::serialize(&opts);
+        self.request("debug_traceTransaction", [tx_hash, opts]).await
+    }
+
+    /// Returns an empty [`Batch`] of calls that are sent with this provider
+    pub fn batch(&self) -> Batch<'_, P> {
+        Batch::new(self)
+    }
+
+    /// The `eth_blockNumber` call for a [`Batch`], see [`Middleware::get_block_number`]
+    pub fn get_block_number_call(&self) -> RpcCall<U64> {
+        RpcCall::new("eth_blockNumber", ())
+    }
+
+    /// The `eth_getBalance` call for a [`Batch`], see [`Middleware::get_balance`]
+    pub fn get_balance_call(&self, from: Address, block: Option<BlockId>) -> RpcCall<U256> {
+        let block = block.unwrap_or_else(|| BlockNumber::Latest.into());
+        RpcCall::new("eth_getBalance", [utils::serialize(&from), utils::serialize(&block)])
+    }
+
+    /// The `eth_getTransactionCount` call for a [`Batch`], see
+    /// [`Middleware::get_transaction_count`]
+    pub fn get_transaction_count_call(
+        &self,
+        from: Address,
+        block: Option<BlockId>,
+    ) -> RpcCall<U256> {
+        let block = block.unwrap_or_else(|| BlockNumber::Latest.into());
+        RpcCall::new("eth_getTransactionCount", [utils::serialize(&from), utils::serialize(&block)])
+    }
+
+    /// The `eth_getCode` call for a [`Batch`], see [`Middleware::get_code`]
+    pub fn get_code_call(&self, at: Address, block: Option<BlockId>) -> RpcCall<Bytes> {
+        let block = block.unwrap_or_else(|| BlockNumber::Latest.into());
+        RpcCall::new("eth_getCode", [utils::serialize(&at), utils::serialize(&block)])
     }
 
     async fn get_block_gen<Tx: Default + Serialize + DeserializeOwned + Debug>(
@@ -273,68 +526,7 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         tx: &mut TypedTransaction,
         block: Option<BlockId>,
     ) -> Result<(), Self::Error> {
-        if let Some(default_sender) = self.default_sender() {
-            if tx.from().is_none() {
-                tx.set_from(default_sender);
-            }
-        }
-
-        // TODO: Join the name resolution and gas price future
-
-        // set the ENS name
-        if let Some(NameOrAddress::Name(ref ens_name)) = tx.to() {
-            let addr = self.resolve_name(ens_name).await?;
-            tx.set_to(addr);
-        }
-
-        // fill gas price
-        match tx {
-            TypedTransaction::Eip2930(_) | TypedTransaction::Legacy(_) => {
-                let gas_price = maybe(tx.gas_price(), self.get_gas_price()).await?;
-                tx.set_gas_price(gas_price);
-            }
-            TypedTransaction::Eip1559(ref mut inner) => {
-                if inner.max_fee_per_gas.is_none() || inner.max_priority_fee_per_gas.is_none() {
-                    let (max_fee_per_gas, max_priority_fee_per_gas) =
-                        self.estimate_eip1559_fees(None).await?;
-                    inner.max_fee_per_gas = Some(max_fee_per_gas);
-                    inner.max_priority_fee_per_gas = Some(max_priority_fee_per_gas);
-                };
-            }
-        }
-
-        // If the tx has an access list but it is empty, it is an Eip1559 or Eip2930 tx,
-        // and we attempt to populate the acccess list. This may require `eth_estimateGas`,
-        // in which case we save the result in maybe_gas_res for later
-        let mut maybe_gas = None;
-        if let Some(starting_al) = tx.access_list() {
-            if starting_al.0.is_empty() {
-                let (gas_res, al_res) = futures_util::join!(
-                    maybe(tx.gas().cloned(), self.estimate_gas(tx)),
-                    self.create_access_list(tx, block)
-                );
-                let mut gas = gas_res?;
-
-                if let Ok(al_with_gas) = al_res {
-                    // Set access list if it saves gas over the estimated (or previously set) value
-                    if al_with_gas.gas_used < gas {
-                        // Update the gas estimate with the lower amount
-                        gas = al_with_gas.gas_used;
-                        tx.set_access_list(al_with_gas.access_list);
-                    }
-                }
-                maybe_gas = Some(gas);
-            }
-        }
-
-        // Set gas to estimated value only if it was not set by the caller,
-        // even if the access list has been populated and saves gas
-        if tx.gas().is_none() {
-            let gas_estimate = maybe(maybe_gas, self.estimate_gas(tx)).await?;
-            tx.set_gas(gas_estimate);
-        }
-
-        Ok(())
+        Provider::fill_transaction(self, tx, block).await.map(drop)
     }
 
     /// Gets the latest block number via the `eth_BlockNumber` API
@@ -558,6 +750,30 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
         self.request("eth_call", [tx, block]).await
     }
 
+    async fn call_with_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<Bytes, ProviderError> {
+        let tx = utils::serialize(tx);
+        let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
+        let overrides = utils::serialize(overrides);
+        self.request("eth_call", [tx, block, overrides]).await
+    }
+
+    async fn estimate_gas_with_overrides(
+        &self,
+        tx: &TypedTransaction,
+        block: Option<BlockId>,
+        overrides: &StateOverride,
+    ) -> Result<U256, ProviderError> {
+        let tx = utils::serialize(tx);
+        let block = utils::serialize(&block.unwrap_or_else(|| BlockNumber::Latest.into()));
+        let overrides = utils::serialize(overrides);
+        self.request("eth_estimateGas", [tx, block, overrides]).await
+    }
+
     /// Sends a transaction to a single Ethereum node and return the estimated amount of gas
     /// required (as a U256) to send it This is free, but only an estimate. Providing too little
     /// gas will result in a transaction being rejected (while still consuming all provided
@@ -578,6 +794,10 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
 
     /// Sends the transaction to the entire Ethereum network and returns the transaction's hash
     /// This will consume gas from the account that signed the transaction.
+    ///
+    /// The transaction is validated before it is sent, see [`TypedTransaction::validate`]. If the
+    /// transaction has a chain id, this costs an additional `eth_chainId` request to check that it
+    /// matches the chain of the node.
     async fn send_transaction<T: Into<TypedTransaction> + Send + Sync>(
         &self,
         tx: T,
@@ -585,6 +805,11 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     ) -> Result<PendingTransaction<'_, P>, ProviderError> {
         let mut tx = tx.into();
         self.fill_transaction(&mut tx, block).await?;
+        match tx.chain_id() {
+            // the node signs the transaction, so it must be for the chain of the node
+            Some(_) => tx.validate_for_chain(self.get_chainid().await?.as_u64())?,
+            None => tx.validate()?,
+        }
         let tx_hash = self.request("eth_sendTransaction", [tx]).await?;
 
         Ok(PendingTransaction::new(tx_hash, self).interval(self.get_interval()))
@@ -792,14 +1017,18 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     }
 
     /// Returns the ENS name the `address` resolves to (or None if not configured).
+    ///
+    /// The name of the reverse record is resolved again, and
+    /// [`ProviderError::EnsNotOwned`] is returned if it doesn't resolve to the `address`, since
+    /// anyone can set the reverse record of their address to any name. See
+    /// [`Provider::lookup_address_unverified`] for the name of the reverse record alone.
+    ///
     /// # Panics
     ///
     /// If the bytes returned from the ENS registrar/resolver cannot be interpreted as
     /// a string. This should theoretically never happen.
     async fn lookup_address(&self, address: Address) -> Result<String, ProviderError> {
-        let ens_name = ens::reverse_address(address);
-        let domain: String =
-            self.query_resolver(ParamType::String, &ens_name, ens::NAME_SELECTOR).await?;
+        let domain = self.lookup_address_unverified(address).await?;
         let reverse_address = self.resolve_name(&domain).await?;
         if address != reverse_address {
             Err(ProviderError::EnsNotOwned(domain))
@@ -828,62 +1057,8 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     /// If the bytes returned from the ENS registrar/resolver cannot be interpreted as
     /// a string. This should theoretically never happen.
     async fn resolve_avatar(&self, ens_name: &str) -> Result<Url, ProviderError> {
-        let (field, owner) =
-            try_join!(self.resolve_field(ens_name, "avatar"), self.resolve_name(ens_name))?;
-        let url = Url::from_str(&field).map_err(|e| ProviderError::CustomError(e.to_string()))?;
-        match url.scheme() {
-            "https" | "data" => Ok(url),
-            "ipfs" => erc::http_link_ipfs(url).map_err(ProviderError::CustomError),
-            "eip155" => {
-                let token =
-                    erc::ERCNFT::from_str(url.path()).map_err(ProviderError::CustomError)?;
-                match token.type_ {
-                    erc::ERCNFTType::ERC721 => {
-                        let tx = TransactionRequest {
-                            data: Some(
-                                [&erc::ERC721_OWNER_SELECTOR[..], &token.id].concat().into(),
-                            ),
-                            to: Some(NameOrAddress::Address(token.contract)),
-                            ..Default::default()
-                        };
-                        let data = self.call(&tx.into(), None).await?;
-                        if decode_bytes::<Address>(ParamType::Address, data) != owner {
-                            return Err(ProviderError::CustomError("Incorrect owner.".to_string()))
-                        }
-                    }
-                    erc::ERCNFTType::ERC1155 => {
-                        let tx = TransactionRequest {
-                            data: Some(
-                                [
-                                    &erc::ERC1155_BALANCE_SELECTOR[..],
-                                    &[0x0; 12],
-                                    &owner.0,
-                                    &token.id,
-                                ]
-                                .concat()
-                                .into(),
-                            ),
-                            to: Some(NameOrAddress::Address(token.contract)),
-                            ..Default::default()
-                        };
-                        let data = self.call(&tx.into(), None).await?;
-                        if decode_bytes::<u64>(ParamType::Uint(64), data) == 0 {
-                            return Err(ProviderError::CustomError("Incorrect balance.".to_string()))
-                        }
-                    }
-                }
-
-                let image_url = self.resolve_nft(token).await?;
-                match image_url.scheme() {
-                    "https" | "data" => Ok(image_url),
-                    "ipfs" => erc::http_link_ipfs(image_url).map_err(ProviderError::CustomError),
-                    _ => Err(ProviderError::CustomError(
-                        "Unsupported scheme for the image".to_string(),
-                    )),
-                }
-            }
-            _ => Err(ProviderError::CustomError("Unsupported scheme".to_string())),
-        }
+        let field = self.resolve_field(ens_name, "avatar").await?;
+        self.avatar_url(ens_name, &field).await
     }
 
     /// Returns the URL (not necesserily HTTP) of the image behind a token.
@@ -948,22 +1123,50 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     /// Returns the details of all transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_content)
+    ///
+    /// Fails with [`ProviderError::UnsupportedRPC`] if the node doesn't implement the `txpool`
+    /// namespace.
     async fn txpool_content(&self) -> Result<TxpoolContent, ProviderError> {
-        self.request("txpool_content", ()).await
+        self.request("txpool_content", ()).await.map_err(unsupported_rpc)
     }
 
     /// Returns a summary of all the transactions currently pending for inclusion in the next
     /// block(s), as well as the ones that are being scheduled for future execution only.
     /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_inspect)
+    ///
+    /// Fails with [`ProviderError::UnsupportedRPC`] if the node doesn't implement the `txpool`
+    /// namespace.
     async fn txpool_inspect(&self) -> Result<TxpoolInspect, ProviderError> {
-        self.request("txpool_inspect", ()).await
+        self.request("txpool_inspect", ()).await.map_err(unsupported_rpc)
     }
 
     /// Returns the number of transactions currently pending for inclusion in the next block(s), as
     /// well as the ones that are being scheduled for future execution only.
     /// Ref: [Here](https://geth.ethereum.org/docs/rpc/ns-txpool#txpool_status)
+    ///
+    /// Fails with [`ProviderError::UnsupportedRPC`] if the node doesn't implement the `txpool`
+    /// namespace.
     async fn txpool_status(&self) -> Result<TxpoolStatus, ProviderError> {
-        self.request("txpool_status", ()).await
+        self.request("txpool_status", ()).await.map_err(unsupported_rpc)
+    }
+
+    /// Replays the transaction with the builtin `callTracer` and returns its call tree
+    async fn debug_trace_transaction_call_tracer(
+        &self,
+        tx_hash: TxHash,
+        opts: GethDebugTracingOptions,
+    ) -> Result<GethTrace<CallFrame>, ProviderError> {
+        self.debug_trace_transaction(tx_hash, opts, CALL_TRACER).await
+    }
+
+    /// Replays the transaction with the builtin `prestateTracer` and returns the state of the
+    /// accounts it touched
+    async fn debug_trace_transaction_prestate_tracer(
+        &self,
+        tx_hash: TxHash,
+        opts: GethDebugTracingOptions,
+    ) -> Result<GethTrace<PreStateFrame>, ProviderError> {
+        self.debug_trace_transaction(tx_hash, opts, PRESTATE_TRACER).await
     }
 
     /// Executes the given call and returns a number of possible traces for it
@@ -1115,26 +1318,193 @@ impl<P: JsonRpcClient> Middleware for Provider<P> {
     ) -> Result<FeeHistory, Self::Error> {
         let block_count = block_count.into();
         let last_block = utils::serialize(&last_block);
-        let reward_percentiles = utils::serialize(&reward_percentiles);
+        let reward_percentiles_json = utils::serialize(&reward_percentiles);
 
         // The blockCount param is expected to be an unsigned integer up to geth v1.10.6.
         // Geth v1.10.7 onwards, this has been updated to a hex encoded form. Failure to
         // decode the param from client side would fallback to the old API spec.
-        self.request(
-            "eth_feeHistory",
-            [utils::serialize(&block_count), last_block.clone(), reward_percentiles.clone()],
-        )
-        .await
-        .or(self
+        let history: FeeHistory = match self
             .request(
                 "eth_feeHistory",
-                [utils::serialize(&block_count.as_u64()), last_block, reward_percentiles],
+                [
+                    utils::serialize(&block_count),
+                    last_block.clone(),
+                    reward_percentiles_json.clone(),
+                ],
             )
-            .await)
+            .await
+        {
+            Ok(history) => history,
+            Err(_) => {
+                self.request(
+                    "eth_feeHistory",
+                    [utils::serialize(&block_count.as_u64()), last_block, reward_percentiles_json],
+                )
+                .await?
+            }
+        };
+        check_fee_history_rewards(&history, reward_percentiles)?;
+        Ok(history)
     }
 }
 
 impl<P: JsonRpcClient> Provider<P> {
+    /// Returns the name of the reverse record of the `address`, without checking that the name
+    /// resolves to the `address`.
+    ///
+    /// The reverse record is set by the owner of the address and can claim any name, use
+    /// [`Middleware::lookup_address`] to only accept names that resolve to the address.
+    pub async fn lookup_address_unverified(
+        &self,
+        address: Address,
+    ) -> Result<String, ProviderError> {
+        let ens_name = ens::reverse_address(address);
+        self.query_resolver(ParamType::String, &ens_name, ens::NAME_SELECTOR).await
+    }
+
+    /// Returns the text record `key` of the `ens_name`, e.g. `email`, `url` or `com.twitter`.
+    ///
+    /// Unlike [`Middleware::resolve_field`], returns `None` if the name has no resolver, the
+    /// resolver doesn't support text records according to EIP-165, or the record is not set.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use ethers_providers::{Provider, Http as HttpProvider};
+    /// # use std::convert::TryFrom;
+    /// # #[tokio::main(flavor = "current_thread")]
+    /// # async fn main() {
+    /// # let provider = Provider::<HttpProvider>::try_from("https://mainnet.infura.io/v3/c60b0bb42f8a4c6481ecd229eddaca27").unwrap();
+    /// let twitter = provider.resolve_text("vitalik.eth", "com.twitter").await.unwrap();
+    /// # }
+    /// ```
+    pub async fn resolve_text(
+        &self,
+        ens_name: &str,
+        key: &str,
+    ) -> Result<Option<String>, ProviderError> {
+        let resolver = match self.get_ens_resolver(ens_name).await? {
+            Some(resolver) => resolver,
+            None => return Ok(None),
+        };
+        // resolvers that predate EIP-165 revert or return nothing, which also means that text
+        // records are missing
+        let supported = match self
+            .call(&ens::supports_interface(resolver, ens::FIELD_SELECTOR).into(), None)
+            .await
+        {
+            Ok(data) => abi::decode(&[ParamType::Bool], data.as_ref())
+                .ok()
+                .and_then(|tokens| bool::from_tokens(tokens).ok())
+                .unwrap_or_default(),
+            Err(err) if is_revert(&err) => false,
+            Err(err) => return Err(err),
+        };
+        if !supported {
+            return Ok(None)
+        }
+
+        let data = self
+            .call(
+                &ens::resolve(
+                    resolver,
+                    ens::FIELD_SELECTOR,
+                    ens_name,
+                    Some(&ens::parameterhash(key)),
+                )
+                .into(),
+                None,
+            )
+            .await?;
+        let text = abi::decode(&[ParamType::String], data.as_ref())
+            .ok()
+            .and_then(|tokens| String::from_tokens(tokens).ok());
+        Ok(text.filter(|text| !text.is_empty()))
+    }
+
+    /// Returns the HTTP link of the avatar of the `ens_name`, like
+    /// [`Middleware::resolve_avatar`], or `None` if the name has no `avatar` text record, see
+    /// [`Self::resolve_text`].
+    ///
+    /// NFT avatars (`eip155:1/erc721:<contract>/<id>`) are resolved to the image of the token,
+    /// if the token is owned by the address of the name.
+    pub async fn try_resolve_avatar(&self, ens_name: &str) -> Result<Option<Url>, ProviderError> {
+        match self.resolve_text(ens_name, "avatar").await? {
+            Some(avatar) => self.avatar_url(ens_name, &avatar).await.map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the resolver of the `ens_name`, or `None` if it has none
+    async fn get_ens_resolver(&self, ens_name: &str) -> Result<Option<Address>, ProviderError> {
+        let ens_addr = self.ens.unwrap_or(ens::ENS_ADDRESS);
+        let data = self.call(&ens::get_resolver(ens_addr, ens_name).into(), None).await?;
+        if data.0.is_empty() {
+            return Ok(None)
+        }
+        let resolver: Address = decode_bytes(ParamType::Address, data);
+        Ok(if resolver == Address::zero() { None } else { Some(resolver) })
+    }
+
+    /// Interprets the `avatar` text record of the `ens_name` as an HTTP link, see
+    /// [`Middleware::resolve_avatar`]
+    async fn avatar_url(&self, ens_name: &str, avatar: &str) -> Result<Url, ProviderError> {
+        let url = Url::from_str(avatar).map_err(|e| ProviderError::CustomError(e.to_string()))?;
+        match url.scheme() {
+            "https" | "data" => Ok(url),
+            "ipfs" => erc::http_link_ipfs(url).map_err(ProviderError::CustomError),
+            "eip155" => {
+                let token =
+                    erc::ERCNFT::from_str(url.path()).map_err(ProviderError::CustomError)?;
+                let owner = self.resolve_name(ens_name).await?;
+                match token.type_ {
+                    erc::ERCNFTType::ERC721 => {
+                        let tx = TransactionRequest {
+                            data: Some(
+                                [&erc::ERC721_OWNER_SELECTOR[..], &token.id].concat().into(),
+                            ),
+                            to: Some(NameOrAddress::Address(token.contract)),
+                            ..Default::default()
+                        };
+                        let data = self.call(&tx.into(), None).await?;
+                        if decode_bytes::<Address>(ParamType::Address, data) != owner {
+                            return Err(ProviderError::CustomError("Incorrect owner.".to_string()))
+                        }
+                    }
+                    erc::ERCNFTType::ERC1155 => {
+                        let tx = TransactionRequest {
+                            data: Some(
+                                [
+                                    &erc::ERC1155_BALANCE_SELECTOR[..],
+                                    &[0x0; 12],
+                                    &owner.0,
+                                    &token.id,
+                                ]
+                                .concat()
+                                .into(),
+                            ),
+                            to: Some(NameOrAddress::Address(token.contract)),
+                            ..Default::default()
+                        };
+                        let data = self.call(&tx.into(), None).await?;
+                        if decode_bytes::<u64>(ParamType::Uint(64), data) == 0 {
+                            return Err(ProviderError::CustomError("Incorrect balance.".to_string()))
+                        }
+                    }
+                }
+
+                let image_url = self.resolve_nft(token).await?;
+                match image_url.scheme() {
+                    "https" | "data" => Ok(image_url),
+                    "ipfs" => erc::http_link_ipfs(image_url).map_err(ProviderError::CustomError),
+                    _ => Err(ProviderError::CustomError(
+                        "Unsupported scheme for the image".to_string(),
+                    )),
+                }
+            }
+            _ => Err(ProviderError::CustomError("Unsupported scheme".to_string())),
+        }
+    }
+
     async fn query_resolver<T: Detokenize>(
         &self,
         param: ParamType,
@@ -1205,6 +1575,313 @@ impl<P: JsonRpcClient> Provider<P> {
     pub fn get_interval(&self) -> Duration {
         self.interval.unwrap_or(DEFAULT_POLL_INTERVAL)
     }
+
+    /// Sets the steps and their order of [`Provider::fill_transaction`]
+    /// (default: [`TxFillOrder::default`])
+    #[must_use]
+    pub fn fill_order(mut self, fill_order: TxFillOrder) -> Self {
+        self.fill_order = fill_order;
+        self
+    }
+
+    /// Sets the missing fields of the transaction with the steps of the [`TxFillOrder`] of the
+    /// provider and returns which fields were set. Fields that are already set are never
+    /// changed.
+    ///
+    /// This is what [`Middleware::fill_transaction`] does for providers, e.g. before sending a
+    /// transaction.
+    pub async fn fill_transaction(
+        &self,
+        tx: &mut TypedTransaction,
+        block: Option<BlockId>,
+    ) -> Result<FilledFields, ProviderError> {
+        let mut filled = FilledFields::default();
+        // the gas estimate of the access list step, which is reused by the gas step
+        let mut estimated_gas = None;
+
+        for step in self.fill_order.steps() {
+            match step {
+                TxFillStep::Sender => {
+                    if tx.from().is_none() {
+                        if let Some(sender) = self.from {
+                            tx.set_from(sender);
+                            filled.from = true;
+                        }
+                    }
+                }
+                TxFillStep::Ens => {
+                    if let Some(NameOrAddress::Name(ref ens_name)) = tx.to() {
+                        let addr = self.resolve_name(ens_name).await?;
+                        tx.set_to(addr);
+                        filled.to = true;
+                    }
+                }
+                TxFillStep::ChainId => {
+                    if tx.chain_id().is_none() {
+                        tx.set_chain_id(self.get_chainid().await?.as_u64());
+                        filled.chain_id = true;
+                    }
+                }
+                TxFillStep::Nonce => {
+                    if tx.nonce().is_none() {
+                        if let Some(from) = tx.from().copied() {
+                            tx.set_nonce(self.get_transaction_count(from, block).await?);
+                            filled.nonce = true;
+                        }
+                    }
+                }
+                TxFillStep::Fees => self.fill_fees(tx, &mut filled).await?,
+                TxFillStep::AccessList => {
+                    if let Some(gas) = fill_access_list(self, tx, block).await? {
+                        filled.access_list =
+                            tx.access_list().map_or(false, |access_list| !access_list.0.is_empty());
+                        estimated_gas = Some(gas);
+                    }
+                }
+                TxFillStep::Gas => {
+                    if tx.gas().is_none() {
+                        tx.set_gas(maybe(estimated_gas, self.estimate_gas(tx)).await?);
+                        filled.gas = true;
+                    }
+                }
+            }
+        }
+
+        Ok(filled)
+    }
+
+    /// Sets the missing fees of the transaction, see [`TxFillStep::Fees`]
+    async fn fill_fees(
+        &self,
+        tx: &mut TypedTransaction,
+        filled: &mut FilledFields,
+    ) -> Result<(), ProviderError> {
+        match tx {
+            TypedTransaction::Eip2930(_) | TypedTransaction::Legacy(_) => {
+                if tx.gas_price().is_none() {
+                    tx.set_gas_price(self.get_gas_price().await?);
+                    filled.gas_price = true;
+                }
+            }
+            TypedTransaction::Eip1559(ref mut inner) => {
+                if inner.max_fee_per_gas.is_some() && inner.max_priority_fee_per_gas.is_some() {
+                    return Ok(())
+                }
+
+                let base_fee_per_gas = self
+                    .get_block(BlockNumber::Latest)
+                    .await?
+                    .ok_or_else(|| ProviderError::CustomError("Latest block not found".into()))?
+                    .base_fee_per_gas;
+                let base_fee_per_gas = match base_fee_per_gas {
+                    Some(base_fee_per_gas) => base_fee_per_gas,
+                    None if inner.max_fee_per_gas.is_none() &&
+                        inner.max_priority_fee_per_gas.is_none() =>
+                    {
+                        // EIP-1559 is not activated, fall back to a legacy gas price
+                        let access_list = std::mem::take(&mut inner.access_list);
+                        let legacy = TransactionRequest::from(inner.clone());
+                        *tx = if access_list.0.is_empty() {
+                            legacy.into()
+                        } else {
+                            Eip2930TransactionRequest::new(legacy, access_list).into()
+                        };
+                        tx.set_gas_price(self.get_gas_price().await?);
+                        filled.gas_price = true;
+                        return Ok(())
+                    }
+                    None => return Err(ProviderError::CustomError("EIP-1559 not activated".into())),
+                };
+
+                let fee_history = self
+                    .fee_history(
+                        utils::EIP1559_FEE_ESTIMATION_PAST_BLOCKS,
+                        BlockNumber::Latest,
+                        &[utils::EIP1559_FEE_ESTIMATION_REWARD_PERCENTILE],
+                    )
+                    .await?;
+                let (max_fee_per_gas, max_priority_fee_per_gas) =
+                    utils::eip1559_default_estimator(base_fee_per_gas, fee_history.reward);
+
+                if inner.max_fee_per_gas.is_none() {
+                    inner.max_fee_per_gas = Some(max_fee_per_gas);
+                    filled.max_fee_per_gas = true;
+                }
+                if inner.max_priority_fee_per_gas.is_none() {
+                    // the priority fee must not exceed a max fee set by the user
+                    let max_fee_per_gas = inner.max_fee_per_gas.unwrap_or(max_fee_per_gas);
+                    inner.max_priority_fee_per_gas =
+                        Some(max_priority_fee_per_gas.min(max_fee_per_gas));
+                    filled.max_priority_fee_per_gas = true;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Sets the polling interval for event filters and pending transactions to half of the
+    /// average block time of the connected chain and returns it.
+    ///
+    /// The block time of known chains is taken from [`Chain::average_blocktime_hint`], for other
+    /// chains it is estimated from the timestamps of the latest blocks. The interval is not
+    /// changed if the block time can not be determined, e.g. on a fresh dev chain.
+    pub async fn set_interval_from_chain(&mut self) -> Result<Duration, ProviderError> {
+        let chain = Chain::try_from(self.get_chainid().await?).ok();
+        let block_time = match chain.and_then(|chain| chain.average_blocktime_hint()) {
+            Some(block_time) => Some(block_time),
+            None => self.sample_block_time().await?,
+        };
+        if let Some(block_time) = block_time {
+            self.interval = Some(block_time / 2);
+        }
+        Ok(self.get_interval())
+    }
+
+    /// Returns the average block time of the latest [`BLOCK_TIME_SAMPLE_SIZE`] blocks, or `None`
+    /// if there are no blocks or their timestamps do not increase
+    async fn sample_block_time(&self) -> Result<Option<Duration>, ProviderError> {
+        let latest = match self.get_block(BlockNumber::Latest).await? {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+        let number = latest.number.unwrap_or_default().as_u64();
+        let samples = number.min(BLOCK_TIME_SAMPLE_SIZE);
+        if samples == 0 {
+            return Ok(None)
+        }
+        let earlier = match self.get_block(number - samples).await? {
+            Some(block) => block,
+            None => return Ok(None),
+        };
+
+        let elapsed = latest.timestamp.saturating_sub(earlier.timestamp).low_u64();
+        if elapsed == 0 {
+            return Ok(None)
+        }
+        Ok(Some(Duration::from_millis(elapsed * 1000 / samples)))
+    }
+
+    /// Returns the pending block with its full transactions, the block that the node would mine
+    /// next.
+    ///
+    /// The pending transactions are the same [`Transaction`] type as mined transactions, without
+    /// the block hash, block number and transaction index. The hash, nonce and miner of the
+    /// pending block are `None` or zero.
+    pub async fn get_pending_block(&self) -> Result<Option<Block<Transaction>>, ProviderError> {
+        self.get_block_with_txs(BlockNumber::Pending).await
+    }
+
+    /// Waits until the chain reaches the block `number` and returns the block, polling the block
+    /// number at the [interval](Self::get_interval) of the provider.
+    ///
+    /// Returns immediately if the chain is already past the block. Fails with
+    /// [`ProviderError::Timeout`] if the block isn't reached within the `timeout`.
+    ///
+    /// Pubsub providers can wait for new heads instead, see
+    /// [`Self::wait_for_block_with_subscription`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use ethers_providers::{Provider, Http};
+    /// # use std::{convert::TryFrom, time::Duration};
+    /// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let provider = Provider::<Http>::try_from("http://localhost:8545")?;
+    /// let block = provider.wait_for_block(15_000_000u64, Some(Duration::from_secs(60))).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_block(
+        &self,
+        number: impl Into<U64>,
+        timeout: Option<Duration>,
+    ) -> Result<Block<TxHash>, ProviderError> {
+        with_timeout(self.poll_for_block(number.into()), timeout).await
+    }
+
+    /// Polls the block number until the chain reaches the block
+    async fn poll_for_block(&self, number: U64) -> Result<Block<TxHash>, ProviderError> {
+        let mut ticks = interval(self.get_interval());
+        loop {
+            if let Some(block) = self.get_block_if_reached(number).await? {
+                return Ok(block)
+            }
+            ticks.next().await;
+        }
+    }
+
+    /// Returns the block if the chain already reached it
+    async fn get_block_if_reached(
+        &self,
+        number: U64,
+    ) -> Result<Option<Block<TxHash>>, ProviderError> {
+        if self.get_block_number().await? < number {
+            return Ok(None)
+        }
+        self.get_block(number).await
+    }
+}
+
+/// Ensures that the history has rewards only if percentiles were requested, and then one reward
+/// per percentile for every block
+fn check_fee_history_rewards(
+    history: &FeeHistory,
+    reward_percentiles: &[f64],
+) -> Result<(), ProviderError> {
+    let valid = if reward_percentiles.is_empty() {
+        history.reward.is_empty()
+    } else {
+        history.reward.len() == history.gas_used_ratio.len() &&
+            history.reward.iter().all(|reward| reward.len() == reward_percentiles.len())
+    };
+    if valid {
+        Ok(())
+    } else {
+        Err(ProviderError::CustomError(format!(
+            "eth_feeHistory returned {} rewards for {} blocks and {} reward percentiles",
+            history.reward.len(),
+            history.gas_used_ratio.len(),
+            reward_percentiles.len()
+        )))
+    }
+}
+
+/// Returns `true` if the error is the JSON-RPC error of a reverted call
+fn is_revert(err: &ProviderError) -> bool {
+    // EIP-1474 reserves code 3 for reverts, but most nodes use the generic -32000 or -32603
+    err.as_json_rpc_error()
+        .map_or(false, |err| err.code == 3 || err.message.to_lowercase().contains("revert"))
+}
+
+/// Maps the error of a node that doesn't implement the method to [`ProviderError::UnsupportedRPC`]
+fn unsupported_rpc(err: ProviderError) -> ProviderError {
+    // -32601 is the JSON-RPC error code of unknown methods
+    if err.as_json_rpc_error().map_or(false, |err| err.code == -32601) {
+        return ProviderError::UnsupportedRPC
+    }
+    err
+}
+
+/// Runs the future to completion, or fails with [`ProviderError::Timeout`] if it takes longer than
+/// the `timeout`
+pub(crate) async fn with_timeout<T>(
+    fut: impl Future<Output = Result<T, ProviderError>>,
+    timeout: Option<Duration>,
+) -> Result<T, ProviderError> {
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return fut.await,
+    };
+    #[cfg(not(target_arch = "wasm32"))]
+    let delay = futures_timer::Delay::new(timeout);
+    #[cfg(target_arch = "wasm32")]
+    let delay = wasm_timer::Delay::new(timeout);
+
+    pin_mut!(fut);
+    match future::select(fut, delay).await {
+        Either::Left((res, _)) => res,
+        Either::Right(_) => Err(ProviderError::Timeout(timeout)),
+    }
 }
 
 #[cfg(feature = "ws")]
@@ -1226,7 +1903,7 @@ impl Provider<crate::Ws> {
     }
 }
 
-#[cfg(all(target_family = "unix", feature = "ipc"))]
+#[cfg(all(feature = "ipc", any(unix, windows)))]
 impl Provider<crate::Ipc> {
     /// Direct connection to an IPC socket.
     pub async fn connect_ipc(path: impl AsRef<std::path::Path>) -> Result<Self, ProviderError> {
@@ -1248,6 +1925,90 @@ where
     }
 }
 
+impl<P: PubsubClient> Provider<P> {
+    /// Streams all logs of the filter from `from_block` on, first the past logs up to the current
+    /// block, then the logs of a live subscription.
+    ///
+    /// The subscription is installed before the past logs are requested, so that no logs are
+    /// missed in between. Logs that are returned by both are only yielded once. The past logs are
+    /// requested in chunks of [`DEFAULT_BACKFILL_CHUNK_SIZE`](crate::DEFAULT_BACKFILL_CHUNK_SIZE)
+    /// blocks, which are halved whenever the node rejects a chunk because it contains too many
+    /// logs. The block range of the filter is ignored.
+    pub async fn subscribe_logs_with_backfill(
+        &self,
+        filter: &Filter,
+        from_block: impl Into<U64>,
+    ) -> Result<BackfillLogStream<'_>, ProviderError> {
+        let start_head = self.get_block_number().await?;
+        let live = self.subscribe_logs(filter).await?;
+        let head = self.get_block_number().await?;
+        Ok(BackfillLogStream::new(self, filter.clone(), live, from_block.into(), start_head, head))
+    }
+
+    /// Streams the full bodies of pending transactions.
+    ///
+    /// Subscribes to `newPendingTransactions` with full transactions, which is supported by geth.
+    /// If the node rejects the subscription, falls back to subscribing to the hashes and fetching
+    /// the body of each transaction, with no more than `max_concurrent` requests at a time.
+    ///
+    /// Call [`FullPendingTxStream::dedup`] to skip transactions that were already yielded or
+    /// mined in the meantime.
+    pub async fn subscribe_full_pending_txs(
+        &self,
+        max_concurrent: usize,
+    ) -> Result<FullPendingTxStream<'_>, ProviderError> {
+        let notifications = match self.subscribe(("newPendingTransactions", true)).await {
+            Ok(notifications) => notifications,
+            Err(err) => {
+                trace!(
+                    "full newPendingTransactions subscription failed, fetching by hash: {:?}",
+                    err
+                );
+                self.subscribe(["newPendingTransactions"]).await?
+            }
+        };
+        Ok(FullPendingTxStream::new(self, notifications, max_concurrent))
+    }
+
+    /// Waits until the chain reaches the block `number` and returns the block, like
+    /// [`Self::wait_for_block`], but waits for new heads of a `newHeads` subscription.
+    ///
+    /// Falls back to polling the block number if the node rejects the subscription or the
+    /// subscription ends.
+    pub async fn wait_for_block_with_subscription(
+        &self,
+        number: impl Into<U64>,
+        timeout: Option<Duration>,
+    ) -> Result<Block<TxHash>, ProviderError> {
+        with_timeout(self.subscribe_for_block(number.into()), timeout).await
+    }
+
+    /// Waits for new heads until the chain reaches the block
+    async fn subscribe_for_block(&self, number: U64) -> Result<Block<TxHash>, ProviderError> {
+        let mut heads = match self.subscribe_blocks().await {
+            Ok(heads) => heads,
+            Err(err) => {
+                trace!("newHeads subscription failed, polling instead: {:?}", err);
+                return self.poll_for_block(number).await
+            }
+        };
+        // subscribed first, so that no head is missed if the block isn't reached yet
+        if let Some(block) = self.get_block_if_reached(number).await? {
+            return Ok(block)
+        }
+        while let Some(head) = heads.next().await {
+            if head.number.map_or(false, |head| head >= number) {
+                // heads don't include the transactions, and could be past the block
+                if let Some(block) = self.get_block(number).await? {
+                    return Ok(block)
+                }
+            }
+        }
+        trace!("newHeads subscription ended, polling instead");
+        self.poll_for_block(number).await
+    }
+}
+
 impl<T: JsonRpcClientWrapper> Provider<QuorumProvider<T>> {
     /// Provider that uses a quorum
     pub fn quorum(inner: QuorumProvider<T>) -> Self {
@@ -1735,7 +2496,15 @@ mod tests {
 
     #[tokio::test]
     async fn test_fill_transaction_1559() {
-        let (mut provider, mock) = Provider::mocked();
+        let (provider, mock) = Provider::mocked();
+        // populating the access list is opt-in
+        let mut provider = provider.fill_order(TxFillOrder::new([
+            TxFillStep::Sender,
+            TxFillStep::Ens,
+            TxFillStep::Fees,
+            TxFillStep::AccessList,
+            TxFillStep::Gas,
+        ]));
         provider.from = Some("0x6fC21092DA55B392b045eD78F4732bff3C580e2c".parse().unwrap());
 
         let gas = U256::from(21000_usize);
@@ -1869,4 +2638,436 @@ mod tests {
         assert_eq!(tx.gas_price(), Some(gas_price));
         assert!(tx.access_list().is_none());
     }
+
+    #[tokio::test]
+    async fn fill_transaction_returns_filled_fields() {
+        let from = Address::repeat_byte(1);
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.with_sender(from).fill_order(TxFillOrder::all());
+
+        let mut tx = TransactionRequest::new().gas(21000).into();
+        mock.push(U256::from(50)).unwrap();
+        mock.push(U256::from(7)).unwrap();
+        mock.push(U256::from(1)).unwrap();
+        let filled = provider.fill_transaction(&mut tx, None).await.unwrap();
+
+        assert_eq!(
+            filled,
+            FilledFields {
+                from: true,
+                chain_id: true,
+                nonce: true,
+                gas_price: true,
+                ..Default::default()
+            }
+        );
+        assert_eq!(tx.chain_id(), Some(1u64.into()));
+        assert_eq!(tx.nonce(), Some(&7.into()));
+        assert_eq!(tx.gas_price(), Some(50.into()));
+        assert_eq!(tx.gas(), Some(&21000.into()));
+
+        mock.assert_request("eth_chainId", ()).unwrap();
+        mock.assert_request("eth_getTransactionCount", [format!("{:?}", from), "latest".into()])
+            .unwrap();
+        mock.assert_request("eth_gasPrice", ()).unwrap();
+
+        // filling again sets nothing
+        assert!(provider.fill_transaction(&mut tx, None).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn fill_transaction_falls_back_to_legacy_fees() {
+        let (provider, mock) = Provider::mocked();
+        let mut tx = Eip1559TransactionRequest::new().gas(21000).into();
+
+        // the latest block has no base fee
+        mock.push(U256::from(50)).unwrap();
+        mock.push(Block::<TxHash>::default()).unwrap();
+        let filled = provider.fill_transaction(&mut tx, None).await.unwrap();
+
+        assert_eq!(filled, FilledFields { gas_price: true, ..Default::default() });
+        assert!(matches!(tx, TypedTransaction::Legacy(_)));
+        assert_eq!(tx.gas_price(), Some(50.into()));
+    }
+
+    #[tokio::test]
+    async fn calls_request_hook() {
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.on_request({
+            let events = events.clone();
+            move |event| {
+                events.lock().unwrap().push((
+                    event.method.to_string(),
+                    event.id,
+                    event.params.clone(),
+                    event.result.map(Clone::clone).map_err(ToString::to_string),
+                ))
+            }
+        });
+
+        mock.push(U64::from(7)).unwrap();
+        provider.get_block_number().await.unwrap();
+        provider.get_balance(Address::zero(), None).await.unwrap_err();
+
+        let events = events.lock().unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(
+            events[0],
+            ("eth_blockNumber".to_string(), 0, serde_json::Value::Null, Ok("0x7".into()))
+        );
+        assert_eq!(events[1].0, "eth_getBalance");
+        assert_eq!(events[1].1, 1);
+        assert_eq!(events[1].2, serde_json::json!([Address::zero(), "latest"]));
+        assert!(events[1].3.is_err());
+    }
+
+    #[tokio::test]
+    async fn can_trace_transaction_with_call_tracer() {
+        let (provider, mock) = Provider::mocked();
+        let frame: CallFrame = serde_json::from_value(serde_json::json!({
+            "type": "CALL",
+            "from": Address::zero(),
+            "to": Address::repeat_byte(1),
+            "gas": "0x5208",
+            "gasUsed": "0x5208",
+            "input": "0x",
+        }))
+        .unwrap();
+        mock.push(frame.clone()).unwrap();
+
+        let tx_hash = H256::repeat_byte(2);
+        let opts =
+            GethDebugTracingOptions { timeout: Some("10s".to_string()), ..Default::default() };
+        let trace = provider.debug_trace_transaction_call_tracer(tx_hash, opts).await.unwrap();
+        assert_eq!(trace, GethTrace::Tracer(frame));
+
+        mock.assert_request(
+            "debug_traceTransaction",
+            [
+                utils::serialize(&tx_hash),
+                serde_json::json!({ "tracer": "callTracer", "timeout": "10s" }),
+            ],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn can_call_with_state_overrides() {
+        let (provider, mock) = Provider::mocked();
+        let from = Address::repeat_byte(1);
+        let tx: TypedTransaction =
+            TransactionRequest::new().from(from).to(Address::repeat_byte(2)).into();
+        let overrides = StateOverride::new().balance(from, 100u64);
+
+        // the mock returns the last pushed response first
+        mock.push(U256::from(21000)).unwrap();
+        mock.push(Bytes::from(vec![1])).unwrap();
+
+        let res = provider.call_with_overrides(&tx, None, &overrides).await.unwrap();
+        assert_eq!(res, Bytes::from(vec![1]));
+        let gas = provider
+            .estimate_gas_with_overrides(&tx, Some(BlockNumber::Pending.into()), &overrides)
+            .await
+            .unwrap();
+        assert_eq!(gas, U256::from(21000));
+
+        let overrides_json = serde_json::json!({ format!("{:?}", from): { "balance": "0x64" } });
+        mock.assert_request(
+            "eth_call",
+            [utils::serialize(&tx), "latest".into(), overrides_json.clone()],
+        )
+        .unwrap();
+        mock.assert_request(
+            "eth_estimateGas",
+            [utils::serialize(&tx), "pending".into(), overrides_json],
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn can_set_interval_from_chain() {
+        let (mut provider, mock) = Provider::mocked();
+        mock.push(U256::from(137)).unwrap();
+        let interval = provider.set_interval_from_chain().await.unwrap();
+        assert_eq!(interval, Duration::from_millis(1_050));
+        assert_eq!(provider.get_interval(), interval);
+
+        // unknown chains sample the timestamps of the latest blocks
+        let block = |number: u64, timestamp: u64| Block::<TxHash> {
+            number: Some(number.into()),
+            timestamp: timestamp.into(),
+            ..Default::default()
+        };
+        mock.push(block(90, 1_000)).unwrap();
+        mock.push(block(100, 1_040)).unwrap();
+        mock.push(U256::from(31337)).unwrap();
+        let interval = provider.set_interval_from_chain().await.unwrap();
+        assert_eq!(interval, Duration::from_millis(2_000));
+
+        mock.assert_request("eth_chainId", ()).unwrap();
+        mock.assert_request("eth_chainId", ()).unwrap();
+        mock.assert_request("eth_getBlockByNumber", serde_json::json!(["latest", false])).unwrap();
+        mock.assert_request("eth_getBlockByNumber", serde_json::json!(["0x5a", false])).unwrap();
+    }
+
+    #[tokio::test]
+    async fn can_wait_for_block() {
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.interval(Duration::from_millis(1));
+        let block = Block::<TxHash> { number: Some(10u64.into()), ..Default::default() };
+
+        mock.push(block.clone()).unwrap();
+        mock.push(U64::from(10)).unwrap();
+        mock.push(U64::from(9)).unwrap();
+        assert_eq!(provider.wait_for_block(10u64, None).await.unwrap(), block);
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getBlockByNumber", serde_json::json!(["0xa", false])).unwrap();
+
+        // the chain is already past the block
+        mock.push(block.clone()).unwrap();
+        mock.push(U64::from(12)).unwrap();
+        assert_eq!(provider.wait_for_block(10u64, None).await.unwrap(), block);
+        mock.assert_request("eth_blockNumber", ()).unwrap();
+        mock.assert_request("eth_getBlockByNumber", serde_json::json!(["0xa", false])).unwrap();
+
+        let provider = provider.interval(Duration::from_millis(50));
+        for _ in 0..10 {
+            mock.push(U64::from(9)).unwrap();
+        }
+        let timeout = Duration::from_millis(10);
+        let err = provider.wait_for_block(10u64, Some(timeout)).await.unwrap_err();
+        assert!(matches!(err, ProviderError::Timeout(t) if t == timeout));
+    }
+
+    #[tokio::test]
+    async fn can_get_pending_block() {
+        let (provider, mock) = Provider::mocked();
+        let block = Block::<Transaction> { number: Some(11u64.into()), ..Default::default() };
+        mock.push(block.clone()).unwrap();
+        assert_eq!(provider.get_pending_block().await.unwrap(), Some(block));
+        mock.assert_request("eth_getBlockByNumber", serde_json::json!(["pending", true])).unwrap();
+    }
+
+    #[tokio::test]
+    async fn checks_fee_history_rewards() {
+        let (provider, mock) = Provider::mocked();
+        let history: FeeHistory = serde_json::from_value(serde_json::json!({
+            "oldestBlock": "0x1",
+            "baseFeePerGas": ["0x3b9aca00", "0x3b9aca00"],
+            "gasUsedRatio": [0.5],
+            "reward": [["0x1"]]
+        }))
+        .unwrap();
+
+        mock.push(history.clone()).unwrap();
+        let res = provider.fee_history(1u64, BlockNumber::Latest, &[50.0]).await.unwrap();
+        assert_eq!(res, history);
+        mock.assert_request("eth_feeHistory", (U256::from(1), "latest", [50.0])).unwrap();
+        // the request is only sent once if it succeeds
+        assert!(mock.assert_request("eth_feeHistory", ()).is_err());
+
+        // the request is sent again with the legacy block count if the first one fails
+        mock.push(history.clone()).unwrap();
+        mock.push_error(JsonRpcError {
+            code: -32602,
+            message: "invalid argument 0: json: cannot unmarshal string into Go value of type int"
+                .to_string(),
+            data: None,
+        });
+        let res = provider.fee_history(1u64, BlockNumber::Latest, &[50.0]).await.unwrap();
+        assert_eq!(res, history);
+        mock.assert_request("eth_feeHistory", (U256::from(1), "latest", [50.0])).unwrap();
+        mock.assert_request("eth_feeHistory", (1u64, "latest", [50.0])).unwrap();
+
+        // the rewards must match the requested percentiles
+        mock.push(history).unwrap();
+        let err = provider.fee_history(1u64, BlockNumber::Latest, &[]).await.unwrap_err();
+        assert!(matches!(err, ProviderError::CustomError(_)));
+    }
+
+    #[tokio::test]
+    async fn pins_reads_to_block_hash() {
+        let (provider, mock) = Provider::mocked();
+        let hash = H256::repeat_byte(1);
+        let block = serde_json::json!({ "blockHash": format!("{:?}", hash) });
+        let address = Address::from_low_u64_be(1);
+
+        mock.push(Bytes::default()).unwrap();
+        let tx: TypedTransaction = TransactionRequest::new().to(address).into();
+        provider.call(&tx, Some(hash.into())).await.unwrap();
+        mock.assert_request("eth_call", (&tx, &block)).unwrap();
+
+        mock.push(U256::zero()).unwrap();
+        provider.get_balance(address, Some(hash.into())).await.unwrap();
+        mock.assert_request("eth_getBalance", (address, &block)).unwrap();
+
+        mock.push(Bytes::default()).unwrap();
+        provider.get_code(address, Some(hash.into())).await.unwrap();
+        mock.assert_request("eth_getCode", (address, &block)).unwrap();
+
+        mock.push(H256::zero()).unwrap();
+        provider.get_storage_at(address, H256::zero(), Some(hash.into())).await.unwrap();
+        mock.assert_request("eth_getStorageAt", (address, H256::zero(), &block)).unwrap();
+    }
+
+    #[tokio::test]
+    async fn verifies_reverse_records() {
+        let (provider, mock) = Provider::mocked();
+        let encode = |token| Bytes::from(abi::encode(&[token]));
+        let address = Address::from_low_u64_be(2);
+        let resolver = encode(abi::Token::Address(Address::from_low_u64_be(1)));
+        let name = encode(abi::Token::String("ethers.eth".to_string()));
+        // returns the reverse record and then the forward resolution of the name
+        let push = |forward: Address| {
+            mock.push(encode(abi::Token::Address(forward))).unwrap();
+            mock.push(resolver.clone()).unwrap();
+            mock.push(name.clone()).unwrap();
+            mock.push(resolver.clone()).unwrap();
+        };
+
+        push(address);
+        assert_eq!(provider.lookup_address(address).await.unwrap(), "ethers.eth");
+
+        // the name resolves to another address
+        push(Address::from_low_u64_be(3));
+        let err = provider.lookup_address(address).await.unwrap_err();
+        assert!(matches!(err, ProviderError::EnsNotOwned(name) if name == "ethers.eth"));
+
+        mock.push(name.clone()).unwrap();
+        mock.push(resolver.clone()).unwrap();
+        assert_eq!(provider.lookup_address_unverified(address).await.unwrap(), "ethers.eth");
+    }
+
+    #[tokio::test]
+    async fn resolves_text_records() {
+        let (provider, mock) = Provider::mocked();
+        let encode = |token| Bytes::from(abi::encode(&[token]));
+        let resolver = encode(abi::Token::Address(Address::from_low_u64_be(1)));
+
+        mock.push(encode(abi::Token::String("@ethers".to_string()))).unwrap();
+        mock.push(encode(abi::Token::Bool(true))).unwrap();
+        mock.push(resolver.clone()).unwrap();
+        let text = provider.resolve_text("ethers.eth", "com.twitter").await.unwrap();
+        assert_eq!(text.as_deref(), Some("@ethers"));
+
+        // the resolver doesn't support text records
+        mock.push(encode(abi::Token::Bool(false))).unwrap();
+        mock.push(resolver.clone()).unwrap();
+        assert_eq!(provider.resolve_text("ethers.eth", "com.twitter").await.unwrap(), None);
+
+        // the resolver predates EIP-165
+        mock.push_error(JsonRpcError {
+            code: -32000,
+            message: "execution reverted".to_string(),
+            data: None,
+        });
+        mock.push(resolver.clone()).unwrap();
+        assert_eq!(provider.resolve_text("ethers.eth", "com.twitter").await.unwrap(), None);
+
+        // other errors are not mistaken for missing text records
+        mock.push_error(JsonRpcError {
+            code: 429,
+            message: "rate limited".to_string(),
+            data: None,
+        });
+        mock.push(resolver).unwrap();
+        let err = provider.resolve_text("ethers.eth", "com.twitter").await.unwrap_err();
+        assert_eq!(err.as_json_rpc_error().map(|err| err.code), Some(429));
+
+        // the name has no resolver
+        mock.push(encode(abi::Token::Address(Address::zero()))).unwrap();
+        assert_eq!(provider.try_resolve_avatar("ethers.eth").await.unwrap(), None);
+    }
+
+    /// A transport that never answers
+    #[derive(Debug)]
+    struct StalledClient;
+
+    #[async_trait]
+    impl JsonRpcClient for StalledClient {
+        type Error = crate::MockError;
+
+        async fn request<T, R>(&self, _method: &str, _params: T) -> Result<R, Self::Error>
+        where
+            T: Debug + Serialize + Send + Sync,
+            R: DeserializeOwned,
+        {
+            futures_util::future::pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn times_out_requests() {
+        let timeout = Duration::from_millis(10);
+        let provider = Provider::new(StalledClient).with_request_timeout(timeout);
+        assert_eq!(provider.get_request_timeout(), Some(timeout));
+
+        let err = provider.get_block_number().await.unwrap_err();
+        assert!(matches!(err, ProviderError::Timeout(t) if t == timeout));
+
+        let mut batch = provider.batch();
+        let _block = batch.add(provider.get_block_number_call());
+        let err = batch.send().await.unwrap_err();
+        assert!(matches!(err, ProviderError::Timeout(t) if t == timeout));
+
+        let (provider, mock) = Provider::mocked();
+        let provider = provider.with_request_timeout(timeout);
+        mock.push(U64::from(7)).unwrap();
+        assert_eq!(provider.get_block_number().await.unwrap(), U64::from(7));
+    }
+
+    #[test]
+    fn maps_unknown_methods_to_unsupported_rpc() {
+        let err = JsonRpcError {
+            code: -32601,
+            message: "the method txpool_content does not exist/is not available".to_string(),
+            data: None,
+        };
+        let err = unsupported_rpc(ProviderError::JsonRpcClientError(Box::new(err)));
+        assert!(matches!(err, ProviderError::UnsupportedRPC));
+
+        let err = JsonRpcError { code: -32000, message: "busy".to_string(), data: None };
+        let err = unsupported_rpc(ProviderError::JsonRpcClientError(Box::new(err)));
+        assert!(matches!(err, ProviderError::JsonRpcClientError(_)));
+
+        // only the error code is checked, not the message
+        let err = JsonRpcError { code: -32000, message: "code: -32601".to_string(), data: None };
+        let err = unsupported_rpc(ProviderError::JsonRpcClientError(Box::new(err)));
+        assert!(matches!(err, ProviderError::JsonRpcClientError(_)));
+        let err = MockError::JsonRpcError(JsonRpcError {
+            code: -32601,
+            message: "method not found".to_string(),
+            data: None,
+        });
+        let err = unsupported_rpc(ProviderError::JsonRpcClientError(Box::new(err)));
+        assert!(matches!(err, ProviderError::UnsupportedRPC));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_transactions() {
+        let (provider, mock) = Provider::mocked();
+        let tx = TransactionRequest::new()
+            .from(Address::repeat_byte(1))
+            .to(Address::repeat_byte(2))
+            .gas_price(1)
+            .gas(0);
+        let err = provider.send_transaction(tx.clone(), None).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ProviderError::InvalidTransaction(TransactionValidationError::ZeroGasLimit)
+        ));
+
+        // the chain id must match the chain of the node
+        mock.push(U256::from(1)).unwrap();
+        let err = provider.send_transaction(tx.gas(21000).chain_id(5), None).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ProviderError::InvalidTransaction(TransactionValidationError::ChainIdMismatch { .. })
+        ));
+        mock.assert_request("eth_chainId", ()).unwrap();
+        // nothing was sent
+        assert!(mock.assert_request("eth_sendTransaction", ()).is_err());
+    }
 }
@@ -1,12 +1,15 @@
-use crate::{JsonRpcClient, Middleware, Provider, TransactionStream};
+use crate::{
+    GetTransactionError, JsonRpcClient, Middleware, Provider, ProviderError, TransactionStream,
+};
 
-use ethers_core::types::{TxHash, U256};
+use ethers_core::types::{Filter, Log, Transaction, TxHash, H256, U256, U64};
 
-use futures_util::stream::Stream;
+use futures_util::stream::{Stream, StreamExt};
 use pin_project::{pin_project, pinned_drop};
-use serde::de::DeserializeOwned;
+use serde::{de::DeserializeOwned, Deserialize};
 use serde_json::value::RawValue;
 use std::{
+    collections::{HashSet, VecDeque},
     marker::PhantomData,
     pin::Pin,
     task::{Context, Poll},
@@ -113,3 +116,493 @@ where
         TransactionStream::new(self.provider, self, n)
     }
 }
+
+/// The number of blocks that are requested with a single `eth_getLogs` by
+/// [`Provider::subscribe_logs_with_backfill`], until the node rejects a range with too many logs
+pub const DEFAULT_BACKFILL_CHUNK_SIZE: u64 = 2_000;
+
+#[cfg(target_arch = "wasm32")]
+type LogStream<'a> = Pin<Box<dyn Stream<Item = Result<Log, ProviderError>> + 'a>>;
+#[cfg(not(target_arch = "wasm32"))]
+type LogStream<'a> = Pin<Box<dyn Stream<Item = Result<Log, ProviderError>> + Send + 'a>>;
+
+/// Streams the past logs of a filter followed by the logs of a live subscription, see
+/// [`Provider::subscribe_logs_with_backfill`].
+///
+/// The stream ends after the first error.
+#[must_use = "subscriptions do nothing unless you stream them"]
+pub struct BackfillLogStream<'a> {
+    inner: LogStream<'a>,
+}
+
+impl<'a> BackfillLogStream<'a> {
+    pub(crate) fn new<P: PubsubClient>(
+        provider: &'a Provider<P>,
+        filter: Filter,
+        live: SubscriptionStream<'a, P, Log>,
+        from_block: U64,
+        start_head: U64,
+        head: U64,
+    ) -> Self {
+        let backfill = Backfill {
+            provider,
+            filter,
+            live,
+            next_block: from_block,
+            start_head,
+            head,
+            chunk_size: DEFAULT_BACKFILL_CHUNK_SIZE,
+            buffered: VecDeque::new(),
+            seen: HashSet::new(),
+        };
+        let inner = futures_util::stream::unfold(Some(backfill), |backfill| async move {
+            let mut backfill = backfill?;
+            match backfill.next().await? {
+                Ok(log) => Some((Ok(log), Some(backfill))),
+                Err(err) => Some((Err(err), None)),
+            }
+        });
+        Self { inner: Box::pin(inner) }
+    }
+}
+
+impl<'a> Stream for BackfillLogStream<'a> {
+    type Item = Result<Log, ProviderError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(ctx)
+    }
+}
+
+struct Backfill<'a, P: PubsubClient> {
+    provider: &'a Provider<P>,
+    filter: Filter,
+    live: SubscriptionStream<'a, P, Log>,
+    /// The next block to request the logs of
+    next_block: U64,
+    /// The block number before the subscription was installed, logs from this block on may also
+    /// be sent by the subscription
+    start_head: U64,
+    /// The last block that is backfilled
+    head: U64,
+    chunk_size: u64,
+    /// Logs that were fetched but not yet yielded
+    buffered: VecDeque<Log>,
+    /// The `(block_hash, log_index)` of the backfilled logs that may be sent by the subscription
+    seen: HashSet<(H256, U256)>,
+}
+
+impl<'a, P: PubsubClient> Backfill<'a, P> {
+    async fn next(&mut self) -> Option<Result<Log, ProviderError>> {
+        loop {
+            if let Some(log) = self.buffered.pop_front() {
+                return Some(Ok(log))
+            }
+
+            if self.next_block > self.head {
+                // backfilled up to the head, hand off to the subscription
+                let log = self.live.next().await?;
+                if self.was_backfilled(&log) {
+                    continue
+                }
+                return Some(Ok(log))
+            }
+
+            let to_block = std::cmp::min(self.next_block + self.chunk_size - 1, self.head);
+            let filter = self.filter.clone().from_block(self.next_block).to_block(to_block);
+            match self.provider.get_logs(&filter).await {
+                Ok(logs) => {
+                    for log in &logs {
+                        if log.block_number.map(|num| num >= self.start_head).unwrap_or_default() {
+                            self.seen.extend(log_key(log));
+                        }
+                    }
+                    self.buffered.extend(logs);
+                    self.next_block = to_block + 1;
+                }
+                Err(err) if self.chunk_size > 1 && is_too_many_logs(&err) => {
+                    self.chunk_size /= 2;
+                    tracing::trace!(
+                        "too many logs in blocks {}..={}, retrying with {} blocks",
+                        self.next_block,
+                        to_block,
+                        self.chunk_size
+                    );
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+    }
+
+    /// Whether the log of the subscription was already yielded by the backfill
+    fn was_backfilled(&self, log: &Log) -> bool {
+        if log.removed == Some(true) ||
+            log.block_number.map(|num| num > self.head).unwrap_or_default()
+        {
+            return false
+        }
+        log_key(log).map(|key| self.seen.contains(&key)).unwrap_or_default()
+    }
+}
+
+/// The number of transaction hashes [`FullPendingTxStream::dedup`] remembers
+const DEDUP_CAPACITY: usize = 10_000;
+
+#[cfg(target_arch = "wasm32")]
+type PendingTxStream<'a> =
+    Pin<Box<dyn Stream<Item = Result<Transaction, GetTransactionError>> + 'a>>;
+#[cfg(not(target_arch = "wasm32"))]
+type PendingTxStream<'a> =
+    Pin<Box<dyn Stream<Item = Result<Transaction, GetTransactionError>> + Send + 'a>>;
+
+/// A notification of a `newPendingTransactions` subscription, nodes send either the hash or the
+/// full transaction
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+pub(crate) enum PendingTxNotification {
+    Hash(TxHash),
+    Full(Box<Transaction>),
+}
+
+/// Streams the full bodies of pending transactions, see [`Provider::subscribe_full_pending_txs`].
+///
+/// Transactions that were only announced by their hash are fetched with
+/// `eth_getTransactionByHash`, and may be yielded out of order. Errors of these requests are
+/// yielded and don't end the stream.
+#[must_use = "subscriptions do nothing unless you stream them"]
+pub struct FullPendingTxStream<'a> {
+    inner: PendingTxStream<'a>,
+}
+
+impl<'a> FullPendingTxStream<'a> {
+    pub(crate) fn new<P: PubsubClient>(
+        provider: &'a Provider<P>,
+        notifications: SubscriptionStream<'a, P, PendingTxNotification>,
+        max_concurrent: usize,
+    ) -> Self {
+        let inner = notifications
+            .map(move |notification| async move {
+                match notification {
+                    PendingTxNotification::Full(tx) => Ok(*tx),
+                    PendingTxNotification::Hash(hash) => match provider.get_transaction(hash).await
+                    {
+                        Ok(Some(tx)) => Ok(tx),
+                        Ok(None) => Err(GetTransactionError::NotFound(hash)),
+                        Err(err) => Err(GetTransactionError::ProviderError(hash, err)),
+                    },
+                }
+            })
+            .buffer_unordered(max_concurrent.max(1));
+        Self { inner: Box::pin(inner) }
+    }
+
+    /// Skips transactions that were already yielded, and transactions that were mined by the time
+    /// their body was fetched.
+    ///
+    /// Only the most recent 10,000 hashes are remembered.
+    pub fn dedup(self) -> Self {
+        let mut seen = HashSet::new();
+        let mut order = VecDeque::new();
+        let inner = self.inner.filter(move |res| {
+            let keep = match res {
+                Ok(tx) if tx.block_number.is_some() => false,
+                Ok(tx) => {
+                    if seen.insert(tx.hash) {
+                        order.push_back(tx.hash);
+                        if order.len() > DEDUP_CAPACITY {
+                            if let Some(hash) = order.pop_front() {
+                                seen.remove(&hash);
+                            }
+                        }
+                        true
+                    } else {
+                        false
+                    }
+                }
+                Err(_) => true,
+            };
+            futures_util::future::ready(keep)
+        });
+        Self { inner: Box::pin(inner) }
+    }
+}
+
+impl<'a> Stream for FullPendingTxStream<'a> {
+    type Item = Result<Transaction, GetTransactionError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, ctx: &mut Context) -> Poll<Option<Self::Item>> {
+        self.inner.poll_next_unpin(ctx)
+    }
+}
+
+fn log_key(log: &Log) -> Option<(H256, U256)> {
+    Some((log.block_hash?, log.log_index?))
+}
+
+/// Whether the node rejected `eth_getLogs` because the range contains too many logs, e.g.
+/// `query returned more than 10000 results`
+fn is_too_many_logs(err: &ProviderError) -> bool {
+    let err = err.to_string();
+    err.contains("query returned more than") || err.contains("Log response size exceeded")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{JsonRpcError, MockError, MockProvider};
+    use serde::Serialize;
+    use std::{fmt::Debug, sync::Mutex};
+
+    /// A mocked transport whose subscriptions yield the pushed notifications
+    #[derive(Debug, Default)]
+    struct MockPubsub {
+        mock: MockProvider,
+        notifications: Mutex<Vec<Box<RawValue>>>,
+    }
+
+    impl MockPubsub {
+        fn push_notification<T: Serialize>(&self, notification: &T) {
+            let notification = RawValue::from_string(serde_json::to_string(notification).unwrap());
+            self.notifications.lock().unwrap().push(notification.unwrap());
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", async_trait::async_trait(?Send))]
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait::async_trait)]
+    impl JsonRpcClient for MockPubsub {
+        type Error = MockError;
+
+        async fn request<T, R>(&self, method: &str, params: T) -> Result<R, MockError>
+        where
+            T: Debug + Serialize + Send + Sync,
+            R: DeserializeOwned,
+        {
+            self.mock.request(method, params).await
+        }
+    }
+
+    impl PubsubClient for MockPubsub {
+        type NotificationStream = futures_util::stream::Iter<std::vec::IntoIter<Box<RawValue>>>;
+
+        fn subscribe<T: Into<U256>>(&self, _: T) -> Result<Self::NotificationStream, MockError> {
+            let notifications = std::mem::take(&mut *self.notifications.lock().unwrap());
+            Ok(futures_util::stream::iter(notifications))
+        }
+
+        fn unsubscribe<T: Into<U256>>(&self, _: T) -> Result<(), MockError> {
+            Ok(())
+        }
+    }
+
+    fn log(block: u64, index: u64) -> Log {
+        Log {
+            block_hash: Some(H256::from_low_u64_be(block)),
+            block_number: Some(block.into()),
+            log_index: Some(index.into()),
+            ..Default::default()
+        }
+    }
+
+    fn rpc_error(message: &str) -> JsonRpcError {
+        JsonRpcError { code: -32005, message: message.to_string(), data: None }
+    }
+
+    #[test]
+    fn detects_too_many_logs() {
+        let rpc_error =
+            |message: &str| ProviderError::from(crate::HttpClientError::from(rpc_error(message)));
+        assert!(is_too_many_logs(&rpc_error("query returned more than 10000 results")));
+        assert!(is_too_many_logs(&rpc_error(
+            "Log response size exceeded. You can make eth_getLogs requests with up to a 2K block range"
+        )));
+        assert!(!is_too_many_logs(&rpc_error("header not found")));
+    }
+
+    #[test]
+    fn deserializes_pending_tx_notification() {
+        let hash = "0x5e2bd9b4ac8f6a76bdd1d0e2e4a5a4d3f3ec51b75f5e1b8cd0c8b69b6e1c7e4a";
+        let notification: PendingTxNotification =
+            serde_json::from_str(&format!("\"{}\"", hash)).unwrap();
+        assert!(
+            matches!(notification, PendingTxNotification::Hash(h) if h == hash.parse().unwrap())
+        );
+
+        let tx = Transaction { hash: hash.parse().unwrap(), ..Default::default() };
+        let notification: PendingTxNotification =
+            serde_json::from_value(serde_json::to_value(&tx).unwrap()).unwrap();
+        match notification {
+            PendingTxNotification::Full(full) => assert_eq!(*full, tx),
+            PendingTxNotification::Hash(_) => panic!("expected the full transaction"),
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn backfills_logs_before_live_logs() {
+        let provider = Provider::new(MockPubsub::default());
+        let transport = provider.as_ref();
+        // the subscription resends the logs of the blocks since it was installed
+        transport.push_notification(&log(5, 0));
+        transport.push_notification(&log(6, 1));
+        transport.push_notification(&log(6, 2));
+        transport.push_notification(&log(7, 0));
+        // removed logs are always yielded
+        transport.push_notification(&Log { removed: Some(true), ..log(6, 1) });
+
+        // responses are popped from the back
+        transport.mock.push(vec![log(4, 0), log(5, 0), log(6, 1)]).unwrap();
+        transport.mock.push(U64::from(6)).unwrap();
+        transport.mock.push(U256::from(1)).unwrap();
+        transport.mock.push(U64::from(5)).unwrap();
+
+        let filter = Filter::new();
+        let logs: Vec<_> = provider
+            .subscribe_logs_with_backfill(&filter, 4)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(
+            logs,
+            vec![
+                log(4, 0),
+                log(5, 0),
+                log(6, 1),
+                log(6, 2),
+                log(7, 0),
+                Log { removed: Some(true), ..log(6, 1) }
+            ]
+        );
+
+        transport.mock.assert_request("eth_blockNumber", ()).unwrap();
+        transport.mock.assert_request("eth_subscribe", ("logs", &filter)).unwrap();
+        transport.mock.assert_request("eth_blockNumber", ()).unwrap();
+        transport.mock.assert_request("eth_getLogs", [filter.from_block(4).to_block(6)]).unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn halves_backfill_chunks_with_too_many_logs() {
+        let provider = Provider::new(MockPubsub::default());
+        let transport = provider.as_ref();
+        transport.mock.push(vec![log(3500, 0)]).unwrap();
+        transport.mock.push(Vec::<Log>::new()).unwrap();
+        transport.mock.push(Vec::<Log>::new()).unwrap();
+        transport.mock.push(vec![log(10, 0)]).unwrap();
+        transport.mock.push_error(rpc_error("query returned more than 10000 results"));
+        transport.mock.push(U64::from(3999)).unwrap();
+        transport.mock.push(U256::from(1)).unwrap();
+        transport.mock.push(U64::from(3999)).unwrap();
+
+        let filter = Filter::new();
+        let logs: Vec<_> = provider
+            .subscribe_logs_with_backfill(&filter, 0)
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(logs, vec![log(10, 0), log(3500, 0)]);
+
+        transport.mock.assert_request("eth_blockNumber", ()).unwrap();
+        transport.mock.assert_request("eth_subscribe", ("logs", &filter)).unwrap();
+        transport.mock.assert_request("eth_blockNumber", ()).unwrap();
+        for (from, to) in [(0, 1999), (0, 999), (1000, 1999), (2000, 2999), (3000, 3999)] {
+            let chunk = filter.clone().from_block(from).to_block(to);
+            transport.mock.assert_request("eth_getLogs", [chunk]).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn ends_backfill_after_error() {
+        let provider = Provider::new(MockPubsub::default());
+        let transport = provider.as_ref();
+        transport.push_notification(&log(11, 0));
+        transport.mock.push_error(rpc_error("header not found"));
+        transport.mock.push(U64::from(10)).unwrap();
+        transport.mock.push(U256::from(1)).unwrap();
+        transport.mock.push(U64::from(10)).unwrap();
+
+        let mut stream = provider.subscribe_logs_with_backfill(&Filter::new(), 0).await.unwrap();
+        assert!(stream.next().await.unwrap().is_err());
+        assert!(stream.next().await.is_none());
+    }
+
+    fn pending_tx(n: u64) -> Transaction {
+        Transaction { hash: H256::from_low_u64_be(n), ..Default::default() }
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn fetches_pending_txs_announced_by_hash() {
+        let provider = Provider::new(MockPubsub::default());
+        let transport = provider.as_ref();
+        let hashes: Vec<_> = [1, 3, 4].iter().map(|n| H256::from_low_u64_be(*n)).collect();
+        transport.push_notification(&hashes[0]);
+        transport.push_notification(&pending_tx(2));
+        transport.push_notification(&hashes[1]);
+        transport.push_notification(&hashes[2]);
+
+        transport.mock.push_error(rpc_error("header not found"));
+        transport.mock.push(serde_json::Value::Null).unwrap();
+        transport.mock.push(pending_tx(1)).unwrap();
+        transport.mock.push(U256::from(1)).unwrap();
+        // the node doesn't support subscriptions to full transactions
+        transport.mock.push_error(rpc_error("invalid params"));
+
+        let txs: Vec<_> = provider.subscribe_full_pending_txs(1).await.unwrap().collect().await;
+        assert_eq!(txs.len(), 4);
+        assert_eq!(txs[0].as_ref().unwrap(), &pending_tx(1));
+        assert_eq!(txs[1].as_ref().unwrap(), &pending_tx(2));
+        assert!(matches!(txs[2], Err(GetTransactionError::NotFound(hash)) if hash == hashes[1]));
+        assert!(
+            matches!(txs[3], Err(GetTransactionError::ProviderError(hash, _)) if hash == hashes[2])
+        );
+
+        transport.mock.assert_request("eth_subscribe", ("newPendingTransactions", true)).unwrap();
+        transport.mock.assert_request("eth_subscribe", ["newPendingTransactions"]).unwrap();
+        for hash in hashes {
+            transport.mock.assert_request("eth_getTransactionByHash", [hash]).unwrap();
+        }
+    }
+
+    fn pending_tx_stream(
+        txs: Vec<Result<Transaction, GetTransactionError>>,
+    ) -> FullPendingTxStream<'static> {
+        FullPendingTxStream { inner: Box::pin(futures_util::stream::iter(txs)) }
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn dedups_pending_txs() {
+        let mined = Transaction { block_number: Some(10.into()), ..pending_tx(3) };
+        let stream = pending_tx_stream(vec![
+            Ok(pending_tx(1)),
+            Ok(pending_tx(2)),
+            Ok(pending_tx(1)),
+            Ok(mined),
+            Err(GetTransactionError::NotFound(H256::from_low_u64_be(4))),
+            Ok(pending_tx(2)),
+        ]);
+        let txs: Vec<_> = stream.dedup().collect().await;
+        assert_eq!(txs.len(), 3);
+        assert_eq!(txs[0].as_ref().unwrap(), &pending_tx(1));
+        assert_eq!(txs[1].as_ref().unwrap(), &pending_tx(2));
+        // errors are always yielded
+        assert!(txs[2].is_err());
+    }
+
+    #[tokio::test]
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn dedup_forgets_oldest_hashes() {
+        let mut txs: Vec<_> = (0..=DEDUP_CAPACITY as u64).map(|n| Ok(pending_tx(n))).collect();
+        // the first hash was evicted, the second is still remembered
+        txs.push(Ok(pending_tx(1)));
+        txs.push(Ok(pending_tx(0)));
+        let txs: Vec<_> = pending_tx_stream(txs).dedup().map(Result::unwrap).collect().await;
+        assert_eq!(txs.len(), DEDUP_CAPACITY + 2);
+        assert_eq!(txs.last(), Some(&pending_tx(0)));
+    }
+}
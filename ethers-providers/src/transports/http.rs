@@ -2,11 +2,17 @@
 use crate::{provider::ProviderError, JsonRpcClient};
 
 use async_trait::async_trait;
-use reqwest::{header::HeaderValue, Client, Error as ReqwestError};
-use serde::{de::DeserializeOwned, Serialize};
+use reqwest::{
+    header::{HeaderValue, RETRY_AFTER},
+    Client, Error as ReqwestError, StatusCode,
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
 use std::{
+    convert::TryFrom,
     str::FromStr,
     sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
 };
 use thiserror::Error;
 use url::Url;
@@ -48,6 +54,14 @@ pub enum ClientError {
     #[error("Deserialization Error: {err}. Response: {text}")]
     /// Serde JSON Error
     SerdeJson { err: serde_json::Error, text: String },
+
+    #[error("Too many requests, retry after: {retry_after:?}. Response: {text}")]
+    /// Thrown if the server responded with `429 Too Many Requests`
+    TooManyRequests {
+        /// The delay of the `Retry-After` header, if it's given in seconds
+        retry_after: Option<Duration>,
+        text: String,
+    },
 }
 
 impl From<ClientError> for ProviderError {
@@ -71,8 +85,7 @@ impl JsonRpcClient for Provider {
         let next_id = self.id.fetch_add(1, Ordering::SeqCst);
         let payload = Request::new(next_id, method, params);
 
-        let res = self.client.post(self.url.as_ref()).json(&payload).send().await?;
-        let text = res.text().await?;
+        let text = self.post(&payload).await?;
 
         let raw = match serde_json::from_str(&text) {
             Ok(Response::Success { result, .. }) => result.to_owned(),
@@ -92,9 +105,93 @@ impl JsonRpcClient for Provider {
 
         Ok(res)
     }
+
+    /// Sends all requests in a single POST request as a JSON-RPC batch
+    async fn request_batch(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<Result<Value, ClientError>>, ClientError> {
+        if requests.is_empty() {
+            return Ok(Vec::new())
+        }
+        let first_id = self.id.fetch_add(requests.len() as u64, Ordering::SeqCst);
+        let payload = requests
+            .iter()
+            .zip(first_id..)
+            .map(|((method, params), id)| Request::new(id, method, params))
+            .collect::<Vec<_>>();
+
+        let text = self.post(&payload).await?;
+
+        batch_results(first_id, requests.len(), text)
+    }
+}
+
+/// Matches the responses of a batch to the `len` requests with consecutive ids starting at
+/// `first_id`, the responses may be in any order.
+fn batch_results(
+    first_id: u64,
+    len: usize,
+    text: String,
+) -> Result<Vec<Result<Value, ClientError>>, ClientError> {
+    let responses: Vec<Response<'_>> = match serde_json::from_str(&text) {
+        Ok(responses) => responses,
+        Err(err) => {
+            // the node may reject the entire batch with a single error, which has a `null` id
+            #[derive(Deserialize)]
+            struct BatchError {
+                error: JsonRpcError,
+            }
+            if let Ok(BatchError { error }) = serde_json::from_str(&text) {
+                return Err(error.into())
+            }
+            return Err(ClientError::SerdeJson { err, text })
+        }
+    };
+
+    let mut results = (0..len).map(|_| None).collect::<Vec<_>>();
+    for response in responses {
+        let (id, result) = match response {
+            Response::Success { id, result } => {
+                let value = serde_json::from_str(result.get())
+                    .map_err(|err| ClientError::SerdeJson { err, text: result.to_string() })?;
+                (id, Ok(value))
+            }
+            Response::Error { id, error } => (id, Err(ClientError::JsonRpcError(error))),
+            Response::Notification { .. } => continue,
+        };
+        let slot = id
+            .checked_sub(first_id)
+            .and_then(|idx| usize::try_from(idx).ok())
+            .and_then(|idx| results.get_mut(idx));
+        if let Some(slot) = slot {
+            *slot = Some(result);
+        }
+    }
+
+    results.into_iter().collect::<Option<Vec<_>>>().ok_or_else(|| ClientError::SerdeJson {
+        err: serde::de::Error::custom("missing response to a request of the batch"),
+        text,
+    })
 }
 
 impl Provider {
+    /// Posts the payload and returns the body of the response
+    async fn post<T: Serialize>(&self, payload: &T) -> Result<String, ClientError> {
+        let res = self.client.post(self.url.as_ref()).json(payload).send().await?;
+        if res.status() == StatusCode::TOO_MANY_REQUESTS {
+            let retry_after = res
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+                .map(Duration::from_secs);
+            let text = res.text().await?;
+            return Err(ClientError::TooManyRequests { retry_after, text })
+        }
+        Ok(res.text().await?)
+    }
+
     /// Initializes a new HTTP Client
     ///
     /// # Example
@@ -179,3 +276,32 @@ pub enum HttpClientError {
     #[error(transparent)]
     ClientBuild(#[from] reqwest::Error),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_batch_responses_by_id() {
+        let text = r#"[
+            {"jsonrpc":"2.0","id":12,"error":{"code":-32000,"message":"header not found"}},
+            {"jsonrpc":"2.0","id":11,"result":"0x1"}
+        ]"#;
+        let results = batch_results(11, 2, text.to_string()).unwrap();
+        assert_eq!(results[0].as_ref().unwrap(), &Value::from("0x1"));
+        match &results[1] {
+            Err(ClientError::JsonRpcError(err)) => assert_eq!(err.message, "header not found"),
+            res => panic!("unexpected result {:?}", res),
+        }
+
+        // a response is missing
+        assert!(batch_results(11, 3, text.to_string()).is_err());
+
+        let text =
+            r#"{"jsonrpc":"2.0","id":null,"error":{"code":-32600,"message":"too many requests"}}"#;
+        match batch_results(11, 2, text.to_string()).unwrap_err() {
+            ClientError::JsonRpcError(err) => assert_eq!(err.code, -32600),
+            err => panic!("unexpected error {:?}", err),
+        }
+    }
+}
@@ -2,7 +2,8 @@ use std::{
     cell::RefCell,
     convert::Infallible,
     hash::BuildHasherDefault,
-    path::Path,
+    io,
+    path::{Path, PathBuf},
     sync::{
         atomic::{AtomicU64, Ordering},
         Arc,
@@ -20,15 +21,19 @@ use serde::{de::DeserializeOwned, Serialize};
 use serde_json::{value::RawValue, Deserializer};
 use thiserror::Error;
 use tokio::{
-    io::{AsyncReadExt as _, AsyncWriteExt as _, BufReader},
-    net::{
-        unix::{ReadHalf, WriteHalf},
-        UnixStream,
+    io::{
+        AsyncRead, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _, BufReader, ReadHalf,
+        WriteHalf,
     },
     runtime,
     sync::oneshot::{self, error::RecvError},
 };
 
+#[cfg(windows)]
+use tokio::net::windows::named_pipe::{ClientOptions, NamedPipeClient};
+#[cfg(unix)]
+use tokio::net::UnixStream;
+
 use crate::{
     provider::ProviderError,
     transports::common::{JsonRpcError, Request, Response},
@@ -42,7 +47,7 @@ type FxHashMap<K, V> = std::collections::HashMap<K, V, BuildHasherDefault<FxHash
 type Pending = oneshot::Sender<Result<Box<RawValue>, JsonRpcError>>;
 type Subscription = mpsc::UnboundedSender<Box<RawValue>>;
 
-/// Unix Domain Sockets (IPC) transport.
+/// IPC transport, over Unix domain sockets on Unix and named pipes on Windows.
 #[derive(Debug, Clone)]
 pub struct Ipc {
     id: Arc<AtomicU64>,
@@ -57,13 +62,18 @@ enum TransportMessage {
 }
 
 impl Ipc {
-    /// Creates a new IPC transport from a given path using Unix sockets.
+    /// Creates a new IPC transport from a given path.
+    ///
+    /// On Unix the path is a Unix domain socket, on Windows a named pipe like
+    /// `\\.\pipe\geth.ipc`.
     pub async fn connect(path: impl AsRef<Path>) -> Result<Self, IpcError> {
         let id = Arc::new(AtomicU64::new(1));
         let (request_tx, request_rx) = mpsc::unbounded();
 
-        let stream = UnixStream::connect(path).await?;
-        spawn_ipc_server(stream, request_rx);
+        let (connected_tx, connected_rx) = oneshot::channel();
+        spawn_ipc_server(path.as_ref().to_path_buf(), request_rx, connected_tx);
+        // wait until the IPC server thread connected to the socket
+        connected_rx.await??;
 
         Ok(Self { id, request_tx })
     }
@@ -121,7 +131,30 @@ impl PubsubClient for Ipc {
     }
 }
 
-fn spawn_ipc_server(stream: UnixStream, request_rx: mpsc::UnboundedReceiver<TransportMessage>) {
+#[cfg(unix)]
+async fn connect(path: &Path) -> io::Result<UnixStream> {
+    UnixStream::connect(path).await
+}
+
+#[cfg(windows)]
+async fn connect(path: &Path) -> io::Result<NamedPipeClient> {
+    // all instances of the pipe are busy until the server creates a new one
+    const ERROR_PIPE_BUSY: i32 = 231;
+
+    loop {
+        match ClientOptions::new().open(path) {
+            Err(err) if err.raw_os_error() == Some(ERROR_PIPE_BUSY) => {}
+            res => return res,
+        }
+        futures_timer::Delay::new(std::time::Duration::from_millis(50)).await;
+    }
+}
+
+fn spawn_ipc_server(
+    path: PathBuf,
+    request_rx: mpsc::UnboundedReceiver<TransportMessage>,
+    connected_tx: oneshot::Sender<io::Result<()>>,
+) {
     // 65 KiB should be more than enough for this thread, as all unbounded data
     // growth occurs on heap-allocated data structures and buffers and the call
     // stack is not going to do anything crazy either
@@ -137,13 +170,24 @@ fn spawn_ipc_server(stream: UnixStream, request_rx: mpsc::UnboundedReceiver<Tran
                 .build()
                 .expect("failed to create ipc-server-thread async runtime");
 
-            rt.block_on(run_ipc_server(stream, request_rx));
+            rt.block_on(async move {
+                // the stream is created within the runtime that drives it
+                let stream = match connect(&path).await {
+                    Ok(stream) => stream,
+                    Err(err) => {
+                        let _ = connected_tx.send(Err(err));
+                        return
+                    }
+                };
+                let _ = connected_tx.send(Ok(()));
+                run_ipc_server(stream, request_rx).await
+            });
         })
         .expect("failed to spawn ipc server thread");
 }
 
-async fn run_ipc_server(
-    mut stream: UnixStream,
+async fn run_ipc_server<S: AsyncRead + AsyncWrite>(
+    stream: S,
     request_rx: mpsc::UnboundedReceiver<TransportMessage>,
 ) {
     // the shared state for both reads & writes
@@ -154,7 +198,7 @@ async fn run_ipc_server(
 
     // split the stream and run two independent concurrently (local), thereby
     // allowing reads and writes to occurr concurrently
-    let (reader, writer) = stream.split();
+    let (reader, writer) = tokio::io::split(stream);
     let read = shared.handle_ipc_reads(reader);
     let write = shared.handle_ipc_writes(writer, request_rx);
 
@@ -173,7 +217,10 @@ struct Shared {
 }
 
 impl Shared {
-    async fn handle_ipc_reads(&self, reader: ReadHalf<'_>) -> Result<Infallible, IpcError> {
+    async fn handle_ipc_reads<S: AsyncRead>(
+        &self,
+        reader: ReadHalf<S>,
+    ) -> Result<Infallible, IpcError> {
         let mut reader = BufReader::new(reader);
         let mut buf = BytesMut::with_capacity(4096);
 
@@ -194,9 +241,9 @@ impl Shared {
         }
     }
 
-    async fn handle_ipc_writes(
+    async fn handle_ipc_writes<S: AsyncWrite>(
         &self,
-        mut writer: WriteHalf<'_>,
+        mut writer: WriteHalf<S>,
         mut request_rx: mpsc::UnboundedReceiver<TransportMessage>,
     ) -> Result<Infallible, IpcError> {
         use TransportMessage::*;
@@ -319,9 +366,67 @@ impl From<IpcError> for ProviderError {
         ProviderError::JsonRpcClientError(Box::new(src))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use ethers_core::types::U64;
+
+    /// Answers a single `eth_blockNumber` request
+    async fn serve_block_number(mut stream: impl AsyncRead + AsyncWrite + Unpin) {
+        let mut buf = vec![0; 1024];
+        let read = stream.read(&mut buf).await.unwrap();
+        let request: serde_json::Value = serde_json::from_slice(&buf[..read]).unwrap();
+        assert_eq!(request["method"], "eth_blockNumber");
+
+        let response =
+            serde_json::json!({ "jsonrpc": "2.0", "id": request["id"], "result": "0x2a" });
+        stream.write_all(&serde_json::to_vec(&response).unwrap()).await.unwrap();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn request_with_mock_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("mock.ipc");
+        let listener = tokio::net::UnixListener::bind(&path).unwrap();
+
+        let server = async {
+            let (stream, _) = listener.accept().await.unwrap();
+            serve_block_number(stream).await
+        };
+        let client = async {
+            let ipc = Ipc::connect(&path).await.unwrap();
+            ipc.request::<_, U64>("eth_blockNumber", ()).await.unwrap()
+        };
+        let (_, block_number) = tokio::join!(server, client);
+        assert_eq!(block_number, U64::from(42));
+    }
+
+    #[cfg(windows)]
+    #[tokio::test]
+    async fn request_with_mock_pipe() {
+        use tokio::net::windows::named_pipe::ServerOptions;
+
+        let path = format!(r"\\.\pipe\ethers-ipc-test-{}", std::process::id());
+        let pipe = ServerOptions::new().first_pipe_instance(true).create(&path).unwrap();
+
+        let server = async move {
+            pipe.connect().await.unwrap();
+            serve_block_number(pipe).await
+        };
+        let client = async {
+            let ipc = Ipc::connect(&path).await.unwrap();
+            ipc.request::<_, U64>("eth_blockNumber", ()).await.unwrap()
+        };
+        let (_, block_number) = tokio::join!(server, client);
+        assert_eq!(block_number, U64::from(42));
+    }
+}
+
 #[cfg(all(test, target_family = "unix"))]
 #[cfg(not(feature = "celo"))]
-mod test {
+mod geth_test {
     use super::*;
     use ethers_core::{
         types::{Block, TxHash, U256},
@@ -1,4 +1,4 @@
-use crate::{JsonRpcClient, ProviderError};
+use crate::{JsonRpcClient, JsonRpcError, ProviderError};
 
 use async_trait::async_trait;
 use serde::{de::DeserializeOwned, Serialize};
@@ -14,7 +14,7 @@ use thiserror::Error;
 /// Mock transport used in test environments.
 pub struct MockProvider {
     requests: Arc<Mutex<VecDeque<(String, Value)>>>,
-    responses: Arc<Mutex<VecDeque<Value>>>,
+    responses: Arc<Mutex<VecDeque<Result<Value, JsonRpcError>>>>,
 }
 
 impl Default for MockProvider {
@@ -38,7 +38,7 @@ impl JsonRpcClient for MockProvider {
         self.requests.lock().unwrap().push_back((method.to_owned(), serde_json::to_value(input)?));
         let mut data = self.responses.lock().unwrap();
         let element = data.pop_back().ok_or(MockError::EmptyResponses)?;
-        let res: R = serde_json::from_value(element)?;
+        let res: R = serde_json::from_value(element.map_err(MockError::JsonRpcError)?)?;
 
         Ok(res)
     }
@@ -68,9 +68,14 @@ impl MockProvider {
     /// Pushes the data to the responses
     pub fn push<T: Serialize + Send + Sync, K: Borrow<T>>(&self, data: K) -> Result<(), MockError> {
         let value = serde_json::to_value(data.borrow())?;
-        self.responses.lock().unwrap().push_back(value);
+        self.responses.lock().unwrap().push_back(Ok(value));
         Ok(())
     }
+
+    /// Pushes the error to the responses, the request that pops it fails with the error
+    pub fn push_error(&self, error: JsonRpcError) {
+        self.responses.lock().unwrap().push_back(Err(error));
+    }
 }
 
 #[derive(Error, Debug)]
@@ -84,6 +89,9 @@ pub enum MockError {
 
     #[error("empty responses array, please push some responses")]
     EmptyResponses,
+
+    #[error(transparent)]
+    JsonRpcError(JsonRpcError),
 }
 
 impl From<MockError> for ProviderError {
@@ -119,6 +127,14 @@ mod tests {
         };
     }
 
+    #[tokio::test]
+    async fn pushes_error_response() {
+        let mock = MockProvider::new();
+        mock.push_error(JsonRpcError { code: -32000, message: "nonce too low".into(), data: None });
+        let err = mock.request::<_, U64>("eth_sendTransaction", ()).await.unwrap_err();
+        assert!(matches!(err, MockError::JsonRpcError(ref err) if err.message == "nonce too low"));
+    }
+
     #[tokio::test]
     async fn empty_requests() {
         let mock = MockProvider::new();
@@ -1,5 +1,5 @@
 mod common;
-pub use common::Authorization;
+pub use common::{Authorization, JsonRpcError};
 
 // only used with WS
 #[cfg(feature = "ws")]
@@ -19,9 +19,9 @@ macro_rules! if_not_wasm {
     )*}
 }
 
-#[cfg(all(target_family = "unix", feature = "ipc"))]
+#[cfg(all(feature = "ipc", any(unix, windows)))]
 mod ipc;
-#[cfg(all(target_family = "unix", feature = "ipc"))]
+#[cfg(all(feature = "ipc", any(unix, windows)))]
 pub use ipc::{Ipc, IpcError};
 
 mod http;
@@ -32,10 +32,20 @@ mod ws;
 #[cfg(feature = "ws")]
 pub use ws::{ClientError as WsClientError, Ws};
 
+#[cfg(all(feature = "ws", not(target_arch = "wasm32")))]
+mod reconnecting_ws;
+#[cfg(all(feature = "ws", not(target_arch = "wasm32")))]
+pub use reconnecting_ws::{ReconnectConfig, ReconnectingWs, ReconnectingWsBuilder};
+
 mod quorum;
 pub(crate) use quorum::JsonRpcClientWrapper;
 pub use quorum::{Quorum, QuorumError, QuorumProvider, WeightedProvider};
 
+mod retry;
+pub use retry::{
+    Backoff, RateLimitRetryPolicy, RetryClient, RetryDecision, RetryPolicy, RATE_LIMITED_ERROR_CODE,
+};
+
 mod rw;
 pub use rw::{RwClient, RwClientError};
 
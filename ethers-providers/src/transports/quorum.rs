@@ -18,6 +18,10 @@ use thiserror::Error;
 /// A provider that bundles multiple providers and only returns a value to the
 /// caller once the quorum has been reached.
 ///
+/// Since providers are rarely synced to the exact same block, `eth_blockNumber` doesn't require
+/// matching values, instead it returns the highest block number that providers with the quorum
+/// weight have reached.
+///
 /// # Example
 ///
 /// Create a `QuorumProvider` that only returns a value if the `Quorum::Majority` of
@@ -242,8 +246,13 @@ type PendingRequest<'a> =
 /// reached a quorum.
 struct QuorumRequest<'a, T> {
     inner: &'a QuorumProvider<T>,
+    /// Whether the quorum is reached on the highest block number that enough providers returned,
+    /// instead of an exact match
+    highest_block: bool,
     /// The different answers with their cumulative weight
     responses: Vec<(Value, u64)>,
+    /// The answer of every provider, by its index
+    answers: Vec<Option<Value>>,
     /// All the errors the provider yielded
     errors: Vec<ProviderError>,
     // Requests currently pending
@@ -251,8 +260,57 @@ struct QuorumRequest<'a, T> {
 }
 
 impl<'a, T> QuorumRequest<'a, T> {
-    fn new(inner: &'a QuorumProvider<T>, requests: Vec<PendingRequest<'a>>) -> Self {
-        Self { responses: Vec::new(), errors: Vec::new(), inner, requests }
+    fn new(inner: &'a QuorumProvider<T>, method: &str, requests: Vec<PendingRequest<'a>>) -> Self {
+        Self {
+            highest_block: method == "eth_blockNumber",
+            responses: Vec::new(),
+            answers: vec![None; inner.providers.len()],
+            errors: Vec::new(),
+            inner,
+            requests,
+        }
+    }
+
+    /// Adds the weight to the response and returns the value once the quorum is reached
+    fn add_response(&mut self, val: Value, response_weight: u64) -> Option<Value> {
+        let weight = if let Some((_, weight)) = self.responses.iter_mut().find(|(v, _)| &val == v) {
+            // add the weight to equal response value
+            *weight += response_weight;
+            *weight
+        } else {
+            self.responses.push((val.clone(), response_weight));
+            response_weight
+        };
+
+        if self.highest_block {
+            self.highest_block_number()
+        } else if weight >= self.inner.quorum_weight {
+            Some(val)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the highest block number that providers with at least the quorum weight have
+    /// reached
+    fn highest_block_number(&self) -> Option<Value> {
+        let mut numbers = self
+            .responses
+            .iter()
+            .filter_map(|(val, weight)| {
+                serde_json::from_value::<U64>(val.clone()).ok().map(|num| (num, val, *weight))
+            })
+            .collect::<Vec<_>>();
+        numbers.sort_by(|a, b| b.0.cmp(&a.0));
+
+        let mut weight = 0;
+        for (_, val, response_weight) in numbers {
+            weight += response_weight;
+            if weight >= self.inner.quorum_weight {
+                return Some(val.clone())
+            }
+        }
+        None
     }
 }
 
@@ -266,20 +324,9 @@ impl<'a, T> Future for QuorumRequest<'a, T> {
             match request.poll_unpin(cx) {
                 Poll::Ready((Ok(val), idx)) => {
                     let response_weight = this.inner.providers[idx].weight;
-                    if let Some((_, weight)) = this.responses.iter_mut().find(|(v, _)| &val == v) {
-                        // add the weight to equal response value
-                        *weight += response_weight;
-                        if *weight >= this.inner.quorum_weight {
-                            // reached quorum with multiple responses
-                            return Poll::Ready(Ok(val))
-                        } else {
-                            this.responses.push((val, response_weight));
-                        }
-                    } else if response_weight >= this.inner.quorum_weight {
-                        // reached quorum with single response
+                    this.answers[idx] = Some(val.clone());
+                    if let Some(val) = this.add_response(val, response_weight) {
                         return Poll::Ready(Ok(val))
-                    } else {
-                        this.responses.push((val, response_weight));
                     }
                 }
                 Poll::Ready((Err(err), _)) => this.errors.push(err),
@@ -293,8 +340,9 @@ impl<'a, T> Future for QuorumRequest<'a, T> {
             // No more requests and no quorum reached
             this.responses.sort_by(|a, b| b.1.cmp(&a.1));
             let values = std::mem::take(&mut this.responses).into_iter().map(|r| r.0).collect();
+            let answers = std::mem::take(&mut this.answers);
             let errors = std::mem::take(&mut this.errors);
-            Poll::Ready(Err(QuorumError::NoQuorumReached { values, errors }))
+            Poll::Ready(Err(QuorumError::NoQuorumReached { values, answers, errors }))
         } else {
             Poll::Pending
         }
@@ -324,7 +372,15 @@ impl<T> WeightedProvider<T> {
 /// Error thrown when sending an HTTP request
 pub enum QuorumError {
     #[error("No Quorum reached.")]
-    NoQuorumReached { values: Vec<Value>, errors: Vec<ProviderError> },
+    NoQuorumReached {
+        /// The different values that were returned, sorted by their cumulative weight
+        values: Vec<Value>,
+        /// The value every provider returned, by its index in
+        /// [`QuorumProvider::providers`], `None` if it failed
+        answers: Vec<Option<Value>>,
+        /// The errors of the providers that failed
+        errors: Vec<ProviderError>,
+    },
 }
 
 impl From<QuorumError> for ProviderError {
@@ -422,7 +478,7 @@ where
             })
             .collect::<Vec<_>>();
 
-        let value = QuorumRequest::new(self, requests).await?;
+        let value = QuorumRequest::new(self, method, requests).await?;
         Ok(serde_json::from_value(value)?)
     }
 }
@@ -530,9 +586,9 @@ where
 #[cfg(test)]
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
-    use super::{Quorum, QuorumProvider, WeightedProvider};
-    use crate::{Middleware, MockProvider, Provider};
-    use ethers_core::types::U64;
+    use super::{Quorum, QuorumError, QuorumProvider, WeightedProvider};
+    use crate::{Middleware, MockProvider, Provider, ProviderError};
+    use ethers_core::types::{U256, U64};
 
     async fn test_quorum(q: Quorum) {
         let num = 5u64;
@@ -600,4 +656,41 @@ mod tests {
     async fn all_quorum() {
         test_quorum(Quorum::All).await
     }
+
+    fn mocked_quorum<T: serde::Serialize>(values: &[T]) -> Provider<QuorumProvider<MockProvider>> {
+        let providers = values.iter().map(|value| {
+            let mock = MockProvider::new();
+            mock.push::<T, _>(value).unwrap();
+            WeightedProvider::new(mock)
+        });
+        let quorum = QuorumProvider::builder().add_providers(providers).build();
+        Provider::quorum(quorum)
+    }
+
+    #[tokio::test]
+    async fn highest_block_number_of_quorum() {
+        let provider = mocked_quorum(&[U64::from(10), U64::from(12), U64::from(11)]);
+        assert_eq!(provider.get_block_number().await.unwrap(), U64::from(11));
+    }
+
+    #[tokio::test]
+    async fn reports_all_answers_without_quorum() {
+        let provider = mocked_quorum(&[U256::from(1), U256::from(2), U256::from(3)]);
+        let err = provider.get_chainid().await.unwrap_err();
+        let err = match err {
+            ProviderError::JsonRpcClientError(err) => err,
+            err => panic!("unexpected error {:?}", err),
+        };
+        match err.downcast_ref::<QuorumError>().unwrap() {
+            QuorumError::NoQuorumReached { values, answers, errors } => {
+                assert_eq!(values.len(), 3);
+                assert!(errors.is_empty());
+                let answers = answers
+                    .iter()
+                    .map(|answer| serde_json::from_value(answer.clone().unwrap()).unwrap())
+                    .collect::<Vec<U256>>();
+                assert_eq!(answers, vec![U256::from(1), U256::from(2), U256::from(3)]);
+            }
+        }
+    }
 }
@@ -0,0 +1,482 @@
+//! A [JsonRpcClient] over websockets that reconnects if the connection is lost and resumes all
+//! active subscriptions.
+
+use crate::{
+    transports::{ws::ClientError, Authorization, Ws},
+    JsonRpcClient, PubsubClient,
+};
+use ethers_core::types::U256;
+
+use async_trait::async_trait;
+use futures_channel::mpsc;
+use futures_util::{lock::Mutex as AsyncMutex, stream::StreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::{value::RawValue, Value};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug},
+    sync::{Arc, Mutex, RwLock},
+    time::Duration,
+};
+use tracing::{debug, error, warn};
+
+type Subscription = mpsc::UnboundedSender<Box<RawValue>>;
+
+/// Invoked with the ids of all resumed subscriptions after a reconnect
+type OnReconnect = Arc<dyn Fn(&[U256]) + Send + Sync>;
+
+/// Configures how often and how fast [`ReconnectingWs`] tries to reconnect
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectConfig {
+    /// How many times to retry after the first reconnect attempt failed
+    pub max_retries: u32,
+    /// The delay after the first failed attempt, which is doubled after every retry
+    pub initial_backoff: Duration,
+    /// The maximum delay between two attempts
+    pub max_backoff: Duration,
+}
+
+impl Default for ReconnectConfig {
+    fn default() -> Self {
+        Self {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(30),
+        }
+    }
+}
+
+impl ReconnectConfig {
+    /// Returns the delay after the failed attempt with the given number of previous retries
+    fn backoff(&self, retries: u32) -> Duration {
+        self.initial_backoff
+            .checked_mul(2u32.saturating_pow(retries))
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+}
+
+/// A subscription that is resumed after a reconnect
+struct ActiveSubscription {
+    /// The params of the `eth_subscribe` request
+    params: Value,
+    /// The id of the subscription on the current connection
+    server_id: U256,
+    /// The sink of the stream returned by [`PubsubClient::subscribe`]
+    sink: Option<Subscription>,
+}
+
+/// The current connection, `generation` is incremented on every reconnect
+struct Connection {
+    ws: Ws,
+    generation: u64,
+}
+
+struct Shared {
+    url: String,
+    auth: Option<Authorization>,
+    config: ReconnectConfig,
+    on_reconnect: Option<OnReconnect>,
+    connection: RwLock<Connection>,
+    /// All active subscriptions by the id that was returned to the caller
+    subscriptions: Mutex<BTreeMap<U256, ActiveSubscription>>,
+    /// Ensures only one task reconnects at a time
+    reconnecting: AsyncMutex<()>,
+}
+
+/// A JSON-RPC Client over Websockets that reconnects if the connection is lost.
+///
+/// All subscriptions created with `eth_subscribe` are re-issued on the new connection and
+/// resumed on the streams returned by [`PubsubClient::subscribe`], so callers keep using the same
+/// subscription id. Notifications emitted while disconnected are lost, use
+/// [`ReconnectingWsBuilder::on_reconnect`] to backfill them.
+///
+/// Requests that are in flight when the connection is lost fail with
+/// [`ClientError::ConnectionLost`], they are not retried since they may have been executed.
+///
+/// ```no_run
+/// # async fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// use ethers_providers::ReconnectingWs;
+/// use std::time::Duration;
+///
+/// let ws = ReconnectingWs::builder("wss://localhost:8545")
+///     .max_retries(5)
+///     .backoff(Duration::from_secs(1), Duration::from_secs(10))
+///     .on_reconnect(|subscriptions| println!("resumed {} subscriptions", subscriptions.len()))
+///     .connect()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ReconnectingWs {
+    shared: Arc<Shared>,
+}
+
+impl Debug for ReconnectingWs {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReconnectingWs")
+            .field("url", &self.shared.url)
+            .field("config", &self.shared.config)
+            .finish()
+    }
+}
+
+impl ReconnectingWs {
+    /// Connects to the given url with the default [`ReconnectConfig`]
+    pub async fn connect(url: impl Into<String>) -> Result<Self, ClientError> {
+        Self::builder(url).connect().await
+    }
+
+    /// Returns a builder to configure the reconnects
+    pub fn builder(url: impl Into<String>) -> ReconnectingWsBuilder {
+        ReconnectingWsBuilder {
+            url: url.into(),
+            auth: None,
+            config: ReconnectConfig::default(),
+            on_reconnect: None,
+        }
+    }
+
+    /// Returns true if the WS connection is active, false otherwise
+    pub fn ready(&self) -> bool {
+        self.shared.current().0.ready()
+    }
+}
+
+/// Builder for [`ReconnectingWs`]
+pub struct ReconnectingWsBuilder {
+    url: String,
+    auth: Option<Authorization>,
+    config: ReconnectConfig,
+    on_reconnect: Option<OnReconnect>,
+}
+
+impl ReconnectingWsBuilder {
+    /// Authenticates every connection with the given authorization
+    #[must_use]
+    pub fn auth(mut self, auth: Authorization) -> Self {
+        self.auth = Some(auth);
+        self
+    }
+
+    /// Sets how many times to retry after the first reconnect attempt failed
+    #[must_use]
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.config.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay after the first failed reconnect attempt, which is doubled after every
+    /// retry up to `max`
+    #[must_use]
+    pub fn backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.config.initial_backoff = initial;
+        self.config.max_backoff = max;
+        self
+    }
+
+    /// Sets the [`ReconnectConfig`]
+    #[must_use]
+    pub fn config(mut self, config: ReconnectConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Sets a hook that is invoked with the ids of all resumed subscriptions after every
+    /// reconnect.
+    ///
+    /// Since notifications emitted while disconnected are lost, this can be used to backfill
+    /// them, for example by requesting all logs since the last received block.
+    #[must_use]
+    pub fn on_reconnect(mut self, on_reconnect: impl Fn(&[U256]) + Send + Sync + 'static) -> Self {
+        self.on_reconnect = Some(Arc::new(on_reconnect));
+        self
+    }
+
+    /// Establishes the first connection
+    pub async fn connect(self) -> Result<ReconnectingWs, ClientError> {
+        let ws = connect(&self.url, self.auth.as_ref()).await?;
+        let shared = Shared {
+            url: self.url,
+            auth: self.auth,
+            config: self.config,
+            on_reconnect: self.on_reconnect,
+            connection: RwLock::new(Connection { ws, generation: 0 }),
+            subscriptions: Default::default(),
+            reconnecting: AsyncMutex::new(()),
+        };
+        Ok(ReconnectingWs { shared: Arc::new(shared) })
+    }
+}
+
+async fn connect(url: &str, auth: Option<&Authorization>) -> Result<Ws, ClientError> {
+    match auth {
+        Some(auth) => Ws::connect_with_auth(url, auth.clone()).await,
+        None => Ws::connect(url).await,
+    }
+}
+
+/// Whether the error means that the connection is gone
+fn is_connection_lost(err: &ClientError) -> bool {
+    matches!(
+        err,
+        ClientError::ConnectionLost | ClientError::UnexpectedClose | ClientError::ChannelError(_)
+    )
+}
+
+impl Shared {
+    fn current(&self) -> (Ws, u64) {
+        let connection = self.connection.read().unwrap();
+        (connection.ws.clone(), connection.generation)
+    }
+
+    /// Returns the current connection, reconnects first if it's lost
+    async fn connection(self: &Arc<Self>) -> Result<(Ws, u64), ClientError> {
+        let (ws, generation) = self.current();
+        if ws.ready() {
+            return Ok((ws, generation))
+        }
+        self.reconnect(generation).await?;
+        Ok(self.current())
+    }
+
+    /// Reconnects unless the connection of the given generation was already replaced
+    async fn reconnect(self: &Arc<Self>, generation: u64) -> Result<(), ClientError> {
+        let _guard = self.reconnecting.lock().await;
+        if self.current().1 != generation {
+            return Ok(())
+        }
+
+        let mut retries = 0;
+        let ws = loop {
+            match connect(&self.url, self.auth.as_ref()).await {
+                Ok(ws) => break ws,
+                Err(err) if retries >= self.config.max_retries => {
+                    error!("failed to reconnect to {}: {}", self.url, err);
+                    // end all subscription streams
+                    self.subscriptions.lock().unwrap().clear();
+                    return Err(err)
+                }
+                Err(err) => {
+                    let delay = self.config.backoff(retries);
+                    warn!("failed to reconnect to {}, retrying in {:?}: {}", self.url, delay, err);
+                    futures_timer::Delay::new(delay).await;
+                    retries += 1;
+                }
+            }
+        };
+        let generation = generation + 1;
+        *self.connection.write().unwrap() = Connection { ws: ws.clone(), generation };
+        debug!("reconnected to {}", self.url);
+
+        let subscriptions = self
+            .subscriptions
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, sub)| (*id, sub.params.clone()))
+            .collect::<Vec<_>>();
+        let mut resumed = Vec::with_capacity(subscriptions.len());
+        for (id, params) in subscriptions {
+            let server_id: U256 = match ws.request("eth_subscribe", &params).await {
+                Ok(server_id) => server_id,
+                Err(err) => {
+                    warn!("failed to resume subscription {:?}: {}", id, err);
+                    self.subscriptions.lock().unwrap().remove(&id);
+                    continue
+                }
+            };
+            let sink = match self.subscriptions.lock().unwrap().get_mut(&id) {
+                Some(sub) => {
+                    sub.server_id = server_id;
+                    sub.sink.clone()
+                }
+                // unsubscribed in the meantime
+                None => continue,
+            };
+            if let Some(sink) = sink {
+                self.forward(id, server_id, &ws, generation, sink)?;
+            }
+            resumed.push(id);
+        }
+
+        if let Some(on_reconnect) = self.on_reconnect.as_ref() {
+            on_reconnect(&resumed);
+        }
+        Ok(())
+    }
+
+    /// Forwards the notifications of the subscription on the connection to the caller's stream
+    fn forward(
+        self: &Arc<Self>,
+        id: U256,
+        server_id: U256,
+        ws: &Ws,
+        generation: u64,
+        sink: Subscription,
+    ) -> Result<(), ClientError> {
+        let mut notifications = ws.subscribe(server_id)?;
+        let shared = Arc::clone(self);
+        tokio::spawn(async move {
+            while let Some(notification) = notifications.next().await {
+                if sink.unbounded_send(notification).is_err() {
+                    // the caller dropped the stream
+                    return
+                }
+            }
+            // the stream ends if the subscription was removed or the connection was lost
+            let active = shared.subscriptions.lock().unwrap().contains_key(&id);
+            let (ws, current) = shared.current();
+            if active && current == generation && !ws.ready() {
+                let _ = shared.reconnect(generation).await;
+            }
+        });
+        Ok(())
+    }
+
+    /// Tracks a new subscription and returns the id for the caller
+    fn register(&self, server_id: U256, params: Value) -> U256 {
+        let mut subscriptions = self.subscriptions.lock().unwrap();
+        // after a reconnect the node may hand out an id that's already used by a resumed
+        // subscription
+        let mut id = server_id;
+        while subscriptions.contains_key(&id) {
+            id = id.overflowing_add(U256::one()).0;
+        }
+        subscriptions.insert(id, ActiveSubscription { params, server_id, sink: None });
+        id
+    }
+
+    /// Replaces the caller's subscription id in the params of `eth_unsubscribe` with the id on
+    /// the current connection
+    fn translate_unsubscribe(&self, params: &mut Value) {
+        if let Some(param) = params.get_mut(0) {
+            let id = match serde_json::from_value::<U256>(param.clone()) {
+                Ok(id) => id,
+                Err(_) => return,
+            };
+            if let Some(sub) = self.subscriptions.lock().unwrap().get(&id) {
+                *param = serde_json::to_value(sub.server_id).expect("U256 serializes");
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl JsonRpcClient for ReconnectingWs {
+    type Error = ClientError;
+
+    async fn request<T: Serialize + Send + Sync, R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: T,
+    ) -> Result<R, ClientError> {
+        let mut params = serde_json::to_value(params)?;
+        if method == "eth_unsubscribe" {
+            self.shared.translate_unsubscribe(&mut params);
+        }
+
+        let (ws, generation) = self.shared.connection().await?;
+        let result: Value = match ws.request(method, &params).await {
+            Ok(result) => result,
+            Err(err) if is_connection_lost(&err) => {
+                let shared = Arc::clone(&self.shared);
+                tokio::spawn(async move {
+                    let _ = shared.reconnect(generation).await;
+                });
+                return Err(ClientError::ConnectionLost)
+            }
+            Err(err) => return Err(err),
+        };
+
+        if method == "eth_subscribe" {
+            let server_id: U256 = serde_json::from_value(result)?;
+            let id = self.shared.register(server_id, params);
+            return Ok(serde_json::from_value(serde_json::to_value(id)?)?)
+        }
+        Ok(serde_json::from_value(result)?)
+    }
+}
+
+impl PubsubClient for ReconnectingWs {
+    type NotificationStream = mpsc::UnboundedReceiver<Box<RawValue>>;
+
+    fn subscribe<T: Into<U256>>(&self, id: T) -> Result<Self::NotificationStream, ClientError> {
+        let id = id.into();
+        let (ws, generation) = self.shared.current();
+        let (sink, stream) = mpsc::unbounded();
+        let server_id = match self.shared.subscriptions.lock().unwrap().get_mut(&id) {
+            Some(sub) => {
+                sub.sink = Some(sink.clone());
+                sub.server_id
+            }
+            // not created with `eth_subscribe` on this client, so it can't be resumed
+            None => return ws.subscribe(id),
+        };
+        self.shared.forward(id, server_id, &ws, generation, sink)?;
+        Ok(stream)
+    }
+
+    fn unsubscribe<T: Into<U256>>(&self, id: T) -> Result<(), ClientError> {
+        let id = id.into();
+        let server_id =
+            self.shared.subscriptions.lock().unwrap().remove(&id).map(|sub| sub.server_id);
+        self.shared.current().0.unsubscribe(server_id.unwrap_or(id))
+    }
+}
+
+#[cfg(test)]
+#[cfg(not(feature = "celo"))]
+mod tests {
+    use super::*;
+    use ethers_core::{
+        types::{Block, TxHash},
+        utils::Ganache,
+    };
+
+    #[test]
+    fn backoff_is_capped() {
+        let config = ReconnectConfig {
+            max_retries: 10,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(1),
+        };
+        assert_eq!(config.backoff(0), Duration::from_millis(100));
+        assert_eq!(config.backoff(1), Duration::from_millis(200));
+        assert_eq!(config.backoff(3), Duration::from_millis(800));
+        assert_eq!(config.backoff(4), Duration::from_secs(1));
+        assert_eq!(config.backoff(u32::MAX), Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn resumes_subscriptions() {
+        let ganache = Ganache::new().block_time(1u64).spawn();
+        let port = ganache.port();
+        let (tx, mut reconnects) = mpsc::unbounded();
+        let ws = ReconnectingWs::builder(ganache.ws_endpoint())
+            .backoff(Duration::from_millis(100), Duration::from_secs(1))
+            .on_reconnect(move |ids| tx.unbounded_send(ids.to_vec()).unwrap())
+            .connect()
+            .await
+            .unwrap();
+
+        let sub_id: U256 = ws.request("eth_subscribe", ["newHeads"]).await.unwrap();
+        let mut stream = ws.subscribe(sub_id).unwrap();
+        let item = stream.next().await.unwrap();
+        let block: Block<TxHash> = serde_json::from_str(item.get()).unwrap();
+        assert_eq!(block.number.unwrap().as_u64(), 1);
+
+        // restart the node on the same port, which restarts the chain
+        drop(ganache);
+        let _ganache = Ganache::new().port(port).block_time(1u64).spawn();
+
+        assert_eq!(reconnects.next().await.unwrap(), vec![sub_id]);
+        let item = stream.next().await.unwrap();
+        let block: Block<TxHash> = serde_json::from_str(item.get()).unwrap();
+        assert_eq!(block.number.unwrap().as_u64(), 1);
+
+        let unsubscribed: bool = ws.request("eth_unsubscribe", [sub_id]).await.unwrap();
+        assert!(unsubscribed);
+    }
+}
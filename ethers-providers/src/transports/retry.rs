@@ -0,0 +1,335 @@
+//! A [JsonRpcClient] implementation that retries failed requests according to a [RetryPolicy]
+
+use super::{common::JsonRpcError, http::ClientError as HttpClientError};
+use crate::JsonRpcClient;
+
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::{
+    collections::hash_map::RandomState,
+    fmt,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    time::Duration,
+};
+use tracing::trace;
+
+/// Whether and after which delay a failed request is retried, see [`RetryPolicy`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryDecision {
+    /// Whether the request is sent again
+    pub retry: bool,
+    /// The delay before the request is sent again
+    pub delay: Duration,
+}
+
+impl RetryDecision {
+    /// Sends the request again after the delay
+    pub fn retry_after(delay: Duration) -> Self {
+        Self { retry: true, delay }
+    }
+
+    /// Returns the error without sending the request again
+    pub fn give_up() -> Self {
+        Self { retry: false, delay: Duration::default() }
+    }
+}
+
+/// Decides whether a failed request of a [`RetryClient`] is sent again.
+///
+/// This is implemented for closures `Fn(&E, u32) -> RetryDecision`.
+pub trait RetryPolicy<E>: Send + Sync {
+    /// Returns the decision for the error of a request, `attempt` is the number of times the
+    /// request was already retried
+    fn decide(&self, error: &E, attempt: u32) -> RetryDecision;
+}
+
+impl<E, F> RetryPolicy<E> for F
+where
+    F: Fn(&E, u32) -> RetryDecision + Send + Sync,
+{
+    fn decide(&self, error: &E, attempt: u32) -> RetryDecision {
+        self(error, attempt)
+    }
+}
+
+/// Exponential backoff with full jitter.
+///
+/// The delay of a retry is chosen at random between zero and `initial * 2^attempt`, capped at
+/// `max`, so that clients that were rate limited at the same time don't retry at the same time.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Backoff {
+    /// The upper bound of the delay of the first retry
+    pub initial: Duration,
+    /// The upper bound of the delay of all retries
+    pub max: Duration,
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self { initial: Duration::from_millis(500), max: Duration::from_secs(30) }
+    }
+}
+
+impl Backoff {
+    /// Creates a new backoff with the given bounds
+    pub fn new(initial: Duration, max: Duration) -> Self {
+        Self { initial, max }
+    }
+
+    /// Returns the upper bound of the delay of the retry
+    pub fn max_delay(&self, attempt: u32) -> Duration {
+        2u32.checked_pow(attempt)
+            .and_then(|factor| self.initial.checked_mul(factor))
+            .map(|delay| delay.min(self.max))
+            .unwrap_or(self.max)
+    }
+
+    /// Returns a random delay between zero and [`Backoff::max_delay`]
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let max = self.max_delay(attempt).as_millis() as u64;
+        Duration::from_millis(random() % max.saturating_add(1))
+    }
+}
+
+/// Returns a random number, good enough for jitter
+fn random() -> u64 {
+    RandomState::new().build_hasher().finish()
+}
+
+/// The JSON-RPC error code of rate limited requests, used by Infura, Alchemy and others
+pub const RATE_LIMITED_ERROR_CODE: i64 = -32005;
+
+/// A [`RetryPolicy`] for [`Http`](crate::Http) that retries rate limited requests.
+///
+/// A request is rate limited if the server responded with `429 Too Many Requests` or returned a
+/// JSON-RPC error with one of the [error codes](RateLimitRetryPolicy::error_codes), by default
+/// `-32005` and `429`. The request is retried after the delay the server asked for, either
+/// with the `Retry-After` header or the `backoff_seconds` of the error data, otherwise the delay
+/// of the [`Backoff`] is used.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitRetryPolicy {
+    backoff: Backoff,
+    error_codes: Vec<i64>,
+}
+
+impl Default for RateLimitRetryPolicy {
+    fn default() -> Self {
+        Self { backoff: Backoff::default(), error_codes: vec![RATE_LIMITED_ERROR_CODE, 429] }
+    }
+}
+
+impl RateLimitRetryPolicy {
+    /// Sets the backoff that is used if the server doesn't ask for a delay
+    #[must_use]
+    pub fn backoff(mut self, backoff: Backoff) -> Self {
+        self.backoff = backoff;
+        self
+    }
+
+    /// Sets the JSON-RPC error codes of rate limited requests
+    #[must_use]
+    pub fn error_codes(mut self, error_codes: impl IntoIterator<Item = i64>) -> Self {
+        self.error_codes = error_codes.into_iter().collect();
+        self
+    }
+
+    fn is_rate_limited(&self, error: &JsonRpcError) -> bool {
+        self.error_codes.contains(&error.code)
+    }
+}
+
+impl RetryPolicy<HttpClientError> for RateLimitRetryPolicy {
+    fn decide(&self, error: &HttpClientError, attempt: u32) -> RetryDecision {
+        match error {
+            HttpClientError::TooManyRequests { retry_after, .. } => RetryDecision::retry_after(
+                retry_after.unwrap_or_else(|| self.backoff.delay(attempt)),
+            ),
+            HttpClientError::JsonRpcError(error) if self.is_rate_limited(error) => {
+                // e.g. `{"rate": {"allowed_rps": 1, "backoff_seconds": 30, "current_rps": 1.1}}`
+                let delay = error
+                    .data
+                    .as_ref()
+                    .and_then(|data| data.pointer("/rate/backoff_seconds"))
+                    .and_then(Value::as_f64)
+                    .filter(|secs| secs.is_finite() && *secs >= 0.)
+                    .map(Duration::from_secs_f64)
+                    .unwrap_or_else(|| self.backoff.delay(attempt));
+                RetryDecision::retry_after(delay)
+            }
+            _ => RetryDecision::give_up(),
+        }
+    }
+}
+
+/// A client that sends failed requests of the inner client again, as decided by its
+/// [`RetryPolicy`].
+///
+/// # Example
+///
+/// ```no_run
+/// use ethers_providers::{Http, Provider, RateLimitRetryPolicy, RetryClient};
+/// use std::str::FromStr;
+///
+/// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+/// let http = Http::from_str("http://localhost:8545")?;
+/// let client = RetryClient::new(http, RateLimitRetryPolicy::default(), 10);
+/// let provider = Provider::new(client);
+/// # Ok(())
+/// # }
+/// ```
+pub struct RetryClient<T: JsonRpcClient> {
+    inner: T,
+    policy: Box<dyn RetryPolicy<T::Error>>,
+    max_retries: u32,
+}
+
+impl<T: JsonRpcClient> RetryClient<T> {
+    /// Creates a new client that retries a request at most `max_retries` times
+    pub fn new(inner: T, policy: impl RetryPolicy<T::Error> + 'static, max_retries: u32) -> Self {
+        Self { inner, policy: Box::new(policy), max_retries }
+    }
+
+    /// Returns the inner client
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Calls the request until it succeeds or the policy gives up
+    async fn retry<F, Fut, R>(&self, method: &str, request: F) -> Result<R, T::Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<R, T::Error>>,
+    {
+        let mut attempt = 0;
+        loop {
+            let err = match request().await {
+                Ok(res) => return Ok(res),
+                Err(err) => err,
+            };
+            let decision = self.policy.decide(&err, attempt);
+            if !decision.retry || attempt >= self.max_retries {
+                return Err(err)
+            }
+            attempt += 1;
+            trace!(method, attempt, delay = ?decision.delay, "retrying request: {}", err);
+            sleep(decision.delay).await;
+        }
+    }
+}
+
+impl<T: JsonRpcClient> fmt::Debug for RetryClient<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryClient")
+            .field("inner", &self.inner)
+            .field("max_retries", &self.max_retries)
+            .finish()
+    }
+}
+
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+impl<T: JsonRpcClient> JsonRpcClient for RetryClient<T> {
+    type Error = T::Error;
+
+    async fn request<A, R>(&self, method: &str, params: A) -> Result<R, Self::Error>
+    where
+        A: fmt::Debug + Serialize + Send + Sync,
+        R: DeserializeOwned,
+    {
+        self.retry(method, || self.inner.request(method, &params)).await
+    }
+
+    async fn request_batch(
+        &self,
+        requests: &[(String, Value)],
+    ) -> Result<Vec<Result<Value, Self::Error>>, Self::Error> {
+        self.retry("batch", || self.inner.request_batch(requests)).await
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+async fn sleep(delay: Duration) {
+    futures_timer::Delay::new(delay).await
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn sleep(delay: Duration) {
+    let _ = wasm_timer::Delay::new(delay).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockError, MockProvider};
+    use ethers_core::types::U64;
+
+    fn rpc_error(code: i64, data: Option<Value>) -> HttpClientError {
+        JsonRpcError { code, message: "rate limited".to_string(), data }.into()
+    }
+
+    #[test]
+    fn backoff_is_capped_and_jittered() {
+        let backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        assert_eq!(backoff.max_delay(0), Duration::from_millis(100));
+        assert_eq!(backoff.max_delay(3), Duration::from_millis(800));
+        assert_eq!(backoff.max_delay(4), Duration::from_secs(1));
+        assert_eq!(backoff.max_delay(100), Duration::from_secs(1));
+        for attempt in 0..10 {
+            assert!(backoff.delay(attempt) <= backoff.max_delay(attempt));
+        }
+    }
+
+    #[test]
+    fn rate_limit_policy_honors_server_delay() {
+        let policy = RateLimitRetryPolicy::default()
+            .backoff(Backoff::new(Duration::from_millis(100), Duration::from_millis(100)));
+
+        let err = HttpClientError::TooManyRequests {
+            retry_after: Some(Duration::from_secs(3)),
+            text: String::new(),
+        };
+        assert_eq!(policy.decide(&err, 0), RetryDecision::retry_after(Duration::from_secs(3)));
+
+        let err = HttpClientError::TooManyRequests { retry_after: None, text: String::new() };
+        let decision = policy.decide(&err, 0);
+        assert!(decision.retry && decision.delay <= Duration::from_millis(100));
+
+        let data = serde_json::json!({ "rate": { "backoff_seconds": 1.5 } });
+        assert_eq!(
+            policy.decide(&rpc_error(RATE_LIMITED_ERROR_CODE, Some(data)), 0),
+            RetryDecision::retry_after(Duration::from_millis(1500))
+        );
+        assert!(policy.decide(&rpc_error(RATE_LIMITED_ERROR_CODE, None), 0).retry);
+
+        assert!(!policy.decide(&rpc_error(-32000, None), 0).retry);
+        let policy = policy.error_codes([-32000]);
+        assert!(policy.decide(&rpc_error(-32000, None), 0).retry);
+        assert!(!policy.decide(&rpc_error(RATE_LIMITED_ERROR_CODE, None), 0).retry);
+    }
+
+    #[tokio::test]
+    async fn retries_until_max_retries() {
+        let mock = MockProvider::new();
+        let retry_empty = |err: &MockError, _attempt: u32| match err {
+            MockError::EmptyResponses => RetryDecision::retry_after(Duration::default()),
+            _ => RetryDecision::give_up(),
+        };
+        let client = RetryClient::new(mock.clone(), retry_empty, 2);
+
+        let res: Result<U64, _> = client.request("eth_blockNumber", ()).await;
+        assert!(matches!(res, Err(MockError::EmptyResponses)));
+        for _ in 0..3 {
+            mock.assert_request("eth_blockNumber", ()).unwrap();
+        }
+        assert!(matches!(
+            mock.assert_request("eth_blockNumber", ()),
+            Err(MockError::EmptyRequests)
+        ));
+
+        mock.push(U64::from(1)).unwrap();
+        let res: U64 = client.request("eth_blockNumber", ()).await.unwrap();
+        assert_eq!(res, U64::from(1));
+    }
+}
@@ -180,8 +180,9 @@ impl JsonRpcClient for Ws {
         // send the data
         self.send(payload)?;
 
-        // wait for the response (the request itself may have errors as well)
-        let res = receiver.await??;
+        // wait for the response (the request itself may have errors as well), the sender is
+        // dropped if the connection is lost before the response arrived
+        let res = receiver.await.map_err(|_| ClientError::ConnectionLost)??;
 
         // parse it
         Ok(serde_json::from_str(res.get())?)
@@ -457,6 +458,10 @@ pub enum ClientError {
     #[error("WebSocket connection closed unexpectedly")]
     UnexpectedClose,
 
+    /// The connection was lost before the response to a request arrived
+    #[error("WebSocket connection lost")]
+    ConnectionLost,
+
     /// Could not create an auth header for websocket handshake
     #[error(transparent)]
     #[cfg(not(target_arch = "wasm32"))]
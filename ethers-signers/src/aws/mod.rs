@@ -202,19 +202,23 @@ impl<'a> AwsSigner<'a> {
         self.sign_digest_with_key(self.key_id.clone(), digest).await
     }
 
+    /// Sign a digest with this signer's key and recover the `v` value of the signature, which
+    /// is 27 or 28
+    #[instrument(err, skip(digest), fields(digest = %hex::encode(&digest)))]
+    async fn sign_digest_recoverable(&self, digest: H256) -> Result<EthSig, AwsSignerError> {
+        let sig = self.sign_digest(digest.into()).await?;
+        let sig = utils::rsig_from_digest_bytes_trial_recovery(&sig, digest.into(), &self.pubkey)?;
+        Ok(rsig_to_ethsig(&sig))
+    }
+
     /// Sign a digest with this signer's key and add the eip155 `v` value
     /// corresponding to the input chain_id
-    #[instrument(err, skip(digest), fields(digest = %hex::encode(&digest)))]
     async fn sign_digest_with_eip155(
         &self,
         digest: H256,
         chain_id: u64,
     ) -> Result<EthSig, AwsSignerError> {
-        let sig = self.sign_digest(digest.into()).await?;
-
-        let sig = utils::rsig_from_digest_bytes_trial_recovery(&sig, digest.into(), &self.pubkey);
-
-        let mut sig = rsig_to_ethsig(&sig);
+        let mut sig = self.sign_digest_recoverable(digest).await?;
         apply_eip155(&mut sig, chain_id);
         Ok(sig)
     }
@@ -234,7 +238,7 @@ impl<'a> super::Signer for AwsSigner<'a> {
         trace!("{:?}", message_hash);
         trace!("{:?}", message);
 
-        self.sign_digest_with_eip155(message_hash, self.chain_id).await
+        self.sign_digest_recoverable(message_hash).await
     }
 
     #[instrument(err)]
@@ -243,7 +247,7 @@ impl<'a> super::Signer for AwsSigner<'a> {
         let chain_id = tx_with_chain.chain_id().map(|id| id.as_u64()).unwrap_or(self.chain_id);
         tx_with_chain.set_chain_id(chain_id);
 
-        let sighash = tx.sighash();
+        let sighash = tx_with_chain.sighash();
         self.sign_digest_with_eip155(sighash, chain_id).await
     }
 
@@ -253,9 +257,7 @@ impl<'a> super::Signer for AwsSigner<'a> {
     ) -> Result<EthSig, Self::Error> {
         let hash = payload.encode_eip712().map_err(|e| Self::Error::Eip712Error(e.to_string()))?;
 
-        let digest = self.sign_digest_with_eip155(hash.into(), self.chain_id).await?;
-
-        Ok(digest)
+        self.sign_digest_recoverable(hash.into()).await
     }
 
     fn address(&self) -> Address {
@@ -280,10 +282,18 @@ mod tests {
         credential::{EnvironmentProvider, StaticProvider},
         Client, HttpClient, Region,
     };
+    use rusoto_mock::{
+        MockCredentialsProvider, MockRequestDispatcher, MultipleMockRequestDispatcher,
+    };
+    use std::str::FromStr;
     use tracing::metadata::LevelFilter;
 
     use super::*;
-    use crate::Signer;
+    use crate::{LocalWallet, Signer};
+    use ethers_core::{
+        k256::{elliptic_curve::sec1::ToEncodedPoint, FieldBytes},
+        types::{TransactionRequest, U256},
+    };
 
     #[allow(dead_code)]
     fn setup_tracing() {
@@ -308,6 +318,97 @@ mod tests {
         KmsClient::new_with_client(client, Region::UsWest1)
     }
 
+    const KEY_ID: &str = "0b4b5c1e-6f5e-4d4a-9c1c-3a6a34c5b7f4";
+
+    /// Returns a client that answers a `GetPublicKey` and a `Sign` request
+    fn mock_client(pubkey: &[u8], signature: &[u8]) -> KmsClient {
+        let dispatcher = MultipleMockRequestDispatcher::new(vec![
+            MockRequestDispatcher::with_status(200).with_body(
+                &serde_json::json!({ "KeyId": KEY_ID, "PublicKey": base64::encode(pubkey) })
+                    .to_string(),
+            ),
+            MockRequestDispatcher::with_status(200).with_body(
+                &serde_json::json!({
+                    "KeyId": KEY_ID,
+                    "Signature": base64::encode(signature),
+                    "SigningAlgorithm": "ECDSA_SHA_256",
+                })
+                .to_string(),
+            ),
+        ]);
+        KmsClient::new_with(dispatcher, MockCredentialsProvider, Region::UsWest1)
+    }
+
+    fn wallet() -> LocalWallet {
+        "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse().unwrap()
+    }
+
+    /// The DER encoded public key of the wallet, as returned by KMS
+    fn kms_pubkey(wallet: &LocalWallet) -> Vec<u8> {
+        // the `SubjectPublicKeyInfo` header of an uncompressed secp256k1 key
+        let mut der = hex::decode("3056301006072a8648ce3d020106052b8104000a034200").unwrap();
+        der.extend_from_slice(wallet.signer().verifying_key().to_encoded_point(false).as_bytes());
+        der
+    }
+
+    /// The DER encoded signature of the digest, as returned by KMS. KMS doesn't normalize `s`,
+    /// so the signature can have a high `s`.
+    fn kms_signature(wallet: &LocalWallet, digest: H256, high_s: bool) -> Vec<u8> {
+        let sig = wallet.sign_hash(digest);
+        let s = if high_s {
+            U256::from_str("fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141")
+                .unwrap() -
+                sig.s
+        } else {
+            sig.s
+        };
+        let (mut r_bytes, mut s_bytes) = ([0u8; 32], [0u8; 32]);
+        sig.r.to_big_endian(&mut r_bytes);
+        s.to_big_endian(&mut s_bytes);
+        let sig = KSig::from_scalars(
+            *FieldBytes::from_slice(&r_bytes),
+            *FieldBytes::from_slice(&s_bytes),
+        )
+        .unwrap();
+        sig.to_der().as_bytes().to_vec()
+    }
+
+    #[tokio::test]
+    async fn signs_messages_with_mocked_kms() {
+        let wallet = wallet();
+        let message = b"hello";
+        let signature = kms_signature(&wallet, hash_message(message), true);
+        let client = mock_client(&kms_pubkey(&wallet), &signature);
+
+        let signer = AwsSigner::new(&client, KEY_ID, 1).await.unwrap();
+        assert_eq!(signer.address(), wallet.address());
+
+        let sig = signer.sign_message(message).await.unwrap();
+        assert_eq!(sig, wallet.sign_message(message).await.unwrap());
+        assert!(sig.v == 27 || sig.v == 28);
+        sig.verify(&message[..], wallet.address()).unwrap();
+    }
+
+    #[tokio::test]
+    async fn signs_transactions_with_eip155() {
+        let chain_id = 5u64;
+        let wallet = wallet().with_chain_id(chain_id);
+        let tx: TypedTransaction =
+            TransactionRequest::pay(Address::zero(), 100).nonce(0).gas(21000).gas_price(1).into();
+
+        // the transaction is signed with the chain id of the signer
+        let mut tx_with_chain = tx.clone();
+        tx_with_chain.set_chain_id(chain_id);
+        let signature = kms_signature(&wallet, tx_with_chain.sighash(), false);
+        let client = mock_client(&kms_pubkey(&wallet), &signature);
+
+        let signer = AwsSigner::new(&client, KEY_ID, chain_id).await.unwrap();
+        let sig = signer.sign_transaction(&tx).await.unwrap();
+        assert_eq!(sig, wallet.sign_transaction(&tx).await.unwrap());
+        assert!(sig.v == chain_id * 2 + 35 || sig.v == chain_id * 2 + 36);
+        sig.verify(tx_with_chain.sighash(), wallet.address()).unwrap();
+    }
+
     #[tokio::test]
     async fn it_signs_messages() {
         let chain_id = 1;
@@ -46,17 +46,14 @@ pub(super) fn rsig_from_digest_bytes_trial_recovery(
     sig: &KSig,
     digest: [u8; 32],
     vk: &VerifyingKey,
-) -> RSig {
-    let sig_0 = RSig::new(sig, Id::new(0).unwrap()).unwrap();
-    let sig_1 = RSig::new(sig, Id::new(1).unwrap()).unwrap();
-
-    if check_candidate(&sig_0, digest, vk) {
-        sig_0
-    } else if check_candidate(&sig_1, digest, vk) {
-        sig_1
-    } else {
-        panic!("bad sig");
+) -> Result<RSig, AwsSignerError> {
+    for id in 0..2 {
+        let candidate = RSig::new(sig, Id::new(id)?)?;
+        if check_candidate(&candidate, digest, vk) {
+            return Ok(candidate)
+        }
     }
+    Err(AwsSignerError::from("Signature does not match the public key of the KMS key".to_owned()))
 }
 
 /// Modify the v value of a signature to conform to eip155
@@ -83,7 +83,7 @@ impl LedgerEthereum {
         };
 
         let answer = block_on(transport.exchange(&command))?;
-        let result = answer.data().ok_or(LedgerError::UnexpectedNullResponse)?;
+        let result = Self::answer_data(&command, &answer)?;
 
         let address = {
             // extract the address from the response
@@ -97,8 +97,8 @@ impl LedgerEthereum {
         Ok(address)
     }
 
-    /// Returns the semver of the Ethereum ledger app
-    pub async fn version(&self) -> Result<String, LedgerError> {
+    /// Returns the version and settings of the Ethereum ledger app
+    pub async fn version(&self) -> Result<LedgerVersion, LedgerError> {
         let transport = self.transport.lock().await;
 
         let command = APDUCommand {
@@ -110,9 +110,9 @@ impl LedgerEthereum {
         };
 
         let answer = block_on(transport.exchange(&command))?;
-        let result = answer.data().ok_or(LedgerError::UnexpectedNullResponse)?;
+        let result = Self::answer_data(&command, &answer)?;
 
-        Ok(format!("{}.{}.{}", result[1], result[2], result[3]))
+        LedgerVersion::from_configuration(result)
     }
 
     /// Signs an Ethereum transaction (requires confirmation on the ledger)
@@ -173,7 +173,7 @@ impl LedgerEthereum {
         // See comment for v1.6.0 requirement
         // https://github.com/LedgerHQ/app-ethereum/issues/105#issuecomment-765316999
         let req = semver::VersionReq::parse(EIP712_MIN_VERSION)?;
-        let version = semver::Version::parse(&self.version().await?)?;
+        let version = semver::Version::from(self.version().await?);
 
         // Enforce app version is greater than EIP712_MIN_VERSION
         if !req.matches(&version) {
@@ -217,7 +217,7 @@ impl LedgerEthereum {
             command.data = APDUData::new(&data);
 
             let answer = block_on(transport.exchange(&command))?;
-            result = answer.data().ok_or(LedgerError::UnexpectedNullResponse)?.to_vec();
+            result = Self::answer_data(&command, &answer)?.to_vec();
 
             // We need more data
             command.p1 = P1::MORE as u8;
@@ -229,6 +229,17 @@ impl LedgerEthereum {
         Ok(Signature { r, s, v })
     }
 
+    // helper which returns the data of a successful answer, or the error of its status word
+    fn answer_data<'a>(
+        command: &APDUCommand,
+        answer: &'a APDUAnswer,
+    ) -> Result<&'a [u8], LedgerError> {
+        match status_error(command.ins, answer.retcode()) {
+            Some(err) => Err(err),
+            None => answer.data().ok_or(LedgerError::UnexpectedNullResponse),
+        }
+    }
+
     // helper which converts a derivation path to bytes
     fn path_to_bytes(derivation: &DerivationType) -> Vec<u8> {
         let derivation = derivation.to_string();
@@ -250,6 +261,22 @@ impl LedgerEthereum {
     }
 }
 
+/// Returns the error of the status word of an answer to the instruction, or `None` on success
+fn status_error(ins: u8, retcode: u16) -> Option<LedgerError> {
+    let err = match retcode {
+        status::OK => return None,
+        status::INVALID_DATA if ins == INS::SIGN as u8 || ins == INS::SIGN_ETH_EIP_712 as u8 => {
+            LedgerError::BlindSigningDisabled
+        }
+        status::INS_NOT_SUPPORTED |
+        status::CLA_NOT_SUPPORTED |
+        status::APP_NOT_OPEN |
+        status::LOCKED_OR_APP_NOT_OPEN => LedgerError::NotEthereumApp,
+        retcode => LedgerError::UnexpectedStatus(retcode),
+    };
+    Some(err)
+}
+
 #[cfg(all(test, feature = "ledger"))]
 mod tests {
     use super::*;
@@ -319,7 +346,51 @@ mod tests {
         let ledger = LedgerEthereum::new(DerivationType::LedgerLive(0), 1).await.unwrap();
 
         let version = ledger.version().await.unwrap();
-        assert_eq!(version, "1.3.7");
+        assert_eq!(version.to_string(), "1.3.7");
+    }
+
+    #[test]
+    fn parses_app_configuration() {
+        let version = LedgerVersion::from_configuration(&[0x01, 1, 9, 17]).unwrap();
+        assert_eq!(version.to_string(), "1.9.17");
+        assert!(version.blind_signing_enabled);
+        assert!(semver::VersionReq::parse(EIP712_MIN_VERSION).unwrap().matches(&version.into()));
+
+        let version = LedgerVersion::from_configuration(&[0x0a, 1, 3, 7]).unwrap();
+        assert!(!version.blind_signing_enabled);
+        assert!(!semver::VersionReq::parse(EIP712_MIN_VERSION).unwrap().matches(&version.into()));
+
+        assert!(matches!(
+            LedgerVersion::from_configuration(&[0x01, 1]),
+            Err(LedgerError::UnexpectedNullResponse)
+        ));
+    }
+
+    #[test]
+    fn maps_status_words() {
+        assert!(status_error(INS::SIGN as u8, status::OK).is_none());
+        assert!(matches!(
+            status_error(INS::SIGN_ETH_EIP_712 as u8, status::INVALID_DATA),
+            Some(LedgerError::BlindSigningDisabled)
+        ));
+        assert!(matches!(
+            status_error(INS::SIGN as u8, status::INVALID_DATA),
+            Some(LedgerError::BlindSigningDisabled)
+        ));
+        assert!(matches!(
+            status_error(INS::SIGN_PERSONAL_MESSAGE as u8, status::INVALID_DATA),
+            Some(LedgerError::UnexpectedStatus(0x6a80))
+        ));
+        for retcode in [0x6d00, 0x6e00, 0x6e01, 0x6511] {
+            assert!(matches!(
+                status_error(INS::GET_PUBLIC_KEY as u8, retcode),
+                Some(LedgerError::NotEthereumApp)
+            ));
+        }
+        assert!(matches!(
+            status_error(INS::SIGN as u8, 0x6985),
+            Some(LedgerError::UnexpectedStatus(0x6985))
+        ));
     }
 
     #[tokio::test]
@@ -51,6 +51,75 @@ pub enum LedgerError {
     /// Error when signing EIP712 struct with not compatible Ledger ETH app
     #[error("Ledger ethereum app requires at least version: {0:?}")]
     UnsupportedAppVersion(String),
+    /// The device refused to sign data it cannot display, because blind signing is disabled
+    #[error("Blind signing is disabled, enable it in the settings of the Ledger ethereum app")]
+    BlindSigningDisabled,
+    /// The app that is open on the device is not the Ethereum app
+    #[error("The Ledger ethereum app is not open on the device")]
+    NotEthereumApp,
+    /// The device responded with an error status word
+    #[error("Ledger device returned status 0x{0:04x}")]
+    UnexpectedStatus(u16),
+}
+
+/// The version and settings of the Ethereum ledger app
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct LedgerVersion {
+    /// The major version
+    pub major: u8,
+    /// The minor version
+    pub minor: u8,
+    /// The patch version
+    pub patch: u8,
+    /// Whether blind signing, i.e. signing of contract data and EIP712 hashes, is enabled
+    pub blind_signing_enabled: bool,
+}
+
+impl LedgerVersion {
+    /// Parses the response to `GET_APP_CONFIGURATION`, which is a flags byte followed by the
+    /// major, minor and patch version
+    pub(crate) fn from_configuration(config: &[u8]) -> Result<Self, LedgerError> {
+        match *config {
+            [flags, major, minor, patch, ..] => Ok(Self {
+                major,
+                minor,
+                patch,
+                blind_signing_enabled: flags & APP_FLAG_BLIND_SIGNING != 0,
+            }),
+            _ => Err(LedgerError::UnexpectedNullResponse),
+        }
+    }
+}
+
+impl From<LedgerVersion> for semver::Version {
+    fn from(version: LedgerVersion) -> Self {
+        semver::Version::new(version.major as u64, version.minor as u64, version.patch as u64)
+    }
+}
+
+impl fmt::Display for LedgerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// The flag of the app configuration that is set if blind signing is enabled
+pub const APP_FLAG_BLIND_SIGNING: u8 = 0x01;
+
+/// Status words of APDU responses
+pub mod status {
+    /// The command succeeded
+    pub const OK: u16 = 0x9000;
+    /// The data is invalid, returned by the signing commands if blind signing is disabled
+    pub const INVALID_DATA: u16 = 0x6a80;
+    /// The instruction is not supported by the open app
+    pub const INS_NOT_SUPPORTED: u16 = 0x6d00;
+    /// The class is not supported by the open app
+    pub const CLA_NOT_SUPPORTED: u16 = 0x6e00;
+    /// No app is open, returned by the dashboard
+    pub const APP_NOT_OPEN: u16 = 0x6e01;
+    /// The device is locked or no app is open
+    pub const LOCKED_OR_APP_NOT_OPEN: u16 = 0x6511;
 }
 
 pub const P1_FIRST: u8 = 0x00;
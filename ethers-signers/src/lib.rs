@@ -20,7 +20,7 @@ mod ledger;
 #[cfg(feature = "ledger")]
 pub use ledger::{
     app::LedgerEthereum as Ledger,
-    types::{DerivationType as HDPath, LedgerError},
+    types::{DerivationType as HDPath, LedgerError, LedgerVersion},
 };
 
 #[cfg(feature = "trezor")]
@@ -2,7 +2,10 @@
 //! specifications
 use crate::{Wallet, WalletError};
 
-use coins_bip32::path::DerivationPath;
+use coins_bip32::{
+    path::DerivationPath,
+    prelude::{Parent, XPriv},
+};
 use coins_bip39::{Mnemonic, Wordlist};
 use ethers_core::{
     k256::ecdsa::SigningKey,
@@ -15,6 +18,9 @@ use thiserror::Error;
 
 const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0/";
 
+/// The parent of the keys at [`DEFAULT_DERIVATION_PATH_PREFIX`]
+const DEFAULT_DERIVATION_PATH_PARENT: &str = "m/44'/60'/0'/0";
+
 /// Represents a structure that can resolve into a `Wallet<SigningKey>`.
 #[derive(Clone, Debug, PartialEq)]
 pub struct MnemonicBuilder<W: Wordlist> {
@@ -146,14 +152,52 @@ impl<W: Wordlist> MnemonicBuilder<W> {
     /// Builds a `LocalWallet` using the parameters set in mnemonic builder. This method expects
     /// the phrase field to be set.
     pub fn build(&self) -> Result<Wallet<SigningKey>, WalletError> {
-        let mnemonic = match &self.phrase {
+        let mnemonic = self.read_mnemonic()?;
+        self.mnemonic_to_wallet(&mnemonic)
+    }
+
+    /// Builds the `count` wallets at the derivation paths "m/44'/60'/0'/0/{index}" starting at
+    /// the index `start`, e.g. to list the accounts of a HD wallet. The derivation path of the
+    /// builder is ignored. This method expects the phrase field to be set.
+    ///
+    /// The seed is only computed once for all wallets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_signers::{MnemonicBuilder, coins_bip39::English};
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    ///
+    /// let wallets = MnemonicBuilder::<English>::default()
+    ///     .phrase("abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about")
+    ///     .derive_range(0, 10)?;
+    /// assert_eq!(wallets.len(), 10);
+    ///
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn derive_range(
+        &self,
+        start: u32,
+        count: u32,
+    ) -> Result<Vec<Wallet<SigningKey>>, WalletError> {
+        let mnemonic = self.read_mnemonic()?;
+        let parent = mnemonic
+            .master_key(self.password.as_deref())?
+            .derive_path(&DerivationPath::from_str(DEFAULT_DERIVATION_PATH_PARENT)?)?;
+        (start..start.saturating_add(count))
+            .map(|index| key_to_wallet(&parent.derive_child(index)?))
+            .collect()
+    }
+
+    fn read_mnemonic(&self) -> Result<Mnemonic<W>, WalletError> {
+        match &self.phrase {
             Some(path_or_string) => {
                 let phrase = path_or_string.read()?;
-                Mnemonic::<W>::new_from_phrase(&phrase)?
+                Ok(Mnemonic::<W>::new_from_phrase(&phrase)?)
             }
-            None => return Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
-        };
-        self.mnemonic_to_wallet(&mnemonic)
+            None => Err(MnemonicBuilderError::ExpectedPhraseNotFound.into()),
+        }
     }
 
     /// Builds a `LocalWallet` using the parameters set in the mnemonic builder and constructing
@@ -180,14 +224,18 @@ impl<W: Wordlist> MnemonicBuilder<W> {
     ) -> Result<Wallet<SigningKey>, WalletError> {
         let derived_priv_key =
             mnemonic.derive_key(&self.derivation_path, self.password.as_deref())?;
-        let key: &coins_bip32::prelude::SigningKey = derived_priv_key.as_ref();
-        let signer = SigningKey::from_bytes(&key.to_bytes())?;
-        let address = secret_key_to_address(&signer);
-
-        Ok(Wallet::<SigningKey> { signer, address, chain_id: 1 })
+        key_to_wallet(&derived_priv_key)
     }
 }
 
+fn key_to_wallet(derived_priv_key: &XPriv) -> Result<Wallet<SigningKey>, WalletError> {
+    let key: &coins_bip32::prelude::SigningKey = derived_priv_key.as_ref();
+    let signer = SigningKey::from_bytes(&key.to_bytes())?;
+    let address = secret_key_to_address(&signer);
+
+    Ok(Wallet::<SigningKey> { signer, address, chain_id: 1 })
+}
+
 #[cfg(test)]
 #[cfg(not(target_arch = "wasm32"))]
 mod tests {
@@ -247,6 +295,38 @@ mod tests {
         })
     }
 
+    #[test]
+    fn derives_range_of_wallets() {
+        let phrase =
+            "work man father plunge mystery proud hollow address reunion sauce theory bonus";
+        let builder = MnemonicBuilder::<English>::default().phrase(phrase).password("TREZOR123");
+
+        let wallets = builder.derive_range(0, 3).unwrap();
+        assert_eq!(wallets.len(), 3);
+        assert_eq!(
+            &to_checksum(&wallets[0].address, None),
+            "0x431a00DA1D54c281AeF638A73121B3D153e0b0F6"
+        );
+        for (index, wallet) in (0u32..).zip(&wallets) {
+            let expected = builder.clone().index(index).unwrap().build().unwrap();
+            assert_eq!(wallet.address, expected.address);
+        }
+
+        let wallets = builder.derive_range(2, 2).unwrap();
+        assert_eq!(
+            wallets.iter().map(|wallet| wallet.address).collect::<Vec<_>>(),
+            vec![
+                builder.clone().index(2u32).unwrap().build().unwrap().address,
+                builder.clone().index(3u32).unwrap().build().unwrap().address,
+            ]
+        );
+
+        assert!(matches!(
+            MnemonicBuilder::<English>::default().derive_range(0, 1),
+            Err(WalletError::MnemonicBuilderError(MnemonicBuilderError::ExpectedPhraseNotFound))
+        ));
+    }
+
     #[tokio::test]
     async fn mnemonic_write_read() {
         let dir = tempdir().unwrap();
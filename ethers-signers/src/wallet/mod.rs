@@ -18,7 +18,7 @@ use ethers_core::{
     },
     types::{
         transaction::{eip2718::TypedTransaction, eip712::Eip712},
-        Address, Signature, H256, U256,
+        Address, Bytes, Signature, H256, U256,
     },
     utils::hash_message,
 };
@@ -130,6 +130,51 @@ impl<D: DigestSigner<Sha256Proxy, RecoverableSignature>> Wallet<D> {
         sig
     }
 
+    /// Signs the provided transaction without filling any of its fields, e.g. on an air-gapped
+    /// machine.
+    ///
+    /// Unlike [`Signer::sign_transaction`] the chain id of the wallet is not used, the chain id,
+    /// nonce, gas limit and fees of the transaction must be set, otherwise
+    /// [`WalletError::MissingTransactionField`] is returned.
+    pub fn sign_transaction_offline(
+        &self,
+        tx: &TypedTransaction,
+    ) -> Result<Signature, WalletError> {
+        if let Some(field) = missing_offline_field(tx) {
+            return Err(WalletError::MissingTransactionField(field))
+        }
+        Ok(self.sign_transaction_sync(tx))
+    }
+
+    /// Signs the provided transaction like [`Wallet::sign_transaction_offline`] and returns the
+    /// RLP encoded signed transaction, which can be broadcast with `eth_sendRawTransaction` from
+    /// another machine.
+    ///
+    /// ```
+    /// use ethers_core::types::{transaction::eip2718::TypedTransaction, TransactionRequest};
+    /// use ethers_signers::{LocalWallet, Signer};
+    ///
+    /// # fn foo() -> Result<(), Box<dyn std::error::Error>> {
+    /// let wallet: LocalWallet =
+    ///     "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse()?;
+    /// let tx: TypedTransaction = TransactionRequest::pay(wallet.address(), 100)
+    ///     .chain_id(1)
+    ///     .nonce(0)
+    ///     .gas(21_000)
+    ///     .gas_price(20_000_000_000u64)
+    ///     .into();
+    /// let raw_tx = wallet.sign_raw_transaction_offline(&tx)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn sign_raw_transaction_offline(
+        &self,
+        tx: &TypedTransaction,
+    ) -> Result<Bytes, WalletError> {
+        let signature = self.sign_transaction_offline(tx)?;
+        Ok(tx.rlp_signed(&signature))
+    }
+
     /// Signs the provided hash.
     pub fn sign_hash(&self, hash: H256) -> Signature {
         let recoverable_sig: RecoverableSignature =
@@ -151,6 +196,30 @@ impl<D: DigestSigner<Sha256Proxy, RecoverableSignature>> Wallet<D> {
     }
 }
 
+/// Returns the first field a transaction needs to be signed offline that is not set
+fn missing_offline_field(tx: &TypedTransaction) -> Option<&'static str> {
+    if tx.chain_id().is_none() {
+        return Some("chain_id")
+    }
+    if tx.nonce().is_none() {
+        return Some("nonce")
+    }
+    if tx.gas().is_none() {
+        return Some("gas")
+    }
+    match tx {
+        TypedTransaction::Eip1559(inner) if inner.max_fee_per_gas.is_none() => {
+            Some("max_fee_per_gas")
+        }
+        TypedTransaction::Eip1559(inner) if inner.max_priority_fee_per_gas.is_none() => {
+            Some("max_priority_fee_per_gas")
+        }
+        TypedTransaction::Eip1559(_) => None,
+        _ if tx.gas_price().is_none() => Some("gas_price"),
+        _ => None,
+    }
+}
+
 // do not log the signer
 impl<D: DigestSigner<Sha256Proxy, RecoverableSignature>> fmt::Debug for Wallet<D> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -46,6 +46,9 @@ pub enum WalletError {
     /// Error type from Eip712Error message
     #[error("error encoding eip712 struct: {0:?}")]
     Eip712Error(String),
+    /// A field that is required to sign a transaction offline is not set
+    #[error("the `{0}` field of the transaction must be set to sign it offline")]
+    MissingTransactionField(&'static str),
 }
 
 impl Clone for Wallet<SigningKey> {
@@ -252,6 +255,70 @@ mod tests {
         assert!(sig.verify(sighash, wallet.address).is_ok());
     }
 
+    #[test]
+    #[cfg(not(feature = "celo"))]
+    fn signs_tx_offline() {
+        use crate::TypedTransaction;
+        use ethers_core::types::{Eip1559TransactionRequest, TransactionRequest};
+
+        let wallet: Wallet<SigningKey> =
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318".parse().unwrap();
+        let to = "F0109fC8DF283027b6285cc889F5aA624EaC1F55".parse::<Address>().unwrap();
+
+        let txs: Vec<TypedTransaction> = vec![
+            TransactionRequest::new()
+                .from(wallet.address)
+                .to(to)
+                .value(1_000_000_000)
+                .gas(2_000_000)
+                .nonce(0)
+                .gas_price(21_000_000_000u128)
+                .chain_id(1)
+                .into(),
+            Eip1559TransactionRequest::new()
+                .from(wallet.address)
+                .to(to)
+                .value(1_000_000_000)
+                .gas(2_000_000)
+                .nonce(3)
+                .max_fee_per_gas(30_000_000_000u128)
+                .max_priority_fee_per_gas(1_000_000_000)
+                .chain_id(5)
+                .into(),
+        ];
+        for tx in txs {
+            let raw = wallet.sign_raw_transaction_offline(&tx).unwrap();
+            // the sender is recovered from the signature when decoding
+            let (decoded, sig) =
+                TypedTransaction::decode_signed(&ethers_core::utils::rlp::Rlp::new(&raw)).unwrap();
+            assert_eq!(decoded, tx);
+            assert_eq!(sig, wallet.sign_transaction_offline(&tx).unwrap());
+            assert_eq!(sig.recover(tx.sighash()).unwrap(), wallet.address);
+        }
+    }
+
+    #[test]
+    #[cfg(not(feature = "celo"))]
+    fn offline_signing_requires_all_fields() {
+        use crate::TypedTransaction;
+        use ethers_core::types::{Eip1559TransactionRequest, TransactionRequest};
+
+        let wallet = Wallet::<SigningKey>::new(&mut rand::thread_rng()).with_chain_id(1u64);
+        let missing = |tx: TypedTransaction| match wallet.sign_transaction_offline(&tx) {
+            Err(WalletError::MissingTransactionField(field)) => field,
+            res => panic!("unexpected result {:?}", res),
+        };
+
+        let tx = TransactionRequest::new().gas(21_000).nonce(0);
+        // the chain id of the wallet is not used
+        assert_eq!(missing(tx.clone().gas_price(1).into()), "chain_id");
+        assert_eq!(missing(tx.chain_id(1).into()), "gas_price");
+
+        let tx = Eip1559TransactionRequest::new().chain_id(1).gas(21_000).max_fee_per_gas(2);
+        assert_eq!(missing(tx.clone().into()), "nonce");
+        assert_eq!(missing(tx.nonce(0).into()), "max_priority_fee_per_gas");
+    }
+
     #[test]
     fn key_to_address() {
         let wallet: Wallet<SigningKey> =
@@ -0,0 +1,31 @@
+//! deserialize AST node attributes
+#[macro_use]
+extern crate criterion;
+
+use criterion::Criterion;
+use ethers_solc::artifacts::{Ast, Node, NodeType};
+
+fn attribute_benchmark(c: &mut Criterion) {
+    let ast: Ast = serde_json::from_str(include_str!("../test-data/ast/ast-erc4626.json")).unwrap();
+    let functions = ast.collect(NodeType::FunctionDefinition);
+
+    let mut group = c.benchmark_group("ast attribute");
+    group.bench_function("cloned", |b| {
+        b.iter(|| {
+            for function in &functions {
+                let params = function.other.get("parameters").cloned().unwrap();
+                let _: Node = serde_json::from_value(params).unwrap();
+            }
+        });
+    });
+    group.bench_function("borrowed", |b| {
+        b.iter(|| {
+            for function in &functions {
+                let _: Node = function.attribute_ref("parameters").unwrap();
+            }
+        });
+    });
+}
+
+criterion_group!(benches, attribute_benchmark);
+criterion_main!(benches);
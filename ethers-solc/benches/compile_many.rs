@@ -32,6 +32,35 @@ fn compile_many_benchmark(c: &mut Criterion) {
     }
 }
 
+/// compiles a project with sources for 3 different solc versions
+#[cfg(all(feature = "svm-solc", feature = "project-util"))]
+fn compile_versions_benchmark(c: &mut Criterion) {
+    use ethers_solc::{project_util::TempProject, ConfigurableArtifacts};
+
+    let mut project = TempProject::<ConfigurableArtifacts>::dapptools().unwrap();
+    for (name, pragma) in [("A", "=0.6.12"), ("B", "=0.7.6"), ("C", "=0.8.10")] {
+        project
+            .add_source(name, format!("pragma solidity {};\ncontract {} {{}}", pragma, name))
+            .unwrap();
+    }
+    project.project_mut().cached = false;
+    project.project_mut().no_artifacts = true;
+
+    let mut group = c.benchmark_group("compile versions");
+    group.sample_size(10);
+    for jobs in [1, 3] {
+        project.project_mut().set_solc_jobs(jobs);
+        group.bench_function(format!("{} jobs", jobs), |b| {
+            b.iter(|| {
+                let _ = project.compile().unwrap();
+            });
+        });
+    }
+}
+
+#[cfg(not(all(feature = "svm-solc", feature = "project-util")))]
+fn compile_versions_benchmark(_: &mut Criterion) {}
+
 fn load_compiler_inputs() -> Vec<CompilerInput> {
     let mut inputs = Vec::new();
     for file in std::fs::read_dir(Path::new(&env!("CARGO_MANIFEST_DIR")).join("test-data/in"))
@@ -47,5 +76,5 @@ fn load_compiler_inputs() -> Vec<CompilerInput> {
     inputs
 }
 
-criterion_group!(benches, compile_many_benchmark);
+criterion_group!(benches, compile_many_benchmark, compile_versions_benchmark);
 criterion_main!(benches);
@@ -9,7 +9,7 @@ use semver::Version;
 use serde::{de::DeserializeOwned, Serialize};
 use std::{
     borrow::Cow,
-    collections::btree_map::BTreeMap,
+    collections::{btree_map::BTreeMap, HashSet},
     fmt, fs, io,
     path::{Path, PathBuf},
 };
@@ -77,10 +77,16 @@ pub struct ArtifactFile<T> {
 
 impl<T: Serialize> ArtifactFile<T> {
     /// Writes the given contract to the `out` path creating all parent directories
+    ///
+    /// The file is not rewritten if it already has the same content, so that its modification
+    /// date stays the same.
     pub fn write(&self) -> Result<()> {
+        let content = serde_json::to_vec_pretty(&self.artifact)?;
+        if fs::read(&self.file).map(|existing| existing == content).unwrap_or_default() {
+            return Ok(())
+        }
         utils::create_parent_dir_all(&self.file)?;
-        fs::write(&self.file, serde_json::to_vec_pretty(&self.artifact)?)
-            .map_err(|err| SolcError::io(err, &self.file))?;
+        fs::write(&self.file, content).map_err(|err| SolcError::io(err, &self.file))?;
         Ok(())
     }
 }
@@ -264,6 +270,28 @@ impl<T> Artifacts<T> {
         Artifacts(artifacts)
     }
 
+    /// Returns the artifact `T` of the contract with the given name in the given source file
+    pub fn get(&self, file: impl AsRef<str>, contract_name: impl AsRef<str>) -> Option<&T> {
+        self.0
+            .get(file.as_ref())
+            .and_then(|contracts| contracts.get(contract_name.as_ref()))
+            .and_then(|c| c.get(0).map(|a| &a.artifact))
+    }
+
+    /// Returns the `ArtifactFile` of the contract in the given source file that was compiled with
+    /// the given version
+    pub fn find_artifact_file(
+        &self,
+        file: impl AsRef<str>,
+        contract_name: impl AsRef<str>,
+        version: &Version,
+    ) -> Option<&ArtifactFile<T>> {
+        self.0
+            .get(file.as_ref())
+            .and_then(|contracts| contracts.get(contract_name.as_ref()))
+            .and_then(|artifacts| artifacts.iter().find(|a| a.version == *version))
+    }
+
     /// Finds the first artifact `T` with a matching contract name
     pub fn find(&self, contract_name: impl AsRef<str>) -> Option<&T> {
         let contract_name = contract_name.as_ref();
@@ -457,7 +485,7 @@ pub trait ArtifactOutput {
         artifacts.join_all(&layout.artifacts);
         artifacts.write_all()?;
 
-        self.write_extras(contracts, layout)?;
+        self.write_artifact_extras(contracts, &artifacts)?;
 
         Ok(artifacts)
     }
@@ -475,6 +503,10 @@ pub trait ArtifactOutput {
     /// respective fields of the [`Contract`] will `None`. If they'll be manually added to the
     /// `output_selection`, then we're also creating individual files for this output, such as
     /// `Greeter.iropt`, `Gretter.ewasm`
+    ///
+    /// The extra files are written next to the default artifact location of the contract, see
+    /// [`Self::output_file`], which doesn't account for artifacts that are nested because of
+    /// same-named contracts. [`Self::on_output`] uses [`Self::write_artifact_extras`] instead.
     fn write_extras(
         &self,
         contracts: &VersionedContracts,
@@ -500,6 +532,27 @@ pub trait ArtifactOutput {
         Ok(())
     }
 
+    /// Writes additional files for the contracts like [`Self::write_extras`], but next to the
+    /// contract's artifact in `artifacts`.
+    fn write_artifact_extras(
+        &self,
+        contracts: &VersionedContracts,
+        artifacts: &Artifacts<Self::Artifact>,
+    ) -> Result<()> {
+        for (file, contracts) in contracts.as_ref().iter() {
+            for (name, versioned_contracts) in contracts {
+                for c in versioned_contracts {
+                    if let Some(artifact) = artifacts.find_artifact_file(file, name, &c.version) {
+                        utils::create_parent_dir_all(&artifact.file)?;
+                        self.write_contract_extras(&c.contract, &artifact.file)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
     /// Returns the file name for the contract's artifact
     /// `Greeter.json`
     fn output_file_name(name: impl AsRef<str>) -> PathBuf {
@@ -609,13 +662,38 @@ pub trait ArtifactOutput {
     ///
     /// **Note:** This does only convert, but _NOT_ write the artifacts to disk, See
     /// [`Self::on_output()`]
+    ///
+    /// # Collisions
+    ///
+    /// Different source files can define contracts with the same name, like `src/Token.sol` and
+    /// `lib/vendor/Token.sol`, which would share the same default output file
+    /// `Token.sol/Token.json`. Output files are compared case insensitively, since the artifacts
+    /// could be written to a case insensitive file system.
+    ///
+    /// The source file with the fewest path components, then the one that comes first in
+    /// lexicographic order, keeps the default output file. The output files of the other
+    /// contracts are nested in the parent directories of their source file, starting with the
+    /// closest one, until the output file is unique: `vendor/Token.sol/Token.json`.
+    ///
+    /// Source files with the same file name are always recompiled together, so their output
+    /// files don't depend on which of them changed.
     fn output_to_artifacts(
         &self,
         contracts: &VersionedContracts,
         sources: &VersionedSourceFiles,
     ) -> Artifacts<Self::Artifact> {
+        let mut files = contracts.as_ref().iter().collect::<Vec<_>>();
+        files.sort_by(|(a, _), (b, _)| {
+            Path::new(a)
+                .components()
+                .count()
+                .cmp(&Path::new(b).components().count())
+                .then_with(|| a.cmp(b))
+        });
+
+        let mut taken_paths = HashSet::new();
         let mut artifacts = ArtifactsMap::new();
-        for (file, contracts) in contracts.as_ref().iter() {
+        for (file, contracts) in files {
             let mut entries = BTreeMap::new();
             for (name, versioned_contracts) in contracts {
                 let mut contracts = Vec::with_capacity(versioned_contracts.len());
@@ -628,6 +706,9 @@ pub trait ArtifactOutput {
                     } else {
                         Self::output_file(file, name)
                     };
+                    let artifact_path =
+                        conflict_free_output_file(&taken_paths, artifact_path, file);
+                    taken_paths.insert(artifact_path_key(&artifact_path));
 
                     let artifact = self.contract_to_artifact(
                         file,
@@ -651,6 +732,41 @@ pub trait ArtifactOutput {
     }
 }
 
+/// Returns the key of the artifact path in the set of taken paths, paths that only differ in case
+/// are the same file on case insensitive file systems
+fn artifact_path_key(path: &Path) -> String {
+    path.to_string_lossy().to_lowercase()
+}
+
+/// Returns `artifact_path` if it's not taken yet, otherwise nests it in the parent directories of
+/// the contract's source file until it's unique.
+///
+/// `lib/vendor/Token.sol` with `Token.sol/Token.json` -> `vendor/Token.sol/Token.json`
+fn conflict_free_output_file(
+    taken_paths: &HashSet<String>,
+    artifact_path: PathBuf,
+    contract_file: impl AsRef<Path>,
+) -> PathBuf {
+    if !taken_paths.contains(&artifact_path_key(&artifact_path)) {
+        return artifact_path
+    }
+    let mut candidate = artifact_path;
+    let mut parent = contract_file.as_ref().parent();
+    while let Some(dir) = parent.and_then(|p| p.file_name()) {
+        candidate = Path::new(dir).join(candidate);
+        if !taken_paths.contains(&artifact_path_key(&candidate)) {
+            break
+        }
+        parent = parent.and_then(|p| p.parent());
+    }
+    tracing::trace!(
+        "conflicting output file for {}, using {}",
+        contract_file.as_ref().display(),
+        candidate.display()
+    );
+    candidate
+}
+
 /// An `Artifact` implementation that uses a compact representation
 ///
 /// Creates a single json artifact with
@@ -727,6 +843,7 @@ impl ArtifactOutput for MinimalCombinedArtifactsHardhatFallback {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::compile::output::contracts::VersionedContract;
 
     #[test]
     fn is_artifact() {
@@ -735,4 +852,37 @@ mod tests {
         assert_artifact::<CompactContractBytecode>();
         assert_artifact::<serde_json::Value>();
     }
+
+    #[test]
+    fn nests_conflicting_output_files() {
+        let contract: Contract = serde_json::from_str("{}").unwrap();
+        let version = Version::new(0, 8, 13);
+        let files = ["src/Token.sol", "lib/vendor/Token.sol", "lib/other/vendor/token.sol"];
+        let contracts = VersionedContracts(
+            files
+                .iter()
+                .map(|file| {
+                    let versioned =
+                        VersionedContract { contract: contract.clone(), version: version.clone() };
+                    (file.to_string(), BTreeMap::from([("Token".to_string(), vec![versioned])]))
+                })
+                .collect(),
+        );
+
+        let artifacts = MinimalCombinedArtifacts::default()
+            .output_to_artifacts(&contracts, &VersionedSourceFiles::default());
+        let output_file = |file: &str| {
+            artifacts.find_artifact_file(file, "Token", &version).unwrap().file.clone()
+        };
+        assert_eq!(output_file("src/Token.sol"), PathBuf::from("Token.sol/Token.json"));
+        assert_eq!(
+            output_file("lib/vendor/Token.sol"),
+            PathBuf::from("vendor/Token.sol/Token.json")
+        );
+        assert_eq!(
+            output_file("lib/other/vendor/token.sol"),
+            PathBuf::from("other/vendor/token.sol/Token.json")
+        );
+        assert!(artifacts.get("lib/vendor/Token.sol", "Token").is_some());
+    }
 }
@@ -0,0 +1,267 @@
+//! Lints that are based on the AST of a single source unit
+
+use super::{Ast, ImportDirective, NodeType, SourceLocation};
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet};
+
+/// An import that is never used, see [`unused_imports`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnusedImport {
+    /// The id of the `ImportDirective`
+    pub id: usize,
+    /// The imported file, as written in the import
+    pub file: String,
+    /// The name of the unused symbol or unit alias, `None` for unused whole-file imports:
+    /// `import "file";`
+    pub symbol: Option<String>,
+    /// The location of the unused symbol, or of the entire import if it's not a symbol import
+    pub src: SourceLocation,
+}
+
+/// Returns all imported symbols of the AST that are never referenced.
+///
+/// A symbol of an `import {A, B as C} from "file";` is used if an `Identifier`, `IdentifierPath`
+/// or `UserDefinedTypeName` refers to its declaration, as listed in the AST's `exportedSymbols`,
+/// or has its local name. A unit alias of `import "file" as X;` is used if `X` is referenced.
+///
+/// Whole-file imports, `import "file";`, are often used only for their side effects, so they are
+/// only reported if none of the declarations of the imported file are referenced. solc assigns
+/// the ids of a file consecutively, ending with the id of its `SourceUnit`, which is used to find
+/// the file of a referenced declaration. If a declaration can't be attributed to any of the
+/// imported files, for example because it's imported transitively or an import has no
+/// `sourceUnit`, all whole-file imports are considered used.
+pub fn unused_imports(ast: &Ast) -> Vec<UnusedImport> {
+    let imports = ast.imports();
+    let usages = Usages::new(ast);
+
+    let mut unused = Vec::new();
+    // declarations that are attributed to symbol or unit alias imports
+    let mut aliased = BTreeSet::new();
+    // the files of all imports by the id of their `SourceUnit`
+    let mut units = BTreeMap::new();
+    units.insert(ast.id, None);
+    let mut whole_file_imports = Vec::new();
+
+    for import in &imports {
+        if !import.unit_alias.is_empty() {
+            aliased.insert(import.id);
+            aliased.extend(declarations(ast, &import.unit_alias));
+            let used = usages.references(import.id) || usages.names_path(&import.unit_alias);
+            if !used {
+                unused.push(UnusedImport::new(
+                    import,
+                    Some(import.unit_alias.clone()),
+                    import.src.clone(),
+                ));
+            }
+            units.extend(import.source_unit.map(|unit| (unit, None)));
+        } else if !import.symbol_aliases.is_empty() {
+            for symbol in &import.symbol_aliases {
+                let name = match symbol.local_name() {
+                    Some(name) => name,
+                    None => continue,
+                };
+                let mut ids = declarations(ast, name);
+                ids.extend(
+                    symbol
+                        .foreign
+                        .get("referencedDeclaration")
+                        .and_then(Value::as_u64)
+                        .map(|id| id as usize),
+                );
+                let used = ids.iter().any(|id| usages.references(*id)) || usages.names_path(name);
+                aliased.extend(ids);
+                if !used {
+                    let src = symbol
+                        .foreign
+                        .get("src")
+                        .and_then(Value::as_str)
+                        .and_then(|src| src.parse().ok())
+                        .unwrap_or_else(|| import.src.clone());
+                    unused.push(UnusedImport::new(import, Some(name.to_string()), src));
+                }
+            }
+            units.extend(import.source_unit.map(|unit| (unit, None)));
+        } else {
+            units.extend(import.source_unit.map(|unit| (unit, Some(whole_file_imports.len()))));
+            whole_file_imports.push((import, false));
+        }
+    }
+
+    let external = usages
+        .referenced
+        .iter()
+        .filter(|id| !usages.declared.contains(*id) && !aliased.contains(*id));
+    let can_attribute = whole_file_imports.iter().all(|(import, _)| import.source_unit.is_some());
+    for id in external {
+        match units.range(id..).next() {
+            // declared in the file of a symbol or unit alias import
+            Some((_, None)) if can_attribute => {}
+            Some((_, Some(idx))) if can_attribute => whole_file_imports[*idx].1 = true,
+            _ => whole_file_imports.iter_mut().for_each(|(_, used)| *used = true),
+        }
+    }
+    for (import, used) in whole_file_imports {
+        if !used {
+            unused.push(UnusedImport::new(import, None, import.src.clone()));
+        }
+    }
+
+    unused
+}
+
+impl UnusedImport {
+    fn new(import: &ImportDirective, symbol: Option<String>, src: SourceLocation) -> Self {
+        Self { id: import.id, file: import.file.clone(), symbol, src }
+    }
+}
+
+/// Returns the ids of the exported symbol with the given name
+fn declarations(ast: &Ast, name: &str) -> Vec<usize> {
+    ast.exported_symbols.get(name).cloned().unwrap_or_default()
+}
+
+/// All declarations and references of a source unit, excluding its imports
+#[derive(Default)]
+struct Usages {
+    /// ids of all nodes
+    declared: BTreeSet<usize>,
+    /// all `referencedDeclaration`s, builtins like `msg` have negative ids and are skipped
+    referenced: BTreeSet<usize>,
+    /// names of all identifiers that reference something
+    names: BTreeSet<String>,
+}
+
+impl Usages {
+    fn new(ast: &Ast) -> Self {
+        let mut usages = Usages::default();
+        for node in ast.nodes.iter().filter(|node| node.node_type != NodeType::ImportDirective) {
+            if let Ok(value) = serde_json::to_value(node) {
+                usages.visit(&value);
+            }
+        }
+        usages
+    }
+
+    fn visit(&mut self, value: &Value) {
+        match value {
+            Value::Object(map) => {
+                if let Some(id) = map.get("id").and_then(Value::as_u64) {
+                    self.declared.insert(id as usize);
+                }
+                let is_reference = matches!(
+                    map.get("nodeType").and_then(Value::as_str),
+                    Some("Identifier") | Some("IdentifierPath") | Some("UserDefinedTypeName")
+                );
+                if is_reference {
+                    if let Some(id) = map.get("referencedDeclaration").and_then(Value::as_u64) {
+                        self.referenced.insert(id as usize);
+                    }
+                    if let Some(name) = map.get("name").and_then(Value::as_str) {
+                        self.names.insert(name.to_string());
+                    }
+                }
+                map.values().for_each(|value| self.visit(value));
+            }
+            Value::Array(values) => values.iter().for_each(|value| self.visit(value)),
+            _ => {}
+        }
+    }
+
+    fn references(&self, id: usize) -> bool {
+        self.referenced.contains(&id)
+    }
+
+    /// Whether the name is referenced, either on its own or as the first part of a path like
+    /// `Lib.Struct`
+    fn names_path(&self, name: &str) -> bool {
+        self.names.iter().any(|used| {
+            used == name || used.strip_prefix(name).map(|s| s.starts_with('.')).unwrap_or_default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifacts::ast::Node;
+
+    fn ast() -> Ast {
+        serde_json::from_str(include_str!("../../../test-data/ast/ast-erc4626.json")).unwrap()
+    }
+
+    fn import_node(ast: &mut Ast, file: &str) -> &mut Node {
+        ast.nodes
+            .iter_mut()
+            .find(|node| {
+                node.node_type == NodeType::ImportDirective &&
+                    node.other.get("file") == Some(&Value::from(file))
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn all_imports_used() {
+        assert!(unused_imports(&ast()).is_empty());
+    }
+
+    #[test]
+    fn can_detect_unused_symbols() {
+        let mut ast = ast();
+        let import = import_node(&mut ast, "../tokens/ERC20.sol");
+        let import_id = import.id;
+        let aliases = import.other.get_mut("symbolAliases").unwrap().as_array_mut().unwrap();
+        aliases.push(serde_json::json!({
+            "foreign": {
+                "id": 99998,
+                "name": "Unused",
+                "nodeType": "Identifier",
+                "overloadedDeclarations": [],
+                "src": "83:6:4",
+                "typeDescriptions": {}
+            },
+            "local": "Renamed",
+            "nameLocation": "-1:-1:-1"
+        }));
+        ast.exported_symbols.insert("Renamed".to_string(), vec![99999]);
+
+        assert_eq!(
+            unused_imports(&ast),
+            vec![UnusedImport {
+                id: import_id,
+                file: "../tokens/ERC20.sol".to_string(),
+                symbol: Some("Renamed".to_string()),
+                src: "83:6:4".parse().unwrap(),
+            }]
+        );
+    }
+
+    #[test]
+    fn can_detect_unused_whole_file_imports() {
+        let mut ast = ast();
+        let mut unused = import_node(&mut ast, "../tokens/ERC20.sol").clone();
+        unused.id = 99999;
+        unused.other.insert("file".to_string(), "Unused.sol".into());
+        unused.other.insert("sourceUnit".to_string(), 40000.into());
+        unused.other.insert("symbolAliases".to_string(), Value::Array(vec![]));
+        ast.nodes.insert(1, unused.clone());
+
+        let expected = vec![UnusedImport {
+            id: 99999,
+            file: "Unused.sol".to_string(),
+            symbol: None,
+            src: unused.src.clone(),
+        }];
+        assert_eq!(unused_imports(&ast), expected);
+
+        // `ERC20` and its members are still used through the whole-file import
+        import_node(&mut ast, "../tokens/ERC20.sol")
+            .other
+            .insert("symbolAliases".to_string(), Value::Array(vec![]));
+        assert_eq!(unused_imports(&ast), expected);
+
+        // can't tell which file the declaration belongs to without its `sourceUnit`
+        import_node(&mut ast, "Unused.sol").other.remove("sourceUnit");
+        assert!(unused_imports(&ast).is_empty());
+    }
+}
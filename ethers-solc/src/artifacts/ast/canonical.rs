@@ -0,0 +1,91 @@
+//! Serialization of an AST with a deterministic field order
+
+use super::Ast;
+use serde::{ser::SerializeMap, Serialize, Serializer};
+use serde_json::Value;
+
+/// Attributes that are serialized before all others, in this order
+const LEADING_ATTRIBUTES: &[&str] = &["id", "nodeType", "src"];
+
+/// Attributes that are serialized after all others, in this order
+const TRAILING_ATTRIBUTES: &[&str] = &["nodes"];
+
+impl Ast {
+    /// Serializes the AST as pretty printed JSON with a deterministic field order.
+    ///
+    /// Every node starts with its `id`, `nodeType` and `src`, followed by all other attributes in
+    /// alphabetical order and its child `nodes` last. All other objects are serialized in
+    /// alphabetical order. This is stable across round-trips, so snapshots of an AST diff cleanly.
+    pub fn to_canonical_json(&self) -> String {
+        let value = serde_json::to_value(self).expect("AST serialization can't fail");
+        to_canonical_json(&value)
+    }
+}
+
+/// Serializes a json AST with the field order of [`Ast::to_canonical_json`]
+pub(crate) fn to_canonical_json(value: &Value) -> String {
+    serde_json::to_string_pretty(&Canonical(value)).expect("json serialization can't fail")
+}
+
+/// Serializes the wrapped value in canonical order
+struct Canonical<'a>(&'a Value);
+
+impl<'a> Serialize for Canonical<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self.0 {
+            Value::Object(map) => {
+                let mut keys = map.keys().map(String::as_str).collect::<Vec<_>>();
+                if map.contains_key("nodeType") {
+                    keys.sort_by_key(|key| {
+                        let leading = LEADING_ATTRIBUTES.iter().position(|k| k == key);
+                        let trailing = TRAILING_ATTRIBUTES.iter().position(|k| k == key);
+                        (trailing, leading.is_none(), leading, *key)
+                    });
+                } else {
+                    keys.sort_unstable();
+                }
+
+                let mut s = serializer.serialize_map(Some(keys.len()))?;
+                for key in keys {
+                    s.serialize_entry(key, &Canonical(&map[key]))?;
+                }
+                s.end()
+            }
+            Value::Array(values) => serializer.collect_seq(values.iter().map(Canonical)),
+            value => value.serialize(serializer),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_serialize_canonical() {
+        let json = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(json).unwrap();
+
+        let canonical = ast.to_canonical_json();
+        assert!(canonical.starts_with(
+            "{\n  \"id\": 2954,\n  \"nodeType\": \"SourceUnit\",\n  \"src\": \"42:6474:4\",\n  \"absolutePath\""
+        ));
+
+        let round_tripped: Ast = serde_json::from_str(&canonical).unwrap();
+        assert_eq!(round_tripped, ast);
+        assert_eq!(round_tripped.to_canonical_json(), canonical);
+    }
+
+    #[test]
+    fn can_serialize_canonical_fixture() {
+        // a solc AST whose fields are already in the canonical order
+        let json = include_str!("../../../test-data/ast/ast-canonical.json");
+        let ast: Ast = serde_json::from_str(json).unwrap();
+
+        let without_whitespace = |s: &str| s.split_whitespace().collect::<String>();
+        assert_eq!(without_whitespace(&ast.to_canonical_json()), without_whitespace(json));
+    }
+}
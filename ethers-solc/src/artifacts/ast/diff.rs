@@ -0,0 +1,217 @@
+//! Structural diff of two ASTs
+
+use super::{Ast, Node};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Attributes that are ignored when comparing nodes, because they contain source locations or
+/// node ids which change whenever unrelated parts of the source change.
+const IGNORED_ATTRIBUTES: &[&str] = &[
+    "id",
+    "src",
+    "nameLocation",
+    "nameLocations",
+    "referencedDeclaration",
+    "scope",
+    "overloadedDeclarations",
+    "linearizedBaseContracts",
+    "contractDependencies",
+    "baseFunctions",
+    "functionReturnParameters",
+    "sourceUnit",
+    "usedErrors",
+    "typeIdentifier",
+];
+
+/// A change between two ASTs, see [`diff`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AstChange {
+    /// A node that only exists in the new AST, with its id in the new AST
+    Added(usize),
+    /// A node that only exists in the old AST, with its id in the old AST
+    Removed(usize),
+    /// A node that exists in both ASTs, with its id in the new AST and the names of all
+    /// attributes that changed
+    Modified(usize, Vec<String>),
+}
+
+/// Compares two ASTs of the same source file and returns all changed nodes.
+///
+/// Nodes are matched by their `nodeType` and `name` attribute if they have one, then by their
+/// id and finally by their position among siblings of the same `nodeType`.
+///
+/// Source locations and all node ids, including references to other nodes like
+/// `referencedDeclaration`, are ignored. A diff of a file where only a comment changed is empty,
+/// even though that changes the `src` of all nodes after it.
+///
+/// Children that are nested in the attributes of a node, like the `statements` of a `Block`, are
+/// compared as a whole and are reported as a modified attribute of their parent.
+pub fn diff(old: &Ast, new: &Ast) -> Vec<AstChange> {
+    let mut changes = Vec::new();
+    diff_nodes(&old.nodes, &new.nodes, &mut changes);
+    changes
+}
+
+fn diff_nodes(old: &[Node], new: &[Node], changes: &mut Vec<AstChange>) {
+    let mut unmatched_old = (0..old.len()).collect::<BTreeSet<_>>();
+    let mut matched = vec![None; new.len()];
+
+    // match by type and name
+    for (idx, node) in new.iter().enumerate() {
+        if let Some(node_name) = name(node) {
+            let found = unmatched_old
+                .iter()
+                .copied()
+                .find(|&o| old[o].node_type == node.node_type && name(&old[o]) == Some(node_name));
+            if let Some(o) = found {
+                unmatched_old.remove(&o);
+                matched[idx] = Some(o);
+            }
+        }
+    }
+
+    // match by id
+    for (idx, node) in new.iter().enumerate() {
+        if matched[idx].is_some() {
+            continue
+        }
+        let found = unmatched_old
+            .iter()
+            .copied()
+            .find(|&o| old[o].id == node.id && old[o].node_type == node.node_type);
+        if let Some(o) = found {
+            unmatched_old.remove(&o);
+            matched[idx] = Some(o);
+        }
+    }
+
+    // match by position among the remaining nodes of the same type
+    for (idx, node) in new.iter().enumerate() {
+        if matched[idx].is_some() {
+            continue
+        }
+        let found = unmatched_old.iter().copied().find(|&o| old[o].node_type == node.node_type);
+        if let Some(o) = found {
+            unmatched_old.remove(&o);
+            matched[idx] = Some(o);
+        }
+    }
+
+    for (node, matched) in new.iter().zip(matched) {
+        match matched {
+            Some(o) => diff_node(&old[o], node, changes),
+            None => changes.push(AstChange::Added(node.id)),
+        }
+    }
+    changes.extend(unmatched_old.into_iter().map(|o| AstChange::Removed(old[o].id)));
+}
+
+fn diff_node(old: &Node, new: &Node, changes: &mut Vec<AstChange>) {
+    let keys = old.other.keys().chain(new.other.keys()).collect::<BTreeSet<_>>();
+    let modified = keys
+        .into_iter()
+        .filter(|key| !IGNORED_ATTRIBUTES.contains(&key.as_str()))
+        .filter(|key| old.other.get(*key).map(normalize) != new.other.get(*key).map(normalize))
+        .cloned()
+        .collect::<Vec<_>>();
+    if !modified.is_empty() {
+        changes.push(AstChange::Modified(new.id, modified));
+    }
+
+    diff_nodes(&old.nodes, &new.nodes, changes);
+    match (&old.body, &new.body) {
+        (Some(old), Some(new)) => {
+            diff_nodes(std::slice::from_ref(&**old), std::slice::from_ref(&**new), changes)
+        }
+        (Some(old), None) => changes.push(AstChange::Removed(old.id)),
+        (None, Some(new)) => changes.push(AstChange::Added(new.id)),
+        (None, None) => {}
+    }
+}
+
+fn name(node: &Node) -> Option<&str> {
+    node.other.get("name").and_then(Value::as_str).filter(|name| !name.is_empty())
+}
+
+/// Returns a copy of the value without any of the [`IGNORED_ATTRIBUTES`]
+fn normalize(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => Value::Object(
+            map.iter()
+                .filter(|(key, _)| !IGNORED_ATTRIBUTES.contains(&key.as_str()))
+                .map(|(key, value)| (key.clone(), normalize(value)))
+                .collect(),
+        ),
+        Value::Array(values) => Value::Array(values.iter().map(normalize).collect()),
+        value => value.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifacts::ast::NodeType;
+
+    fn ast() -> Ast {
+        serde_json::from_str(include_str!("../../../test-data/ast/ast-erc4626.json")).unwrap()
+    }
+
+    /// Shifts all source locations and ids of the json AST
+    fn shift(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (key, value) in map.iter_mut() {
+                    if key == "id" {
+                        let id = value.as_u64().unwrap();
+                        *value = (id + 1000).into();
+                    } else if key == "src" {
+                        let src = value.as_str().unwrap().to_string();
+                        let (start, rest) = src.split_once(':').unwrap();
+                        *value =
+                            format!("{}:{}", start.parse::<usize>().unwrap() + 10, rest).into();
+                    } else {
+                        shift(value);
+                    }
+                }
+            }
+            Value::Array(values) => values.iter_mut().for_each(shift),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn ignores_shifted_locations() {
+        let old = ast();
+        let mut json = serde_json::to_value(&old).unwrap();
+        shift(&mut json);
+        let new: Ast = serde_json::from_value(json).unwrap();
+        assert_ne!(old, new);
+        assert!(diff(&old, &new).is_empty());
+    }
+
+    #[test]
+    fn can_diff_asts() {
+        let old = ast();
+        let mut new = old.clone();
+        let contract =
+            new.nodes.iter_mut().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+
+        let removed = contract.nodes.pop().unwrap();
+        let renamed = contract
+            .nodes
+            .iter_mut()
+            .find(|n| n.other.get("name") == Some(&Value::from("deposit")))
+            .unwrap();
+        renamed.other.insert("name".to_string(), "deposit2".into());
+        let renamed = renamed.id;
+
+        let changes = diff(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                AstChange::Modified(renamed, vec!["name".to_string()]),
+                AstChange::Removed(removed.id)
+            ]
+        );
+    }
+}
@@ -0,0 +1,175 @@
+//! Bindings for the legacy AST format emitted by older solc versions (`legacyAST`)
+
+use super::{Ast, Node, NodeType, SourceLocation};
+use crate::artifacts::serde_helpers;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The legacy AST of a source file.
+///
+/// Unlike the modern format, nodes are identified by a `name` and keep their attributes and
+/// children in separate `attributes` and `children` fields.
+///
+/// Can be converted into the modern [`Ast`] representation via `From`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct LegacyAst(pub LegacyNode);
+
+/// A node of the legacy AST format
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LegacyNode {
+    /// The node ID.
+    pub id: usize,
+    /// The node type, like `ContractDefinition`.
+    pub name: String,
+    /// The location of the node in the source file.
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    /// The attributes of the node.
+    #[serde(default)]
+    pub attributes: BTreeMap<String, serde_json::Value>,
+    /// All child nodes.
+    #[serde(default)]
+    pub children: Vec<LegacyNode>,
+}
+
+impl From<LegacyNode> for Node {
+    /// Converts a legacy node into the modern representation.
+    ///
+    /// Children of `FunctionDefinition` and `ModifierDefinition` nodes are mapped to the
+    /// `parameters`, `returnParameters`, `modifiers` attributes and the `body`, the
+    /// `InheritanceSpecifier` children of a `ContractDefinition` are mapped to `baseContracts`.
+    /// All other children are kept in `nodes`.
+    fn from(legacy: LegacyNode) -> Self {
+        let LegacyNode { id, name, src, attributes: mut other, children } = legacy;
+        let node_type: NodeType = name.parse().expect("infallible");
+
+        let mut nodes = Vec::with_capacity(children.len());
+        let mut body = None;
+        match node_type {
+            NodeType::FunctionDefinition | NodeType::ModifierDefinition => {
+                let mut parameter_lists = Vec::new();
+                let mut modifiers = Vec::new();
+                for child in children.into_iter().map(Node::from) {
+                    match child.node_type {
+                        NodeType::ParameterList => parameter_lists.push(child),
+                        NodeType::ModifierInvocation => modifiers.push(child),
+                        NodeType::Block => body = Some(Box::new(child)),
+                        _ => nodes.push(child),
+                    }
+                }
+                let mut parameter_lists = parameter_lists.into_iter();
+                if let Some(params) = parameter_lists.next() {
+                    other.insert("parameters".to_string(), to_value(params));
+                }
+                if let Some(params) = parameter_lists.next() {
+                    other.insert("returnParameters".to_string(), to_value(params));
+                }
+                if node_type == NodeType::FunctionDefinition {
+                    other.insert(
+                        "modifiers".to_string(),
+                        serde_json::Value::Array(modifiers.into_iter().map(to_value).collect()),
+                    );
+                }
+            }
+            NodeType::ContractDefinition => {
+                let mut base_contracts = Vec::new();
+                for child in children.into_iter().map(Node::from) {
+                    if child.node_type == NodeType::InheritanceSpecifier {
+                        base_contracts.push(to_value(child));
+                    } else {
+                        nodes.push(child);
+                    }
+                }
+                other.insert("baseContracts".to_string(), serde_json::Value::Array(base_contracts));
+            }
+            _ => nodes.extend(children.into_iter().map(Node::from)),
+        }
+
+        Node { id, node_type, src, nodes, body, other }
+    }
+}
+
+impl From<LegacyAst> for Ast {
+    fn from(legacy: LegacyAst) -> Self {
+        let Node { id, node_type, src, nodes, body: _, mut other } = Node::from(legacy.0);
+        let absolute_path = other
+            .remove("absolutePath")
+            .and_then(|path| serde_json::from_value(path).ok())
+            .unwrap_or_default();
+        let exported_symbols = other
+            .remove("exportedSymbols")
+            .and_then(|symbols| serde_json::from_value(symbols).ok())
+            .unwrap_or_default();
+        Ast { absolute_path, id, exported_symbols, node_type, src, nodes, other }
+    }
+}
+
+fn to_value(node: Node) -> serde_json::Value {
+    serde_json::to_value(node).expect("node serialization can't fail")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_convert_legacy_ast() {
+        let legacy = r#"{
+            "attributes": {
+                "absolutePath": "A.sol",
+                "exportedSymbols": { "A": [12] }
+            },
+            "children": [
+                {
+                    "attributes": { "literals": ["solidity", "^", "0.4", ".24"] },
+                    "id": 1,
+                    "name": "PragmaDirective",
+                    "src": "0:24:0"
+                },
+                {
+                    "attributes": { "contractKind": "contract", "name": "A" },
+                    "children": [
+                        {
+                            "attributes": { "implemented": true, "name": "foo", "visibility": "public" },
+                            "children": [
+                                { "children": [], "id": 2, "name": "ParameterList", "src": "50:2:0" },
+                                { "children": [], "id": 3, "name": "ParameterList", "src": "60:0:0" },
+                                { "children": [], "id": 4, "name": "Block", "src": "60:2:0" }
+                            ],
+                            "id": 5,
+                            "name": "FunctionDefinition",
+                            "src": "38:24:0"
+                        }
+                    ],
+                    "id": 12,
+                    "name": "ContractDefinition",
+                    "src": "26:38:0"
+                }
+            ],
+            "id": 13,
+            "name": "SourceUnit",
+            "src": "0:64:0"
+        }"#;
+
+        let ast = Ast::from(serde_json::from_str::<LegacyAst>(legacy).unwrap());
+        assert_eq!(ast.absolute_path, "A.sol");
+        assert_eq!(ast.node_type, NodeType::SourceUnit);
+        assert_eq!(ast.exported_symbols["A"], vec![12]);
+        assert_eq!(ast.nodes.len(), 2);
+
+        let contract = &ast.nodes[1];
+        assert_eq!(contract.node_type, NodeType::ContractDefinition);
+        assert_eq!(contract.attribute::<String>("name").unwrap(), "A");
+
+        let function = &contract.nodes[0];
+        assert_eq!(function.node_type, NodeType::FunctionDefinition);
+        assert_eq!(function.body.as_ref().unwrap().id, 4);
+        assert_eq!(function.attribute::<Node>("parameters").unwrap().id, 2);
+        assert_eq!(function.attribute::<Node>("returnParameters").unwrap().id, 3);
+
+        // the version is detected when deserializing an `Ast` directly
+        let detected: Ast = serde_json::from_str(legacy).unwrap();
+        assert_eq!(detected, ast);
+    }
+}
@@ -0,0 +1,1548 @@
+//! Bindings for solc's `ast` output field
+
+use crate::artifacts::serde_helpers;
+use serde::{
+    de::{self, DeserializeOwned},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    convert::{Infallible, TryFrom},
+    fmt,
+    fmt::Write,
+    str::FromStr,
+};
+
+pub mod analysis;
+pub use analysis::{unused_imports, UnusedImport};
+mod canonical;
+mod diff;
+pub use diff::{diff, AstChange};
+pub mod legacy;
+pub use legacy::{LegacyAst, LegacyNode};
+pub mod natspec;
+pub use natspec::{parse_natspec, NatSpec};
+pub mod printer;
+pub use printer::UnprintableNode;
+mod yul;
+pub use yul::{YulBlock, YulNode};
+
+/// Represents the AST field in the solc output
+///
+/// Deserializing also accepts the [`LegacyAst`] format of older solc versions, which is
+/// normalized to the modern representation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Ast {
+    #[serde(rename = "absolutePath")]
+    pub absolute_path: String,
+    pub id: usize,
+    #[serde(default, rename = "exportedSymbols")]
+    pub exported_symbols: BTreeMap<String, Vec<usize>>,
+    #[serde(rename = "nodeType")]
+    pub node_type: NodeType,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<Node>,
+
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+impl<'de> Deserialize<'de> for Ast {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = serde_json::Value::deserialize(deserializer)?;
+        if value.get("nodeType").is_none() && value.get("name").is_some() {
+            return LegacyAst::deserialize(value).map(Into::into).map_err(de::Error::custom)
+        }
+
+        let Node { id, node_type, src, nodes, body: _, mut other } =
+            Node::deserialize(value).map_err(de::Error::custom)?;
+        let absolute_path = other
+            .remove("absolutePath")
+            .ok_or_else(|| de::Error::missing_field("absolutePath"))
+            .and_then(|path| serde_json::from_value(path).map_err(de::Error::custom))?;
+        let exported_symbols = other
+            .remove("exportedSymbols")
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(de::Error::custom)?
+            .unwrap_or_default();
+        Ok(Ast { absolute_path, id, exported_symbols, node_type, src, nodes, other })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Node {
+    /// The node ID.
+    pub id: usize,
+
+    /// The node type.
+    #[serde(rename = "nodeType")]
+    pub node_type: NodeType,
+
+    /// The location of the node in the source file.
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+
+    /// Child nodes for some node types.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<Node>,
+
+    /// Body node for some node types.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Box<Node>>,
+
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+impl Node {
+    /// Deserialize a serialized node attribute.
+    pub fn attribute<D: DeserializeOwned>(&self, key: impl AsRef<str>) -> Option<D> {
+        self.attribute_ref(key)
+    }
+
+    /// Deserialize a serialized node attribute that may borrow from this node, like `&str`.
+    ///
+    /// The attribute is deserialized from the stored value directly, without copying it first.
+    pub fn attribute_ref<'de, D: Deserialize<'de>>(&'de self, key: impl AsRef<str>) -> Option<D> {
+        self.other.get(key.as_ref()).and_then(|v| D::deserialize(v).ok())
+    }
+
+    /// Returns a typed representation of this node.
+    ///
+    /// Known node types are deserialized into the matching [`TypedNode`] variant, all other node
+    /// types are returned as [`TypedNode::Unknown`] which keeps the node as is.
+    ///
+    /// Returns `None` if the node's attributes don't match the expected shape of its node type.
+    pub fn typed(&self) -> Option<TypedNode> {
+        if !TypedNode::is_typed(&self.node_type) {
+            return Some(TypedNode::Unknown(self.clone()))
+        }
+        serde_json::to_value(self).and_then(serde_json::from_value).ok()
+    }
+
+    /// Returns the typed attributes of a `SourceUnit` node, or `None` if this is not a
+    /// `SourceUnit`.
+    ///
+    /// The raw attributes remain available in `other`.
+    pub fn as_source_unit(&self) -> Option<SourceUnit> {
+        if self.node_type != NodeType::SourceUnit {
+            return None
+        }
+        let mut unit = SourceUnit::new(self.id, self.src.clone(), &self.nodes, &self.other);
+        unit.absolute_path = self.attribute("absolutePath").unwrap_or_default();
+        unit.exported_symbols = self.attribute("exportedSymbols").unwrap_or_default();
+        Some(unit)
+    }
+}
+
+impl Ast {
+    /// Visits all nodes of this AST, see [`Node::accept`].
+    pub fn accept<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        for node in &self.nodes {
+            node.accept(visitor);
+        }
+    }
+
+    /// Visits all nodes of this AST mutably, see [`Node::accept_mut`].
+    pub fn accept_mut<V: Visitor + ?Sized>(&mut self, visitor: &mut V) {
+        for node in &mut self.nodes {
+            node.accept_mut(visitor);
+        }
+    }
+
+    /// Returns the typed attributes of the `SourceUnit` this AST represents.
+    pub fn as_source_unit(&self) -> SourceUnit {
+        let mut unit = SourceUnit::new(self.id, self.src.clone(), &self.nodes, &self.other);
+        unit.absolute_path = self.absolute_path.clone();
+        unit.exported_symbols = self.exported_symbols.clone();
+        unit
+    }
+
+    /// Returns all `ImportDirective`s of this AST.
+    ///
+    /// Imports are only allowed at the top level of a source unit so only the AST's top-level
+    /// `nodes` are checked.
+    pub fn imports(&self) -> Vec<ImportDirective> {
+        self.nodes
+            .iter()
+            .filter(|node| node.node_type == NodeType::ImportDirective)
+            .filter_map(|node| match node.typed()? {
+                TypedNode::ImportDirective(import) => Some(import),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns all nodes of the given type, in depth-first pre-order.
+    ///
+    /// This walks the entire AST, including `body` nodes.
+    pub fn collect(&self, node_type: NodeType) -> Vec<&Node> {
+        self.nodes_recursive().filter(|node| node.node_type == node_type).collect()
+    }
+
+    /// Returns the node with the given id.
+    ///
+    /// This scans the entire AST, use [`Ast::id_index`] for repeated lookups.
+    pub fn node_by_id(&self, id: usize) -> Option<&Node> {
+        self.nodes_recursive().find(|node| node.id == id)
+    }
+
+    /// Returns an index of all nodes of this AST by their id.
+    ///
+    /// The index borrows the AST.
+    pub fn id_index(&self) -> AstIndex<'_> {
+        AstIndex::new(self)
+    }
+
+    /// Resolves the ids of the `exported_symbols` to their nodes.
+    ///
+    /// Symbols that are imported from other source files can't be resolved within this AST and
+    /// are skipped, see [`Ast::unresolved_exports`].
+    pub fn resolved_exports(&self) -> BTreeMap<String, Vec<&Node>> {
+        let index = self.id_index();
+        self.exported_symbols
+            .iter()
+            .filter_map(|(name, ids)| {
+                let nodes = ids.iter().filter_map(|id| index.get(*id)).collect::<Vec<_>>();
+                (!nodes.is_empty()).then(|| (name.clone(), nodes))
+            })
+            .collect()
+    }
+
+    /// Returns the ids of all `exported_symbols` that don't resolve to a node of this AST, like
+    /// symbols that are imported from other source files.
+    pub fn unresolved_exports(&self) -> BTreeMap<String, Vec<usize>> {
+        let index = self.id_index();
+        self.exported_symbols
+            .iter()
+            .filter_map(|(name, ids)| {
+                let ids =
+                    ids.iter().copied().filter(|id| index.get(*id).is_none()).collect::<Vec<_>>();
+                (!ids.is_empty()).then(|| (name.clone(), ids))
+            })
+            .collect()
+    }
+
+    /// Returns a map of node ids to the id of their parent node.
+    ///
+    /// This includes children reachable through `nodes` as well as `body`, top-level nodes are
+    /// mapped to the id of the AST itself.
+    pub fn parent_map(&self) -> HashMap<usize, usize> {
+        let mut parents = HashMap::new();
+        let mut stack = self.nodes.iter().map(|node| (self.id, node)).collect::<Vec<_>>();
+        while let Some((parent, node)) = stack.pop() {
+            parents.insert(node.id, parent);
+            stack.extend(
+                node.nodes.iter().chain(node.body.as_deref()).map(|child| (node.id, child)),
+            );
+        }
+        parents
+    }
+
+    /// Returns the closest ancestor of the node with the given `id` that is of the given
+    /// `node_type`, see [`Node::find_enclosing`]
+    pub fn find_enclosing(&self, id: usize, node_type: NodeType) -> Option<&Node> {
+        self.nodes.iter().find_map(|node| node.find_enclosing(id, node_type.clone()))
+    }
+
+    /// Returns a lazy iterator over all nodes of this AST, depth-first pre-order.
+    ///
+    /// See also [`Node::descendants`]
+    pub fn nodes_recursive(&self) -> Descendants<'_> {
+        Descendants { stack: self.nodes.iter().rev().collect() }
+    }
+
+    /// Returns a lazy iterator over mutable views of all nodes of this AST, depth-first
+    /// pre-order.
+    ///
+    /// See also [`Node::descendants_mut`]
+    pub fn nodes_recursive_mut(&mut self) -> DescendantsMut<'_> {
+        DescendantsMut { stack: self.nodes.iter_mut().rev().collect() }
+    }
+}
+
+impl Node {
+    /// Returns a lazy iterator over all descendants of this node, excluding the node itself.
+    ///
+    /// Nodes are yielded in depth-first pre-order, descending into both `nodes` and `body`, the
+    /// same order as [`Node::accept`].
+    pub fn descendants(&self) -> Descendants<'_> {
+        let mut stack = Vec::with_capacity(self.nodes.len() + 1);
+        stack.extend(self.body.as_deref());
+        stack.extend(self.nodes.iter().rev());
+        Descendants { stack }
+    }
+
+    /// Returns this node and all of its descendants that are of the given type, in depth-first
+    /// pre-order.
+    pub fn collect_of_type(&self, node_type: NodeType) -> Vec<&Node> {
+        std::iter::once(self)
+            .chain(self.descendants())
+            .filter(|node| node.node_type == node_type)
+            .collect()
+    }
+
+    /// Returns a lazy iterator over mutable views of all descendants of this node, excluding the
+    /// node itself.
+    ///
+    /// Since the children of a node are yielded by the iterator itself, the yielded
+    /// [`NodeMut`] provides access to all fields of a node except for `nodes` and `body`.
+    pub fn descendants_mut(&mut self) -> DescendantsMut<'_> {
+        let mut stack = Vec::with_capacity(self.nodes.len() + 1);
+        stack.extend(self.body.as_deref_mut());
+        stack.extend(self.nodes.iter_mut().rev());
+        DescendantsMut { stack }
+    }
+
+    /// Returns the closest ancestor of the node with the given `id` that is of the given
+    /// `node_type`.
+    ///
+    /// The search is limited to this node and its descendants, so this node is the outermost
+    /// possible result, e.g. "which contract is this function in?" can be answered by calling
+    /// this on the `SourceUnit`'s nodes. Returns `None` if there is no node with the `id` or it
+    /// has no such ancestor.
+    pub fn find_enclosing(&self, id: usize, node_type: NodeType) -> Option<&Node> {
+        self.path_to(id)?.into_iter().skip(1).find(|node| node.node_type == node_type)
+    }
+
+    /// Returns the path from the node with the given `id` up to this node
+    fn path_to(&self, id: usize) -> Option<Vec<&Node>> {
+        if self.id == id {
+            return Some(vec![self])
+        }
+        let mut path = self.nodes.iter().chain(self.body.as_deref()).find_map(|n| n.path_to(id))?;
+        path.push(self);
+        Some(path)
+    }
+
+    /// Returns the innermost node that contains the given byte offset, this includes the node
+    /// itself.
+    ///
+    /// Returns `None` if the offset is not within this node's `src`.
+    pub fn innermost_at(&self, byte: usize) -> Option<&Node> {
+        if !self.src.contains_offset(byte) {
+            return None
+        }
+        let mut node = self;
+        while let Some(child) =
+            node.nodes.iter().chain(node.body.as_deref()).find(|n| n.src.contains_offset(byte))
+        {
+            node = child;
+        }
+        Some(node)
+    }
+
+    /// Walks this node and all of its children with the given [`Visitor`].
+    ///
+    /// The traversal is depth-first pre-order: the node itself is visited first, then all child
+    /// `nodes` in order and finally the `body`, if any.
+    pub fn accept<V: Visitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.visit_node(self);
+        for node in &self.nodes {
+            node.accept(visitor);
+        }
+        if let Some(body) = &self.body {
+            body.accept(visitor);
+        }
+    }
+
+    /// Walks this node and all of its children with the given [`Visitor`], allowing the visitor
+    /// to modify nodes in place.
+    ///
+    /// Uses the same order as [`Node::accept`]. Since the node is visited before its children,
+    /// changes the visitor makes to `nodes` or `body` affect which children are visited.
+    pub fn accept_mut<V: Visitor + ?Sized>(&mut self, visitor: &mut V) {
+        visitor.visit_node_mut(self);
+        for node in &mut self.nodes {
+            node.accept_mut(visitor);
+        }
+        if let Some(body) = &mut self.body {
+            body.accept_mut(visitor);
+        }
+    }
+}
+
+/// A visitor that can be used to walk the [`Node`]s of an [`Ast`].
+///
+/// All methods are no-ops by default.
+///
+/// # Example
+///
+/// Count all `FunctionDefinition` nodes
+///
+/// ```
+/// use ethers_solc::artifacts::{ast::Visitor, Ast, Node, NodeType};
+///
+/// struct Counter(usize);
+///
+/// impl Visitor for Counter {
+///     fn visit_node(&mut self, node: &Node) {
+///         if node.node_type == NodeType::FunctionDefinition {
+///             self.0 += 1;
+///         }
+///     }
+/// }
+///
+/// # fn count(ast: &Ast) -> usize {
+/// let mut counter = Counter(0);
+/// ast.accept(&mut counter);
+/// counter.0
+/// # }
+/// ```
+pub trait Visitor {
+    /// Called for every node visited by [`Node::accept`]
+    fn visit_node(&mut self, _node: &Node) {}
+
+    /// Called for every node visited by [`Node::accept_mut`]
+    fn visit_node_mut(&mut self, _node: &mut Node) {}
+}
+
+/// Iterator over nodes of an AST in depth-first pre-order.
+///
+/// Created by [`Node::descendants`] and [`Ast::nodes_recursive`]
+#[derive(Debug, Clone)]
+pub struct Descendants<'a> {
+    stack: Vec<&'a Node>,
+}
+
+impl<'a> Iterator for Descendants<'a> {
+    type Item = &'a Node;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.stack.pop()?;
+        self.stack.extend(node.body.as_deref());
+        self.stack.extend(node.nodes.iter().rev());
+        Some(node)
+    }
+}
+
+/// Iterator over mutable views of the nodes of an AST in depth-first pre-order.
+///
+/// Created by [`Node::descendants_mut`] and [`Ast::nodes_recursive_mut`]
+#[derive(Debug)]
+pub struct DescendantsMut<'a> {
+    stack: Vec<&'a mut Node>,
+}
+
+impl<'a> Iterator for DescendantsMut<'a> {
+    type Item = NodeMut<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Node { id, node_type, src, nodes, body, other } = self.stack.pop()?;
+        self.stack.extend(body.as_deref_mut());
+        self.stack.extend(nodes.iter_mut().rev());
+        Some(NodeMut { id, node_type, src, other })
+    }
+}
+
+/// An index of the nodes of an [`Ast`] by their id, that borrows the AST.
+///
+/// Created by [`Ast::id_index`]
+#[derive(Debug, Clone, Default)]
+pub struct AstIndex<'a> {
+    nodes: HashMap<usize, &'a Node>,
+}
+
+impl<'a> AstIndex<'a> {
+    /// Indexes all nodes of the AST
+    pub fn new(ast: &'a Ast) -> Self {
+        Self { nodes: ast.nodes_recursive().map(|node| (node.id, node)).collect() }
+    }
+
+    /// Returns the node with the given id
+    pub fn get(&self, id: usize) -> Option<&'a Node> {
+        self.nodes.get(&id).copied()
+    }
+
+    /// Returns the number of indexed nodes
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the index is empty
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
+
+/// Mutable access to the fields of a [`Node`] yielded by [`DescendantsMut`].
+///
+/// The child `nodes` and `body` are not included since they're yielded by the iterator itself.
+#[derive(Debug)]
+pub struct NodeMut<'a> {
+    pub id: &'a mut usize,
+    pub node_type: &'a mut NodeType,
+    pub src: &'a mut SourceLocation,
+    pub other: &'a mut BTreeMap<String, serde_json::Value>,
+}
+
+/// A typed view of a [`Node`].
+///
+/// Obtained via [`Node::typed`] and can be converted back into a [`Node`] via `TryFrom`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "nodeType")]
+pub enum TypedNode {
+    ContractDefinition(ContractDefinition),
+    FunctionDefinition(FunctionDefinition),
+    ModifierDefinition(ModifierDefinition),
+    EventDefinition(EventDefinition),
+    ErrorDefinition(ErrorDefinition),
+    StructDefinition(StructDefinition),
+    EnumDefinition(EnumDefinition),
+    VariableDeclaration(VariableDeclaration),
+    ImportDirective(ImportDirective),
+    PragmaDirective(PragmaDirective),
+    /// A node type without a typed representation.
+    #[serde(skip)]
+    Unknown(Node),
+}
+
+impl TypedNode {
+    /// Whether the given node type has a typed representation
+    fn is_typed(node_type: &NodeType) -> bool {
+        matches!(
+            node_type,
+            NodeType::ContractDefinition |
+                NodeType::FunctionDefinition |
+                NodeType::ModifierDefinition |
+                NodeType::EventDefinition |
+                NodeType::ErrorDefinition |
+                NodeType::StructDefinition |
+                NodeType::EnumDefinition |
+                NodeType::VariableDeclaration |
+                NodeType::ImportDirective |
+                NodeType::PragmaDirective
+        )
+    }
+
+    /// Returns the id of the node
+    pub fn id(&self) -> usize {
+        match self {
+            TypedNode::ContractDefinition(n) => n.id,
+            TypedNode::FunctionDefinition(n) => n.id,
+            TypedNode::ModifierDefinition(n) => n.id,
+            TypedNode::EventDefinition(n) => n.id,
+            TypedNode::ErrorDefinition(n) => n.id,
+            TypedNode::StructDefinition(n) => n.id,
+            TypedNode::EnumDefinition(n) => n.id,
+            TypedNode::VariableDeclaration(n) => n.id,
+            TypedNode::ImportDirective(n) => n.id,
+            TypedNode::PragmaDirective(n) => n.id,
+            TypedNode::Unknown(n) => n.id,
+        }
+    }
+}
+
+impl TryFrom<TypedNode> for Node {
+    type Error = serde_json::Error;
+
+    fn try_from(node: TypedNode) -> Result<Self, Self::Error> {
+        match node {
+            TypedNode::Unknown(node) => Ok(node),
+            node => serde_json::from_value(serde_json::to_value(node)?),
+        }
+    }
+}
+
+/// A `ContractDefinition` node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContractDefinition {
+    pub id: usize,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    pub name: String,
+    pub contract_kind: ContractKind,
+    #[serde(default, rename = "abstract")]
+    pub is_abstract: bool,
+    #[serde(default)]
+    pub base_contracts: Vec<Node>,
+    #[serde(default)]
+    pub linearized_base_contracts: Vec<usize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub nodes: Vec<Node>,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// A `FunctionDefinition` node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FunctionDefinition {
+    pub id: usize,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    pub name: String,
+    pub kind: FunctionKind,
+    pub visibility: Visibility,
+    pub state_mutability: StateMutability,
+    #[serde(default, rename = "virtual")]
+    pub is_virtual: bool,
+    #[serde(default)]
+    pub implemented: bool,
+    pub parameters: Node,
+    pub return_parameters: Node,
+    #[serde(default)]
+    pub modifiers: Vec<Node>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub function_selector: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Box<Node>>,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// A `ModifierDefinition` node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ModifierDefinition {
+    pub id: usize,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    pub name: String,
+    pub visibility: Visibility,
+    #[serde(default, rename = "virtual")]
+    pub is_virtual: bool,
+    pub parameters: Node,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<Box<Node>>,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// An `EventDefinition` node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventDefinition {
+    pub id: usize,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    pub name: String,
+    #[serde(default)]
+    pub anonymous: bool,
+    pub parameters: Node,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// An `ErrorDefinition` node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ErrorDefinition {
+    pub id: usize,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    pub name: String,
+    pub parameters: Node,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// A `StructDefinition` node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StructDefinition {
+    pub id: usize,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    pub name: String,
+    pub visibility: Visibility,
+    #[serde(default)]
+    pub members: Vec<Node>,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// An `EnumDefinition` node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EnumDefinition {
+    pub id: usize,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    pub name: String,
+    #[serde(default)]
+    pub members: Vec<Node>,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// A `VariableDeclaration` node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VariableDeclaration {
+    pub id: usize,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub type_name: Option<Node>,
+    pub visibility: Visibility,
+    #[serde(default)]
+    pub constant: bool,
+    #[serde(default)]
+    pub state_variable: bool,
+    pub storage_location: StorageLocation,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mutability: Option<Mutability>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub indexed: Option<bool>,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// An `ImportDirective` node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportDirective {
+    pub id: usize,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    pub absolute_path: String,
+    pub file: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source_unit: Option<usize>,
+    #[serde(default)]
+    pub unit_alias: String,
+    #[serde(default)]
+    pub symbol_aliases: Vec<SymbolAlias>,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// A symbol imported by an [`ImportDirective`]: `import {foreign as local} from "file";`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SymbolAlias {
+    /// The imported `Identifier` node
+    pub foreign: serde_json::Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub local: Option<String>,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+impl SymbolAlias {
+    /// Returns the name of the imported symbol
+    pub fn foreign_name(&self) -> Option<&str> {
+        self.foreign.get("name").and_then(serde_json::Value::as_str)
+    }
+
+    /// Returns the name under which the symbol is imported, this is the `local` alias if set
+    pub fn local_name(&self) -> Option<&str> {
+        self.local.as_deref().or_else(|| self.foreign_name())
+    }
+}
+
+/// A `PragmaDirective` node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PragmaDirective {
+    pub id: usize,
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+    pub literals: Vec<String>,
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, serde_json::Value>,
+}
+
+/// The attributes of a `SourceUnit` node, see [`Node::as_source_unit`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceUnit {
+    pub id: usize,
+    pub src: SourceLocation,
+    pub absolute_path: String,
+    pub exported_symbols: BTreeMap<String, Vec<usize>>,
+    /// The SPDX license identifier of the source file
+    pub license: Option<String>,
+    /// The version requirements of all `pragma solidity` directives, like `^0.8.0`
+    pub solidity_versions: Vec<String>,
+    /// The `pragma abicoder` version, like `v2`
+    pub abicoder: Option<String>,
+    /// All `pragma experimental` features, like `ABIEncoderV2` or `SMTChecker`
+    pub experimental: Vec<String>,
+}
+
+impl SourceUnit {
+    fn new(
+        id: usize,
+        src: SourceLocation,
+        nodes: &[Node],
+        other: &BTreeMap<String, serde_json::Value>,
+    ) -> Self {
+        let mut unit = SourceUnit {
+            id,
+            src,
+            absolute_path: String::new(),
+            exported_symbols: BTreeMap::new(),
+            license: other.get("license").and_then(serde_json::Value::as_str).map(str::to_string),
+            solidity_versions: Vec::new(),
+            abicoder: None,
+            experimental: Vec::new(),
+        };
+        let pragmas = nodes
+            .iter()
+            .filter(|node| node.node_type == NodeType::PragmaDirective)
+            .filter_map(|node| node.attribute::<Vec<String>>("literals"));
+        for literals in pragmas {
+            let (name, value) = match literals.split_first() {
+                Some((name, value)) => (name, value.concat()),
+                None => continue,
+            };
+            match name.as_str() {
+                "solidity" => unit.solidity_versions.push(value),
+                "abicoder" => unit.abicoder = Some(value),
+                "experimental" => unit.experimental.push(value),
+                _ => {}
+            }
+        }
+        unit
+    }
+}
+
+/// The kind of a [`ContractDefinition`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ContractKind {
+    Contract,
+    Interface,
+    Library,
+}
+
+/// The kind of a [`FunctionDefinition`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FunctionKind {
+    Function,
+    Receive,
+    Constructor,
+    Fallback,
+    FreeFunction,
+}
+
+/// Visibility of a definition
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Visibility {
+    External,
+    Public,
+    Internal,
+    Private,
+}
+
+/// State mutability of a function
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StateMutability {
+    Payable,
+    Pure,
+    Nonpayable,
+    View,
+}
+
+/// Mutability of a variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Mutability {
+    Mutable,
+    Immutable,
+    Constant,
+}
+
+/// Data location of a variable
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum StorageLocation {
+    Calldata,
+    Default,
+    Memory,
+    Storage,
+}
+
+/// Represents the source location of a node: `<start byte>:<length>:<source index>`.
+///
+/// The `length` and `index` can be -1 which is represented as `None`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceLocation {
+    pub start: usize,
+    pub length: Option<usize>,
+    pub index: Option<usize>,
+}
+
+impl SourceLocation {
+    /// Returns the end byte of this location (exclusive).
+    ///
+    /// A location without a `length` is treated as zero-width at `start`.
+    pub fn end(&self) -> usize {
+        self.start + self.length.unwrap_or_default()
+    }
+
+    /// Whether the given byte offset lies within this location: `start <= byte < end`
+    pub fn contains_offset(&self, byte: usize) -> bool {
+        self.start <= byte && byte < self.end()
+    }
+
+    /// Whether `other` lies entirely within this location.
+    ///
+    /// Locations in different source files never contain each other.
+    pub fn contains(&self, other: &SourceLocation) -> bool {
+        self.index == other.index && self.start <= other.start && other.end() <= self.end()
+    }
+
+    /// Whether this location and `other` share at least one byte.
+    ///
+    /// Locations in different source files never overlap, zero-width locations don't overlap
+    /// anything.
+    pub fn overlaps(&self, other: &SourceLocation) -> bool {
+        self.index == other.index && self.start < other.end() && other.start < self.end()
+    }
+
+    /// Returns the start and end [`LineColumn`] of this location in the given source.
+    ///
+    /// Returns `None` if the `length` is `None` or the location is out of bounds of the source.
+    ///
+    /// This needs to scan the entire source, use [`SourceLocation::to_line_col_indexed`] when
+    /// resolving multiple locations of the same source.
+    pub fn to_line_col(&self, source: &str) -> Option<(LineColumn, LineColumn)> {
+        self.to_line_col_indexed(&LineIndex::new(source))
+    }
+
+    /// Same as [`SourceLocation::to_line_col`] but uses a precomputed [`LineIndex`] of the source
+    pub fn to_line_col_indexed(&self, index: &LineIndex<'_>) -> Option<(LineColumn, LineColumn)> {
+        let end = self.start.checked_add(self.length?)?;
+        Some((index.line_col(self.start)?, index.line_col(end)?))
+    }
+}
+
+/// A 1-based line and column position in a source file.
+///
+/// The column counts characters, not bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl fmt::Display for LineColumn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+/// An index of the line offsets of a source file, used to convert byte offsets into
+/// [`LineColumn`]s.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex<'a> {
+    source: &'a str,
+    /// byte offsets at which each line starts
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Creates the index of the given source
+    pub fn new(source: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(source.bytes().enumerate().filter(|(_, b)| *b == b'\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { source, line_starts }
+    }
+
+    /// Returns the [`LineColumn`] of the given byte offset.
+    ///
+    /// If the offset points into a multi-byte character, the position of that character is
+    /// returned. An offset equal to the length of the source is the position after the last
+    /// character.
+    ///
+    /// Returns `None` if the offset is out of bounds.
+    pub fn line_col(&self, offset: usize) -> Option<LineColumn> {
+        if offset > self.source.len() {
+            return None
+        }
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+        let line_start = self.line_starts[line];
+        let column = self.source[line_start..]
+            .char_indices()
+            .take_while(|(i, c)| line_start + i + c.len_utf8() <= offset)
+            .count();
+        Some(LineColumn { line: line + 1, column: column + 1 })
+    }
+}
+
+impl FromStr for SourceLocation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid_location = move || format!("{} invalid source location", s);
+
+        let mut split = s.split(':');
+        let start = split
+            .next()
+            .ok_or_else(invalid_location)?
+            .parse::<usize>()
+            .map_err(|_| invalid_location())?;
+        let length = split
+            .next()
+            .ok_or_else(invalid_location)?
+            .parse::<isize>()
+            .map_err(|_| invalid_location())?;
+        let index = split
+            .next()
+            .ok_or_else(invalid_location)?
+            .parse::<isize>()
+            .map_err(|_| invalid_location())?;
+
+        let length = if length < 0 { None } else { Some(length as usize) };
+        let index = if index < 0 { None } else { Some(index as usize) };
+
+        Ok(Self { start, length, index })
+    }
+}
+
+impl fmt::Display for SourceLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.start.fmt(f)?;
+        f.write_char(':')?;
+        if let Some(length) = self.length {
+            length.fmt(f)?;
+        } else {
+            f.write_str("-1")?;
+        }
+        f.write_char(':')?;
+        if let Some(index) = self.index {
+            index.fmt(f)?;
+        } else {
+            f.write_str("-1")?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! node_types {
+    ($($(#[$attr:meta])* $name:ident,)*) => {
+        /// The type of an AST node
+        ///
+        /// The `Display` and `FromStr` implementations use the same spelling as solc's `nodeType`,
+        /// unknown node types are represented as [`NodeType::Other`].
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum NodeType {
+            $($(#[$attr])* $name,)*
+            /// An unknown AST node type.
+            Other(String),
+        }
+
+        impl NodeType {
+            /// Returns the name of the node type as used by solc
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $(NodeType::$name => stringify!($name),)*
+                    NodeType::Other(name) => name,
+                }
+            }
+        }
+
+        impl FromStr for NodeType {
+            type Err = Infallible;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let ty = match s {
+                    $(stringify!($name) => NodeType::$name,)*
+                    other => NodeType::Other(other.to_string()),
+                };
+                Ok(ty)
+            }
+        }
+    };
+}
+
+node_types! {
+    // Expressions
+    Assignment,
+    BinaryOperation,
+    Conditional,
+    ElementaryTypeNameExpression,
+    FunctionCall,
+    FunctionCallOptions,
+    Identifier,
+    IndexAccess,
+    IndexRangeAccess,
+    Literal,
+    MemberAccess,
+    NewExpression,
+    TupleExpression,
+    UnaryOperation,
+
+    // Statements
+    Block,
+    Break,
+    Continue,
+    DoWhileStatement,
+    EmitStatement,
+    ExpressionStatement,
+    ForStatement,
+    IfStatement,
+    InlineAssembly,
+    PlaceholderStatement,
+    Return,
+    RevertStatement,
+    TryStatement,
+    UncheckedBlock,
+    VariableDeclarationStatement,
+    VariableDeclaration,
+    WhileStatement,
+
+    // Yul statements
+    YulAssignment,
+    YulBlock,
+    YulBreak,
+    YulContinue,
+    YulExpressionStatement,
+    YulLeave,
+    YulForLoop,
+    YulFunctionDefinition,
+    YulIf,
+    YulSwitch,
+    YulVariableDeclaration,
+
+    // Yul expressions
+    YulFunctionCall,
+    YulIdentifier,
+    YulLiteral,
+
+    // Yul literals
+    YulLiteralValue,
+    YulHexValue,
+
+    // Definitions
+    ContractDefinition,
+    FunctionDefinition,
+    EventDefinition,
+    ErrorDefinition,
+    ModifierDefinition,
+    StructDefinition,
+    EnumDefinition,
+    UserDefinedValueTypeDefinition,
+
+    // Directives
+    PragmaDirective,
+    ImportDirective,
+    UsingForDirective,
+
+    // Misc
+    SourceUnit,
+    InheritanceSpecifier,
+    ElementaryTypeName,
+    FunctionTypeName,
+    ParameterList,
+    TryCatchClause,
+    ModifierInvocation,
+    ArrayTypeName,
+    Mapping,
+    UserDefinedTypeName,
+    IdentifierPath,
+    OverrideSpecifier,
+    StructuredDocumentation,
+    EnumValue,
+}
+
+impl fmt::Display for NodeType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Serialize for NodeType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for NodeType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Ok(s.parse().expect("infallible"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_ast() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let _ast: Ast = serde_json::from_str(ast).unwrap();
+    }
+
+    #[test]
+    fn can_borrow_attributes() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+        let contract =
+            ast.nodes.iter().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+
+        assert_eq!(contract.attribute_ref::<&str>("name"), Some("ERC4626"));
+        assert_eq!(contract.attribute::<String>("name").as_deref(), Some("ERC4626"));
+        assert_eq!(contract.attribute_ref::<&str>("abstract"), None);
+        assert_eq!(contract.attribute_ref::<bool>("abstract"), Some(true));
+        let base = contract.attribute_ref::<Vec<Node>>("baseContracts").unwrap();
+        assert_eq!(base.len(), 1);
+    }
+
+    #[test]
+    fn can_read_source_unit() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+
+        let unit = ast.as_source_unit();
+        assert_eq!(unit.id, ast.id);
+        assert_eq!(unit.absolute_path, ast.absolute_path);
+        assert_eq!(unit.license.as_deref(), Some("AGPL-3.0-only"));
+        assert_eq!(unit.solidity_versions, vec![">=0.8.0".to_string()]);
+        assert_eq!(unit.abicoder, None);
+        assert!(unit.experimental.is_empty());
+
+        let mut node: Node = serde_json::from_value(serde_json::to_value(&ast).unwrap()).unwrap();
+        node.nodes.push(Node {
+            id: 0,
+            node_type: NodeType::PragmaDirective,
+            src: "0:0:0".parse().unwrap(),
+            nodes: vec![],
+            body: None,
+            other: vec![(
+                "literals".to_string(),
+                serde_json::json!(["experimental", "ABIEncoderV2"]),
+            )]
+            .into_iter()
+            .collect(),
+        });
+        let from_node = node.as_source_unit().unwrap();
+        assert_eq!(from_node.exported_symbols, unit.exported_symbols);
+        assert_eq!(from_node.experimental, vec!["ABIEncoderV2".to_string()]);
+        assert!(node.other.contains_key("license"));
+        assert!(ast.nodes[0].as_source_unit().is_none());
+    }
+
+    #[test]
+    fn can_convert_typed_nodes() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+
+        let contract =
+            ast.nodes.iter().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+        let typed = contract.typed().unwrap();
+        match typed {
+            TypedNode::ContractDefinition(ref def) => {
+                assert_eq!(def.name, "ERC4626");
+                assert_eq!(def.contract_kind, ContractKind::Contract);
+                assert!(def.is_abstract);
+                assert_eq!(def.base_contracts.len(), 1);
+
+                let functions = def
+                    .nodes
+                    .iter()
+                    .filter_map(|n| n.typed())
+                    .filter_map(|n| match n {
+                        TypedNode::FunctionDefinition(f) => Some(f),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                assert!(!functions.is_empty());
+                assert!(functions.iter().any(|f| f.name == "deposit" &&
+                    f.visibility == Visibility::Public &&
+                    f.kind == FunctionKind::Function));
+            }
+            _ => panic!("expected contract definition"),
+        }
+
+        let node = Node::try_from(typed).unwrap();
+        assert_eq!(&node, contract);
+
+        let pragma = ast.nodes.iter().find(|n| n.node_type == NodeType::PragmaDirective).unwrap();
+        assert!(matches!(pragma.typed(), Some(TypedNode::PragmaDirective(_))));
+    }
+
+    #[test]
+    fn can_visit_nodes() {
+        struct Collect(Vec<(usize, NodeType)>);
+
+        impl Visitor for Collect {
+            fn visit_node(&mut self, node: &Node) {
+                self.0.push((node.id, node.node_type.clone()));
+            }
+        }
+
+        struct ShiftSrc(usize);
+
+        impl Visitor for ShiftSrc {
+            fn visit_node_mut(&mut self, node: &mut Node) {
+                node.src.start += self.0;
+            }
+        }
+
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let mut ast: Ast = serde_json::from_str(ast).unwrap();
+
+        let mut visitor = Collect(Vec::new());
+        ast.accept(&mut visitor);
+        let visited = visitor.0;
+
+        // pre-order: the contract is visited before its children
+        let contract =
+            ast.nodes.iter().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+        let contract_pos = visited.iter().position(|(id, _)| *id == contract.id).unwrap();
+        let first_child = visited.iter().position(|(id, _)| *id == contract.nodes[0].id).unwrap();
+        assert_eq!(contract_pos + 1, first_child);
+        // function bodies are visited too
+        assert!(visited.iter().any(|(_, ty)| *ty == NodeType::Block));
+
+        let before = contract.src.start;
+        ast.accept_mut(&mut ShiftSrc(10));
+        let contract =
+            ast.nodes.iter().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+        assert_eq!(contract.src.start, before + 10);
+    }
+
+    #[test]
+    fn can_iterate_descendants() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let mut ast: Ast = serde_json::from_str(ast).unwrap();
+
+        struct Collect(Vec<usize>);
+
+        impl Visitor for Collect {
+            fn visit_node(&mut self, node: &Node) {
+                self.0.push(node.id);
+            }
+        }
+
+        let mut visitor = Collect(Vec::new());
+        ast.accept(&mut visitor);
+        let ids = ast.nodes_recursive().map(|n| n.id).collect::<Vec<_>>();
+        assert_eq!(ids, visitor.0);
+
+        let contract =
+            ast.nodes.iter().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+        let mut visitor = Collect(Vec::new());
+        contract.accept(&mut visitor);
+        assert_eq!(contract.descendants().count() + 1, visitor.0.len());
+        assert!(contract.descendants().any(|n| n.node_type == NodeType::Block));
+
+        for node in ast.nodes_recursive_mut() {
+            node.src.index = Some(1);
+        }
+        assert!(ast.nodes_recursive().all(|n| n.src.index == Some(1)));
+    }
+
+    #[test]
+    fn can_convert_to_line_col() {
+        let source = "pragma solidity ^0.8.0;\ncontract A {\n    uint x;\n}\n";
+        let loc: SourceLocation = "24:26:0".parse().unwrap();
+        let (start, end) = loc.to_line_col(source).unwrap();
+        assert_eq!(start, LineColumn { line: 2, column: 1 });
+        assert_eq!(end, LineColumn { line: 4, column: 2 });
+
+        let loc: SourceLocation = "24:-1:0".parse().unwrap();
+        assert!(loc.to_line_col(source).is_none());
+
+        let loc: SourceLocation = "50:10:0".parse().unwrap();
+        assert!(loc.to_line_col(source).is_none());
+    }
+
+    #[test]
+    fn can_convert_to_line_col_utf8() {
+        // `ö` is 2 bytes
+        let source = "// ööö\nstring s = \"ö\";";
+        let index = LineIndex::new(source);
+        assert_eq!(index.line_col(3), Some(LineColumn { line: 1, column: 4 }));
+        assert_eq!(index.line_col(5), Some(LineColumn { line: 1, column: 5 }));
+        // offset inside of the second `ö`
+        assert_eq!(index.line_col(6), Some(LineColumn { line: 1, column: 5 }));
+        // start of the second line
+        assert_eq!(index.line_col(10), Some(LineColumn { line: 2, column: 1 }));
+
+        let loc = SourceLocation { start: 22, length: Some(2), index: Some(0) };
+        let (start, end) = loc.to_line_col_indexed(&index).unwrap();
+        assert_eq!(start, LineColumn { line: 2, column: 13 });
+        assert_eq!(end, LineColumn { line: 2, column: 14 });
+    }
+
+    #[test]
+    fn can_compare_source_locations() {
+        let outer: SourceLocation = "10:20:0".parse().unwrap();
+        let inner: SourceLocation = "15:5:0".parse().unwrap();
+        let other_file: SourceLocation = "15:5:1".parse().unwrap();
+        let partial: SourceLocation = "25:10:0".parse().unwrap();
+        let unknown_length: SourceLocation = "15:-1:0".parse().unwrap();
+
+        assert!(outer.contains(&inner));
+        assert!(!inner.contains(&outer));
+        assert!(!outer.contains(&other_file));
+        assert!(!outer.contains(&partial));
+        assert!(outer.contains(&unknown_length));
+
+        assert!(outer.overlaps(&inner));
+        assert!(outer.overlaps(&partial));
+        assert!(partial.overlaps(&outer));
+        assert!(!outer.overlaps(&other_file));
+        assert!(!outer.overlaps(&unknown_length));
+
+        assert!(outer.contains_offset(10));
+        assert!(outer.contains_offset(29));
+        assert!(!outer.contains_offset(30));
+        assert!(!unknown_length.contains_offset(15));
+    }
+
+    #[test]
+    fn can_find_innermost_node() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+        let contract =
+            ast.nodes.iter().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+
+        let function = contract
+            .nodes
+            .iter()
+            .find(|n| {
+                n.node_type == NodeType::FunctionDefinition &&
+                    n.attribute::<String>("name").unwrap() == "deposit"
+            })
+            .unwrap();
+        let body = function.body.as_deref().unwrap();
+
+        assert_eq!(contract.innermost_at(body.src.start).unwrap(), body);
+        assert_eq!(contract.innermost_at(function.src.start).unwrap(), function);
+        assert_eq!(contract.innermost_at(contract.src.start).unwrap(), contract);
+        assert!(contract.innermost_at(contract.src.end()).is_none());
+    }
+
+    #[test]
+    fn can_parse_node_type() {
+        assert_eq!("FunctionCall".parse::<NodeType>().unwrap(), NodeType::FunctionCall);
+        assert_eq!(NodeType::YulBlock.to_string(), "YulBlock");
+
+        let other = NodeType::Other("Foo".to_string());
+        assert_eq!(other.to_string(), "Foo");
+        assert_eq!(other.to_string().parse::<NodeType>().unwrap(), other);
+
+        assert_eq!(serde_json::to_string(&NodeType::FunctionCall).unwrap(), "\"FunctionCall\"");
+        assert_eq!(serde_json::to_string(&other).unwrap(), "\"Foo\"");
+        assert_eq!(serde_json::from_str::<NodeType>("\"Foo\"").unwrap(), other);
+    }
+
+    #[test]
+    fn can_collect_imports() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+
+        let imports = ast.imports();
+        assert_eq!(imports.len(), 3);
+        assert_eq!(imports[0].file, "../tokens/ERC20.sol");
+        assert!(imports[0].absolute_path.ends_with("solmate/src/tokens/ERC20.sol"));
+        assert_eq!(imports[0].src, "68:42:4".parse().unwrap());
+        assert_eq!(imports[0].symbol_aliases.len(), 1);
+        assert_eq!(imports[0].symbol_aliases[0].foreign_name(), Some("ERC20"));
+        assert_eq!(imports[0].symbol_aliases[0].local_name(), Some("ERC20"));
+    }
+
+    #[test]
+    fn can_find_parents() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+        let parents = ast.parent_map();
+
+        let contract =
+            ast.nodes.iter().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+        assert_eq!(parents[&contract.id], ast.id);
+
+        let function =
+            contract.nodes.iter().find(|n| n.node_type == NodeType::FunctionDefinition).unwrap();
+        let body = function.body.as_deref().unwrap();
+        assert_eq!(parents[&function.id], contract.id);
+        assert_eq!(parents[&body.id], function.id);
+        assert_eq!(parents.len(), ast.nodes_recursive().count());
+
+        assert_eq!(ast.find_enclosing(body.id, NodeType::ContractDefinition), Some(contract));
+        assert_eq!(ast.find_enclosing(body.id, NodeType::FunctionDefinition), Some(function));
+        assert_eq!(contract.find_enclosing(body.id, NodeType::Block), None);
+        assert_eq!(ast.find_enclosing(contract.id, NodeType::ContractDefinition), None);
+    }
+
+    #[test]
+    fn can_index_nodes() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+        let index = ast.id_index();
+        assert_eq!(index.len(), ast.nodes_recursive().count());
+
+        let contract =
+            ast.nodes.iter().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+        assert_eq!(index.get(contract.id), Some(contract));
+        assert_eq!(ast.node_by_id(contract.id), Some(contract));
+
+        let body = contract.descendants().find(|n| n.node_type == NodeType::Block).unwrap();
+        assert_eq!(index.get(body.id), Some(body));
+        assert_eq!(index.get(usize::MAX), None);
+    }
+
+    #[test]
+    fn can_collect_nodes_of_type() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+
+        let functions = ast.collect(NodeType::FunctionDefinition);
+        assert_eq!(functions.len(), 18);
+        assert_eq!(ast.collect(NodeType::EventDefinition).len(), 2);
+        assert!(ast.collect(NodeType::Other("Unknown".to_string())).is_empty());
+
+        let contract = ast.collect(NodeType::ContractDefinition)[0];
+        assert_eq!(contract.collect_of_type(NodeType::ContractDefinition), vec![contract]);
+        assert_eq!(contract.collect_of_type(NodeType::FunctionDefinition), functions);
+        // function bodies are reachable via `body`
+        assert!(!contract.collect_of_type(NodeType::Block).is_empty());
+    }
+
+    #[test]
+    fn can_resolve_exports() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+
+        let exports = ast.resolved_exports();
+        assert_eq!(exports.len(), 1);
+        let contract = exports["ERC4626"][0];
+        assert_eq!(contract.node_type, NodeType::ContractDefinition);
+        assert_eq!(contract.id, ast.exported_symbols["ERC4626"][0]);
+
+        let unresolved = ast.unresolved_exports();
+        assert_eq!(
+            unresolved.keys().collect::<Vec<_>>(),
+            vec!["ERC20", "FixedPointMathLib", "SafeTransferLib"]
+        );
+        assert_eq!(unresolved["ERC20"], ast.exported_symbols["ERC20"]);
+    }
+}
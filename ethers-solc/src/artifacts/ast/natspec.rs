@@ -0,0 +1,189 @@
+//! NatSpec documentation attached to AST definitions
+
+use super::Node;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+impl Node {
+    /// Returns the NatSpec documentation text attached to this node, if any.
+    ///
+    /// Supports both the `StructuredDocumentation` object form and the plain string form of
+    /// older solc versions. Use [`parse_natspec`] to split it into its tags.
+    pub fn documentation(&self) -> Option<String> {
+        match self.other.get("documentation")? {
+            serde_json::Value::String(text) => Some(text.clone()),
+            serde_json::Value::Object(doc) => doc.get("text")?.as_str().map(str::to_string),
+            _ => None,
+        }
+    }
+}
+
+/// The tags of a NatSpec comment, see [`parse_natspec`]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NatSpec {
+    /// `@title`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// `@author`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// `@notice`, this is also used for text without a tag
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notice: Option<String>,
+    /// `@dev`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dev: Option<String>,
+    /// `@param <name> <description>`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub params: BTreeMap<String, String>,
+    /// All `@return` tags in order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub returns: Vec<String>,
+    /// `@inheritdoc <contract>`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inheritdoc: Option<String>,
+    /// `@custom:<name>` tags
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, String>,
+}
+
+/// Parses the text of a NatSpec comment, as returned by [`Node::documentation`].
+///
+/// Text that spans multiple lines is joined with `\n`, leading `*` of `/** */` style comments
+/// are stripped. Unknown tags are ignored.
+///
+/// # Example
+///
+/// ```
+/// use ethers_solc::artifacts::ast::parse_natspec;
+///
+/// let natspec = parse_natspec("@notice Deposits assets\n @param assets The amount\n @return shares");
+/// assert_eq!(natspec.notice.as_deref(), Some("Deposits assets"));
+/// assert_eq!(natspec.params["assets"], "The amount");
+/// assert_eq!(natspec.returns, vec!["shares".to_string()]);
+/// ```
+pub fn parse_natspec(text: &str) -> NatSpec {
+    let mut natspec = NatSpec::default();
+    // the current tag and its content
+    let mut current: Option<(String, String)> = None;
+
+    for line in text.lines() {
+        let line = line.trim();
+        let line = line.strip_prefix('*').map(str::trim_start).unwrap_or(line);
+        if let Some(tagged) = line.strip_prefix('@') {
+            if let Some((tag, content)) = current.take() {
+                natspec.insert(&tag, content);
+            }
+            let (tag, content) = split_word(tagged);
+            current = Some((tag.to_string(), content.to_string()));
+        } else if let Some((_, content)) = current.as_mut() {
+            if !content.is_empty() {
+                content.push('\n');
+            }
+            content.push_str(line);
+        } else if !line.is_empty() {
+            current = Some(("notice".to_string(), line.to_string()));
+        }
+    }
+    if let Some((tag, content)) = current {
+        natspec.insert(&tag, content);
+    }
+    natspec
+}
+
+impl NatSpec {
+    fn insert(&mut self, tag: &str, content: String) {
+        let content = content.trim_end().to_string();
+        match tag {
+            "title" => append(&mut self.title, content),
+            "author" => append(&mut self.author, content),
+            "notice" => append(&mut self.notice, content),
+            "dev" => append(&mut self.dev, content),
+            "param" => {
+                let (name, description) = split_word(&content);
+                self.params.insert(name.to_string(), description.to_string());
+            }
+            "return" => self.returns.push(content),
+            "inheritdoc" => self.inheritdoc = Some(content),
+            tag => {
+                if let Some(name) = tag.strip_prefix("custom:") {
+                    self.custom.insert(name.to_string(), content);
+                }
+            }
+        }
+    }
+}
+
+/// Appends the content to an existing value of a tag that occurs multiple times
+fn append(value: &mut Option<String>, content: String) {
+    match value {
+        Some(value) => {
+            value.push('\n');
+            value.push_str(&content);
+        }
+        None => *value = Some(content),
+    }
+}
+
+/// Splits off the first word
+fn split_word(s: &str) -> (&str, &str) {
+    let s = s.trim_start();
+    match s.find(char::is_whitespace) {
+        Some(idx) => (&s[..idx], s[idx..].trim_start()),
+        None => (s, ""),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::artifacts::ast::{Ast, NodeType};
+
+    #[test]
+    fn can_read_documentation() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+        let contract =
+            ast.nodes.iter().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+
+        let natspec = parse_natspec(&contract.documentation().unwrap());
+        assert_eq!(
+            natspec.notice.as_deref(),
+            Some("Minimal ERC4626 tokenized Vault implementation.")
+        );
+        assert_eq!(
+            natspec.author.as_deref(),
+            Some("Solmate (https://github.com/Rari-Capital/solmate/blob/main/src/mixins/ERC4626.sol)")
+        );
+
+        let mut legacy = contract.clone();
+        legacy.other.insert("documentation".to_string(), "@dev legacy".into());
+        assert_eq!(legacy.documentation().as_deref(), Some("@dev legacy"));
+    }
+
+    #[test]
+    fn can_parse_natspec() {
+        let natspec = parse_natspec(
+            " Implicit notice\n * @dev Some details\n *  over two lines\n * @param a The first\n * @param b\n * @return x The result\n * @return y\n * @custom:security-contact me@example.com\n * @inheritdoc IERC20",
+        );
+        assert_eq!(
+            natspec,
+            NatSpec {
+                notice: Some("Implicit notice".to_string()),
+                dev: Some("Some details\nover two lines".to_string()),
+                params: vec![
+                    ("a".to_string(), "The first".to_string()),
+                    ("b".to_string(), String::new())
+                ]
+                .into_iter()
+                .collect(),
+                returns: vec!["x The result".to_string(), "y".to_string()],
+                inheritdoc: Some("IERC20".to_string()),
+                custom: vec![("security-contact".to_string(), "me@example.com".to_string())]
+                    .into_iter()
+                    .collect(),
+                ..Default::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,871 @@
+//! Prints AST nodes as Solidity source code
+//!
+//! The output is not byte-identical to the original source: formatting, comments and NatSpec are
+//! not preserved, but it can be compiled again.
+
+use super::{Ast, Node, NodeType};
+use serde_json::Value;
+
+const INDENT: &str = "    ";
+
+/// An error returned if a node can't be printed as Solidity source code.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("can't print `{node_type}` node {id:?} as Solidity source")]
+pub struct UnprintableNode {
+    /// The type of the first node that can't be printed
+    pub node_type: NodeType,
+    /// The id of the node, if any
+    pub id: Option<usize>,
+}
+
+impl UnprintableNode {
+    fn new(node: &Value) -> Self {
+        Self {
+            node_type: ty(node).parse().expect("infallible"),
+            id: node.get("id").and_then(Value::as_u64).map(|id| id as usize),
+        }
+    }
+}
+
+type Result<T, E = UnprintableNode> = std::result::Result<T, E>;
+
+impl Ast {
+    /// Reconstructs the Solidity source code of this AST, see [`Node::to_solidity`]
+    pub fn to_solidity(&self) -> Result<String> {
+        print(&serde_json::to_value(self).expect("ast serialization can't fail"), 0)
+    }
+}
+
+impl Node {
+    /// Reconstructs the Solidity source code of this node.
+    ///
+    /// The output is not identical to the original source since formatting, comments and
+    /// NatSpec are not preserved, but it can be parsed by solc again.
+    ///
+    /// Returns an [`UnprintableNode`] error with the first node that can't be printed, for
+    /// example `InlineAssembly`.
+    pub fn to_solidity(&self) -> Result<String> {
+        print(&serde_json::to_value(self).expect("node serialization can't fail"), 0)
+    }
+}
+
+/// Returns the `nodeType` of the node
+fn ty(node: &Value) -> &str {
+    node.get("nodeType").and_then(Value::as_str).unwrap_or_default()
+}
+
+fn str_attr<'a>(node: &'a Value, key: &str) -> &'a str {
+    node.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+fn bool_attr(node: &Value, key: &str) -> bool {
+    node.get(key).and_then(Value::as_bool).unwrap_or_default()
+}
+
+/// Returns the child node with the given key, `None` if missing or `null`
+fn child<'a>(node: &'a Value, key: &str) -> Option<&'a Value> {
+    node.get(key).filter(|v| !v.is_null())
+}
+
+fn list<'a>(node: &'a Value, key: &str) -> &'a [Value] {
+    node.get(key).and_then(Value::as_array).map(Vec::as_slice).unwrap_or_default()
+}
+
+fn indent(level: usize) -> String {
+    INDENT.repeat(level)
+}
+
+/// Prints all nodes with the given printer and joins them with `sep`
+fn join<'a>(
+    nodes: impl IntoIterator<Item = &'a Value>,
+    sep: &str,
+    printer: impl FnMut(&'a Value) -> Result<String>,
+) -> Result<String> {
+    Ok(nodes.into_iter().map(printer).collect::<Result<Vec<_>>>()?.join(sep))
+}
+
+/// Prints any node, the first line is not indented
+fn print(node: &Value, level: usize) -> Result<String> {
+    match ty(node) {
+        "SourceUnit" => source_unit(node),
+        "PragmaDirective" => Ok(pragma(node)),
+        "ImportDirective" => import(node),
+        "ContractDefinition" => contract(node, level),
+        "UsingForDirective" => using_for(node),
+        "FunctionDefinition" => function(node, level),
+        "ModifierDefinition" => modifier(node, level),
+        "EventDefinition" => Ok(format!(
+            "event {}({}){};",
+            str_attr(node, "name"),
+            parameters(child(node, "parameters"))?,
+            if bool_attr(node, "anonymous") { " anonymous" } else { "" }
+        )),
+        "ErrorDefinition" => Ok(format!(
+            "error {}({});",
+            str_attr(node, "name"),
+            parameters(child(node, "parameters"))?
+        )),
+        "StructDefinition" => {
+            let mut s = format!("struct {} {{\n", str_attr(node, "name"));
+            for member in list(node, "members") {
+                s.push_str(&format!("{}{};\n", indent(level + 1), variable(member)?));
+            }
+            s.push_str(&indent(level));
+            s.push('}');
+            Ok(s)
+        }
+        "EnumDefinition" => Ok(format!(
+            "enum {} {{ {} }}",
+            str_attr(node, "name"),
+            join(list(node, "members"), ", ", |m| Ok(str_attr(m, "name").to_string()))?
+        )),
+        "UserDefinedValueTypeDefinition" => Ok(format!(
+            "type {} is {};",
+            str_attr(node, "name"),
+            type_name(child(node, "underlyingType").ok_or_else(|| UnprintableNode::new(node))?)?
+        )),
+        "VariableDeclaration" => variable(node),
+        "ParameterList" => parameters(Some(node)),
+        "Block" |
+        "UncheckedBlock" |
+        "ExpressionStatement" |
+        "VariableDeclarationStatement" |
+        "IfStatement" |
+        "ForStatement" |
+        "WhileStatement" |
+        "DoWhileStatement" |
+        "Return" |
+        "Break" |
+        "Continue" |
+        "EmitStatement" |
+        "RevertStatement" |
+        "PlaceholderStatement" |
+        "TryStatement" |
+        "InlineAssembly" => statement(node, level),
+        _ => expression(node),
+    }
+}
+
+fn source_unit(node: &Value) -> Result<String> {
+    let mut s = join(list(node, "nodes"), "\n\n", |n| member(n, 0))?;
+    s.push('\n');
+    Ok(s)
+}
+
+/// Prints a member of a source unit or contract
+fn member(node: &Value, level: usize) -> Result<String> {
+    let mut s = print(node, level)?;
+    if ty(node) == "VariableDeclaration" {
+        s.push(';');
+    }
+    Ok(s)
+}
+
+fn pragma(node: &Value) -> String {
+    let literals = list(node, "literals").iter().filter_map(Value::as_str).collect::<Vec<_>>();
+    match literals.split_first() {
+        Some((name, value)) => format!("pragma {} {};", name, value.concat()),
+        None => "pragma;".to_string(),
+    }
+}
+
+fn import(node: &Value) -> Result<String> {
+    let file = quote(str_attr(node, "file"));
+    let aliases = list(node, "symbolAliases");
+    let unit_alias = str_attr(node, "unitAlias");
+    if !aliases.is_empty() {
+        let aliases = join(aliases, ", ", |alias| {
+            let foreign = alias.get("foreign").ok_or_else(|| UnprintableNode::new(node))?;
+            let mut s = str_attr(foreign, "name").to_string();
+            if let Some(local) = alias.get("local").and_then(Value::as_str) {
+                s.push_str(" as ");
+                s.push_str(local);
+            }
+            Ok(s)
+        })?;
+        Ok(format!("import {{{}}} from {};", aliases, file))
+    } else if !unit_alias.is_empty() {
+        Ok(format!("import {} as {};", file, unit_alias))
+    } else {
+        Ok(format!("import {};", file))
+    }
+}
+
+fn contract(node: &Value, level: usize) -> Result<String> {
+    let mut s = String::new();
+    if bool_attr(node, "abstract") {
+        s.push_str("abstract ");
+    }
+    s.push_str(&format!("{} {}", str_attr(node, "contractKind"), str_attr(node, "name")));
+
+    let base_contracts = list(node, "baseContracts");
+    if !base_contracts.is_empty() {
+        s.push_str(" is ");
+        s.push_str(&join(base_contracts, ", ", |base| {
+            let mut s =
+                type_name(child(base, "baseName").ok_or_else(|| UnprintableNode::new(base))?)?;
+            if let Some(args) = child(base, "arguments") {
+                s.push_str(&format!(
+                    "({})",
+                    join(args.as_array().into_iter().flatten(), ", ", expression)?
+                ));
+            }
+            Ok(s)
+        })?);
+    }
+
+    s.push_str(" {\n");
+    let members = join(list(node, "nodes"), "\n\n", |n| {
+        Ok(format!("{}{}", indent(level + 1), member(n, level + 1)?))
+    })?;
+    if !members.is_empty() {
+        s.push_str(&members);
+        s.push('\n');
+    }
+    s.push_str(&indent(level));
+    s.push('}');
+    Ok(s)
+}
+
+fn using_for(node: &Value) -> Result<String> {
+    let library = match child(node, "libraryName") {
+        Some(library) => type_name(library)?,
+        None => format!(
+            "{{{}}}",
+            join(list(node, "functionList"), ", ", |f| {
+                type_name(child(f, "function").ok_or_else(|| UnprintableNode::new(node))?)
+            })?
+        ),
+    };
+    let ty = match child(node, "typeName") {
+        Some(ty) => type_name(ty)?,
+        None => "*".to_string(),
+    };
+    let global = if bool_attr(node, "global") { " global" } else { "" };
+    Ok(format!("using {} for {}{};", library, ty, global))
+}
+
+fn function(node: &Value, level: usize) -> Result<String> {
+    let kind = str_attr(node, "kind");
+    let mut s = match kind {
+        "constructor" | "fallback" | "receive" => kind.to_string(),
+        _ => format!("function {}", str_attr(node, "name")),
+    };
+    s.push_str(&format!("({})", parameters(child(node, "parameters"))?));
+
+    if kind != "constructor" && kind != "freeFunction" {
+        s.push(' ');
+        s.push_str(str_attr(node, "visibility"));
+    }
+    let mutability = str_attr(node, "stateMutability");
+    if !mutability.is_empty() && mutability != "nonpayable" {
+        s.push(' ');
+        s.push_str(mutability);
+    }
+    s.push_str(&modifiers(node)?);
+
+    let returns = parameters(child(node, "returnParameters"))?;
+    if !returns.is_empty() {
+        s.push_str(&format!(" returns ({})", returns));
+    }
+    s.push_str(&body(node, level)?);
+    Ok(s)
+}
+
+fn modifier(node: &Value, level: usize) -> Result<String> {
+    let mut s =
+        format!("modifier {}({})", str_attr(node, "name"), parameters(child(node, "parameters"))?);
+    s.push_str(&modifiers(node)?);
+    s.push_str(&body(node, level)?);
+    Ok(s)
+}
+
+/// Prints the `virtual`, `override` and modifier invocations of a function or modifier
+fn modifiers(node: &Value) -> Result<String> {
+    let mut s = String::new();
+    if bool_attr(node, "virtual") {
+        s.push_str(" virtual");
+    }
+    if let Some(overrides) = child(node, "overrides") {
+        s.push(' ');
+        s.push_str(&override_specifier(overrides)?);
+    }
+    for invocation in list(node, "modifiers") {
+        let name =
+            child(invocation, "modifierName").ok_or_else(|| UnprintableNode::new(invocation))?;
+        s.push(' ');
+        s.push_str(&type_name(name)?);
+        if let Some(args) = child(invocation, "arguments") {
+            s.push_str(&format!(
+                "({})",
+                join(args.as_array().into_iter().flatten(), ", ", expression)?
+            ));
+        }
+    }
+    Ok(s)
+}
+
+fn override_specifier(node: &Value) -> Result<String> {
+    let overrides = list(node, "overrides");
+    if overrides.is_empty() {
+        Ok("override".to_string())
+    } else {
+        Ok(format!("override({})", join(overrides, ", ", type_name)?))
+    }
+}
+
+/// Prints the body of a function or modifier or `;` if it has none
+fn body(node: &Value, level: usize) -> Result<String> {
+    match child(node, "body") {
+        Some(body) => Ok(format!(" {}", block(body, level)?)),
+        None => Ok(";".to_string()),
+    }
+}
+
+fn parameters(node: Option<&Value>) -> Result<String> {
+    match node {
+        Some(node) => join(list(node, "parameters"), ", ", variable),
+        None => Ok(String::new()),
+    }
+}
+
+fn variable(node: &Value) -> Result<String> {
+    let mut s = match child(node, "typeName") {
+        Some(ty) => type_name(ty)?,
+        None => "var".to_string(),
+    };
+    let location = str_attr(node, "storageLocation");
+    if !location.is_empty() && location != "default" {
+        s.push(' ');
+        s.push_str(location);
+    }
+    if bool_attr(node, "indexed") {
+        s.push_str(" indexed");
+    }
+    if bool_attr(node, "stateVariable") {
+        s.push(' ');
+        s.push_str(str_attr(node, "visibility"));
+    }
+    if str_attr(node, "mutability") == "immutable" {
+        s.push_str(" immutable");
+    } else if bool_attr(node, "constant") {
+        s.push_str(" constant");
+    }
+    if let Some(overrides) = child(node, "overrides") {
+        s.push(' ');
+        s.push_str(&override_specifier(overrides)?);
+    }
+    let name = str_attr(node, "name");
+    if !name.is_empty() {
+        s.push(' ');
+        s.push_str(name);
+    }
+    if let Some(value) = child(node, "value") {
+        s.push_str(" = ");
+        s.push_str(&expression(value)?);
+    }
+    Ok(s)
+}
+
+fn type_name(node: &Value) -> Result<String> {
+    match ty(node) {
+        "ElementaryTypeName" => {
+            let name = str_attr(node, "name");
+            if name == "address" && str_attr(node, "stateMutability") == "payable" {
+                Ok("address payable".to_string())
+            } else {
+                Ok(name.to_string())
+            }
+        }
+        "UserDefinedTypeName" => match child(node, "pathNode") {
+            Some(path) => Ok(str_attr(path, "name").to_string()),
+            None => Ok(str_attr(node, "name").to_string()),
+        },
+        "IdentifierPath" => Ok(str_attr(node, "name").to_string()),
+        "ArrayTypeName" => {
+            let base =
+                type_name(child(node, "baseType").ok_or_else(|| UnprintableNode::new(node))?)?;
+            let length = child(node, "length").map(expression).transpose()?.unwrap_or_default();
+            Ok(format!("{}[{}]", base, length))
+        }
+        "Mapping" => {
+            let key = type_name(child(node, "keyType").ok_or_else(|| UnprintableNode::new(node))?)?;
+            let value =
+                type_name(child(node, "valueType").ok_or_else(|| UnprintableNode::new(node))?)?;
+            Ok(format!("mapping({} => {})", key, value))
+        }
+        "FunctionTypeName" => {
+            let mut s = format!(
+                "function ({}) {}",
+                parameters(child(node, "parameterTypes"))?,
+                str_attr(node, "visibility")
+            );
+            let mutability = str_attr(node, "stateMutability");
+            if mutability != "nonpayable" {
+                s.push(' ');
+                s.push_str(mutability);
+            }
+            let returns = parameters(child(node, "returnParameterTypes"))?;
+            if !returns.is_empty() {
+                s.push_str(&format!(" returns ({})", returns));
+            }
+            Ok(s)
+        }
+        _ => Err(UnprintableNode::new(node)),
+    }
+}
+
+fn block(node: &Value, level: usize) -> Result<String> {
+    let statements = list(node, "statements");
+    if statements.is_empty() {
+        return Ok(if ty(node) == "UncheckedBlock" { "unchecked {}" } else { "{}" }.to_string())
+    }
+    let mut s = if ty(node) == "UncheckedBlock" { "unchecked {\n" } else { "{\n" }.to_string();
+    for stmt in statements {
+        s.push_str(&format!("{}{}\n", indent(level + 1), statement(stmt, level + 1)?));
+    }
+    s.push_str(&indent(level));
+    s.push('}');
+    Ok(s)
+}
+
+fn statement(node: &Value, level: usize) -> Result<String> {
+    let required = |key: &str| child(node, key).ok_or_else(|| UnprintableNode::new(node));
+    match ty(node) {
+        "Block" | "UncheckedBlock" => block(node, level),
+        "ExpressionStatement" => Ok(format!("{};", expression(required("expression")?)?)),
+        "VariableDeclarationStatement" => {
+            let declarations = list(node, "declarations");
+            let mut s = match declarations {
+                [decl] if !decl.is_null() => variable(decl)?,
+                _ => format!(
+                    "({})",
+                    join(declarations, ", ", |d| if d.is_null() {
+                        Ok(String::new())
+                    } else {
+                        variable(d)
+                    })?
+                ),
+            };
+            if let Some(value) = child(node, "initialValue") {
+                s.push_str(" = ");
+                s.push_str(&expression(value)?);
+            }
+            s.push(';');
+            Ok(s)
+        }
+        "IfStatement" => {
+            let true_body = required("trueBody")?;
+            let mut s = format!("if ({}) ", expression(required("condition")?)?);
+            match child(node, "falseBody") {
+                Some(false_body) => {
+                    // wrap nested `if` statements so the `else` isn't attached to them
+                    if ty(true_body) == "IfStatement" {
+                        s.push_str(&format!(
+                            "{{\n{}{}\n{}}}",
+                            indent(level + 1),
+                            statement(true_body, level + 1)?,
+                            indent(level)
+                        ));
+                    } else {
+                        s.push_str(&statement(true_body, level)?);
+                    }
+                    s.push_str(" else ");
+                    s.push_str(&statement(false_body, level)?);
+                }
+                None => s.push_str(&statement(true_body, level)?),
+            }
+            Ok(s)
+        }
+        "ForStatement" => {
+            let init = child(node, "initializationExpression")
+                .map(|init| statement(init, level))
+                .transpose()?
+                .unwrap_or_else(|| ";".to_string());
+            let condition =
+                child(node, "condition").map(expression).transpose()?.unwrap_or_default();
+            let loop_expression = child(node, "loopExpression")
+                .and_then(|stmt| child(stmt, "expression"))
+                .map(expression)
+                .transpose()?
+                .unwrap_or_default();
+            Ok(format!(
+                "for ({} {}; {}) {}",
+                init,
+                condition,
+                loop_expression,
+                statement(required("body")?, level)?
+            ))
+        }
+        "WhileStatement" => Ok(format!(
+            "while ({}) {}",
+            expression(required("condition")?)?,
+            statement(required("body")?, level)?
+        )),
+        "DoWhileStatement" => Ok(format!(
+            "do {} while ({});",
+            statement(required("body")?, level)?,
+            expression(required("condition")?)?
+        )),
+        "Return" => match child(node, "expression") {
+            Some(expr) => Ok(format!("return {};", expression(expr)?)),
+            None => Ok("return;".to_string()),
+        },
+        "Break" => Ok("break;".to_string()),
+        "Continue" => Ok("continue;".to_string()),
+        "PlaceholderStatement" => Ok("_;".to_string()),
+        "EmitStatement" => Ok(format!("emit {};", expression(required("eventCall")?)?)),
+        "RevertStatement" => Ok(format!("revert {};", expression(required("errorCall")?)?)),
+        "TryStatement" => {
+            let mut s = format!("try {}", expression(required("externalCall")?)?);
+            for (idx, clause) in list(node, "clauses").iter().enumerate() {
+                let params =
+                    child(clause, "parameters").map(|p| parameters(Some(p))).transpose()?;
+                if idx == 0 {
+                    if let Some(params) = params {
+                        s.push_str(&format!(" returns ({})", params));
+                    }
+                } else {
+                    s.push_str(" catch");
+                    let error_name = str_attr(clause, "errorName");
+                    if !error_name.is_empty() {
+                        s.push(' ');
+                        s.push_str(error_name);
+                    }
+                    if let Some(params) = params {
+                        s.push_str(&format!("({})", params));
+                    }
+                }
+                let block_node =
+                    child(clause, "block").ok_or_else(|| UnprintableNode::new(clause))?;
+                s.push(' ');
+                s.push_str(&block(block_node, level)?);
+            }
+            Ok(s)
+        }
+        _ => Err(UnprintableNode::new(node)),
+    }
+}
+
+fn expression(node: &Value) -> Result<String> {
+    let required = |key: &str| child(node, key).ok_or_else(|| UnprintableNode::new(node));
+    match ty(node) {
+        "Assignment" => Ok(format!(
+            "{} {} {}",
+            expression(required("leftHandSide")?)?,
+            str_attr(node, "operator"),
+            expression(required("rightHandSide")?)?
+        )),
+        "BinaryOperation" => Ok(format!(
+            "{} {} {}",
+            expression(required("leftExpression")?)?,
+            str_attr(node, "operator"),
+            expression(required("rightExpression")?)?
+        )),
+        "UnaryOperation" => {
+            let operator = str_attr(node, "operator");
+            let operand = expression(required("subExpression")?)?;
+            if !bool_attr(node, "prefix") {
+                Ok(format!("{}{}", operand, operator))
+            } else if operator == "delete" || operand.starts_with(&['-', '+'][..]) {
+                Ok(format!("{} {}", operator, operand))
+            } else {
+                Ok(format!("{}{}", operator, operand))
+            }
+        }
+        "Conditional" => Ok(format!(
+            "{} ? {} : {}",
+            expression(required("condition")?)?,
+            expression(required("trueExpression")?)?,
+            expression(required("falseExpression")?)?
+        )),
+        "FunctionCall" => {
+            let callee = expression(required("expression")?)?;
+            let args = list(node, "arguments");
+            let names = list(node, "names");
+            if names.is_empty() {
+                Ok(format!("{}({})", callee, join(args, ", ", expression)?))
+            } else {
+                let args = names
+                    .iter()
+                    .zip(args)
+                    .map(|(name, arg)| {
+                        Ok(format!("{}: {}", name.as_str().unwrap_or_default(), expression(arg)?))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(format!("{}({{{}}})", callee, args.join(", ")))
+            }
+        }
+        "FunctionCallOptions" => {
+            let options = list(node, "names")
+                .iter()
+                .zip(list(node, "options"))
+                .map(|(name, opt)| {
+                    Ok(format!("{}: {}", name.as_str().unwrap_or_default(), expression(opt)?))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(format!("{}{{{}}}", expression(required("expression")?)?, options.join(", ")))
+        }
+        "Identifier" | "IdentifierPath" => Ok(str_attr(node, "name").to_string()),
+        "MemberAccess" => {
+            Ok(format!("{}.{}", expression(required("expression")?)?, str_attr(node, "memberName")))
+        }
+        "IndexAccess" => Ok(format!(
+            "{}[{}]",
+            expression(required("baseExpression")?)?,
+            child(node, "indexExpression").map(expression).transpose()?.unwrap_or_default()
+        )),
+        "IndexRangeAccess" => Ok(format!(
+            "{}[{}:{}]",
+            expression(required("baseExpression")?)?,
+            child(node, "startExpression").map(expression).transpose()?.unwrap_or_default(),
+            child(node, "endExpression").map(expression).transpose()?.unwrap_or_default()
+        )),
+        "Literal" => literal(node),
+        "NewExpression" => Ok(format!("new {}", type_name(required("typeName")?)?)),
+        "TupleExpression" => {
+            let components = join(list(node, "components"), ", ", |c| {
+                if c.is_null() {
+                    Ok(String::new())
+                } else {
+                    expression(c)
+                }
+            })?;
+            if bool_attr(node, "isInlineArray") {
+                Ok(format!("[{}]", components))
+            } else {
+                Ok(format!("({})", components))
+            }
+        }
+        "ElementaryTypeNameExpression" => match required("typeName")? {
+            Value::String(name) => Ok(name.clone()),
+            ty if str_attr(ty, "stateMutability") == "payable" => Ok("payable".to_string()),
+            ty => type_name(ty),
+        },
+        "ElementaryTypeName" |
+        "UserDefinedTypeName" |
+        "ArrayTypeName" |
+        "Mapping" |
+        "FunctionTypeName" => type_name(node),
+        _ => Err(UnprintableNode::new(node)),
+    }
+}
+
+fn literal(node: &Value) -> Result<String> {
+    let hex_value = str_attr(node, "hexValue");
+    match (str_attr(node, "kind"), node.get("value").and_then(Value::as_str)) {
+        ("number", Some(value)) => match node.get("subdenomination").and_then(Value::as_str) {
+            Some(unit) => Ok(format!("{} {}", value, unit)),
+            None => Ok(value.to_string()),
+        },
+        ("bool", Some(value)) => Ok(value.to_string()),
+        ("string", Some(value)) => Ok(quote(value)),
+        ("unicodeString", Some(value)) => Ok(format!("unicode{}", escape(value, false))),
+        // strings that are not valid UTF-8 don't have a `value`
+        ("string", None) | ("hexString", _) => Ok(format!("hex\"{}\"", hex_value)),
+        _ => Err(UnprintableNode::new(node)),
+    }
+}
+
+/// Returns the string as a quoted Solidity string literal
+fn quote(s: &str) -> String {
+    escape(s, true)
+}
+
+/// Escapes the string as a quoted Solidity string literal, if `ascii` is set then all non ascii
+/// characters are escaped too
+fn escape(s: &str, ascii: bool) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if c.is_ascii_control() || (ascii && !c.is_ascii()) => {
+                let mut buf = [0; 4];
+                for b in c.encode_utf8(&mut buf).bytes() {
+                    escaped.push_str(&format!("\\x{:02x}", b));
+                }
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solang_parser::pt::{ContractPart, SourceUnitPart};
+
+    #[test]
+    fn can_print_ast() {
+        let ast = include_str!("../../../test-data/ast/ast-erc4626.json");
+        let ast: Ast = serde_json::from_str(ast).unwrap();
+        let source = ast.to_solidity().unwrap();
+
+        let (unit, _) = solang_parser::parse(&source, 0).unwrap();
+        let contracts = unit
+            .0
+            .into_iter()
+            .filter_map(|part| match part {
+                SourceUnitPart::ContractDefinition(def) => Some(def),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(contracts.len(), 1);
+        assert_eq!(contracts[0].name.name, "ERC4626");
+
+        let functions = contracts[0]
+            .parts
+            .iter()
+            .filter(|part| matches!(part, ContractPart::FunctionDefinition(_)))
+            .count();
+        let contract =
+            ast.nodes.iter().find(|n| n.node_type == NodeType::ContractDefinition).unwrap();
+        assert_eq!(
+            functions,
+            contract.nodes.iter().filter(|n| n.node_type == NodeType::FunctionDefinition).count()
+        );
+
+        // printing is deterministic
+        assert_eq!(ast.to_solidity().unwrap(), source);
+    }
+
+    /// Removes the ids and source locations of the nodes, which differ between the original and
+    /// the printed source
+    fn strip_ids_and_locations(value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for key in ["id", "src", "nameLocation"] {
+                    map.remove(key);
+                }
+                map.values_mut().for_each(strip_ids_and_locations);
+            }
+            Value::Array(values) => values.iter_mut().for_each(strip_ids_and_locations),
+            _ => {}
+        }
+    }
+
+    fn compile_ast(source: &str) -> Ast {
+        let output = crate::Solc::default()
+            .compile_source_str("Vault.sol", source, Default::default())
+            .unwrap();
+        assert!(!output.has_error(), "{:?}", output.errors);
+        output.sources["Vault.sol"].ast.clone().unwrap()
+    }
+
+    #[test]
+    fn printed_source_compiles_to_same_ast() {
+        let source = r#"
+pragma solidity >=0.8.0;
+
+contract Vault {
+    struct Deposit {
+        address owner;
+        uint256 amount;
+    }
+
+    enum State { Open, Closed }
+
+    event Deposited(address indexed owner, uint256 amount);
+
+    error Unauthorized(address caller);
+
+    State public state;
+    mapping(address => uint256) public balances;
+    Deposit[] internal deposits;
+    address private immutable admin;
+
+    modifier onlyAdmin() {
+        if (msg.sender != admin) {
+            revert Unauthorized(msg.sender);
+        }
+        _;
+    }
+
+    constructor() {
+        admin = msg.sender;
+    }
+
+    function deposit() external payable {
+        require(state == State.Open, "closed");
+        balances[msg.sender] += msg.value;
+        deposits.push(Deposit(msg.sender, msg.value));
+        emit Deposited(msg.sender, msg.value);
+    }
+
+    function total() public view returns (uint256 sum) {
+        for (uint256 i = 0; i < deposits.length; i++) {
+            sum += deposits[i].amount;
+        }
+    }
+
+    function close() external onlyAdmin {
+        state = State.Closed;
+    }
+}
+"#;
+        let ast = compile_ast(source);
+        let printed = ast.to_solidity().unwrap();
+        let recompiled = compile_ast(&printed);
+
+        let mut expected = serde_json::to_value(&ast).unwrap();
+        let mut actual = serde_json::to_value(&recompiled).unwrap();
+        strip_ids_and_locations(&mut expected);
+        strip_ids_and_locations(&mut actual);
+        assert_eq!(actual, expected, "printed source:\n{}", printed);
+    }
+
+    #[test]
+    fn can_print_expressions() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "nodeType": "ExpressionStatement",
+            "src": "0:0:0",
+            "expression": {
+                "id": 2,
+                "nodeType": "Assignment",
+                "operator": "+=",
+                "leftHandSide": { "id": 3, "nodeType": "Identifier", "name": "x" },
+                "rightHandSide": {
+                    "id": 4,
+                    "nodeType": "Literal",
+                    "kind": "number",
+                    "value": "1",
+                    "subdenomination": "ether"
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(node.to_solidity().unwrap(), "x += 1 ether;");
+    }
+
+    #[test]
+    fn fails_on_inline_assembly() {
+        let node: Node = serde_json::from_value(serde_json::json!({
+            "id": 1,
+            "nodeType": "Block",
+            "src": "0:0:0",
+            "statements": [
+                { "id": 2, "nodeType": "InlineAssembly", "src": "0:0:0" }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(
+            node.to_solidity().unwrap_err(),
+            UnprintableNode { node_type: NodeType::InlineAssembly, id: Some(2) }
+        );
+    }
+
+    #[test]
+    fn can_escape_strings() {
+        assert_eq!(quote("a\"b\\c\n"), r#""a\"b\\c\n""#);
+        assert_eq!(quote("ö"), r#""\xc3\xb6""#);
+        assert_eq!(escape("ö", false), "\"ö\"");
+    }
+}
@@ -0,0 +1,262 @@
+//! Inline assembly (Yul) blocks of an AST
+
+use super::{Ast, Node, NodeType, SourceLocation};
+use crate::artifacts::serde_helpers;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+impl Ast {
+    /// Returns all `InlineAssembly` nodes of this AST, in depth-first pre-order.
+    ///
+    /// Most statements aren't first-class [`Node`]s but nested in the attributes of their parent,
+    /// like the `statements` of a `Block`. These are searched as well, which is why the nodes are
+    /// returned by value.
+    ///
+    /// Use [`Node::yul_ast`] to get the Yul code of a block.
+    pub fn yul_blocks(&self) -> Vec<Node> {
+        let mut blocks = Vec::new();
+        for node in self.nodes_recursive() {
+            if node.node_type == NodeType::InlineAssembly {
+                blocks.push(node.clone());
+            } else {
+                node.other.values().for_each(|value| collect_inline_assembly(value, &mut blocks));
+            }
+        }
+        blocks
+    }
+}
+
+impl Node {
+    /// Returns the root `YulBlock` of an `InlineAssembly` node.
+    ///
+    /// Yul nodes don't have an id, so they are represented as [`YulNode`]s instead of [`Node`]s.
+    pub fn yul_ast(&self) -> Option<YulBlock> {
+        serde_json::from_value(self.raw_yul_ast()?.clone()).ok()
+    }
+
+    /// Returns the json of the root `YulBlock` of an `InlineAssembly` node.
+    fn raw_yul_ast(&self) -> Option<&Value> {
+        if self.node_type != NodeType::InlineAssembly {
+            return None
+        }
+        self.other.get("AST")
+    }
+
+    /// Returns all Yul nodes of an `InlineAssembly` node in depth-first pre-order, starting with
+    /// the root `YulBlock`.
+    ///
+    /// Returns an empty list if this is not an `InlineAssembly` node.
+    pub fn yul_nodes(&self) -> Vec<&Value> {
+        let mut nodes = Vec::new();
+        if let Some(root) = self.raw_yul_ast() {
+            collect_yul_nodes(root, &mut nodes);
+        }
+        nodes
+    }
+}
+
+/// A `YulBlock` node, the root of the Yul AST of an `InlineAssembly` node, see [`Node::yul_ast`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YulBlock {
+    /// The node type, this is always `YulBlock`.
+    #[serde(rename = "nodeType")]
+    pub node_type: NodeType,
+
+    /// The location of the node in the source file.
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+
+    /// The statements of the block.
+    #[serde(default)]
+    pub statements: Vec<YulNode>,
+
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, Value>,
+}
+
+/// A node of the Yul AST.
+///
+/// Mirrors [`Node`], except that Yul nodes don't have an id.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct YulNode {
+    /// The node type.
+    #[serde(rename = "nodeType")]
+    pub node_type: NodeType,
+
+    /// The location of the node in the source file.
+    #[serde(with = "serde_helpers::display_from_str")]
+    pub src: SourceLocation,
+
+    /// The statements of a nested `YulBlock`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub statements: Vec<YulNode>,
+
+    /// Body block of `YulForLoop`, `YulFunctionDefinition`, `YulIf` and `YulCase` nodes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<YulBlock>,
+
+    /// Node attributes that were not deserialized.
+    #[serde(flatten)]
+    pub other: BTreeMap<String, Value>,
+}
+
+impl YulNode {
+    /// Deserialize a serialized node attribute.
+    pub fn attribute<D: DeserializeOwned>(&self, key: impl AsRef<str>) -> Option<D> {
+        self.other.get(key.as_ref()).and_then(|v| D::deserialize(v).ok())
+    }
+
+    /// Returns all Yul nodes nested in the attributes of this node, in source order.
+    ///
+    /// These are for example the `expression` of a `YulExpressionStatement`, the `arguments` of
+    /// a `YulFunctionCall` or the `pre` and `post` blocks of a `YulForLoop`. The `statements` and
+    /// the `body` are not included.
+    pub fn children(&self) -> Vec<YulNode> {
+        let mut children = Vec::new();
+        for value in self.other.values() {
+            match value {
+                Value::Array(values) => {
+                    children.extend(values.iter().filter_map(yul_node));
+                }
+                value => children.extend(yul_node(value)),
+            }
+        }
+        children.sort_by_key(|node| node.src.start);
+        children
+    }
+}
+
+/// Deserializes a json Yul node
+fn yul_node(value: &Value) -> Option<YulNode> {
+    node_type(value)?;
+    serde_json::from_value(value.clone()).ok()
+}
+
+/// Returns the `nodeType` of a json node
+fn node_type(value: &Value) -> Option<&str> {
+    value.get("nodeType").and_then(Value::as_str)
+}
+
+fn collect_inline_assembly(value: &Value, blocks: &mut Vec<Node>) {
+    match value {
+        Value::Object(map) => {
+            if node_type(value) == Some("InlineAssembly") {
+                if let Ok(node) = serde_json::from_value(value.clone()) {
+                    blocks.push(node);
+                }
+                return
+            }
+            map.values().for_each(|value| collect_inline_assembly(value, blocks));
+        }
+        Value::Array(values) => {
+            values.iter().for_each(|value| collect_inline_assembly(value, blocks))
+        }
+        _ => {}
+    }
+}
+
+fn collect_yul_nodes<'a>(value: &'a Value, nodes: &mut Vec<&'a Value>) {
+    match value {
+        Value::Object(map) => {
+            if node_type(value).map(|ty| ty.starts_with("Yul")).unwrap_or_default() {
+                nodes.push(value);
+            }
+            map.values().for_each(|value| collect_yul_nodes(value, nodes));
+        }
+        Value::Array(values) => values.iter().for_each(|value| collect_yul_nodes(value, nodes)),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_find_yul_blocks() {
+        let ast: Ast = serde_json::from_str(
+            r#"{
+                "absolutePath": "A.sol",
+                "id": 10,
+                "nodeType": "SourceUnit",
+                "src": "0:100:0",
+                "nodes": [
+                    {
+                        "id": 9,
+                        "nodeType": "ContractDefinition",
+                        "src": "0:100:0",
+                        "name": "A",
+                        "nodes": [
+                            {
+                                "id": 8,
+                                "nodeType": "FunctionDefinition",
+                                "src": "10:80:0",
+                                "name": "foo",
+                                "body": {
+                                    "id": 7,
+                                    "nodeType": "Block",
+                                    "src": "30:60:0",
+                                    "statements": [
+                                        {
+                                            "id": 6,
+                                            "nodeType": "InlineAssembly",
+                                            "src": "40:40:0",
+                                            "evmVersion": "london",
+                                            "externalReferences": [],
+                                            "AST": {
+                                                "nodeType": "YulBlock",
+                                                "src": "49:30:0",
+                                                "statements": [
+                                                    {
+                                                        "nodeType": "YulExpressionStatement",
+                                                        "src": "59:10:0",
+                                                        "expression": {
+                                                            "nodeType": "YulFunctionCall",
+                                                            "src": "59:10:0",
+                                                            "arguments": [],
+                                                            "functionName": {
+                                                                "name": "stop",
+                                                                "nodeType": "YulIdentifier",
+                                                                "src": "59:4:0"
+                                                            }
+                                                        }
+                                                    }
+                                                ]
+                                            }
+                                        }
+                                    ]
+                                }
+                            }
+                        ]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+
+        let blocks = ast.yul_blocks();
+        assert_eq!(blocks.len(), 1);
+        let block = &blocks[0];
+        assert_eq!(block.id, 6);
+        let yul = block.yul_ast().unwrap();
+        assert_eq!(yul.node_type, NodeType::YulBlock);
+        assert_eq!(yul.statements.len(), 1);
+        let statement = &yul.statements[0];
+        assert_eq!(statement.node_type, NodeType::YulExpressionStatement);
+        let children = statement.children();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].node_type, NodeType::YulFunctionCall);
+        let function = children[0].attribute::<YulNode>("functionName").unwrap();
+        assert_eq!(function.attribute::<String>("name").unwrap(), "stop");
+        assert!(ast.nodes[0].yul_ast().is_none());
+
+        let yul = block.yul_nodes().into_iter().filter_map(node_type).collect::<Vec<_>>();
+        assert_eq!(
+            yul,
+            vec!["YulBlock", "YulExpressionStatement", "YulFunctionCall", "YulIdentifier"]
+        );
+        assert!(ast.nodes[0].yul_nodes().is_empty());
+    }
+}
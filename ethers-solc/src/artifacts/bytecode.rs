@@ -5,7 +5,11 @@ use crate::{
     sourcemap::{self, SourceMap, SyntaxError},
     utils,
 };
-use ethers_core::{abi::Address, types::Bytes};
+use ethers_core::{
+    abi::Address,
+    types::{Bytes, H256},
+    utils::keccak256,
+};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 
@@ -410,3 +414,268 @@ impl From<CompactDeployedBytecode> for DeployedBytecode {
         }
     }
 }
+
+/// Removes the CBOR-encoded metadata that solc appends to the bytecode.
+///
+/// The metadata is a CBOR map followed by its length as two big-endian bytes, like
+/// `0xa264697066735822<hash>64736f6c6343<version>0033` for solc >= 0.6 or
+/// `0xa165627a7a72305820<hash>0029` for older versions.
+///
+/// Returns the bytecode unchanged if it doesn't end with a valid metadata map.
+///
+/// # Example
+///
+/// ```
+/// use ethers_solc::artifacts::strip_metadata;
+/// let code: ethers_core::types::Bytes = "0x6080a165627a7a7230582000000000000000000000000000000000000000000000000000000000000000000029".parse().unwrap();
+/// assert_eq!(strip_metadata(&code).as_ref(), &[0x60, 0x80]);
+/// ```
+pub fn strip_metadata(bytecode: &Bytes) -> Bytes {
+    let code = bytecode.as_ref();
+    match metadata_len(code) {
+        Some(len) => code[..code.len() - len].to_vec().into(),
+        None => bytecode.clone(),
+    }
+}
+
+/// Returns the keccak256 hash of the runtime bytecode without its metadata, see
+/// [`strip_metadata`].
+///
+/// Contracts that were compiled from the same source but with different metadata, like a
+/// different source path or comment, have the same hash.
+pub fn runtime_code_hash_ignoring_metadata(bytecode: &Bytes) -> H256 {
+    keccak256(strip_metadata(bytecode)).into()
+}
+
+/// Returns the hash of the contract metadata that solc appends to the bytecode, the value of the
+/// `ipfs`, `bzzr1` or `bzzr0` entry of the CBOR metadata map, see [`strip_metadata`].
+///
+/// Contracts compiled from the same sources with the same settings have the same metadata hash.
+pub fn metadata_hash(bytecode: &Bytes) -> Option<Bytes> {
+    let code = bytecode.as_ref();
+    let len = metadata_len(code)?;
+    let cbor = &code[code.len() - len..code.len() - 2];
+    let mut pos = 1;
+    while pos < cbor.len() {
+        let (key, value_pos) = (cbor_string(cbor, pos)?, skip_cbor_item(cbor, pos)?);
+        if matches!(key, b"ipfs" | b"bzzr1" | b"bzzr0") {
+            return cbor_string(cbor, value_pos).map(|hash| hash.to_vec().into())
+        }
+        pos = skip_cbor_item(cbor, value_pos)?;
+    }
+    None
+}
+
+/// How the deployed runtime bytecode of a contract compares to the runtime bytecode its sources
+/// compile to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BytecodeMatch {
+    /// The bytecode is the same, including the metadata hash, so the contract was compiled from
+    /// exactly the same sources and settings
+    Full,
+    /// The bytecode is the same except for the metadata, e.g. because a comment or the path of a
+    /// source file differs
+    PartialMetadataDiffers,
+    /// The bytecode differs
+    Mismatch,
+}
+
+impl BytecodeMatch {
+    /// Compares the deployed runtime bytecode to the compiled runtime bytecode.
+    ///
+    /// The values of immutable variables, which are only written into the runtime bytecode on
+    /// deployment, are ignored.
+    pub fn compare(deployed: &Bytes, compiled: &CompactDeployedBytecode) -> Self {
+        let compiled_code = match compiled.bytecode.as_ref().and_then(|b| b.object.as_bytes()) {
+            Some(code) => code,
+            None => return BytecodeMatch::Mismatch,
+        };
+
+        // the metadata can have a different length, e.g. if the compiler versions differ in their
+        // number of digits, so the immutables are also replaced if the lengths differ
+        let mut deployed = deployed.to_vec();
+        for offsets in compiled.immutable_references.values().flatten() {
+            let (start, end) = (offsets.start as usize, (offsets.start + offsets.length) as usize);
+            if end <= deployed.len() && end <= compiled_code.as_ref().len() {
+                deployed[start..end].copy_from_slice(&compiled_code.as_ref()[start..end]);
+            }
+        }
+        let deployed = Bytes::from(deployed);
+
+        if &deployed == compiled_code {
+            BytecodeMatch::Full
+        } else if strip_metadata(&deployed) == strip_metadata(compiled_code) {
+            BytecodeMatch::PartialMetadataDiffers
+        } else {
+            BytecodeMatch::Mismatch
+        }
+    }
+
+    /// Returns `true` if the bytecode matches, apart from the metadata
+    pub fn is_match(&self) -> bool {
+        !matches!(self, BytecodeMatch::Mismatch)
+    }
+}
+
+/// Returns the length of the metadata at the end of the bytecode, including the two length
+/// bytes, if the bytecode ends with a valid CBOR map
+fn metadata_len(code: &[u8]) -> Option<usize> {
+    if code.len() < 2 {
+        return None
+    }
+    let len = u16::from_be_bytes([code[code.len() - 2], code[code.len() - 1]]) as usize;
+    let start = code.len().checked_sub(len + 2)?;
+    let cbor = &code[start..code.len() - 2];
+
+    let entries = match cbor.first()? {
+        head @ 0xa1..=0xb7 => (head - 0xa0) as usize,
+        _ => return None,
+    };
+    let mut pos = 1;
+    for _ in 0..entries {
+        // keys are text strings
+        if cbor.get(pos)? >> 5 != 3 {
+            return None
+        }
+        pos = skip_cbor_item(cbor, pos)?;
+        pos = skip_cbor_item(cbor, pos)?;
+    }
+    (pos == cbor.len()).then(|| len + 2)
+}
+
+/// Returns the contents of the CBOR byte or text string at `pos`
+fn cbor_string(cbor: &[u8], pos: usize) -> Option<&[u8]> {
+    let end = skip_cbor_item(cbor, pos)?;
+    let len = match *cbor.get(pos)? & 0x1f {
+        0..=23 => 1,
+        24 => 2,
+        _ => 3,
+    };
+    matches!(cbor[pos] >> 5, 2 | 3).then(|| &cbor[pos + len..end])
+}
+
+/// Returns the position after the CBOR item at `pos`.
+///
+/// Only supports the item types solc uses in the metadata: integers, byte and text strings and
+/// booleans.
+fn skip_cbor_item(cbor: &[u8], pos: usize) -> Option<usize> {
+    let head = *cbor.get(pos)?;
+    let (major, info) = (head >> 5, head & 0x1f);
+    let (value, pos) = match info {
+        0..=23 => (info as usize, pos + 1),
+        24 => (*cbor.get(pos + 1)? as usize, pos + 2),
+        25 => (u16::from_be_bytes([*cbor.get(pos + 1)?, *cbor.get(pos + 2)?]) as usize, pos + 3),
+        _ => return None,
+    };
+    match major {
+        // unsigned integer
+        0 => Some(pos),
+        // byte and text strings
+        2 | 3 => {
+            let end = pos.checked_add(value)?;
+            (end <= cbor.len()).then(|| end)
+        }
+        // `false` and `true`
+        7 if info == 20 || info == 21 => Some(pos),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bytes(s: &str) -> Bytes {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn can_strip_metadata() {
+        let code = "0x6080604052";
+        let ipfs = "a2646970667358221220a5c2dbba5d7d2b1fc1dd1a4ae8c5b47cbb3d4d8d0b4c1fcf2f64e9c5d832e0d064736f6c634300080a0033";
+        let bzzr0 = "a165627a7a72305820a5c2dbba5d7d2b1fc1dd1a4ae8c5b47cbb3d4d8d0b4c1fcf2f64e9c5d832e0d00029";
+        let bzzr1 = "a265627a7a72315820a5c2dbba5d7d2b1fc1dd1a4ae8c5b47cbb3d4d8d0b4c1fcf2f64e9c5d832e0d064736f6c634300050f0032";
+        let experimental = "a364697066735822122011111111111111111111111111111111111111111111111111111111111111116c6578706572696d656e74616cf564736f6c63430008000041";
+
+        for metadata in [ipfs, bzzr0, bzzr1, experimental] {
+            let stripped = strip_metadata(&bytes(&format!("{}{}", code, metadata)));
+            assert_eq!(stripped, bytes(code), "{}", metadata);
+        }
+
+        let a = bytes(&format!("{}{}", code, ipfs));
+        let b = bytes(&format!("{}{}", code, ipfs.replace("a5c2", "1234")));
+        assert_ne!(a, b);
+        assert_eq!(
+            runtime_code_hash_ignoring_metadata(&a),
+            runtime_code_hash_ignoring_metadata(&b)
+        );
+    }
+
+    #[test]
+    fn keeps_bytecode_without_metadata() {
+        for code in ["0x", "0x00", "0x6080604052", "0x60806040520003", "0xa1600001"] {
+            assert_eq!(strip_metadata(&bytes(code)), bytes(code));
+        }
+    }
+
+    #[test]
+    fn can_get_metadata_hash() {
+        let ipfs = "0x6080a2646970667358221220a5c2dbba5d7d2b1fc1dd1a4ae8c5b47cbb3d4d8d0b4c1fcf2f64e9c5d832e0d064736f6c634300080a0033";
+        let hash = metadata_hash(&bytes(ipfs)).unwrap();
+        assert_eq!(
+            hash,
+            bytes("0x1220a5c2dbba5d7d2b1fc1dd1a4ae8c5b47cbb3d4d8d0b4c1fcf2f64e9c5d832e0d0")
+        );
+
+        let bzzr0 = "0x6080a165627a7a72305820a5c2dbba5d7d2b1fc1dd1a4ae8c5b47cbb3d4d8d0b4c1fcf2f64e9c5d832e0d00029";
+        let hash = metadata_hash(&bytes(bzzr0)).unwrap();
+        assert_eq!(
+            hash,
+            bytes("0xa5c2dbba5d7d2b1fc1dd1a4ae8c5b47cbb3d4d8d0b4c1fcf2f64e9c5d832e0d0")
+        );
+
+        assert_eq!(metadata_hash(&bytes("0x6080604052")), None);
+    }
+
+    #[test]
+    fn can_compare_bytecode() {
+        let metadata = "a2646970667358221220a5c2dbba5d7d2b1fc1dd1a4ae8c5b47cbb3d4d8d0b4c1fcf2f64e9c5d832e0d064736f6c634300080a0033";
+        // `PUSH32 <immutable>` followed by the metadata
+        let code = format!("0x7f{}{}", "00".repeat(32), metadata);
+        let compiled = CompactDeployedBytecode {
+            bytecode: Some(CompactBytecode {
+                object: BytecodeObject::Bytecode(bytes(&code)),
+                source_map: None,
+                link_references: Default::default(),
+            }),
+            immutable_references: BTreeMap::from([(
+                "3".to_string(),
+                vec![Offsets { start: 1, length: 32 }],
+            )]),
+        };
+
+        let deployed = bytes(&format!("0x7f{}{}", "11".repeat(32), metadata));
+        assert_eq!(BytecodeMatch::compare(&deployed, &compiled), BytecodeMatch::Full);
+
+        let deployed =
+            bytes(&format!("0x7f{}{}", "11".repeat(32), metadata.replace("a5c2", "1234")));
+        assert_eq!(
+            BytecodeMatch::compare(&deployed, &compiled),
+            BytecodeMatch::PartialMetadataDiffers
+        );
+
+        // metadata of a different length
+        let bzzr0 = format!("a165627a7a72305820{}0029", "22".repeat(32));
+        let deployed = bytes(&format!("0x7f{}{}", "11".repeat(32), bzzr0));
+        assert_eq!(
+            BytecodeMatch::compare(&deployed, &compiled),
+            BytecodeMatch::PartialMetadataDiffers
+        );
+
+        let deployed = bytes(&format!("0x7e{}{}", "11".repeat(32), metadata));
+        assert_eq!(BytecodeMatch::compare(&deployed, &compiled), BytecodeMatch::Mismatch);
+        let deployed = bytes(&format!("0x7e{}{}", "11".repeat(32), bzzr0));
+        assert_eq!(BytecodeMatch::compare(&deployed, &compiled), BytecodeMatch::Mismatch);
+        assert!(!BytecodeMatch::Mismatch.is_match());
+    }
+}
@@ -15,7 +15,7 @@ use crate::{
     compile::*, error::SolcIoError, remappings::Remapping, utils, ProjectPathsConfig, SolcError,
 };
 
-use serde::{de::Visitor, Deserialize, Deserializer, Serialize, Serializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use tracing::warn;
 
 pub mod ast;
@@ -104,6 +104,8 @@ impl CompilerInput {
     pub fn sanitized(mut self, version: &Version) -> Self {
         static PRE_V0_6_0: once_cell::sync::Lazy<VersionReq> =
             once_cell::sync::Lazy::new(|| VersionReq::parse("<0.6.0").unwrap());
+        static PRE_V0_7_5: once_cell::sync::Lazy<VersionReq> =
+            once_cell::sync::Lazy::new(|| VersionReq::parse("<0.7.5").unwrap());
         static PRE_V0_8_10: once_cell::sync::Lazy<VersionReq> =
             once_cell::sync::Lazy::new(|| VersionReq::parse("<0.8.10").unwrap());
 
@@ -117,6 +119,13 @@ impl CompilerInput {
             let _ = self.settings.debug.take();
         }
 
+        if PRE_V0_7_5.matches(version) {
+            // introduced in <https://docs.soliditylang.org/en/v0.7.5/using-the-compiler.html#compiler-api>
+            if self.settings.via_ir.take().is_some() {
+                warn!("`viaIR` is not supported by solc {}, ignoring it", version);
+            }
+        }
+
         if PRE_V0_8_10.matches(version) {
             if let Some(ref mut debug) = self.settings.debug {
                 // introduced in <https://docs.soliditylang.org/en/v0.8.10/using-the-compiler.html#compiler-api>
@@ -351,7 +360,9 @@ impl Settings {
             .insert(key.into(), values.into_iter().map(|s| s.to_string()).collect());
     }
 
-    /// Sets the ``viaIR` valu
+    /// Sets the `viaIR` value
+    ///
+    /// This is ignored for solc versions that don't support it, see [`CompilerInput::sanitized`]
     #[must_use]
     pub fn set_via_ir(mut self, via_ir: bool) -> Self {
         self.via_ir = Some(via_ir);
@@ -1352,6 +1363,61 @@ impl StorageLayout {
     fn is_empty(&self) -> bool {
         self.storage.is_empty() && self.types.is_empty()
     }
+
+    /// Compares the storage variables of an old and a new version of a contract, e.g. the
+    /// implementations of an upgradeable proxy.
+    ///
+    /// Variables are identified by their label and the contract that declares them. Returns the
+    /// variables that were removed, moved to another slot or offset, or whose type occupies a
+    /// different number of bytes. New variables that don't overlap existing ones are not
+    /// reported.
+    pub fn diff(old: &StorageLayout, new: &StorageLayout) -> Vec<StorageLayoutChange> {
+        let mut changes = Vec::new();
+        for old_item in &old.storage {
+            let new_item = match new
+                .storage
+                .iter()
+                .find(|item| item.label == old_item.label && item.contract == old_item.contract)
+            {
+                Some(item) => item,
+                None => {
+                    changes.push(StorageLayoutChange::Removed { item: old_item.clone() });
+                    continue
+                }
+            };
+            if old_item.slot != new_item.slot || old_item.offset != new_item.offset {
+                changes.push(StorageLayoutChange::Moved {
+                    old: old_item.clone(),
+                    new: new_item.clone(),
+                });
+            }
+            let old_bytes = old.types.get(&old_item.storage_type).map(|ty| &ty.number_of_bytes);
+            let new_bytes = new.types.get(&new_item.storage_type).map(|ty| &ty.number_of_bytes);
+            if let (Some(old_bytes), Some(new_bytes)) = (old_bytes, new_bytes) {
+                if old_bytes != new_bytes {
+                    changes.push(StorageLayoutChange::Resized {
+                        old: old_item.clone(),
+                        new: new_item.clone(),
+                        old_bytes: old_bytes.clone(),
+                        new_bytes: new_bytes.clone(),
+                    });
+                }
+            }
+        }
+        changes
+    }
+}
+
+/// A change of a storage variable between two versions of a contract, see
+/// [`StorageLayout::diff`]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageLayoutChange {
+    /// The variable no longer exists, e.g. because it was renamed
+    Removed { item: Storage },
+    /// The variable is stored at a different slot or offset
+    Moved { old: Storage, new: Storage },
+    /// The type of the variable occupies a different number of bytes
+    Resized { old: Storage, new: Storage, old_bytes: String, new_bytes: String },
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq)]
@@ -1372,6 +1438,18 @@ pub struct StorageType {
     pub label: String,
     #[serde(rename = "numberOfBytes")]
     pub number_of_bytes: String,
+    /// The type of the elements of arrays
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    /// The type of the keys of mappings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key: Option<String>,
+    /// The type of the values of mappings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    /// The members of structs, their slots are relative to the slot of the struct
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub members: Option<Vec<Storage>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
@@ -1478,7 +1556,7 @@ impl<'de> Deserialize<'de> for Severity {
     {
         struct SeverityVisitor;
 
-        impl<'de> Visitor<'de> for SeverityVisitor {
+        impl<'de> serde::de::Visitor<'de> for SeverityVisitor {
             type Value = Severity;
 
             fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -1737,6 +1815,46 @@ mod tests {
         assert!(i.settings.metadata.unwrap().bytecode_hash.is_none());
     }
 
+    #[test]
+    fn can_sanitize_via_ir() {
+        let settings = Settings {
+            optimizer: Optimizer {
+                enabled: Some(true),
+                details: Some(OptimizerDetails {
+                    yul: Some(true),
+                    yul_details: Some(YulDetails {
+                        stack_allocation: Some(true),
+                        optimizer_steps: None,
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+        .with_via_ir();
+
+        let input = CompilerInput {
+            language: "Solidity".to_string(),
+            sources: Default::default(),
+            settings,
+        };
+
+        let json = serde_json::to_value(&input.settings).unwrap();
+        assert_eq!(json["viaIR"], true);
+        assert_eq!(
+            json["optimizer"]["details"],
+            serde_json::json!({ "yul": true, "yulDetails": { "stackAllocation": true } })
+        );
+
+        let i = input.clone().sanitized(&"0.8.13".parse().unwrap());
+        assert_eq!(i.settings.via_ir, Some(true));
+
+        let i = input.sanitized(&"0.7.4".parse().unwrap());
+        assert!(i.settings.via_ir.is_none());
+        assert!(i.settings.optimizer.details.is_some());
+    }
+
     #[test]
     fn can_parse_libraries() {
         let libraries = ["./src/lib/LibraryContract.sol:Library:0xaddress".to_string()];
@@ -1803,4 +1921,80 @@ mod tests {
             ])
         );
     }
+
+    #[test]
+    fn can_diff_storage_layouts() {
+        let layout = |storage: serde_json::Value| -> StorageLayout {
+            serde_json::from_value(serde_json::json!({
+                "storage": storage,
+                "types": {
+                    "t_address": { "encoding": "inplace", "label": "address", "numberOfBytes": "20" },
+                    "t_uint128": { "encoding": "inplace", "label": "uint128", "numberOfBytes": "16" },
+                    "t_uint256": { "encoding": "inplace", "label": "uint256", "numberOfBytes": "32" },
+                    "t_mapping(t_address,t_uint256)": {
+                        "encoding": "mapping",
+                        "key": "t_address",
+                        "label": "mapping(address => uint256)",
+                        "numberOfBytes": "32",
+                        "value": "t_uint256"
+                    }
+                }
+            }))
+            .unwrap()
+        };
+        let item = |label: &str, slot: &str, offset: i64, ty: &str| {
+            serde_json::json!({
+                "astId": 1,
+                "contract": "src/Token.sol:Token",
+                "label": label,
+                "offset": offset,
+                "slot": slot,
+                "type": ty
+            })
+        };
+
+        let old = layout(serde_json::json!([
+            item("owner", "0", 0, "t_address"),
+            item("supply", "1", 0, "t_uint256"),
+            item("balances", "2", 0, "t_mapping(t_address,t_uint256)"),
+        ]));
+        let mapping = &old.types["t_mapping(t_address,t_uint256)"];
+        assert_eq!(mapping.key.as_deref(), Some("t_address"));
+        assert_eq!(mapping.value.as_deref(), Some("t_uint256"));
+        assert!(StorageLayout::diff(&old, &old).is_empty());
+
+        // appending a variable is safe
+        let mut new = old.clone();
+        new.storage.push(old.storage[2].clone());
+        new.storage[3].label = "allowances".to_string();
+        new.storage[3].slot = "3".to_string();
+        assert!(StorageLayout::diff(&old, &new).is_empty());
+
+        let new = layout(serde_json::json!([
+            item("paused", "0", 0, "t_uint128"),
+            item("owner", "1", 0, "t_address"),
+            item("supply", "2", 0, "t_uint128"),
+        ]));
+        let changes = StorageLayout::diff(&old, &new);
+        assert_eq!(
+            changes,
+            vec![
+                StorageLayoutChange::Moved {
+                    old: old.storage[0].clone(),
+                    new: new.storage[1].clone()
+                },
+                StorageLayoutChange::Moved {
+                    old: old.storage[1].clone(),
+                    new: new.storage[2].clone()
+                },
+                StorageLayoutChange::Resized {
+                    old: old.storage[1].clone(),
+                    new: new.storage[2].clone(),
+                    old_bytes: "32".to_string(),
+                    new_bytes: "16".to_string(),
+                },
+                StorageLayoutChange::Removed { item: old.storage[2].clone() },
+            ]
+        );
+    }
 }
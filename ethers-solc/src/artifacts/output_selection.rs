@@ -1,7 +1,7 @@
 //! bindings for standard json output selection
 
 use serde::{ser::SerializeMap, Deserialize, Deserializer, Serialize, Serializer};
-use std::{collections::BTreeMap, fmt, str::FromStr};
+use std::{collections::BTreeMap, fmt, path::Path, str::FromStr};
 
 /// Represents the desired outputs based on a File `(file -> (contract -> [outputs]))`
 pub type FileOutputSelection = BTreeMap<String, Vec<String>>;
@@ -119,6 +119,96 @@ impl OutputSelection {
     pub fn empty_file_output_select() -> FileOutputSelection {
         Default::default()
     }
+
+    /// Selects the `outputs` for all contracts of the files that match `file`, in addition to the
+    /// outputs selected for all files (`"*"`).
+    ///
+    /// `file` is either the path of a source file or a glob pattern like `src/**/*.sol`, relative
+    /// paths and patterns match the end of the source file paths, see
+    /// [`OutputSelection::resolve_file_patterns`]. This replaces the outputs previously set for
+    /// `file`.
+    ///
+    /// ```
+    /// use ethers_solc::artifacts::{output_selection::ContractOutputSelection, Settings};
+    /// let mut settings = Settings::default();
+    /// settings.output_selection.set("src/Core.sol", [ContractOutputSelection::StorageLayout]);
+    /// ```
+    pub fn set(
+        &mut self,
+        file: impl Into<String>,
+        outputs: impl IntoIterator<Item = impl ToString>,
+    ) {
+        self.set_contract(file, "*", outputs)
+    }
+
+    /// Selects the `outputs` for the `contract` of the files that match `file`, see
+    /// [`OutputSelection::set`]
+    pub fn set_contract(
+        &mut self,
+        file: impl Into<String>,
+        contract: impl Into<String>,
+        outputs: impl IntoIterator<Item = impl ToString>,
+    ) {
+        self.0
+            .entry(file.into())
+            .or_default()
+            .insert(contract.into(), outputs.into_iter().map(|s| s.to_string()).collect());
+    }
+
+    /// Replaces the file keys that are not the path of one of the `files` with the matching paths.
+    ///
+    /// solc only accepts the exact source unit name or `"*"` as file key, so relative paths and
+    /// glob patterns, e.g. `src/Core.sol` or `src/**/*.sol`, are resolved to all `files` whose
+    /// path matches the key, or ends with a match of the key. The outputs of several keys that
+    /// match the same file are merged. Keys that don't match any file are kept as they are.
+    pub fn resolve_file_patterns<'a>(&mut self, files: impl IntoIterator<Item = &'a Path>) {
+        let files = files.into_iter().map(|f| f.display().to_string()).collect::<Vec<_>>();
+        let patterns = self
+            .0
+            .keys()
+            .filter(|key| key.as_str() != "*" && !files.contains(key))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for key in patterns {
+            let pattern = match glob::Pattern::new(&key) {
+                Ok(pattern) => pattern,
+                Err(_) => continue,
+            };
+            let suffix_pattern =
+                glob::Pattern::new(&format!("**/{}", key.trim_start_matches("./"))).ok();
+            let matches = files
+                .iter()
+                .filter(|file| {
+                    pattern.matches(file) ||
+                        suffix_pattern.as_ref().map(|p| p.matches(file)).unwrap_or_default()
+                })
+                .cloned()
+                .collect::<Vec<_>>();
+            if matches.is_empty() {
+                continue
+            }
+            let selection = self.0.remove(&key).unwrap_or_default();
+            for file in matches {
+                Self::merge_file_output_selection(self.0.entry(file).or_default(), &selection);
+            }
+        }
+    }
+
+    /// Adds the outputs of `other` to the outputs of `selection`
+    pub fn merge_file_output_selection(
+        selection: &mut FileOutputSelection,
+        other: &FileOutputSelection,
+    ) {
+        for (contract, outputs) in other {
+            let values = selection.entry(contract.clone()).or_default();
+            for output in outputs {
+                if !values.contains(output) {
+                    values.push(output.clone());
+                }
+            }
+        }
+    }
 }
 
 // this will make sure that if the `FileOutputSelection` for a certain file is empty will be
@@ -573,4 +663,31 @@ mod tests {
         let s = serde_json::to_string(&empty).unwrap();
         assert_eq!(s, r#"{"contract.sol":{"*":[]}}"#);
     }
+
+    #[test]
+    fn can_set_file_output_selection() {
+        let mut selection = OutputSelection::default_output_selection();
+        selection.set("src/Core.sol", [ContractOutputSelection::StorageLayout]);
+        selection.set_contract("src/**/*.sol", "Token", ["irOptimized"]);
+        selection.set("lib/Missing.sol", ["devdoc"]);
+
+        selection.resolve_file_patterns([
+            Path::new("/project/src/Core.sol"),
+            Path::new("/project/src/token/Token.sol"),
+            Path::new("/project/test/Core.t.sol"),
+        ]);
+
+        let json = serde_json::to_value(&selection).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "*": {
+                    "*": ["abi", "evm.bytecode", "evm.deployedBytecode", "evm.methodIdentifiers"]
+                },
+                "/project/src/Core.sol": { "*": ["storageLayout"], "Token": ["irOptimized"] },
+                "/project/src/token/Token.sol": { "Token": ["irOptimized"] },
+                "lib/Missing.sol": { "*": ["devdoc"] }
+            })
+        );
+    }
 }
@@ -30,6 +30,25 @@ const ETHERS_FORMAT_VERSION: &str = "ethers-rs-sol-cache-3";
 /// The file name of the default cache file
 pub const SOLIDITY_FILES_CACHE_FILENAME: &str = "solidity-files-cache.json";
 
+/// How the cache decides whether a source file changed since it was last compiled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheStrategy {
+    /// A file changed if the hash of its content changed.
+    ///
+    /// The modification date is used as a fast-path: files with an unchanged modification date
+    /// are not hashed again. This way a `git checkout` or a fresh clone, which update the
+    /// modification date but not the content, doesn't cause a recompilation.
+    ContentHash,
+    /// A file changed if its last modification date changed, regardless of its content
+    ModificationTime,
+}
+
+impl Default for CacheStrategy {
+    fn default() -> Self {
+        CacheStrategy::ContentHash
+    }
+}
+
 /// A multi version cache file
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct SolFilesCache {
@@ -536,6 +555,11 @@ impl CacheEntry {
     }
 }
 
+/// Returns the lowercase file name of the path, see [`ArtifactOutput::output_to_artifacts()`]
+fn lowercase_file_name(path: &Path) -> Option<String> {
+    path.file_name().map(|name| name.to_string_lossy().to_lowercase())
+}
+
 /// A helper abstraction over the [`SolFilesCache`] used to determine what files need to compiled
 /// and which `Artifacts` can be reused.
 #[derive(Debug)]
@@ -652,6 +676,23 @@ impl<'a, T: ArtifactOutput> ArtifactsCacheInner<'a, T> {
                 },
             );
 
+        // contracts of source files with the same file name can share an output file, in which
+        // case the conflict is resolved by `ArtifactOutput::output_to_artifacts`, so these files
+        // are recompiled together to keep their artifact paths independent of what changed
+        let dirty_file_names = filtered_sources
+            .keys()
+            .filter_map(|file| lowercase_file_name(file))
+            .collect::<HashSet<_>>();
+        let (conflicting_sources, clean_sources): (Vec<_>, Vec<_>) =
+            clean_sources.into_iter().partition(|source| {
+                lowercase_file_name(&source.file)
+                    .map_or(false, |name| dirty_file_names.contains(&name))
+            });
+        for source in conflicting_sources {
+            imports_of_dirty.extend(self.edges.all_imported_nodes(source.idx));
+            filtered_sources.insert(source.file, FilteredSource::Dirty(source.source));
+        }
+
         // track new cache entries for dirty files
         for (file, filtered) in filtered_sources.iter() {
             self.insert_new_cache_entry(file, filtered.source(), version.clone());
@@ -686,57 +727,94 @@ impl<'a, T: ArtifactOutput> ArtifactsCacheInner<'a, T> {
         }
     }
 
-    /// returns `false` if the corresponding cache entry remained unchanged otherwise `true`
-    fn is_dirty(&self, file: &Path, version: &Version) -> bool {
-        if let Some(hash) = self.content_hashes.get(file) {
-            if let Some(entry) = self.cache.entry(&file) {
-                if entry.content_hash.as_bytes() != hash.as_bytes() {
-                    tracing::trace!("changed content hash for source file \"{}\"", file.display());
-                    return true
-                }
-                if self.project.solc_config != entry.solc_config {
-                    tracing::trace!("changed solc config for source file \"{}\"", file.display());
-                    return true
-                }
-
-                // only check artifact's existence if the file generated artifacts.
-                // e.g. a solidity file consisting only of import statements (like interfaces that
-                // re-export) do not create artifacts
-                if !entry.artifacts.is_empty() {
-                    if !entry.contains_version(version) {
+    /// returns `true` if the file changed since the cache entry was created, according to the
+    /// project's [`CacheStrategy`]
+    fn is_modified(&self, file: &Path, entry: &CacheEntry) -> bool {
+        match self.project.cache_strategy {
+            CacheStrategy::ContentHash => {
+                if let Some(hash) = self.content_hashes.get(file) {
+                    if entry.content_hash.as_bytes() != hash.as_bytes() {
                         tracing::trace!(
-                            "missing linked artifacts for source file `{}` for version \"{}\"",
-                            file.display(),
-                            version
+                            "changed content hash for source file \"{}\"",
+                            file.display()
                         );
                         return true
                     }
+                    return false
+                }
+                tracing::trace!("Missing content hash for {}", file.display());
+                true
+            }
+            CacheStrategy::ModificationTime => {
+                let last_modification_date = CacheEntry::read_last_modification_date(file).ok();
+                if last_modification_date != Some(entry.last_modification_date) {
+                    tracing::trace!(
+                        "changed modification date for source file \"{}\"",
+                        file.display()
+                    );
+                    return true
+                }
+                false
+            }
+        }
+    }
 
-                    if entry.artifacts_for_version(version).any(|artifact_path| {
-                        let missing_artifact = !self.cached_artifacts.has_artifact(artifact_path);
-                        if missing_artifact {
-                            tracing::trace!("missing artifact \"{}\"", artifact_path.display());
-                        }
-                        missing_artifact
-                    }) {
-                        return true
+    /// returns `false` if the corresponding cache entry remained unchanged otherwise `true`
+    fn is_dirty(&self, file: &Path, version: &Version) -> bool {
+        if let Some(entry) = self.cache.entry(&file) {
+            if self.is_modified(file, entry) {
+                return true
+            }
+            if self.project.solc_config != entry.solc_config {
+                tracing::trace!("changed solc config for source file \"{}\"", file.display());
+                return true
+            }
+
+            // only check artifact's existence if the file generated artifacts.
+            // e.g. a solidity file consisting only of import statements (like interfaces that
+            // re-export) do not create artifacts
+            if !entry.artifacts.is_empty() {
+                if !entry.contains_version(version) {
+                    tracing::trace!(
+                        "missing linked artifacts for source file `{}` for version \"{}\"",
+                        file.display(),
+                        version
+                    );
+                    return true
+                }
+
+                if entry.artifacts_for_version(version).any(|artifact_path| {
+                    let missing_artifact = !self.cached_artifacts.has_artifact(artifact_path);
+                    if missing_artifact {
+                        tracing::trace!("missing artifact \"{}\"", artifact_path.display());
                     }
+                    missing_artifact
+                }) {
+                    return true
                 }
-                // all things match, can be reused
-                return false
             }
-            tracing::trace!("Missing cache entry for {}", file.display());
-        } else {
-            tracing::trace!("Missing content hash for {}", file.display());
+            // all things match, can be reused
+            return false
         }
+        tracing::trace!("Missing cache entry for {}", file.display());
         true
     }
 
     /// Adds the file's hashes to the set if not set yet
+    ///
+    /// The hash of the cache entry is reused if the file's modification date didn't change.
     fn fill_hashes(&mut self, sources: &Sources) {
         for (file, source) in sources {
             if let hash_map::Entry::Vacant(entry) = self.content_hashes.entry(file.clone()) {
-                entry.insert(source.content_hash());
+                let cached_hash = self
+                    .cache
+                    .entry(file)
+                    .filter(|cached| {
+                        CacheEntry::read_last_modification_date(file).ok() ==
+                            Some(cached.last_modification_date)
+                    })
+                    .map(|cached| cached.content_hash.clone());
+                entry.insert(cached_hash.unwrap_or_else(|| source.content_hash()));
             }
         }
     }
@@ -874,6 +952,15 @@ impl<'a, T: ArtifactOutput> ArtifactsCache<'a, T> {
                 // keep only those files that were previously filtered (not dirty, reused)
                 cache.retain(filtered.iter().map(|(p, (_, v))| (p.as_path(), v)));
 
+                // files that were touched but didn't change don't need to be hashed again
+                if project.cache_strategy == CacheStrategy::ContentHash {
+                    for (file, entry) in cache.files.iter_mut() {
+                        if let Ok(date) = CacheEntry::read_last_modification_date(file) {
+                            entry.last_modification_date = date;
+                        }
+                    }
+                }
+
                 // add the artifacts to the cache entries, this way we can keep a mapping from
                 // solidity file to its artifacts
                 // this step is necessary because the concrete artifacts are only known after solc
@@ -0,0 +1,169 @@
+//! Support for downloading solc binaries from a custom mirror
+
+use super::RELEASES;
+use crate::error::{Result, SolcError};
+use semver::Version;
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    sync::RwLock,
+};
+
+/// The configured download source, see [`crate::Solc::set_download_base_url`]
+#[derive(Debug, Default)]
+struct DownloadConfig {
+    /// The url that replaces `https://binaries.soliditylang.org/<platform>`
+    base_url: Option<String>,
+    /// The SHA256 checksums that replace the bundled checksums
+    checksums: Option<BTreeMap<Version, Vec<u8>>>,
+}
+
+static CONFIG: once_cell::sync::Lazy<RwLock<DownloadConfig>> =
+    once_cell::sync::Lazy::new(|| RwLock::new(DownloadConfig::default()));
+
+pub(crate) fn set_base_url(url: Option<String>) {
+    CONFIG.write().unwrap().base_url = url;
+}
+
+pub(crate) fn set_checksums(checksums: Option<BTreeMap<Version, Vec<u8>>>) {
+    CONFIG.write().unwrap().checksums = checksums;
+}
+
+/// Returns the configured mirror, if any
+pub(crate) fn base_url() -> Option<String> {
+    CONFIG.read().unwrap().base_url.clone()
+}
+
+/// Returns the SHA256 checksum the solc binary of the given version is verified against.
+///
+/// Returns `Ok(None)` if no custom checksums are configured and the bundled release list could
+/// not be read, since there is nothing to compare against.
+pub(crate) fn checksum(version: &Version) -> Result<Option<Vec<u8>>> {
+    if let Some(checksums) = CONFIG.read().unwrap().checksums.as_ref() {
+        return checksums.get(version).cloned().map(Some).ok_or_else(|| {
+            SolcError::msg(format!("no checksum configured for solc \"{}\"", version))
+        })
+    }
+    if !RELEASES.2 {
+        return Ok(None)
+    }
+    RELEASES
+        .0
+        .get_checksum(version)
+        .map(Some)
+        .ok_or_else(|| SolcError::msg(format!("no checksum found for solc \"{}\"", version)))
+}
+
+/// Verifies the content against the expected SHA256 checksum.
+pub(crate) fn verify(
+    version: &Version,
+    expected: &[u8],
+    content: &[u8],
+    file: &Path,
+) -> Result<()> {
+    use sha2::Digest;
+    let mut hasher = sha2::Sha256::new();
+    hasher.update(content);
+    let detected = &hasher.finalize()[..];
+
+    if detected == expected {
+        Ok(())
+    } else {
+        Err(SolcError::ChecksumMismatch {
+            version: version.clone(),
+            expected: hex::encode(expected),
+            detected: hex::encode(detected),
+            file: file.to_path_buf(),
+        })
+    }
+}
+
+/// Returns the url of the solc binary on the mirror
+fn artifact_url(base_url: &str, version: &Version) -> Result<String> {
+    let artifact = RELEASES.0.get_artifact(version).ok_or(SolcError::VersionNotFound)?;
+    Ok(format!("{}/{}", base_url.trim_end_matches('/'), artifact))
+}
+
+/// Returns the path the solc binary of the given version is installed at, this is the same
+/// location `svm` uses
+fn install_path(version: &Version) -> PathBuf {
+    svm::version_path(version.to_string().as_str()).join(format!("solc-{}", version))
+}
+
+/// Verifies the downloaded binary and writes it to the svm directory
+fn install(version: &Version, content: &[u8]) -> Result<PathBuf> {
+    let path = install_path(version);
+    if let Some(expected) = checksum(version)? {
+        verify(version, &expected, content, &path)?;
+    } else {
+        tracing::warn!(target: "solc", "no checksum to verify solc \"{}\" against", version);
+    }
+
+    let dir = path.parent().expect("has parent dir");
+    std::fs::create_dir_all(dir).map_err(|err| SolcError::io(err, dir))?;
+    std::fs::write(&path, content).map_err(|err| SolcError::io(err, &path))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755))
+            .map_err(|err| SolcError::io(err, &path))?;
+    }
+    Ok(path)
+}
+
+fn download_error(url: &str, err: reqwest::Error) -> SolcError {
+    SolcError::msg(format!("failed to download solc from \"{}\": {}", url, err))
+}
+
+/// Downloads and installs the given version from the mirror at `base_url`
+pub(crate) fn blocking_install(base_url: &str, version: &Version) -> Result<PathBuf> {
+    let url = artifact_url(base_url, version)?;
+    tracing::trace!("downloading solc \"{}\" from {}", version, url);
+    let content = reqwest::blocking::get(&url)
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.bytes())
+        .map_err(|err| download_error(&url, err))?;
+    install(version, &content)
+}
+
+/// Async version of [`blocking_install`]
+pub(crate) async fn install_async(base_url: &str, version: &Version) -> Result<PathBuf> {
+    let url = artifact_url(base_url, version)?;
+    tracing::trace!("downloading solc \"{}\" from {}", version, url);
+    let response = reqwest::get(&url)
+        .await
+        .and_then(|response| response.error_for_status())
+        .map_err(|err| download_error(&url, err))?;
+    let content = response.bytes().await.map_err(|err| download_error(&url, err))?;
+    install(version, &content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_build_artifact_url() {
+        let version = Version::new(0, 8, 10);
+        let artifact = RELEASES.0.get_artifact(&version).unwrap();
+        assert_eq!(
+            artifact_url("https://mirror.example.com/solc/", &version).unwrap(),
+            format!("https://mirror.example.com/solc/{}", artifact)
+        );
+    }
+
+    #[test]
+    fn checksum_mismatch_names_hashes() {
+        let version = Version::new(0, 8, 10);
+        // sha256 of the empty string
+        let expected =
+            hex::decode("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855")
+                .unwrap();
+        verify(&version, &expected, b"", Path::new("solc")).unwrap();
+
+        let err = verify(&version, &[0u8; 32], b"", Path::new("solc")).unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains(&hex::encode([0u8; 32])));
+        assert!(msg.contains("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"));
+    }
+}
@@ -1,8 +1,9 @@
 use crate::{
-    artifacts::Source,
+    artifacts::{BytecodeMatch, Settings, Source, Sources},
     error::{Result, SolcError},
     utils, CompilerInput, CompilerOutput,
 };
+use ethers_core::types::Bytes;
 use semver::{Version, VersionReq};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
@@ -12,6 +13,8 @@ use std::{
     process::{Command, Output, Stdio},
     str::FromStr,
 };
+#[cfg(feature = "svm-solc")]
+mod download;
 pub mod many;
 pub mod output;
 pub use output::{contracts, sources};
@@ -121,7 +124,8 @@ impl fmt::Display for SolcVersion {
 ///
 /// By default the solc path is configured as follows, with descending priority:
 ///   1. `SOLC_PATH` environment variable
-///   2. [svm](https://github.com/roynalnaruto/svm-rs)'s  `global_version` (set via `svm use <version>`), stored at `<svm_home>/.global_version`
+///   2. [svm](https://github.com/roynalnaruto/svm-rs)'s  `global_version` (set via `svm use
+///      <version>`), stored at `<svm_home>/.global_version`
 ///   3. `solc` otherwise
 #[derive(Debug, Clone, Eq, PartialEq, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct Solc {
@@ -348,9 +352,54 @@ impl Solc {
         Ok(version)
     }
 
+    /// Sets the url solc binaries are downloaded from, instead of
+    /// `https://binaries.soliditylang.org/<platform>`.
+    ///
+    /// The mirror must serve the binaries under the same file names as the official release
+    /// source, like `<url>/solc-v0.8.10+commit.fc410830`. Downloaded binaries are still verified
+    /// against the configured checksums, see [`Solc::set_download_checksums`].
+    ///
+    /// This applies to all subsequent installs of the process.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ethers_solc::Solc;
+    /// Solc::set_download_base_url("https://solc.mirror.internal/linux-amd64");
+    /// ```
+    #[cfg(feature = "svm-solc")]
+    pub fn set_download_base_url(url: impl Into<String>) {
+        download::set_base_url(Some(url.into()))
+    }
+
+    /// Resets the download source to the official release source, see
+    /// [`Solc::set_download_base_url`]
+    #[cfg(feature = "svm-solc")]
+    pub fn reset_download_base_url() {
+        download::set_base_url(None)
+    }
+
+    /// Sets the SHA256 checksums all installed solc binaries are verified against, instead of the
+    /// checksums of the bundled release list.
+    ///
+    /// Versions that are not part of the list fail verification.
+    #[cfg(feature = "svm-solc")]
+    pub fn set_download_checksums(checksums: impl IntoIterator<Item = (Version, Vec<u8>)>) {
+        download::set_checksums(Some(checksums.into_iter().collect()))
+    }
+
+    /// Resets the checksums to the ones of the bundled release list, see
+    /// [`Solc::set_download_checksums`]
+    #[cfg(feature = "svm-solc")]
+    pub fn reset_download_checksums() {
+        download::set_checksums(None)
+    }
+
     /// Installs the provided version of Solc in the machine under the svm dir and returns the
     /// [Solc] instance pointing to the installation.
     ///
+    /// The binary is downloaded from [`Solc::set_download_base_url`] if configured.
+    ///
     /// # Example
     /// ```no_run
     /// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
@@ -360,20 +409,35 @@ impl Solc {
     /// # }
     /// ```
     #[cfg(feature = "svm-solc")]
-    pub async fn install(version: &Version) -> std::result::Result<Self, svm::SolcVmError> {
+    pub async fn install(version: &Version) -> Result<Self> {
         tracing::trace!("installing solc version \"{}\"", version);
         crate::report::solc_installation_start(version);
-        let result = svm::install(version).await;
-        crate::report::solc_installation_success(version);
-        result.map(Solc::new)
+        let result = match download::base_url() {
+            Some(base_url) => download::install_async(&base_url, version).await,
+            None => svm::install(version).await.map_err(Into::into),
+        };
+        match result {
+            Ok(path) => {
+                crate::report::solc_installation_success(version);
+                Ok(Solc::new(path))
+            }
+            Err(err) => {
+                crate::report::solc_installation_error(version, &err.to_string());
+                Err(err)
+            }
+        }
     }
 
     /// Blocking version of `Self::install`
     #[cfg(all(feature = "svm-solc"))]
-    pub fn blocking_install(version: &Version) -> std::result::Result<Self, svm::SolcVmError> {
+    pub fn blocking_install(version: &Version) -> Result<Self> {
         tracing::trace!("blocking installing solc version \"{}\"", version);
         crate::report::solc_installation_start(version);
-        match svm::blocking_install(version) {
+        let result = match download::base_url() {
+            Some(base_url) => download::blocking_install(&base_url, version),
+            None => svm::blocking_install(version).map_err(Into::into),
+        };
+        match result {
             Ok(path) => {
                 crate::report::solc_installation_success(version);
                 Ok(Solc::new(path))
@@ -386,7 +450,8 @@ impl Solc {
     }
 
     /// Verify that the checksum for this version of solc is correct. We check against the SHA256
-    /// checksum from the build information published by binaries.soliditylang
+    /// checksum from the build information published by binaries.soliditylang, or the checksums
+    /// configured via [`Solc::set_download_checksums`]
     #[cfg(all(feature = "svm-solc"))]
     pub fn verify_checksum(&self) -> Result<()> {
         let version = self.version_short()?;
@@ -396,25 +461,17 @@ impl Solc {
         let content =
             std::fs::read(&version_path).map_err(|err| SolcError::io(err, version_path.clone()))?;
 
-        if !RELEASES.2 {
+        let expected = match download::checksum(&version)? {
+            Some(expected) => expected,
             // we skip checksum verification because the underlying request to fetch release info
             // failed so we have nothing to compare against
-            return Ok(())
-        }
+            None => return Ok(()),
+        };
 
-        use sha2::Digest;
-        let mut hasher = sha2::Sha256::new();
-        hasher.update(&content);
-        let checksum_calc = &hasher.finalize()[..];
-
-        let checksum_found = &RELEASES.0.get_checksum(&version).expect("checksum not found");
-
-        if checksum_calc == checksum_found {
-            Ok(())
-        } else {
-            tracing:: warn!(target : "solc", "checksum mismatch for {:?}, expected {}, but found {} for file {:?}", version, hex::encode(&checksum_found), hex::encode(checksum_calc), version_path);
-            Err(SolcError::ChecksumMismatch)
-        }
+        download::verify(&version, &expected, &content, &version_path).map_err(|err| {
+            tracing::warn!(target: "solc", "{}", err);
+            err
+        })
     }
 
     /// Convenience function for compiling all sources under the given path
@@ -428,6 +485,56 @@ impl Solc {
         Ok(res)
     }
 
+    /// Compiles a single source that is not read from disk, e.g. the code of a playground.
+    ///
+    /// The source is passed to solc with `name` as its source unit name, so the contracts of the
+    /// output are keyed by `name`. Imports are not supported, see
+    /// [`Self::compile_source_str_with_imports()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use ethers_solc::{artifacts::Settings, Solc};
+    /// let solc = Solc::default();
+    /// let source = "pragma solidity >=0.8.0; contract Greeter {}";
+    /// let output = solc.compile_source_str("Greeter.sol", source, Settings::default())?;
+    /// assert!(output.get("Greeter.sol", "Greeter").is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn compile_source_str(
+        &self,
+        name: &str,
+        source: &str,
+        settings: Settings,
+    ) -> Result<CompilerOutput> {
+        self.compile_source_str_with_imports(name, source, Sources::new(), settings)
+    }
+
+    /// Same as [`Self::compile_source_str()`], but resolves the imports of the source with the
+    /// given in-memory sources.
+    ///
+    /// The imported sources are keyed by their source unit name, i.e. the import path after
+    /// applying the remappings of the `settings`. solc doesn't read imports from disk that are
+    /// part of `imports`.
+    pub fn compile_source_str_with_imports(
+        &self,
+        name: &str,
+        source: &str,
+        imports: Sources,
+        settings: Settings,
+    ) -> Result<CompilerOutput> {
+        let mut sources = imports;
+        sources.insert(PathBuf::from(name), Source { content: source.to_string() });
+        let mut res: CompilerOutput = Default::default();
+        for input in CompilerInput::with_sources(sources) {
+            let output = self.compile(&input.settings(settings.clone()))?;
+            res.merge(output)
+        }
+        Ok(res)
+    }
+
     /// Same as [`Self::compile()`], but only returns those files which are included in the
     /// `CompilerInput`.
     ///
@@ -451,6 +558,68 @@ impl Solc {
         Ok(out)
     }
 
+    /// Compiles the input, e.g. the standard JSON input a contract was verified with, and
+    /// compares the runtime bytecode of the contract to its deployed code, see
+    /// [`BytecodeMatch::compare`].
+    ///
+    /// The contract is identified by its name, or by `<path>:<name>` if multiple source files
+    /// contain a contract with the name.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// use ethers_solc::{artifacts::BytecodeMatch, CompilerInput, Solc};
+    /// let solc = Solc::default();
+    /// let input = CompilerInput::new("./contracts")?[0].clone();
+    /// let deployed = "0x6080604052".parse()?;
+    /// let matched = solc.verify_deployed_bytecode(&input, "Greeter", &deployed)?;
+    /// assert_eq!(matched, BytecodeMatch::Full);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn verify_deployed_bytecode(
+        &self,
+        input: &CompilerInput,
+        contract: &str,
+        deployed: &Bytes,
+    ) -> Result<BytecodeMatch> {
+        let mut input = input.clone();
+        input.settings.push_output_selection("evm.deployedBytecode");
+        let output = self.compile_exact(&input)?;
+        if output.has_error() {
+            let errors = output
+                .errors
+                .iter()
+                .filter(|err| err.severity.is_error())
+                .map(ToString::to_string)
+                .collect::<Vec<_>>();
+            return Err(SolcError::solc(errors.join("\n")))
+        }
+
+        let (path, name) = match contract.rsplit_once(':') {
+            Some((path, name)) => (Some(path), name),
+            None => (None, contract),
+        };
+        output
+            .contracts
+            .iter()
+            .filter(|(file, _)| path.map(|path| path == file.as_str()).unwrap_or(true))
+            .filter_map(|(_, contracts)| {
+                contracts.get(name)?.evm.as_ref()?.deployed_bytecode.clone()
+            })
+            .map(|compiled| BytecodeMatch::compare(deployed, &compiled.into()))
+            // the best match, if multiple files contain the contract
+            .min_by_key(|matched| match matched {
+                BytecodeMatch::Full => 0,
+                BytecodeMatch::PartialMetadataDiffers => 1,
+                BytecodeMatch::Mismatch => 2,
+            })
+            .ok_or_else(|| {
+                SolcError::ArtifactNotFound(path.unwrap_or_default().into(), name.to_string())
+            })
+    }
+
     /// Run `solc --stand-json` and return the `solc`'s output as
     /// `CompilerOutput`
     ///
@@ -690,6 +859,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn can_compile_source_str() {
+        let imports = Sources::from([(
+            PathBuf::from("lib/Base.sol"),
+            Source { content: "pragma solidity >=0.6.0; contract Base {}".to_string() },
+        )]);
+        let source = r#"
+pragma solidity >=0.6.0;
+import "lib/Base.sol";
+contract Greeter is Base {}
+"#;
+        let out = solc()
+            .compile_source_str_with_imports("Greeter.sol", source, imports, Default::default())
+            .unwrap();
+        assert!(!out.has_error(), "{:?}", out.errors);
+        assert!(out.get("Greeter.sol", "Greeter").is_some());
+        assert!(out.get("lib/Base.sol", "Base").is_some());
+    }
+
     #[test]
     fn can_compile_with_remapped_links() {
         let input: CompilerInput =
@@ -3,14 +3,18 @@
 use crate::{
     artifacts::{
         contract::{CompactContractBytecode, CompactContractRef, Contract},
-        Error,
+        metadata_hash, Error,
     },
     sources::{VersionedSourceFile, VersionedSourceFiles},
-    ArtifactId, ArtifactOutput, Artifacts, CompilerOutput, ConfigurableArtifacts,
+    Artifact, ArtifactId, ArtifactOutput, Artifacts, CompilerOutput, ConfigurableArtifacts,
 };
 use contracts::{VersionedContract, VersionedContracts};
 use semver::Version;
-use std::{collections::BTreeMap, fmt, path::Path};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    path::{Path, PathBuf},
+};
 
 pub mod contracts;
 pub mod sources;
@@ -25,8 +29,12 @@ pub struct ProjectCompileOutput<T: ArtifactOutput = ConfigurableArtifacts> {
     pub(crate) compiled_artifacts: Artifacts<T::Artifact>,
     /// All artifacts that were read from cache
     pub(crate) cached_artifacts: Artifacts<T::Artifact>,
+    /// the freshly compiled artifacts whose abi or bytecode changed
+    pub(crate) changed_artifacts: Vec<ChangedArtifact>,
     /// errors that should be omitted
     pub(crate) ignored_error_codes: Vec<u64>,
+    /// whether warnings that are not ignored are treated as errors
+    pub(crate) deny_warnings: bool,
 }
 
 impl<T: ArtifactOutput> ProjectCompileOutput<T> {
@@ -105,6 +113,11 @@ impl<T: ArtifactOutput> ProjectCompileOutput<T> {
         let base = base.as_ref();
         self.cached_artifacts = self.cached_artifacts.into_stripped_file_prefixes(base);
         self.compiled_artifacts = self.compiled_artifacts.into_stripped_file_prefixes(base);
+        for changed in self.changed_artifacts.iter_mut() {
+            if let Ok(path) = changed.id.path.strip_prefix(base) {
+                changed.id.path = path.to_path_buf();
+            }
+        }
         self
     }
 
@@ -132,14 +145,37 @@ impl<T: ArtifactOutput> ProjectCompileOutput<T> {
         self.compiler_output.is_unchanged()
     }
 
-    /// Whether there were errors
+    /// Whether there were errors, or warnings that are not ignored if the project denies
+    /// warnings, see [`crate::ProjectBuilder::deny_warnings`]
     pub fn has_compiler_errors(&self) -> bool {
-        self.compiler_output.has_error()
+        self.diagnostics().has_error()
     }
 
-    /// Whether there were warnings
+    /// Whether there were warnings that are not ignored
     pub fn has_compiler_warnings(&self) -> bool {
-        self.compiler_output.has_warning(&self.ignored_error_codes)
+        self.diagnostics().has_warning()
+    }
+
+    /// Returns the errors and warnings of the compiler, without the warnings whose error codes
+    /// are ignored by the project
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ethers_solc::Project;
+    ///
+    /// let project = Project::builder().ignore_error_code(5667).build().unwrap();
+    /// let output = project.compile().unwrap();
+    /// for warning in output.diagnostics().warnings() {
+    ///     println!("{:?}: {}", warning.error_code, warning.message);
+    /// }
+    /// ```
+    pub fn diagnostics(&self) -> OutputDiagnostics {
+        OutputDiagnostics {
+            compiler_output: &self.compiler_output,
+            ignored_error_codes: &self.ignored_error_codes,
+            deny_warnings: self.deny_warnings,
+        }
     }
 
     /// Finds the first contract with the given name and removes it from the set
@@ -163,6 +199,30 @@ impl<T: ArtifactOutput> ProjectCompileOutput<T> {
         &self.compiled_artifacts
     }
 
+    /// Returns the artifacts that were compiled with `solc` in [`crate::Project::compile_sparse()`]
+    /// and whose ABI or bytecode differs from the artifact they replaced, or that are new.
+    ///
+    /// With caching enabled only dirty files and the files that import them are recompiled, so
+    /// after editing a single file this tells which of the recompiled artifacts actually changed.
+    /// Artifacts that didn't change are not rewritten. This is empty if no artifacts were written,
+    /// because the project has no artifacts or the build failed, and if the changes were not
+    /// detected, like in [`crate::Project::compile()`].
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ethers_solc::Project;
+    ///
+    /// let project = Project::builder().build().unwrap();
+    /// let output = project.compile_sparse(|_: &std::path::Path| true).unwrap();
+    /// for changed in output.changed_artifacts() {
+    ///     println!("{} changed", changed.id.identifier());
+    /// }
+    /// ```
+    pub fn changed_artifacts(&self) -> &[ChangedArtifact] {
+        &self.changed_artifacts
+    }
+
     /// Returns a `BTreeMap` that maps the compiler version used during
     /// [`crate::Project::compile()`] to a Vector of tuples containing the contract name and the
     /// `Contract`
@@ -185,7 +245,52 @@ impl<T: ArtifactOutput> ProjectCompileOutput<T>
 where
     T::Artifact: Clone,
 {
+    /// Finds the contract with the given name that's defined in the source file at `path`
+    ///
+    /// Unlike [`Self::find()`] this can tell apart contracts with the same name from
+    /// different source files, see [`ArtifactOutput::output_to_artifacts()`].
+    ///
+    /// The `path` is either the path of the source file as it's known to the project, or a suffix
+    /// of it: `vendor/Token.sol` finds the contract of `lib/vendor/Token.sol`. An exact match is
+    /// preferred, otherwise the first source file in lexicographic order that ends with `path` is
+    /// used.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ethers_solc::Project;
+    ///
+    /// let output = Project::builder().build().unwrap().compile().unwrap();
+    /// let token = output.find_by_path("src/Token.sol", "Token");
+    /// let vendored_token = output.find_by_path("vendor/Token.sol", "Token");
+    /// ```
+    pub fn find_by_path(
+        &self,
+        path: impl AsRef<Path>,
+        contract_name: impl AsRef<str>,
+    ) -> Option<&T::Artifact> {
+        let path = path.as_ref();
+        let contract_name = contract_name.as_ref();
+        let all_artifacts = [&self.compiled_artifacts, &self.cached_artifacts];
+        if let artifact @ Some(_) = all_artifacts
+            .iter()
+            .find_map(|artifacts| artifacts.get(path.to_string_lossy(), contract_name))
+        {
+            return artifact
+        }
+        all_artifacts.iter().find_map(|artifacts| {
+            artifacts
+                .as_ref()
+                .keys()
+                .filter(|file| Path::new(file).ends_with(path))
+                .find_map(|file| artifacts.get(file, contract_name))
+        })
+    }
+
     /// Finds the first contract with the given name
+    ///
+    /// If source files define contracts with the same name, use [`Self::find_by_path()`] to pick
+    /// the one of the source file.
     pub fn find(&self, contract_name: impl AsRef<str>) -> Option<&T::Artifact> {
         let contract_name = contract_name.as_ref();
         if let artifact @ Some(_) = self.compiled_artifacts.find(contract_name) {
@@ -193,6 +298,26 @@ where
         }
         self.cached_artifacts.find(contract_name)
     }
+
+    /// Finds the first contract whose deployed bytecode has the given metadata hash, see
+    /// [`metadata_hash`].
+    ///
+    /// This can be used to find the contract of the project that some deployed code was compiled
+    /// from.
+    pub fn find_by_metadata_hash(&self, hash: impl AsRef<[u8]>) -> Option<&T::Artifact> {
+        let hash = hash.as_ref();
+        self.compiled_artifacts
+            .artifact_files()
+            .chain(self.cached_artifacts.artifact_files())
+            .map(|file| &file.artifact)
+            .find(|artifact| {
+                artifact
+                    .get_deployed_bytecode_bytes()
+                    .and_then(|code| metadata_hash(&code))
+                    .map(|metadata| metadata.as_ref() == hash)
+                    .unwrap_or_default()
+            })
+    }
 }
 
 impl ProjectCompileOutput<ConfigurableArtifacts> {
@@ -222,8 +347,63 @@ impl<T: ArtifactOutput> fmt::Display for ProjectCompileOutput<T> {
         if self.compiler_output.is_unchanged() {
             f.write_str("Nothing to compile")
         } else {
-            self.compiler_output.diagnostics(&self.ignored_error_codes).fmt(f)
+            self.diagnostics().fmt(f)
+        }
+    }
+}
+
+/// An artifact that was compiled and whose ABI or bytecode changed, see
+/// [`ProjectCompileOutput::changed_artifacts()`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedArtifact {
+    /// The artifact and the contract it was compiled from
+    pub id: ArtifactId,
+    /// Whether there was no previous artifact
+    pub is_new: bool,
+    /// Whether the ABI changed
+    pub abi_changed: bool,
+    /// Whether the bytecode or the deployed bytecode changed
+    pub bytecode_changed: bool,
+}
+
+impl ChangedArtifact {
+    /// Compares the compiled artifacts to the previous artifacts at the same paths
+    pub(crate) fn compare<T: ArtifactOutput>(
+        previous: &BTreeMap<PathBuf, T::Artifact>,
+        compiled: &Artifacts<T::Artifact>,
+    ) -> Vec<ChangedArtifact> {
+        let mut changed = Vec::new();
+        for (file, contracts) in compiled {
+            for (name, artifacts) in contracts {
+                for artifact in artifacts {
+                    let (is_new, abi_changed, bytecode_changed) = match previous.get(&artifact.file)
+                    {
+                        Some(previous) => (
+                            false,
+                            previous.get_abi() != artifact.artifact.get_abi(),
+                            previous.get_bytecode_bytes() != artifact.artifact.get_bytecode_bytes() ||
+                                previous.get_deployed_bytecode_bytes() !=
+                                    artifact.artifact.get_deployed_bytecode_bytes(),
+                        ),
+                        None => (true, true, true),
+                    };
+                    if is_new || abi_changed || bytecode_changed {
+                        changed.push(ChangedArtifact {
+                            id: ArtifactId {
+                                path: artifact.file.clone(),
+                                name: name.clone(),
+                                source: PathBuf::from(file),
+                                version: artifact.version.clone(),
+                            },
+                            is_new,
+                            abi_changed,
+                            bytecode_changed,
+                        });
+                    }
+                }
+            }
         }
+        changed
     }
 }
 
@@ -234,6 +414,9 @@ impl<T: ArtifactOutput> fmt::Display for ProjectCompileOutput<T> {
 pub struct AggregatedCompilerOutput {
     /// all errors from all `CompilerOutput`
     pub errors: Vec<Error>,
+    /// The solc version that emitted the error at the same position in `errors`, `None` for
+    /// errors that were not added with [`Self::extend`]
+    error_versions: Vec<Option<Version>>,
     /// All source files combined with the solc version used to compile them
     pub sources: VersionedSourceFiles,
     /// All compiled contracts combined with the solc version used to compile them
@@ -257,8 +440,19 @@ impl AggregatedCompilerOutput {
         })
     }
 
+    /// Returns all errors together with the solc version that emitted them, the version is
+    /// `None` if the error was added to `errors` directly
+    pub fn versioned_errors(&self) -> impl Iterator<Item = (&Error, Option<&Version>)> + '_ {
+        self.errors.iter().enumerate().map(move |(idx, err)| (err, self.error_version(idx)))
+    }
+
+    /// Returns the solc version that emitted the error at the index of `errors`
+    pub fn error_version(&self, idx: usize) -> Option<&Version> {
+        self.error_versions.get(idx)?.as_ref()
+    }
+
     pub fn diagnostics<'a>(&'a self, ignored_error_codes: &'a [u64]) -> OutputDiagnostics {
-        OutputDiagnostics { compiler_output: self, ignored_error_codes }
+        OutputDiagnostics { compiler_output: self, ignored_error_codes, deny_warnings: false }
     }
 
     pub fn is_empty(&self) -> bool {
@@ -281,6 +475,9 @@ impl AggregatedCompilerOutput {
     /// adds a new `CompilerOutput` to the aggregated output
     pub fn extend(&mut self, version: Version, output: CompilerOutput) {
         let CompilerOutput { errors, sources, contracts } = output;
+        // errors that were added directly have no version
+        self.error_versions.resize(self.errors.len(), None);
+        self.error_versions.extend(std::iter::repeat(Some(version.clone())).take(errors.len()));
         self.errors.extend(errors);
 
         for (path, source_file) in sources {
@@ -362,24 +559,64 @@ impl AggregatedCompilerOutput {
     }
 }
 
-/// Helper type to implement display for solc errors
-#[derive(Clone, Debug)]
+/// The errors and warnings of the compiler, without the warnings that are ignored.
+///
+/// Also implements display for solc errors.
+#[derive(Clone, Copy, Debug)]
 pub struct OutputDiagnostics<'a> {
     /// output of the compiled project
     compiler_output: &'a AggregatedCompilerOutput,
     /// the error codes to ignore
     ignored_error_codes: &'a [u64],
+    /// whether warnings are treated as errors
+    deny_warnings: bool,
 }
 
 impl<'a> OutputDiagnostics<'a> {
-    /// Returns true if there is at least one error of high severity
+    /// Returns true if there is at least one error of high severity, or a warning if warnings
+    /// are denied
     pub fn has_error(&self) -> bool {
-        self.compiler_output.has_error()
+        self.compiler_output.has_error() || (self.deny_warnings && self.has_warning())
     }
 
     /// Returns true if there is at least one warning
     pub fn has_warning(&self) -> bool {
-        self.compiler_output.has_warning(self.ignored_error_codes)
+        self.warnings().next().is_some()
+    }
+
+    /// Returns all errors, warnings and infos that are not ignored
+    pub fn iter(&self) -> impl Iterator<Item = &'a Error> + 'a {
+        let diagnostics = *self;
+        diagnostics.compiler_output.errors.iter().filter(move |err| !diagnostics.is_ignored(err))
+    }
+
+    /// Returns all errors of high severity
+    pub fn errors(&self) -> impl Iterator<Item = &'a Error> + 'a {
+        self.iter().filter(|err| err.severity.is_error())
+    }
+
+    /// Returns all warnings that are not ignored
+    pub fn warnings(&self) -> impl Iterator<Item = &'a Error> + 'a {
+        self.iter().filter(|err| err.severity.is_warning())
+    }
+
+    /// Returns true if the warning should not be reported, errors are never ignored
+    fn is_ignored(&self, err: &Error) -> bool {
+        if !err.severity.is_warning() {
+            return false
+        }
+        err.error_code.as_ref().map_or(false, |code| {
+            if let Some(source_location) = &err.source_location {
+                // we ignore spdx and contract size warnings in test
+                // files. if we are looking at one of these warnings
+                // from a test file we skip
+                if self.is_test(&source_location.file) && (*code == 1878 || *code == 5574) {
+                    return true
+                }
+            }
+
+            self.ignored_error_codes.contains(code)
+        })
     }
 
     /// Returns true if the contract is a expected to be a test
@@ -403,28 +640,85 @@ impl<'a> fmt::Display for OutputDiagnostics<'a> {
         } else {
             f.write_str("Compiler run successful")?;
         }
-        for err in &self.compiler_output.errors {
-            if err.severity.is_warning() {
-                let is_ignored = err.error_code.as_ref().map_or(false, |code| {
-                    if let Some(source_location) = &err.source_location {
-                        // we ignore spdx and contract size warnings in test
-                        // files. if we are looking at one of these warnings
-                        // from a test file we skip
-                        if self.is_test(&source_location.file) && (*code == 1878 || *code == 5574) {
-                            return true
-                        }
-                    }
-
-                    self.ignored_error_codes.contains(code)
-                });
-
-                if !is_ignored {
-                    writeln!(f, "\n{}", err)?;
-                }
-            } else {
-                writeln!(f, "\n{}", err)?;
-            }
+        for err in self.iter() {
+            writeln!(f, "\n{}", err)?;
         }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostic(severity: &str, code: u64) -> Error {
+        serde_json::from_value(serde_json::json!({
+            "sourceLocation": { "file": "src/Greeter.sol", "start": 0, "end": 1 },
+            "type": "Warning",
+            "component": "general",
+            "severity": severity,
+            "errorCode": code.to_string(),
+            "message": "message",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn can_filter_diagnostics() {
+        let compiler_output = AggregatedCompilerOutput {
+            errors: vec![diagnostic("warning", 5667), diagnostic("warning", 2072)],
+            ..Default::default()
+        };
+        let mut output = ProjectCompileOutput::<ConfigurableArtifacts> {
+            compiler_output,
+            ignored_error_codes: vec![5667],
+            ..Default::default()
+        };
+
+        let warnings: Vec<_> = output.diagnostics().warnings().collect();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].error_code, Some(2072));
+        assert_eq!(warnings[0].source_location.as_ref().unwrap().file, "src/Greeter.sol");
+        assert!(output.has_compiler_warnings());
+        assert!(!output.has_compiler_errors());
+
+        output.deny_warnings = true;
+        assert!(output.has_compiler_errors());
+        output.ignored_error_codes.push(2072);
+        assert!(!output.has_compiler_warnings());
+        assert!(!output.has_compiler_errors());
+
+        output.compiler_output.errors.push(diagnostic("error", 5667));
+        assert!(output.has_compiler_errors());
+        assert_eq!(output.diagnostics().errors().count(), 1);
+    }
+
+    #[test]
+    fn tracks_error_versions() {
+        let v1 = Version::new(0, 8, 10);
+        let v2 = Version::new(0, 6, 12);
+        let mut output = AggregatedCompilerOutput::default();
+        output.extend(
+            v1.clone(),
+            CompilerOutput { errors: vec![diagnostic("warning", 5667)], ..Default::default() },
+        );
+        output.errors.push(diagnostic("error", 1234));
+        output.extend(
+            v2.clone(),
+            CompilerOutput {
+                errors: vec![diagnostic("warning", 2072), diagnostic("error", 7576)],
+                ..Default::default()
+            },
+        );
+
+        let versioned: Vec<_> = output
+            .versioned_errors()
+            .map(|(err, version)| (err.error_code.unwrap(), version.cloned()))
+            .collect();
+        assert_eq!(
+            versioned,
+            vec![(5667, Some(v1)), (1234, None), (2072, Some(v2.clone())), (7576, Some(v2))]
+        );
+        assert_eq!(output.error_version(4), None);
+    }
+}
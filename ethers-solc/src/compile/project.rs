@@ -106,7 +106,7 @@ use crate::{
     artifacts::{Settings, VersionedFilteredSources, VersionedSources},
     cache::ArtifactsCache,
     error::Result,
-    output::AggregatedCompilerOutput,
+    output::{AggregatedCompilerOutput, ChangedArtifact},
     report,
     resolver::GraphEdges,
     ArtifactOutput, CompilerInput, Graph, Project, ProjectCompileOutput, ProjectPathsConfig, Solc,
@@ -126,6 +126,8 @@ pub struct ProjectCompiler<'a, T: ArtifactOutput> {
     sources: CompilerSources,
     /// How to select solc [`crate::artifacts::CompilerOutput`] for files
     sparse_output: SparseOutputFilter,
+    /// Whether to detect which of the compiled artifacts changed
+    changed_artifacts: bool,
 }
 
 impl<'a, T: ArtifactOutput> ProjectCompiler<'a, T> {
@@ -165,7 +167,13 @@ impl<'a, T: ArtifactOutput> ProjectCompiler<'a, T> {
             CompilerSources::Sequential(sources_by_version)
         };
 
-        Ok(Self { edges, project, sources, sparse_output: Default::default() })
+        Ok(Self {
+            edges,
+            project,
+            sources,
+            sparse_output: Default::default(),
+            changed_artifacts: false,
+        })
     }
 
     /// Compiles the sources with a pinned `Solc` instance
@@ -179,7 +187,13 @@ impl<'a, T: ArtifactOutput> ProjectCompiler<'a, T> {
         let sources_by_version = BTreeMap::from([(solc, (version, sources))]);
         let sources = CompilerSources::Sequential(sources_by_version);
 
-        Ok(Self { edges, project, sources, sparse_output: Default::default() })
+        Ok(Self {
+            edges,
+            project,
+            sources,
+            sparse_output: Default::default(),
+            changed_artifacts: false,
+        })
     }
 
     /// Applies the specified filter to be applied when selecting solc output for
@@ -189,6 +203,16 @@ impl<'a, T: ArtifactOutput> ProjectCompiler<'a, T> {
         self
     }
 
+    /// Sets whether to detect which compiled artifacts differ from the artifacts they replace
+    /// (default: false), see [`ProjectCompileOutput::changed_artifacts()`].
+    ///
+    /// This reads the previous artifacts before they are overwritten.
+    #[must_use]
+    pub fn with_changed_artifacts(mut self, changed_artifacts: bool) -> Self {
+        self.changed_artifacts = changed_artifacts;
+        self
+    }
+
     /// Compiles all the sources of the `Project` in the appropriate mode
     ///
     /// If caching is enabled, the sources are filtered and only _dirty_ sources are recompiled.
@@ -213,13 +237,13 @@ impl<'a, T: ArtifactOutput> ProjectCompiler<'a, T> {
     ///   - sets proper source unit names
     ///   - check cache
     fn preprocess(self) -> Result<PreprocessedState<'a, T>> {
-        let Self { edges, project, sources, sparse_output } = self;
+        let Self { edges, project, sources, sparse_output, changed_artifacts } = self;
 
         let mut cache = ArtifactsCache::new(project, edges)?;
         // retain and compile only dirty sources and all their imports
         let sources = sources.filtered(&mut cache);
 
-        Ok(PreprocessedState { sources, cache, sparse_output })
+        Ok(PreprocessedState { sources, cache, sparse_output, changed_artifacts })
     }
 }
 
@@ -233,12 +257,13 @@ struct PreprocessedState<'a, T: ArtifactOutput> {
     /// cache that holds [CacheEntry] object if caching is enabled and the project is recompiled
     cache: ArtifactsCache<'a, T>,
     sparse_output: SparseOutputFilter,
+    changed_artifacts: bool,
 }
 
 impl<'a, T: ArtifactOutput> PreprocessedState<'a, T> {
     /// advance to the next state by compiling all sources
     fn compile(self) -> Result<CompiledState<'a, T>> {
-        let PreprocessedState { sources, cache, sparse_output } = self;
+        let PreprocessedState { sources, cache, sparse_output, changed_artifacts } = self;
         let output = sources.compile(
             &cache.project().solc_config.settings,
             &cache.project().paths,
@@ -246,7 +271,7 @@ impl<'a, T: ArtifactOutput> PreprocessedState<'a, T> {
             cache.graph(),
         )?;
 
-        Ok(CompiledState { output, cache })
+        Ok(CompiledState { output, cache, changed_artifacts })
     }
 }
 
@@ -255,6 +280,7 @@ impl<'a, T: ArtifactOutput> PreprocessedState<'a, T> {
 struct CompiledState<'a, T: ArtifactOutput> {
     output: AggregatedCompilerOutput,
     cache: ArtifactsCache<'a, T>,
+    changed_artifacts: bool,
 }
 
 impl<'a, T: ArtifactOutput> CompiledState<'a, T> {
@@ -263,7 +289,8 @@ impl<'a, T: ArtifactOutput> CompiledState<'a, T> {
     /// Writes all output contracts to disk if enabled in the `Project` and if the build was
     /// successful
     fn write_artifacts(self) -> Result<ArtifactsState<'a, T>> {
-        let CompiledState { output, cache } = self;
+        let CompiledState { output, cache, changed_artifacts: detect_changes } = self;
+        let mut changed_artifacts = Vec::new();
 
         // write all artifacts via the handler but only if the build succeeded
         let compiled_artifacts = if cache.project().no_artifacts {
@@ -277,15 +304,35 @@ impl<'a, T: ArtifactOutput> CompiledState<'a, T> {
                 .project()
                 .artifacts_handler()
                 .output_to_artifacts(&output.contracts, &output.sources)
-        } else {
+        } else if !detect_changes {
             cache.project().artifacts_handler().on_output(
                 &output.contracts,
                 &output.sources,
                 &cache.project().paths,
             )?
+        } else {
+            let handler = cache.project().artifacts_handler();
+            // read the artifacts that are about to be replaced, to detect which ones changed
+            let mut previous_artifacts =
+                handler.output_to_artifacts(&output.contracts, &output.sources);
+            previous_artifacts.join_all(&cache.project().paths.artifacts);
+            let previous_artifacts: BTreeMap<_, _> = previous_artifacts
+                .artifact_files()
+                .filter_map(|artifact| {
+                    T::read_cached_artifact(&artifact.file)
+                        .ok()
+                        .map(|previous| (artifact.file.clone(), previous))
+                })
+                .collect();
+
+            let compiled_artifacts =
+                handler.on_output(&output.contracts, &output.sources, &cache.project().paths)?;
+            changed_artifacts =
+                ChangedArtifact::compare::<T>(&previous_artifacts, &compiled_artifacts);
+            compiled_artifacts
         };
 
-        Ok(ArtifactsState { output, cache, compiled_artifacts })
+        Ok(ArtifactsState { output, cache, compiled_artifacts, changed_artifacts })
     }
 }
 
@@ -295,6 +342,7 @@ struct ArtifactsState<'a, T: ArtifactOutput> {
     output: AggregatedCompilerOutput,
     cache: ArtifactsCache<'a, T>,
     compiled_artifacts: Artifacts<T::Artifact>,
+    changed_artifacts: Vec<ChangedArtifact>,
 }
 
 impl<'a, T: ArtifactOutput> ArtifactsState<'a, T> {
@@ -302,15 +350,18 @@ impl<'a, T: ArtifactOutput> ArtifactsState<'a, T> {
     ///
     /// this concludes the [`Project::compile()`] statemachine
     fn write_cache(self) -> Result<ProjectCompileOutput<T>> {
-        let ArtifactsState { output, cache, compiled_artifacts } = self;
+        let ArtifactsState { output, cache, compiled_artifacts, changed_artifacts } = self;
         let ignored_error_codes = cache.project().ignored_error_codes.clone();
+        let deny_warnings = cache.project().deny_warnings;
         let skip_write_to_disk = cache.project().no_artifacts || output.has_error();
         let cached_artifacts = cache.consume(&compiled_artifacts, !skip_write_to_disk)?;
         Ok(ProjectCompileOutput {
             compiler_output: output,
             compiled_artifacts,
             cached_artifacts,
+            changed_artifacts,
             ignored_error_codes,
+            deny_warnings,
         })
     }
 }
@@ -534,7 +585,7 @@ fn compile_parallel(
             }
 
             let job = input
-                .settings(settings.clone())
+                .settings(opt_settings.clone())
                 .normalize_evm_version(&version)
                 .with_remappings(paths.remappings.clone())
                 .sanitized(&version);
@@ -3,14 +3,15 @@ use crate::{
     cache::SOLIDITY_FILES_CACHE_FILENAME,
     error::{Result, SolcError, SolcIoError},
     remappings::Remapping,
-    resolver::{Graph, SolImportAlias},
-    utils, Source, Sources,
+    resolver::{Graph, Node, SolImportAlias},
+    utils, Solc, Source, Sources,
 };
 
 use crate::artifacts::output_selection::ContractOutputSelection;
+use semver::{Comparator, Op, Version};
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::{BTreeSet, HashSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
     fmt::{self, Formatter},
     fs,
     path::{Component, Path, PathBuf},
@@ -246,46 +247,187 @@ impl ProjectPathsConfig {
         vec![utils::find_fave_or_alt_path(root, "lib", "node_modules")]
     }
 
+    /// Loads the remappings of a foundry style project at the given root.
+    ///
+    /// Remappings are collected from, in descending priority:
+    ///   1. the `remappings` of the profile set via `FOUNDRY_PROFILE`, or the `default` profile, in
+    ///      `<root>/foundry.toml`
+    ///   2. `<root>/remappings.txt`, one remapping per line
+    ///   3. the remappings autodetected in `<root>/lib`, like `forge-std/=lib/forge-std/src/`, see
+    ///      [`Remapping::find_many()`]
+    ///
+    /// If multiple remappings have the same name, only the one with the highest priority is kept.
+    /// Relative remapping paths are joined with the `root`. The returned remappings are sorted by
+    /// name.
+    pub fn load_remappings(root: impl AsRef<Path>) -> Result<Vec<Remapping>> {
+        let root = root.as_ref();
+        let mut remappings = Vec::new();
+
+        let foundry_toml = root.join("foundry.toml");
+        if foundry_toml.exists() {
+            let content = fs::read_to_string(&foundry_toml)
+                .map_err(|err| SolcError::io(err, &foundry_toml))?;
+            let config: FoundryToml = toml::from_str(&content).map_err(|err| {
+                SolcError::msg(format!("Failed to parse \"{}\": {}", foundry_toml.display(), err))
+            })?;
+            remappings.extend(config.remappings());
+        }
+
+        let remappings_txt = root.join("remappings.txt");
+        if remappings_txt.exists() {
+            let content = fs::read_to_string(&remappings_txt)
+                .map_err(|err| SolcError::io(err, &remappings_txt))?;
+            for line in content.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') {
+                    continue
+                }
+                let remapping = line.parse::<Remapping>().map_err(|err| {
+                    SolcError::msg(format!(
+                        "Invalid remapping \"{}\" in \"{}\": {}",
+                        line,
+                        remappings_txt.display(),
+                        err
+                    ))
+                })?;
+                remappings.push(remapping);
+            }
+        }
+
+        remappings.extend(Remapping::find_many(root.join("lib")));
+
+        Ok(merge_remappings(
+            remappings.into_iter().map(|remapping| remapping_with_root(remapping, root)),
+        ))
+    }
+
     /// Flattens all file imports into a single string
+    ///
+    /// All imported files are inlined before the files that import them, imports of a file are
+    /// visited in the order they appear in the file, so the output is reproducible.
+    ///
+    /// The flattened file starts with a single SPDX license identifier, a single `pragma solidity`
+    /// that is the intersection of the version requirements of all files and all distinct
+    /// `pragma experimental` directives. These, and all imports, are stripped from the inlined
+    /// files.
+    ///
+    /// Returns an error if the files have different licenses.
     pub fn flatten(&self, target: &Path) -> Result<String> {
         tracing::trace!("flattening file");
         let graph = Graph::resolve(self)?;
-        self.flatten_node(target, &graph, &mut Default::default(), false, false, false).map(|x| {
-            format!("{}\n", utils::RE_THREE_OR_MORE_NEWLINES.replace_all(&x, "\n\n").trim())
-        })
+
+        let mut files = Vec::new();
+        self.flatten_order(target, &graph, &mut Default::default(), &mut files)?;
+
+        let mut license: Option<(&str, String)> = None;
+        let mut versions = Vec::new();
+        let mut experimental = Vec::new();
+        let mut parts = Vec::with_capacity(files.len());
+        for idx in files {
+            let node = graph.node(idx);
+            if let Some(unit) = node.license() {
+                let id = unit.data().trim();
+                match license {
+                    Some((existing, _)) if existing != id => {
+                        return Err(SolcError::msg(format!(
+                            "conflicting licenses \"{}\" and \"{}\" in \"{}\"",
+                            existing,
+                            id,
+                            node.unpack().0.display()
+                        )))
+                    }
+                    Some(_) => {}
+                    None => license = Some((id, node.content()[unit.loc()].trim_end().to_string())),
+                }
+            }
+            if let Some(version) = node.version() {
+                let version = version.data().trim();
+                if !versions.contains(&version) {
+                    versions.push(version);
+                }
+            }
+            if let Some(feature) = node.experimental() {
+                let feature = feature.data().trim();
+                if !experimental.contains(&feature) {
+                    experimental.push(feature);
+                }
+            }
+            parts.push(Self::flatten_content(node)?);
+        }
+
+        let mut flattened = String::new();
+        if let Some((_, license)) = license {
+            flattened.push_str(&license);
+            flattened.push('\n');
+        }
+        if !versions.is_empty() {
+            flattened.push_str(&format!("pragma solidity {};\n", intersect_versions(&versions)?));
+        }
+        for feature in experimental {
+            flattened.push_str(&format!("pragma experimental {};\n", feature));
+        }
+        for part in parts {
+            flattened.push('\n');
+            flattened.push_str(part.trim());
+            flattened.push('\n');
+        }
+
+        Ok(format!("{}\n", utils::RE_THREE_OR_MORE_NEWLINES.replace_all(&flattened, "\n\n").trim()))
     }
 
-    /// Flattens a single node from the dependency graph
-    fn flatten_node(
+    /// Collects the given file and all its imports in depth-first post-order, so every file
+    /// comes after all the files it imports
+    fn flatten_order(
         &self,
         target: &Path,
         graph: &Graph,
-        imported: &mut HashSet<usize>,
-        strip_version_pragma: bool,
-        strip_experimental_pragma: bool,
-        strip_license: bool,
-    ) -> Result<String> {
+        visited: &mut HashSet<usize>,
+        order: &mut Vec<usize>,
+    ) -> Result<()> {
         let target_dir = target.parent().ok_or_else(|| {
             SolcError::msg(format!("failed to get parent directory for \"{:?}\"", target.display()))
         })?;
-        let target_index = graph.files().get(target).ok_or_else(|| {
+        let target_index = *graph.files().get(target).ok_or_else(|| {
             SolcError::msg(format!("cannot resolve file at \"{:?}\"", target.display()))
         })?;
 
-        if imported.contains(target_index) {
+        if !visited.insert(target_index) {
             // short circuit nodes that were already imported, if both A.sol and B.sol import C.sol
-            return Ok(String::new())
+            return Ok(())
         }
-        imported.insert(*target_index);
-
-        let target_node = graph.node(*target_index);
 
-        let mut imports = target_node.imports().clone();
+        let mut imports = graph.node(target_index).imports().clone();
         imports.sort_by_key(|x| x.loc().start);
+        for import in imports.iter() {
+            let import_path = self.resolve_import(target_dir, import.data().path())?;
+            self.flatten_order(&import_path, graph, visited, order)?;
+        }
+        order.push(target_index);
+        Ok(())
+    }
 
-        let mut content = target_node.content().to_owned();
+    /// Returns the content of the file without its license, pragmas and imports, with all
+    /// imported aliases replaced by their original names
+    fn flatten_content(node: &Node) -> Result<String> {
+        let mut removed = node
+            .imports()
+            .iter()
+            .map(|import| import.loc())
+            .chain(node.license().iter().map(|license| license.loc()))
+            .chain(node.version().iter().map(|version| version.loc()))
+            .chain(node.experimental().iter().map(|experimental| experimental.loc()))
+            .collect::<Vec<_>>();
+        // remove from back to front so the remaining ranges stay valid
+        removed.sort_by_key(|range| std::cmp::Reverse(range.start));
+
+        let mut content = node.content().as_bytes().to_vec();
+        for range in removed {
+            content.splice(range, std::iter::empty());
+        }
+        let mut content = String::from_utf8(content).map_err(|err| {
+            SolcError::msg(format!("failed to convert extended bytes to string: {}", err))
+        })?;
 
-        for alias in imports.iter().flat_map(|i| i.data().aliases()) {
+        for alias in node.imports().iter().flat_map(|i| i.data().aliases()) {
             let (alias, target) = match alias {
                 SolImportAlias::Contract(alias, target) => (alias.clone(), target.clone()),
                 _ => continue,
@@ -308,49 +450,7 @@ impl ProjectPathsConfig {
             }
         }
 
-        let mut content = content.as_bytes().to_vec();
-        let mut offset = 0_isize;
-
-        if strip_license {
-            if let Some(license) = target_node.license() {
-                let license_range = license.loc_by_offset(offset);
-                offset -= license_range.len() as isize;
-                content.splice(license_range, std::iter::empty());
-            }
-        }
-
-        if strip_version_pragma {
-            if let Some(version) = target_node.version() {
-                let version_range = version.loc_by_offset(offset);
-                offset -= version_range.len() as isize;
-                content.splice(version_range, std::iter::empty());
-            }
-        }
-
-        if strip_experimental_pragma {
-            if let Some(experiment) = target_node.experimental() {
-                let experimental_pragma_range = experiment.loc_by_offset(offset);
-                offset -= experimental_pragma_range.len() as isize;
-                content.splice(experimental_pragma_range, std::iter::empty());
-            }
-        }
-
-        for import in imports.iter() {
-            let import_path = self.resolve_import(target_dir, import.data().path())?;
-            let s = self.flatten_node(&import_path, graph, imported, true, true, true)?;
-
-            let import_content = s.as_bytes();
-            let import_content_len = import_content.len() as isize;
-            let import_range = import.loc_by_offset(offset);
-            offset += import_content_len - (import_range.len() as isize);
-            content.splice(import_range, import_content.iter().copied());
-        }
-
-        let result = String::from_utf8(content).map_err(|err| {
-            SolcError::msg(format!("failed to convert extended bytes to string: {}", err))
-        })?;
-
-        Ok(result)
+        Ok(content)
     }
 }
 
@@ -457,6 +557,197 @@ impl PathStyle {
     }
 }
 
+/// Returns the intersection of the given solidity version requirements.
+///
+/// Solidity's version pragma separates alternatives with `||` and requirements that all must hold
+/// with whitespace, so `(a || b) c` is the union of `a c` and `b c`. The comparators of each
+/// combination of alternatives are merged into their tightest bounds and combinations no version
+/// satisfies are dropped. A combination whose bounds are the same as one of its alternatives is
+/// kept as written, e.g. `^0.8.10` instead of `>=0.8.10 <0.9.0`.
+///
+/// Returns an error if no version satisfies all requirements.
+fn intersect_versions(versions: &[&str]) -> Result<String> {
+    // the combinations of alternatives, with their range and the alternatives of the combination
+    let mut combinations = vec![(VersionRange::default(), Vec::new())];
+    for version in versions {
+        let mut next = Vec::new();
+        for alternative in version.split("||").map(str::trim) {
+            let range = match VersionRange::parse(alternative)? {
+                Some(range) => range,
+                None => continue,
+            };
+            for (existing, alternatives) in &combinations {
+                if let Some(range) = existing.intersect(&range) {
+                    let mut alternatives = alternatives.clone();
+                    alternatives.push((alternative, range.clone()));
+                    next.push((range, alternatives));
+                }
+            }
+        }
+        combinations = next;
+    }
+
+    let mut intersection: Vec<String> = Vec::new();
+    for (range, alternatives) in combinations {
+        let version = alternatives
+            .iter()
+            .find(|(_, alternative)| *alternative == range)
+            .map(|(alternative, _)| alternative.to_string())
+            .unwrap_or_else(|| range.to_string());
+        if !intersection.contains(&version) {
+            intersection.push(version);
+        }
+    }
+    if intersection.is_empty() {
+        return Err(SolcError::msg(format!(
+            "no solc version satisfies all version requirements {:?}",
+            versions
+        )))
+    }
+    Ok(intersection.join(" || "))
+}
+
+/// The versions `lower <= version < upper`, prereleases are not considered
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct VersionRange {
+    lower: Version,
+    upper: Option<Version>,
+}
+
+impl Default for VersionRange {
+    fn default() -> Self {
+        Self { lower: Version::new(0, 0, 0), upper: None }
+    }
+}
+
+impl VersionRange {
+    /// Parses the comparators of a solidity version requirement without alternatives, returns
+    /// `None` if no version satisfies the requirement
+    fn parse(version: &str) -> Result<Option<Self>> {
+        let req = Solc::version_req(version)?;
+        let mut range = Some(Self::default());
+        for comparator in &req.comparators {
+            let bounds = Self::from_comparator(comparator).ok_or_else(|| {
+                SolcError::msg(format!("unsupported version requirement \"{}\"", version))
+            })?;
+            range = range.and_then(|range| range.intersect(&bounds));
+        }
+        Ok(range)
+    }
+
+    /// Returns the range of versions matched by the comparator
+    fn from_comparator(comparator: &Comparator) -> Option<Self> {
+        let Comparator { major, minor, patch, .. } = *comparator;
+        let lowest = Version::new(major, minor.unwrap_or(0), patch.unwrap_or(0));
+        // the first version that doesn't start with the given parts, e.g. `1.3.0` for `1.2`
+        let next = match (minor, patch) {
+            (None, _) => Version::new(major + 1, 0, 0),
+            (Some(minor), None) => Version::new(major, minor + 1, 0),
+            (Some(minor), Some(patch)) => Version::new(major, minor, patch + 1),
+        };
+        let (lower, upper) = match comparator.op {
+            Op::Exact | Op::Wildcard => (lowest, Some(next)),
+            Op::Greater => (next, None),
+            Op::GreaterEq => (lowest, None),
+            Op::Less => (Version::new(0, 0, 0), Some(lowest)),
+            Op::LessEq => (Version::new(0, 0, 0), Some(next)),
+            Op::Tilde => match minor {
+                Some(minor) => (lowest, Some(Version::new(major, minor + 1, 0))),
+                None => (lowest, Some(next)),
+            },
+            Op::Caret => match (major, minor) {
+                (0, Some(0)) | (0, None) => (lowest, Some(next)),
+                (0, Some(minor)) => (lowest, Some(Version::new(0, minor + 1, 0))),
+                _ => (lowest, Some(Version::new(major + 1, 0, 0))),
+            },
+            _ => return None,
+        };
+        Some(Self { lower, upper })
+    }
+
+    /// Returns the versions in both ranges, or `None` if there are none
+    fn intersect(&self, other: &Self) -> Option<Self> {
+        let lower = self.lower.clone().max(other.lower.clone());
+        let upper = match (&self.upper, &other.upper) {
+            (Some(a), Some(b)) => Some(a.clone().min(b.clone())),
+            (a, b) => a.clone().or_else(|| b.clone()),
+        };
+        if upper.as_ref().map_or(false, |upper| *upper <= lower) {
+            return None
+        }
+        Some(Self { lower, upper })
+    }
+}
+
+impl fmt::Display for VersionRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let Version { major, minor, patch, .. } = self.lower;
+        match &self.upper {
+            Some(upper) if *upper == Version::new(major, minor, patch + 1) => {
+                write!(f, "={}", self.lower)
+            }
+            Some(upper) if self.lower == Version::new(0, 0, 0) => write!(f, "<{}", upper),
+            Some(upper) => write!(f, ">={} <{}", self.lower, upper),
+            None => write!(f, ">={}", self.lower),
+        }
+    }
+}
+
+/// The parts of a `foundry.toml` that are relevant for remappings
+#[derive(Debug, Default, Deserialize)]
+struct FoundryToml {
+    #[serde(default)]
+    profile: BTreeMap<String, FoundryProfile>,
+    /// legacy format without the `profile.` prefix
+    #[serde(default)]
+    default: Option<FoundryProfile>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct FoundryProfile {
+    #[serde(default)]
+    remappings: Vec<Remapping>,
+}
+
+impl FoundryToml {
+    /// Returns the remappings of the active profile
+    fn remappings(self) -> Vec<Remapping> {
+        let FoundryToml { mut profile, default } = self;
+        let active = std::env::var("FOUNDRY_PROFILE").unwrap_or_else(|_| "default".to_string());
+        profile
+            .remove(&active)
+            .filter(|profile| !profile.remappings.is_empty())
+            .or_else(|| profile.remove("default"))
+            .or(default)
+            .map(|profile| profile.remappings)
+            .unwrap_or_default()
+    }
+}
+
+/// Joins a relative remapping path with the root
+fn remapping_with_root(mut remapping: Remapping, root: &Path) -> Remapping {
+    if Path::new(&remapping.path).is_relative() {
+        let trailing_slash = remapping.path.ends_with('/');
+        remapping.path = root.join(&remapping.path).display().to_string();
+        if trailing_slash && !remapping.path.ends_with('/') {
+            remapping.path.push('/');
+        }
+    }
+    remapping
+}
+
+/// Removes all remappings whose name is already used by a previous remapping and sorts them by
+/// name
+fn merge_remappings(remappings: impl IntoIterator<Item = Remapping>) -> Vec<Remapping> {
+    let mut names = HashSet::new();
+    let mut merged = remappings
+        .into_iter()
+        .filter(|remapping| names.insert(remapping.name.clone()))
+        .collect::<Vec<_>>();
+    merged.sort_by(|a, b| a.name.cmp(&b.name));
+    merged
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct ProjectPathsConfigBuilder {
     root: Option<PathBuf>,
@@ -466,6 +757,7 @@ pub struct ProjectPathsConfigBuilder {
     tests: Option<PathBuf>,
     libraries: Option<Vec<PathBuf>>,
     remappings: Option<Vec<Remapping>>,
+    load_remappings: bool,
 }
 
 impl ProjectPathsConfigBuilder {
@@ -526,11 +818,35 @@ impl ProjectPathsConfigBuilder {
         self
     }
 
+    /// Also loads the remappings from the project's `foundry.toml` and `remappings.txt`, see
+    /// [`ProjectPathsConfig::load_remappings()`].
+    ///
+    /// Explicitly set remappings take precedence over loaded remappings with the same name.
+    pub fn load_remappings(mut self) -> Self {
+        self.load_remappings = true;
+        self
+    }
+
     pub fn build_with_root(self, root: impl Into<PathBuf>) -> ProjectPathsConfig {
         let root = utils::canonicalized(root);
 
         let libraries = self.libraries.unwrap_or_else(|| ProjectPathsConfig::find_libs(&root));
 
+        let remappings = if self.load_remappings {
+            match ProjectPathsConfig::load_remappings(&root) {
+                Ok(loaded) => {
+                    merge_remappings(self.remappings.unwrap_or_default().into_iter().chain(loaded))
+                }
+                Err(err) => {
+                    tracing::warn!("failed to load remappings: {}", err);
+                    self.remappings.unwrap_or_default()
+                }
+            }
+        } else {
+            self.remappings
+                .unwrap_or_else(|| libraries.iter().flat_map(Remapping::find_many).collect())
+        };
+
         ProjectPathsConfig {
             cache: self
                 .cache
@@ -540,9 +856,7 @@ impl ProjectPathsConfigBuilder {
                 .unwrap_or_else(|| ProjectPathsConfig::find_artifacts_dir(&root)),
             sources: self.sources.unwrap_or_else(|| ProjectPathsConfig::find_source_dir(&root)),
             tests: self.tests.unwrap_or_else(|| root.join("tests")),
-            remappings: self
-                .remappings
-                .unwrap_or_else(|| libraries.iter().flat_map(Remapping::find_many).collect()),
+            remappings,
             libraries,
             root,
         }
@@ -673,6 +987,26 @@ impl<T: Into<PathBuf>> From<Vec<T>> for AllowedLibPaths {
 mod tests {
     use super::*;
 
+    #[test]
+    fn can_intersect_versions() {
+        assert_eq!(intersect_versions(&["^0.8.10", "^0.7.0 || ^0.8.0"]).unwrap(), "^0.8.10");
+        assert_eq!(
+            intersect_versions(&[">=0.6.0 <0.9.0", "^0.7.0 || ^0.8.0"]).unwrap(),
+            "^0.7.0 || ^0.8.0"
+        );
+        // overlapping alternatives
+        assert_eq!(
+            intersect_versions(&["^0.7.0 || ^0.8.0", ">=0.7.5 <0.8.5"]).unwrap(),
+            ">=0.7.5 <0.8.0 || >=0.8.0 <0.8.5"
+        );
+        assert_eq!(intersect_versions(&[">=0.8.4", "<0.8.12"]).unwrap(), ">=0.8.4 <0.8.12");
+        assert_eq!(intersect_versions(&[">0.8.3", "<=0.8.4"]).unwrap(), "=0.8.4");
+        assert_eq!(intersect_versions(&["0.8.10", "^0.8.0"]).unwrap(), "0.8.10");
+
+        assert!(intersect_versions(&["^0.8.0", "^0.7.0"]).is_err());
+        assert!(intersect_versions(&["^0.8.10", "<0.8.0 || >=0.9.0"]).is_err());
+    }
+
     #[test]
     fn can_autodetect_dirs() {
         let root = crate::utils::tempdir("root").unwrap();
@@ -726,4 +1060,48 @@ mod tests {
             vec![utils::canonicalized(lib)],
         );
     }
+
+    #[test]
+    fn can_load_remappings() {
+        let tmp = crate::utils::tempdir("root").unwrap();
+        let root = utils::canonicalized(tmp.path());
+        for lib in ["forge-std", "solmate"] {
+            let src = root.join("lib").join(lib).join("src");
+            fs::create_dir_all(&src).unwrap();
+            fs::write(src.join("A.sol"), "contract A {}").unwrap();
+        }
+        fs::write(
+            root.join("foundry.toml"),
+            r#"
+[profile.default]
+src = "src"
+remappings = ["solmate/=lib/solmate/src/custom/", "ds-test/=lib/ds-test/src/"]
+"#,
+        )
+        .unwrap();
+        fs::write(root.join("remappings.txt"), "# comment\nds-test/=other/\n\nfoo/=lib/foo/\n")
+            .unwrap();
+
+        let remapping = |name: &str, path: &str| Remapping {
+            name: name.to_string(),
+            path: format!("{}/{}", root.display(), path),
+        };
+        assert_eq!(
+            ProjectPathsConfig::load_remappings(&root).unwrap(),
+            vec![
+                remapping("ds-test/", "lib/ds-test/src/"),
+                remapping("foo/", "lib/foo/"),
+                remapping("forge-std/", "lib/forge-std/src/"),
+                remapping("solmate/", "lib/solmate/src/custom/"),
+            ]
+        );
+
+        let explicit = Remapping { name: "foo/".to_string(), path: "/explicit/".to_string() };
+        let paths = ProjectPathsConfig::builder()
+            .remapping(explicit.clone())
+            .load_remappings()
+            .build_with_root(&root);
+        assert_eq!(paths.remappings.len(), 4);
+        assert!(paths.remappings.contains(&explicit));
+    }
 }
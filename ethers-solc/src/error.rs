@@ -13,8 +13,10 @@ pub enum SolcError {
     PragmaNotFound,
     #[error("Could not find solc version locally or upstream")]
     VersionNotFound,
-    #[error("Checksum mismatch")]
-    ChecksumMismatch,
+    #[error(
+        "Checksum mismatch for solc {version} at {file:?}: expected {expected}, found {detected}"
+    )]
+    ChecksumMismatch { version: semver::Version, expected: String, detected: String, file: PathBuf },
     #[error(transparent)]
     SemverError(#[from] semver::Error),
     /// Deserialization error
@@ -71,6 +71,9 @@ impl SparseOutputFilter {
     /// This will modify the [OutputSelection] of the [Settings] so that we explicitly select the
     /// files' output based on their state.
     ///
+    /// Outputs selected for specific files, e.g. with [OutputSelection::set], are resolved to the
+    /// paths of the sources and remain selected in addition to the outputs selected for all files.
+    ///
     /// This also takes the project's graph as input, this allows us to check if the files the
     /// filter matches depend on libraries that need to be linked
     pub fn sparse_sources(
@@ -79,6 +82,7 @@ impl SparseOutputFilter {
         settings: &mut Settings,
         graph: &GraphEdges,
     ) -> Sources {
+        settings.output_selection.resolve_file_patterns(sources.0.keys().map(PathBuf::as_path));
         match self {
             SparseOutputFilter::AllDirty => {
                 if !sources.all_dirty() {
@@ -117,16 +121,23 @@ impl SparseOutputFilter {
         for (file, source) in sources.0.iter() {
             let key = format!("{}", file.display());
             if source.is_dirty() && f.is_match(file) {
-                settings.output_selection.as_mut().insert(key, selection.clone());
+                OutputSelection::merge_file_output_selection(
+                    settings.output_selection.as_mut().entry(key).or_default(),
+                    &selection,
+                );
 
                 // the filter might not cover link references that will be required by the file, so
                 // we check if the file has any libraries that won't be inlined and include them as
                 // well
                 for link in graph.get_link_references(file) {
-                    settings
-                        .output_selection
-                        .as_mut()
-                        .insert(format!("{}", link.display()), selection.clone());
+                    OutputSelection::merge_file_output_selection(
+                        settings
+                            .output_selection
+                            .as_mut()
+                            .entry(format!("{}", link.display()))
+                            .or_default(),
+                        &selection,
+                    );
                 }
             } else if !settings.output_selection.as_ref().contains_key(&key) {
                 tracing::trace!("using pruned output selection for {}", file.display());
@@ -155,10 +166,14 @@ impl SparseOutputFilter {
 
         for (file, source) in sources.0.iter() {
             if source.is_dirty() {
-                settings
-                    .output_selection
-                    .as_mut()
-                    .insert(format!("{}", file.display()), selection.clone());
+                OutputSelection::merge_file_output_selection(
+                    settings
+                        .output_selection
+                        .as_mut()
+                        .entry(format!("{}", file.display()))
+                        .or_default(),
+                    &selection,
+                );
             } else {
                 tracing::trace!("using pruned output selection for {}", file.display());
                 settings.output_selection.as_mut().insert(
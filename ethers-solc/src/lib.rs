@@ -16,7 +16,7 @@ pub use resolver::Graph;
 
 mod compile;
 pub use compile::{
-    output::{AggregatedCompilerOutput, ProjectCompileOutput},
+    output::{AggregatedCompilerOutput, ChangedArtifact, ProjectCompileOutput},
     *,
 };
 
@@ -34,7 +34,7 @@ pub use filter::{FileFilter, TestFileFilter};
 
 use crate::{
     artifacts::Sources,
-    cache::SolFilesCache,
+    cache::{CacheStrategy, SolFilesCache},
     error::{SolcError, SolcIoError},
     sources::VersionedSourceFiles,
 };
@@ -67,12 +67,16 @@ pub struct Project<T: ArtifactOutput = ConfigurableArtifacts> {
     pub artifacts: T,
     /// Errors/Warnings which match these error codes are not going to be logged
     pub ignored_error_codes: Vec<u64>,
+    /// Whether warnings that are not ignored are treated as errors
+    pub deny_warnings: bool,
     /// The paths which will be allowed for library inclusion
     pub allowed_lib_paths: AllowedLibPaths,
     /// Maximum number of `solc` processes to run simultaneously.
     solc_jobs: usize,
     /// Offline mode, if set, network access (download solc) is disallowed
     pub offline: bool,
+    /// How the cache detects changed source files
+    pub cache_strategy: CacheStrategy,
 }
 
 impl Project {
@@ -221,6 +225,25 @@ impl<T: ArtifactOutput> Project<T> {
         self.compile_with_version(&solc, sources)
     }
 
+    /// Compiles the project like [`Project::compile()`] and reports all steps to the given
+    /// [`Reporter`](crate::report::Reporter) instead of the current default reporter
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use ethers_solc::{report::BasicStdoutReporter, Project};
+    /// # fn demo(project: Project) {
+    /// let project = Project::builder().build().unwrap();
+    /// let output = project.compile_with_reporter(BasicStdoutReporter::default()).unwrap();
+    /// # }
+    /// ```
+    pub fn compile_with_reporter(
+        &self,
+        reporter: impl report::Reporter + Send + Sync,
+    ) -> Result<ProjectCompileOutput<T>> {
+        report::with_scoped(&report::Report::new(reporter), || self.compile())
+    }
+
     /// Compiles a set of contracts using `svm` managed solc installs
     ///
     /// This will autodetect the appropriate `Solc` version(s) to use when compiling the provided
@@ -303,6 +326,9 @@ impl<T: ArtifactOutput> Project<T> {
     /// Same as [`Self::svm_compile()`] but with only with those files as input that match
     /// [FileFilter::is_match()].
     ///
+    /// This also detects which of the compiled artifacts changed, see
+    /// [`ProjectCompileOutput::changed_artifacts()`].
+    ///
     /// # Example - Only compile Test files
     ///
     /// ```
@@ -341,6 +367,7 @@ impl<T: ArtifactOutput> Project<T> {
         if self.auto_detect {
             return project::ProjectCompiler::with_sources(self, sources)?
                 .with_sparse_output(filter)
+                .with_changed_artifacts(true)
                 .compile()
         }
 
@@ -350,6 +377,7 @@ impl<T: ArtifactOutput> Project<T> {
             self.configure_solc(self.solc.clone()),
         )?
         .with_sparse_output(filter)
+        .with_changed_artifacts(true)
         .compile()
     }
 
@@ -389,6 +417,73 @@ impl<T: ArtifactOutput> Project<T> {
         .compile()
     }
 
+    /// Compiles a single source that is not read from disk with the project's settings and
+    /// remappings, see [`Solc::compile_source_str_with_imports()`].
+    ///
+    /// If auto detection is enabled the solc version is detected from the pragma of the source,
+    /// in offline mode only installed versions are used. Nothing is read from or written to the
+    /// cache or the artifacts dir, the contracts of the output are keyed by `name` and the names of
+    /// the `imports`.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use ethers_solc::{artifacts::Sources, Project};
+    /// # fn demo(project: Project) {
+    /// let project = Project::builder().build().unwrap();
+    /// let source = "pragma solidity >=0.8.0; contract Greeter {}";
+    /// let output = project.compile_source_str("Greeter.sol", source, Sources::new()).unwrap();
+    /// # }
+    /// ```
+    pub fn compile_source_str(
+        &self,
+        name: &str,
+        source: &str,
+        imports: Sources,
+    ) -> Result<CompilerOutput> {
+        let source = Source { content: source.to_string() };
+
+        #[cfg(all(feature = "svm-solc"))]
+        let solc = if self.auto_detect {
+            let version = if self.offline {
+                let version_req = Solc::source_version_req(&source)?;
+                let installed: Vec<Version> =
+                    Solc::installed_versions().into_iter().map(Into::into).collect();
+                Solc::find_matching_installation(&installed, &version_req).ok_or_else(|| {
+                    SolcError::msg(format!(
+                        "missing solc \"{}\" installation in offline mode",
+                        version_req
+                    ))
+                })?
+            } else {
+                Solc::detect_version(&source)?
+            };
+            Solc::find_svm_installed_version(version.to_string())?.ok_or_else(|| {
+                SolcError::msg(format!("solc \"{}\" should have been installed", version))
+            })?
+        } else {
+            self.solc.clone()
+        };
+        #[cfg(not(all(feature = "svm-solc")))]
+        let solc = self.solc.clone();
+
+        let solc = self.configure_solc(solc);
+        let version = solc.version()?;
+
+        let mut sources = imports;
+        sources.insert(PathBuf::from(name), source);
+        let mut output: CompilerOutput = Default::default();
+        for input in CompilerInput::with_sources(sources) {
+            let input = input
+                .settings(self.solc_config.settings.clone())
+                .normalize_evm_version(&version)
+                .with_remappings(self.paths.remappings.clone())
+                .sanitized(&version);
+            output.merge(solc.compile(&input)?);
+        }
+        Ok(output)
+    }
+
     /// Removes the project's artifacts and cache file
     ///
     /// If the cache file was the only file in the folder, this also removes the empty folder.
@@ -517,10 +612,14 @@ pub struct ProjectBuilder<T: ArtifactOutput = ConfigurableArtifacts> {
     auto_detect: bool,
     /// Use offline mode
     offline: bool,
+    /// How the cache detects changed source files
+    cache_strategy: CacheStrategy,
     /// handles all artifacts related tasks
     artifacts: T,
     /// Which error codes to ignore
     pub ignored_error_codes: Vec<u64>,
+    /// Whether warnings are treated as errors
+    deny_warnings: bool,
     /// All allowed paths
     pub allowed_paths: Vec<PathBuf>,
     solc_jobs: Option<usize>,
@@ -537,8 +636,10 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
             no_artifacts: false,
             auto_detect: true,
             offline: false,
+            cache_strategy: CacheStrategy::default(),
             artifacts,
             ignored_error_codes: Vec::new(),
+            deny_warnings: false,
             allowed_paths: vec![],
             solc_jobs: None,
         }
@@ -576,6 +677,20 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
         self
     }
 
+    /// Treats compiler warnings that are not ignored as errors, see
+    /// [`ProjectCompileOutput::has_compiler_errors`]
+    #[must_use]
+    pub fn deny_warnings(self) -> Self {
+        self.set_deny_warnings(true)
+    }
+
+    /// Sets whether compiler warnings that are not ignored are treated as errors
+    #[must_use]
+    pub fn set_deny_warnings(mut self, deny_warnings: bool) -> Self {
+        self.deny_warnings = deny_warnings;
+        self
+    }
+
     /// Disables cached builds
     #[must_use]
     pub fn ephemeral(self) -> Self {
@@ -604,6 +719,13 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
         self
     }
 
+    /// Sets how the cache detects changed source files, see [`CacheStrategy`]
+    #[must_use]
+    pub fn cache_strategy(mut self, cache_strategy: CacheStrategy) -> Self {
+        self.cache_strategy = cache_strategy;
+        self
+    }
+
     /// Disables writing artifacts to disk
     #[must_use]
     pub fn no_artifacts(self) -> Self {
@@ -658,9 +780,11 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
             no_artifacts,
             auto_detect,
             ignored_error_codes,
+            deny_warnings,
             allowed_paths,
             solc_jobs,
             offline,
+            cache_strategy,
             ..
         } = self;
         ProjectBuilder {
@@ -671,8 +795,10 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
             no_artifacts,
             auto_detect,
             offline,
+            cache_strategy,
             artifacts,
             ignored_error_codes,
+            deny_warnings,
             allowed_paths,
             solc_jobs,
         }
@@ -708,9 +834,11 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
             auto_detect,
             artifacts,
             ignored_error_codes,
+            deny_warnings,
             mut allowed_paths,
             solc_jobs,
             offline,
+            cache_strategy,
         } = self;
 
         let paths = paths.map(Ok).unwrap_or_else(ProjectPathsConfig::current_hardhat)?;
@@ -732,9 +860,11 @@ impl<T: ArtifactOutput> ProjectBuilder<T> {
             auto_detect,
             artifacts,
             ignored_error_codes,
+            deny_warnings,
             allowed_lib_paths: allowed_paths.into(),
             solc_jobs: solc_jobs.unwrap_or_else(::num_cpus::get),
             offline,
+            cache_strategy,
         })
     }
 }
@@ -769,6 +899,14 @@ impl<T: ArtifactOutput> ArtifactOutput for Project<T> {
         self.artifacts_handler().write_extras(contracts, layout)
     }
 
+    fn write_artifact_extras(
+        &self,
+        contracts: &VersionedContracts,
+        artifacts: &Artifacts<Self::Artifact>,
+    ) -> Result<()> {
+        self.artifacts_handler().write_artifact_extras(contracts, artifacts)
+    }
+
     fn output_file_name(name: impl AsRef<str>) -> PathBuf {
         T::output_file_name(name)
     }
@@ -903,4 +1041,30 @@ mod tests {
         let contracts = compiled.output().contracts;
         assert_eq!(contracts.contracts().count(), 2);
     }
+
+    #[test]
+    fn can_compile_source_str() {
+        use super::*;
+
+        let project = Project::builder().no_artifacts().ephemeral().build().unwrap();
+        let imports = Sources::from([(
+            PathBuf::from("Lib.sol"),
+            Source { content: "pragma solidity >=0.8.0; library Lib {}".to_string() },
+        )]);
+        let source = r#"pragma solidity >=0.8.0; import "Lib.sol"; contract Greeter {}"#;
+        let output = project.compile_source_str("Greeter.sol", source, imports).unwrap();
+        assert!(!output.has_error(), "{:?}", output.errors);
+        assert!(output.get("Greeter.sol", "Greeter").is_some());
+        assert!(output.get("Lib.sol", "Lib").is_some());
+    }
+
+    #[test]
+    fn compile_source_str_does_not_install_solc_offline() {
+        use super::*;
+
+        let project = Project::builder().no_artifacts().ephemeral().offline().build().unwrap();
+        let source = "pragma solidity =0.4.10; contract Greeter {}";
+        let err = project.compile_source_str("Greeter.sol", source, Sources::new()).unwrap_err();
+        assert!(err.to_string().contains("offline mode"), "{}", err);
+    }
 }
@@ -14,7 +14,7 @@
 
 // <https://github.com/tokio-rs/tracing/blob/master/tracing-core/src/dispatch.rs>
 
-use crate::{remappings::Remapping, CompilerInput, CompilerOutput, Solc};
+use crate::{artifacts, remappings::Remapping, CompilerInput, CompilerOutput, Solc};
 use semver::Version;
 use std::{
     any::{Any, TypeId},
@@ -125,6 +125,12 @@ pub trait Reporter: 'static + std::fmt::Debug {
     ) {
     }
 
+    /// Invoked for every error the [`Solc`] of the given [`Version`] reported after
+    /// [`Solc::compile()`], right after [`Reporter::on_solc_success()`]
+    ///
+    /// Warnings and infos are not reported.
+    fn on_compiler_error(&self, _version: &Version, _error: &artifacts::Error) {}
+
     /// Invoked before a new [`Solc`] bin is installed
     fn on_solc_installation_start(&self, _version: &Version) {}
 
@@ -164,6 +170,55 @@ pub trait Reporter: 'static + std::fmt::Debug {
     }
 }
 
+impl<R: Reporter + ?Sized> Reporter for Box<R> {
+    fn on_solc_spawn(
+        &self,
+        solc: &Solc,
+        version: &Version,
+        input: &CompilerInput,
+        dirty_files: &[PathBuf],
+    ) {
+        (**self).on_solc_spawn(solc, version, input, dirty_files)
+    }
+
+    fn on_solc_success(
+        &self,
+        solc: &Solc,
+        version: &Version,
+        output: &CompilerOutput,
+        duration: &Duration,
+    ) {
+        (**self).on_solc_success(solc, version, output, duration)
+    }
+
+    fn on_compiler_error(&self, version: &Version, error: &artifacts::Error) {
+        (**self).on_compiler_error(version, error)
+    }
+
+    fn on_solc_installation_start(&self, version: &Version) {
+        (**self).on_solc_installation_start(version)
+    }
+
+    fn on_solc_installation_success(&self, version: &Version) {
+        (**self).on_solc_installation_success(version)
+    }
+
+    fn on_solc_installation_error(&self, version: &Version, error: &str) {
+        (**self).on_solc_installation_error(version, error)
+    }
+
+    fn on_unresolved_import(&self, import: &Path, remappings: &[Remapping]) {
+        (**self).on_unresolved_import(import, remappings)
+    }
+
+    unsafe fn downcast_raw(&self, id: TypeId) -> Option<NonNull<()>> {
+        if id == TypeId::of::<Self>() {
+            return Some(NonNull::from(self).cast())
+        }
+        (**self).downcast_raw(id)
+    }
+}
+
 impl dyn Reporter {
     /// Returns `true` if this `Reporter` is the same type as `T`.
     pub fn is<T: Any>(&self) -> bool {
@@ -195,7 +250,12 @@ pub(crate) fn solc_success(
     output: &CompilerOutput,
     duration: &Duration,
 ) {
-    get_default(|r| r.reporter.on_solc_success(solc, version, output, duration));
+    get_default(|r| {
+        r.reporter.on_solc_success(solc, version, output, duration);
+        for error in output.errors.iter().filter(|error| error.severity.is_error()) {
+            r.reporter.on_compiler_error(version, error);
+        }
+    });
 }
 
 #[allow(unused)]
@@ -479,4 +539,51 @@ mod tests {
 
         get_default(|reporter| assert!(reporter.is::<BasicStdoutReporter>()))
     }
+
+    #[test]
+    fn reports_compiler_errors() {
+        #[derive(Debug, Default)]
+        struct ErrorCounter(Arc<AtomicUsize>);
+        impl Reporter for ErrorCounter {
+            fn on_compiler_error(&self, _version: &Version, _error: &artifacts::Error) {
+                self.0.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        let output: CompilerOutput = serde_json::from_value(serde_json::json!({
+            "errors": [
+                {
+                    "component": "general",
+                    "formattedMessage": "DeclarationError: Undeclared identifier.",
+                    "message": "Undeclared identifier.",
+                    "severity": "error",
+                    "type": "DeclarationError"
+                },
+                {
+                    "component": "general",
+                    "formattedMessage": "Warning: Unused local variable.",
+                    "message": "Unused local variable.",
+                    "severity": "warning",
+                    "type": "Warning"
+                }
+            ]
+        }))
+        .unwrap();
+
+        let counter = ErrorCounter::default();
+        let count = counter.0.clone();
+        let reporter: Box<dyn Reporter + Send + Sync> = Box::new(counter);
+        let report = Report::new(reporter);
+        assert!(report.is::<ErrorCounter>());
+
+        with_scoped(&report, || {
+            solc_success(
+                &Solc::new("solc"),
+                &Version::new(0, 8, 10),
+                &output,
+                &Duration::from_secs(1),
+            );
+        });
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
 }
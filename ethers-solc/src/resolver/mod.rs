@@ -130,6 +130,100 @@ impl GraphEdges {
         }
     }
 
+    /// Returns the files the given file imports directly, in the order of its import directives
+    pub fn imports_of(&self, file: impl AsRef<Path>) -> Vec<&PathBuf> {
+        if let Some(idx) = self.indices.get(file.as_ref()).copied() {
+            self.edges[idx].iter().map(|idx| &self.rev_indices[idx]).collect()
+        } else {
+            Vec::new()
+        }
+    }
+
+    /// Returns all files that import the given file, directly or through other imports.
+    ///
+    /// These are the files that need to be recompiled if the given file changes.
+    pub fn dependents_of(&self, file: impl AsRef<Path>) -> HashSet<&PathBuf> {
+        let start = match self.indices.get(file.as_ref()).copied() {
+            Some(start) => start,
+            None => return HashSet::new(),
+        };
+        let mut dependents = vec![Vec::new(); self.edges.len()];
+        for (idx, imports) in self.edges.iter().enumerate() {
+            for import in imports {
+                dependents[*import].push(idx);
+            }
+        }
+
+        let mut visited = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(node) = stack.pop() {
+            for dependent in &dependents[node] {
+                if visited.insert(*dependent) {
+                    stack.push(*dependent);
+                }
+            }
+        }
+        visited.remove(&start);
+        visited.into_iter().map(|idx| &self.rev_indices[&idx]).collect()
+    }
+
+    /// Returns all files in an order in which every file comes after the files it imports.
+    ///
+    /// solc accepts import cycles, but there is no such order if the graph has one. In that case
+    /// the first cycle that was found is returned as error, see [`Self::find_cycle()`].
+    pub fn topological_order(&self) -> std::result::Result<Vec<&PathBuf>, Vec<&PathBuf>> {
+        #[derive(Clone, Copy, PartialEq, Eq)]
+        enum Mark {
+            Unvisited,
+            Visiting,
+            Done,
+        }
+
+        fn visit(
+            edges: &[Vec<usize>],
+            node: usize,
+            marks: &mut [Mark],
+            stack: &mut Vec<usize>,
+            order: &mut Vec<usize>,
+        ) -> std::result::Result<(), Vec<usize>> {
+            match marks[node] {
+                Mark::Done => return Ok(()),
+                Mark::Visiting => {
+                    // the node is already on the stack, so the stack from there on is a cycle
+                    let start = stack.iter().position(|idx| *idx == node).unwrap_or_default();
+                    return Err(stack[start..].to_vec())
+                }
+                Mark::Unvisited => {}
+            }
+            marks[node] = Mark::Visiting;
+            stack.push(node);
+            for import in &edges[node] {
+                visit(edges, *import, marks, stack, order)?;
+            }
+            stack.pop();
+            marks[node] = Mark::Done;
+            order.push(node);
+            Ok(())
+        }
+
+        let mut marks = vec![Mark::Unvisited; self.edges.len()];
+        let mut order = Vec::with_capacity(self.edges.len());
+        for node in self.files() {
+            let mut stack = Vec::new();
+            visit(&self.edges, node, &mut marks, &mut stack, &mut order).map_err(|cycle| {
+                cycle.into_iter().map(|idx| &self.rev_indices[&idx]).collect::<Vec<_>>()
+            })?;
+        }
+        Ok(order.into_iter().map(|idx| &self.rev_indices[&idx]).collect())
+    }
+
+    /// Returns an import cycle if the graph has one.
+    ///
+    /// Each file of the cycle imports the next one, and the last file imports the first one.
+    pub fn find_cycle(&self) -> Option<Vec<&PathBuf>> {
+        self.topological_order().err()
+    }
+
     /// Returns the id of the given file
     pub fn node_id(&self, file: impl AsRef<Path>) -> usize {
         self.indices[file.as_ref()]
@@ -281,6 +375,26 @@ impl Graph {
         self.edges.imports(path)
     }
 
+    /// Returns the files the given file imports directly, see [`GraphEdges::imports_of()`]
+    pub fn imports_of(&self, path: impl AsRef<Path>) -> Vec<&PathBuf> {
+        self.edges.imports_of(path)
+    }
+
+    /// Returns all files that import the given file, see [`GraphEdges::dependents_of()`]
+    pub fn dependents_of(&self, path: impl AsRef<Path>) -> HashSet<&PathBuf> {
+        self.edges.dependents_of(path)
+    }
+
+    /// Returns all files ordered by their imports, see [`GraphEdges::topological_order()`]
+    pub fn topological_order(&self) -> std::result::Result<Vec<&PathBuf>, Vec<&PathBuf>> {
+        self.edges.topological_order()
+    }
+
+    /// Returns an import cycle if the graph has one, see [`GraphEdges::find_cycle()`]
+    pub fn find_cycle(&self) -> Option<Vec<&PathBuf>> {
+        self.edges.find_cycle()
+    }
+
     /// Resolves a number of sources within the given config
     pub fn resolve_sources(paths: &ProjectPathsConfig, sources: Sources) -> Result<Graph> {
         /// checks if the given target path was already resolved, if so it adds its id to the list
@@ -862,6 +976,28 @@ mod tests {
         assert_eq!(graph.imported_nodes(1).to_vec(), vec![2, 0]);
     }
 
+    #[test]
+    fn can_query_dapp_dependency_graph() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/dapp-sample");
+        let paths = ProjectPathsConfig::dapptools(root).unwrap();
+        let graph = Graph::resolve(&paths).unwrap();
+
+        let dapp = paths.sources.join("Dapp.sol");
+        let dapp_test = paths.sources.join("Dapp.t.sol");
+        let ds_test = paths.root.join("lib/ds-test/src/test.sol");
+
+        assert_eq!(graph.imports_of(&dapp_test), vec![&ds_test, &dapp]);
+        assert!(graph.imports_of(&dapp).is_empty());
+        assert_eq!(graph.dependents_of(&dapp), HashSet::from([&dapp_test]));
+        assert_eq!(graph.dependents_of(&ds_test), HashSet::from([&dapp_test]));
+        assert!(graph.dependents_of(&dapp_test).is_empty());
+
+        let order = graph.topological_order().unwrap();
+        assert_eq!(order.len(), 3);
+        assert_eq!(order.last(), Some(&&dapp_test));
+        assert!(graph.find_cycle().is_none());
+    }
+
     #[test]
     #[cfg(not(target_os = "windows"))]
     fn can_print_dapp_sample_graph() {
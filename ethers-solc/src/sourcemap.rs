@@ -1,3 +1,4 @@
+use crate::artifacts::ast::SourceLocation;
 use std::{fmt, fmt::Write, iter::Peekable, str::CharIndices};
 
 type Spanned<Token, Loc, Error> = Result<(Token, Loc), Error>;
@@ -176,6 +177,20 @@ pub struct SourceElement {
     pub modifier_depth: usize,
 }
 
+impl SourceElement {
+    /// Returns the source range of this element as [`SourceLocation`].
+    ///
+    /// Elements that are not associated with any source file have neither an `index` nor a
+    /// `length`.
+    pub fn location(&self) -> SourceLocation {
+        SourceLocation {
+            start: self.offset,
+            length: self.index.map(|_| self.length),
+            index: self.index.map(|index| index as usize),
+        }
+    }
+}
+
 impl fmt::Display for SourceElement {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -497,6 +512,41 @@ pub fn parse(input: &str) -> Result<SourceMap, SyntaxError> {
     Parser::new(input).collect()
 }
 
+/// Returns the index of the instruction at the given program counter in the bytecode.
+///
+/// The elements of a source map refer to instructions, not bytes. Returns `None` if the `pc` is
+/// out of bounds or points into the data of a `PUSH` instruction.
+pub fn instruction_index(bytecode: &[u8], pc: usize) -> Option<usize> {
+    let mut current = 0;
+    let mut index = 0;
+    while current < bytecode.len() {
+        if current == pc {
+            return Some(index)
+        }
+        if current > pc {
+            return None
+        }
+        let opcode = bytecode[current];
+        current += 1;
+        // PUSH1 - PUSH32
+        if (0x60..=0x7f).contains(&opcode) {
+            current += (opcode - 0x5f) as usize;
+        }
+        index += 1;
+    }
+    None
+}
+
+/// Returns the element of the source map that belongs to the instruction at the given program
+/// counter in the bytecode, see [`instruction_index`]
+pub fn element_at_pc<'a>(
+    source_map: &'a [SourceElement],
+    bytecode: &[u8],
+    pc: usize,
+) -> Option<&'a SourceElement> {
+    source_map.get(instruction_index(bytecode, pc)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -525,4 +575,26 @@ mod tests {
         let _map = parser.collect::<Result<SourceMap, _>>().unwrap();
         assert_eq!(out, s);
     }
+
+    #[test]
+    fn can_map_pc_to_source() {
+        let output: serde_json::Value =
+            serde_json::from_str(include_str!("../test-data/out/compiler-out-4.json")).unwrap();
+        let deployed =
+            &output["contracts"]["contracts/Greeter.sol"]["Greeter"]["evm"]["deployedBytecode"];
+        let bytecode = hex::decode(deployed["object"].as_str().unwrap()).unwrap();
+        let map = parse(deployed["sourceMap"].as_str().unwrap()).unwrap();
+
+        // `PUSH1 0x80 PUSH1 0x40 MSTORE`
+        assert_eq!(instruction_index(&bytecode, 0), Some(0));
+        assert_eq!(instruction_index(&bytecode, 1), None);
+        assert_eq!(instruction_index(&bytecode, 2), Some(1));
+        assert_eq!(instruction_index(&bytecode, 4), Some(2));
+        assert_eq!(instruction_index(&bytecode, bytecode.len()), None);
+        assert!(map.len() <= instruction_index(&bytecode, bytecode.len() - 1).unwrap() + 1);
+
+        assert_eq!(element_at_pc(&map, &bytecode, 4), Some(&map[2]));
+        assert_eq!(map[0].location(), "93:467:0".parse().unwrap());
+        assert_eq!(map[0].jump, Jump::Regular);
+    }
 }
@@ -7,14 +7,18 @@ use std::{
     str::FromStr,
 };
 
-use ethers_core::types::Address;
+use ethers_core::types::{Address, Bytes};
 use ethers_solc::{
-    artifacts::{BytecodeHash, Libraries},
-    cache::{SolFilesCache, SOLIDITY_FILES_CACHE_FILENAME},
+    artifacts::{
+        metadata_hash, output_selection::OutputSelection, BytecodeHash, BytecodeMatch, Libraries,
+        Settings,
+    },
+    cache::{CacheStrategy, SolFilesCache, SOLIDITY_FILES_CACHE_FILENAME},
     project_util::*,
     remappings::Remapping,
-    CompilerInput, ConfigurableArtifacts, ExtraOutputValues, Graph, Project, ProjectCompileOutput,
-    ProjectPathsConfig, Solc, TestFileFilter,
+    Artifact, CompilerInput, ConfigurableArtifacts, ConfigurableContractArtifact,
+    ExtraOutputValues, Graph, Project, ProjectCompileOutput, ProjectPathsConfig, Solc,
+    TestFileFilter,
 };
 use pretty_assertions::assert_eq;
 
@@ -94,6 +98,127 @@ fn can_compile_dapp_sample() {
     assert_eq!(cache, updated_cache);
 }
 
+#[test]
+fn can_detect_touched_but_unchanged_files() {
+    let mut project = TempProject::<ConfigurableArtifacts>::dapptools().unwrap();
+    let file = project
+        .add_source(
+            "A",
+            r#"
+    pragma solidity ^0.8.10;
+    contract A {}
+   "#,
+        )
+        .unwrap();
+
+    let compiled = project.compile().unwrap();
+    assert!(compiled.find("A").is_some());
+    assert!(!compiled.has_compiler_errors());
+    assert!(!compiled.is_unchanged());
+
+    // rewrite the file with the same content, this only updates its modification date
+    let touch = || {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let content = std::fs::read_to_string(&file).unwrap();
+        std::fs::write(&file, content).unwrap();
+    };
+
+    touch();
+    let compiled = project.compile().unwrap();
+    assert!(compiled.find("A").is_some());
+    assert!(compiled.is_unchanged());
+
+    project.project_mut().cache_strategy = CacheStrategy::ModificationTime;
+    touch();
+    let compiled = project.compile().unwrap();
+    assert!(compiled.find("A").is_some());
+    assert!(!compiled.is_unchanged());
+}
+
+#[test]
+fn can_detect_changed_artifacts() {
+    let mut project = TempProject::<ConfigurableArtifacts>::dapptools().unwrap();
+    // without the metadata hash and the AST the artifact of `B` doesn't depend on the source of
+    // `A`
+    let mut settings = Settings::new(OutputSelection::default_output_selection());
+    settings.metadata = Some(BytecodeHash::None.into());
+    project.project_mut().solc_config.settings = settings;
+    project
+        .add_source(
+            "A",
+            r#"
+    pragma solidity ^0.8.10;
+    contract A { function a() public {} }
+   "#,
+        )
+        .unwrap();
+    project
+        .add_source(
+            "B",
+            r#"
+    pragma solidity ^0.8.10;
+    import "./A.sol";
+    contract B { function b() public {} }
+   "#,
+        )
+        .unwrap();
+
+    let compiled = project.compile_sparse(|_: &Path| true).unwrap();
+    assert!(!compiled.has_compiler_errors());
+    let mut changed: Vec<_> =
+        compiled.changed_artifacts().iter().map(|c| c.id.name.as_str()).collect();
+    changed.sort_unstable();
+    assert_eq!(changed, vec!["A", "B"]);
+    assert!(compiled.changed_artifacts().iter().all(|c| c.is_new));
+
+    let artifact_b = compiled
+        .compiled_artifacts()
+        .artifact_files()
+        .find(|artifact| artifact.file.ends_with("B.json"))
+        .unwrap()
+        .file
+        .clone();
+    let modified = std::fs::metadata(&artifact_b).unwrap().modified().unwrap();
+
+    std::thread::sleep(std::time::Duration::from_millis(10));
+    project
+        .add_source(
+            "A",
+            r#"
+    pragma solidity ^0.8.10;
+    contract A { function a() public {} function a2() public {} }
+   "#,
+        )
+        .unwrap();
+
+    let compiled = project.compile_sparse(|_: &Path| true).unwrap();
+    assert!(!compiled.has_compiler_errors());
+    // `B` is recompiled because it imports `A`, but only `A` changed
+    assert!(compiled.compiled_artifacts().has_artifact(&artifact_b));
+    let changed = compiled.changed_artifacts();
+    assert_eq!(changed.len(), 1);
+    assert_eq!(changed[0].id.name, "A");
+    assert!(!changed[0].is_new);
+    assert!(changed[0].abi_changed);
+    assert!(changed[0].bytecode_changed);
+    assert_eq!(std::fs::metadata(&artifact_b).unwrap().modified().unwrap(), modified);
+
+    // changes are only detected by `compile_sparse`
+    project
+        .add_source(
+            "A",
+            r#"
+    pragma solidity ^0.8.10;
+    contract A { function a() public {} }
+   "#,
+        )
+        .unwrap();
+    let compiled = project.compile().unwrap();
+    assert!(!compiled.has_compiler_errors());
+    assert!(!compiled.is_unchanged());
+    assert!(compiled.changed_artifacts().is_empty());
+}
+
 #[test]
 fn can_compile_yul_sample() {
     let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("test-data/yul-sample");
@@ -221,6 +346,125 @@ fn can_compile_dapp_detect_changes_in_libs() {
     assert!(!compiled.is_unchanged());
 }
 
+#[test]
+fn can_compile_same_named_contracts() {
+    let project = TempProject::<ConfigurableArtifacts>::dapptools().unwrap();
+
+    let src = project
+        .add_source(
+            "Token",
+            r#"
+    pragma solidity ^0.8.10;
+
+    contract Token {
+        function src() public {}
+    }
+   "#,
+        )
+        .unwrap();
+
+    let add_vendored_token = |comment: &str| {
+        project
+            .add_lib(
+                "vendor/Token",
+                format!(
+                    r#"
+    pragma solidity ^0.8.10;
+    // {}
+    contract Token {{
+        function vendor() public {{}}
+    }}
+    "#,
+                    comment
+                ),
+            )
+            .unwrap()
+    };
+    let lib = add_vendored_token("v1");
+
+    let has_function = |artifact: Option<&ConfigurableContractArtifact>, name: &str| {
+        artifact.unwrap().abi.as_ref().unwrap().abi.functions.contains_key(name)
+    };
+
+    let compiled = project.compile().unwrap();
+    assert!(!compiled.has_compiler_errors());
+    assert!(has_function(compiled.find_by_path(&src, "Token"), "src"));
+    assert!(has_function(compiled.find_by_path(&lib, "Token"), "vendor"));
+    assert!(has_function(compiled.find_by_path("vendor/Token.sol", "Token"), "vendor"));
+
+    let artifacts = project.artifacts_path();
+    assert!(artifacts.join("Token.sol/Token.json").exists());
+    assert!(artifacts.join("vendor/Token.sol/Token.json").exists());
+
+    // only the vendored file changed, but the artifacts keep their location
+    add_vendored_token("v2");
+    let compiled = project.compile().unwrap();
+    assert!(!compiled.is_unchanged());
+    assert!(has_function(compiled.find_by_path(&src, "Token"), "src"));
+    assert!(has_function(compiled.find_by_path(&lib, "Token"), "vendor"));
+
+    let artifact: ConfigurableContractArtifact = serde_json::from_str(
+        &std::fs::read_to_string(artifacts.join("Token.sol/Token.json")).unwrap(),
+    )
+    .unwrap();
+    assert!(has_function(Some(&artifact), "src"));
+}
+
+#[test]
+fn can_detect_import_cycles() {
+    let project = TempProject::<ConfigurableArtifacts>::dapptools().unwrap();
+
+    let a = project
+        .add_source(
+            "A",
+            r#"
+    pragma solidity ^0.8.10;
+    import "./B.sol";
+    contract A {}
+   "#,
+        )
+        .unwrap();
+    let b = project
+        .add_source(
+            "B",
+            r#"
+    pragma solidity ^0.8.10;
+    import "./C.sol";
+    contract B {}
+   "#,
+        )
+        .unwrap();
+    let c = project
+        .add_source(
+            "C",
+            r#"
+    pragma solidity ^0.8.10;
+    contract C {}
+   "#,
+        )
+        .unwrap();
+
+    let graph = Graph::resolve(project.paths()).unwrap();
+    assert_eq!(graph.topological_order().unwrap(), vec![&c, &b, &a]);
+    assert_eq!(graph.dependents_of(&c), HashSet::from([&a, &b]));
+    assert!(graph.find_cycle().is_none());
+
+    project
+        .add_source(
+            "C",
+            r#"
+    pragma solidity ^0.8.10;
+    import "./A.sol";
+    contract C {}
+   "#,
+        )
+        .unwrap();
+
+    let graph = Graph::resolve(project.paths()).unwrap();
+    assert_eq!(graph.find_cycle(), Some(vec![&a, &b, &c]));
+    assert_eq!(graph.topological_order(), Err(vec![&a, &b, &c]));
+}
+
 #[test]
 fn can_compile_dapp_detect_changes_in_sources() {
     let project = TempProject::<ConfigurableArtifacts>::dapptools().unwrap();
@@ -502,6 +746,84 @@ contract A { }
     );
 }
 
+#[test]
+fn can_flatten_diamond() {
+    let project = TempProject::dapptools().unwrap();
+
+    let f = project
+        .add_source(
+            "A",
+            r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+import "./C.sol";
+import "./B.sol";
+contract A is B, C { }
+"#,
+        )
+        .unwrap();
+
+    project
+        .add_source(
+            "B",
+            r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+import "./D.sol";
+contract B is D { }
+"#,
+        )
+        .unwrap();
+
+    project
+        .add_source(
+            "C",
+            r#"// SPDX-License-Identifier: MIT
+pragma solidity >=0.8.4 || ^0.7.0;
+import "./D.sol";
+contract C is D { }
+"#,
+        )
+        .unwrap();
+
+    project
+        .add_source(
+            "D",
+            r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.10;
+contract D { }
+"#,
+        )
+        .unwrap();
+
+    let result = project.flatten(&f).unwrap();
+    assert_eq!(
+        result,
+        r#"// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.10;
+
+contract D { }
+
+contract C is D { }
+
+contract B is D { }
+
+contract A is B, C { }
+"#
+    );
+    assert_eq!(project.flatten(&f).unwrap(), result);
+
+    // conflicting licenses
+    project
+        .add_source(
+            "D",
+            r#"// SPDX-License-Identifier: UNLICENSED
+pragma solidity ^0.8.10;
+contract D { }
+"#,
+        )
+        .unwrap();
+    assert!(project.flatten(&f).is_err());
+}
+
 #[test]
 fn can_flatten_experimental_pragma() {
     let project = TempProject::dapptools().unwrap();
@@ -1310,3 +1632,43 @@ fn can_compile_std_json_input() {
         assert!(out.sources.contains_key("lib/ds-test/src/test.sol"));
     }
 }
+
+#[test]
+fn can_verify_deployed_bytecode() {
+    let tmp = TempProject::dapptools().unwrap();
+    let source = tmp
+        .add_source(
+            "Greeter",
+            r#"
+// SPDX-License-Identifier: MIT
+pragma solidity 0.8.10;
+
+contract Greeter {
+    function greet() public pure returns (string memory) {
+        return "hello";
+    }
+}
+"#,
+        )
+        .unwrap();
+
+    let compiled = tmp.compile().unwrap();
+    assert!(!compiled.has_compiler_errors());
+    let deployed =
+        compiled.find("Greeter").unwrap().get_deployed_bytecode_bytes().unwrap().into_owned();
+    let hash = metadata_hash(&deployed).unwrap();
+    assert!(compiled.find_by_metadata_hash(&hash).is_some());
+    assert!(compiled.find_by_metadata_hash([0u8; 34]).is_none());
+
+    // should be installed
+    if let Some(solc) = Solc::find_svm_installed_version("0.8.10").ok().flatten() {
+        let input: CompilerInput = tmp.project().standard_json_input(source).unwrap().into();
+        let matched = solc.verify_deployed_bytecode(&input, "Greeter", &deployed).unwrap();
+        assert!(matched.is_match());
+
+        let other = Bytes::from(vec![0x60, 0x80]);
+        let matched = solc.verify_deployed_bytecode(&input, "Greeter", &other).unwrap();
+        assert_eq!(matched, BytecodeMatch::Mismatch);
+        assert!(solc.verify_deployed_bytecode(&input, "Missing", &deployed).is_err());
+    }
+}